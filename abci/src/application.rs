@@ -5,103 +5,195 @@ pub mod echo;
 #[cfg(feature = "kvstore-app")]
 pub mod kvstore;
 
-use tendermint_proto::abci::{
-    request::Value, response, Request, RequestApplySnapshotChunk, RequestBeginBlock,
-    RequestCheckTx, RequestDeliverTx, RequestEcho, RequestEndBlock, RequestInfo, RequestInitChain,
-    RequestLoadSnapshotChunk, RequestOfferSnapshot, RequestQuery, Response,
-    ResponseApplySnapshotChunk, ResponseBeginBlock, ResponseCheckTx, ResponseCommit,
-    ResponseDeliverTx, ResponseEcho, ResponseEndBlock, ResponseFlush, ResponseInfo,
-    ResponseInitChain, ResponseListSnapshots, ResponseLoadSnapshotChunk, ResponseOfferSnapshot,
-    ResponseQuery,
-};
+use tendermint::abci::{request, response, Request, Response};
 
 /// An ABCI application.
 ///
+/// Tendermint opens four separate connections to an ABCI application, one for
+/// each [`MethodKind`]. Their methods are modeled by the [`Consensus`],
+/// [`Mempool`], [`Info`] and [`Snapshot`] traits, and any type implementing all
+/// four of them is an `Application`.
+///
 /// Applications are `Send` + `Clone` + `'static` because they are cloned for
 /// each incoming connection to the ABCI [`Server`]. It is up to the
 /// application developer to manage shared state between these clones of their
 /// application.
 ///
+/// Application methods take and return the validated domain types from
+/// [`tendermint::abci`]. Requests that cannot be converted into their domain
+/// representation never reach the application: the [`Server`] instead
+/// responds to them with a [`response::Exception`].
+///
+/// [`MethodKind`]: tendermint::abci::MethodKind
 /// [`Server`]: crate::Server
-pub trait Application: Send + Clone + 'static {
-    /// Echo back the same message as provided in the request.
-    fn echo(&self, request: RequestEcho) -> ResponseEcho {
-        ResponseEcho {
-            message: request.message,
-        }
-    }
+pub trait Application: Consensus + Mempool + Info + Snapshot + Send + Clone + 'static {}
 
-    /// Provide information about the ABCI application.
-    fn info(&self, _request: RequestInfo) -> ResponseInfo {
+impl<A> Application for A where A: Consensus + Mempool + Info + Snapshot + Send + Clone + 'static {}
+
+/// Methods called on the consensus connection, which is responsible for block
+/// execution.
+///
+/// Tendermint calls these methods in a strict order: [`init_chain`] once upon
+/// genesis, and then [`begin_block`], [`deliver_tx`] for each transaction,
+/// [`end_block`] and [`commit`] for every block.
+///
+/// Nodes speaking ABCI++ additionally call [`prepare_proposal`] when proposing
+/// a block, [`process_proposal`] when receiving a proposal, and
+/// [`extend_vote`] and [`verify_vote_extension`] when precommitting, and
+/// execute each decided block with a single [`finalize_block`] call instead of
+/// `begin_block`, `deliver_tx` and `end_block`. The defaults of the methods
+/// that decide a block accept every proposal and vote extension.
+///
+/// [`init_chain`]: Consensus::init_chain
+/// [`begin_block`]: Consensus::begin_block
+/// [`deliver_tx`]: Consensus::deliver_tx
+/// [`end_block`]: Consensus::end_block
+/// [`commit`]: Consensus::commit
+/// [`prepare_proposal`]: Consensus::prepare_proposal
+/// [`process_proposal`]: Consensus::process_proposal
+/// [`extend_vote`]: Consensus::extend_vote
+/// [`verify_vote_extension`]: Consensus::verify_vote_extension
+/// [`finalize_block`]: Consensus::finalize_block
+pub trait Consensus {
+    /// Called once upon genesis.
+    fn init_chain(&self, _request: request::InitChain) -> response::InitChain {
         Default::default()
     }
 
-    /// Called once upon genesis.
-    fn init_chain(&self, _request: RequestInitChain) -> ResponseInitChain {
+    /// Signals the beginning of a new block, prior to any `DeliverTx` calls.
+    fn begin_block(&self, _request: request::BeginBlock) -> response::BeginBlock {
         Default::default()
     }
 
-    /// Query the application for data at the current or past height.
-    fn query(&self, _request: RequestQuery) -> ResponseQuery {
+    /// Apply a transaction to the application's state.
+    fn deliver_tx(&self, _request: request::DeliverTx) -> response::DeliverTx {
         Default::default()
     }
 
-    /// Check the given transaction before putting it into the local mempool.
-    fn check_tx(&self, _request: RequestCheckTx) -> ResponseCheckTx {
+    /// Signals the end of a block.
+    fn end_block(&self, _request: request::EndBlock) -> response::EndBlock {
         Default::default()
     }
 
-    /// Signals the beginning of a new block, prior to any `DeliverTx` calls.
-    fn begin_block(&self, _request: RequestBeginBlock) -> ResponseBeginBlock {
+    /// Commit the current state at the current height.
+    fn commit(&self) -> response::Commit {
         Default::default()
     }
 
-    /// Apply a transaction to the application's state.
-    fn deliver_tx(&self, _request: RequestDeliverTx) -> ResponseDeliverTx {
+    /// Prepare the transactions of a block that this node is proposing.
+    ///
+    /// By default, the transactions are proposed as given, up to the
+    /// request's `max_tx_bytes`.
+    fn prepare_proposal(&self, request: request::PrepareProposal) -> response::PrepareProposal {
+        default_prepare_proposal(request)
+    }
+
+    /// Validate a block proposed by another node. Accepts every proposal by
+    /// default.
+    fn process_proposal(&self, _request: request::ProcessProposal) -> response::ProcessProposal {
+        response::ProcessProposal::Accept
+    }
+
+    /// Provide the data to attach to this node's precommit vote. Empty by
+    /// default.
+    fn extend_vote(&self, _request: request::ExtendVote) -> response::ExtendVote {
         Default::default()
     }
 
-    /// Signals the end of a block.
-    fn end_block(&self, _request: RequestEndBlock) -> ResponseEndBlock {
+    /// Verify the vote extension of another validator. Accepts every vote
+    /// extension by default.
+    fn verify_vote_extension(
+        &self,
+        _request: request::VerifyVoteExtension,
+    ) -> response::VerifyVoteExtension {
+        response::VerifyVoteExtension::Accept
+    }
+
+    /// Execute a decided block, returning the hash of the application's
+    /// resulting state.
+    ///
+    /// Over the v0.38 protocol, this replaces [`begin_block`], [`deliver_tx`]
+    /// and [`end_block`], and the application hash is taken from its response
+    /// rather than from [`commit`].
+    ///
+    /// As in CometBFT's `BaseApplication`, the default returns a default
+    /// result for each of the block's transactions and an empty application
+    /// hash. Applications served over the v0.38 protocol that maintain any
+    /// state must implement this method.
+    ///
+    /// [`begin_block`]: Consensus::begin_block
+    /// [`deliver_tx`]: Consensus::deliver_tx
+    /// [`end_block`]: Consensus::end_block
+    /// [`commit`]: Consensus::commit
+    fn finalize_block(&self, request: request::FinalizeBlock) -> response::FinalizeBlock {
+        default_finalize_block(request)
+    }
+}
+
+/// Methods called on the mempool connection, which is used to validate new
+/// transactions before they are shared or included in a block.
+pub trait Mempool {
+    /// Check the given transaction before putting it into the local mempool.
+    fn check_tx(&self, _request: request::CheckTx) -> response::CheckTx {
         Default::default()
     }
+}
+
+/// Methods called on the info connection, which is used for initialization and
+/// user queries.
+pub trait Info {
+    /// Echo back the same message as provided in the request.
+    fn echo(&self, request: request::Echo) -> response::Echo {
+        response::Echo {
+            message: request.message,
+        }
+    }
 
-    /// Signals that messages queued on the client should be flushed to the server.
-    fn flush(&self) -> ResponseFlush {
-        ResponseFlush {}
+    /// Provide information about the ABCI application.
+    fn info(&self, _request: request::Info) -> response::Info {
+        default_info()
     }
 
-    /// Commit the current state at the current height.
-    fn commit(&self) -> ResponseCommit {
+    /// Query the application for data at the current or past height.
+    fn query(&self, _request: request::Query) -> response::Query {
         Default::default()
     }
+}
 
+/// Methods called on the snapshot connection, which is used for serving and
+/// restoring state snapshots during state sync.
+pub trait Snapshot {
     /// Used during state sync to discover available snapshots on peers.
-    fn list_snapshots(&self) -> ResponseListSnapshots {
+    fn list_snapshots(&self) -> response::ListSnapshots {
         Default::default()
     }
 
     /// Called when bootstrapping the node using state sync.
-    fn offer_snapshot(&self, _request: RequestOfferSnapshot) -> ResponseOfferSnapshot {
+    fn offer_snapshot(&self, _request: request::OfferSnapshot) -> response::OfferSnapshot {
         Default::default()
     }
 
     /// Used during state sync to retrieve chunks of snapshots from peers.
-    fn load_snapshot_chunk(&self, _request: RequestLoadSnapshotChunk) -> ResponseLoadSnapshotChunk {
+    fn load_snapshot_chunk(
+        &self,
+        _request: request::LoadSnapshotChunk,
+    ) -> response::LoadSnapshotChunk {
         Default::default()
     }
 
     /// Apply the given snapshot chunk to the application's state.
     fn apply_snapshot_chunk(
         &self,
-        _request: RequestApplySnapshotChunk,
-    ) -> ResponseApplySnapshotChunk {
+        _request: request::ApplySnapshotChunk,
+    ) -> response::ApplySnapshotChunk {
         Default::default()
     }
 }
 
 /// Provides a mechanism for the [`Server`] to execute incoming requests while
 /// expecting the correct response types.
+///
+/// [`Server`]: crate::Server
 pub trait RequestDispatcher {
     /// Executes the relevant application method based on the type of the
     /// request, and produces the corresponding response.
@@ -112,29 +204,74 @@ pub trait RequestDispatcher {
 impl<A: Application> RequestDispatcher for A {
     fn handle(&self, request: Request) -> Response {
         tracing::debug!("Incoming request: {:?}", request);
-        Response {
-            value: Some(match request.value.unwrap() {
-                Value::Echo(req) => response::Value::Echo(self.echo(req)),
-                Value::Flush(_) => response::Value::Flush(self.flush()),
-                Value::Info(req) => response::Value::Info(self.info(req)),
-                Value::InitChain(req) => response::Value::InitChain(self.init_chain(req)),
-                Value::Query(req) => response::Value::Query(self.query(req)),
-                Value::BeginBlock(req) => response::Value::BeginBlock(self.begin_block(req)),
-                Value::CheckTx(req) => response::Value::CheckTx(self.check_tx(req)),
-                Value::DeliverTx(req) => response::Value::DeliverTx(self.deliver_tx(req)),
-                Value::EndBlock(req) => response::Value::EndBlock(self.end_block(req)),
-                Value::Commit(_) => response::Value::Commit(self.commit()),
-                Value::ListSnapshots(_) => response::Value::ListSnapshots(self.list_snapshots()),
-                Value::OfferSnapshot(req) => {
-                    response::Value::OfferSnapshot(self.offer_snapshot(req))
-                },
-                Value::LoadSnapshotChunk(req) => {
-                    response::Value::LoadSnapshotChunk(self.load_snapshot_chunk(req))
-                },
-                Value::ApplySnapshotChunk(req) => {
-                    response::Value::ApplySnapshotChunk(self.apply_snapshot_chunk(req))
-                },
-            }),
+        match request {
+            Request::Echo(req) => Response::Echo(self.echo(req)),
+            Request::Flush => Response::Flush,
+            Request::Info(req) => Response::Info(self.info(req)),
+            Request::InitChain(req) => Response::InitChain(self.init_chain(req)),
+            Request::Query(req) => Response::Query(self.query(req)),
+            Request::BeginBlock(req) => Response::BeginBlock(self.begin_block(req)),
+            Request::CheckTx(req) => Response::CheckTx(self.check_tx(req)),
+            Request::DeliverTx(req) => Response::DeliverTx(self.deliver_tx(req)),
+            Request::EndBlock(req) => Response::EndBlock(self.end_block(req)),
+            Request::Commit => Response::Commit(self.commit()),
+            Request::ListSnapshots => Response::ListSnapshots(self.list_snapshots()),
+            Request::OfferSnapshot(req) => Response::OfferSnapshot(self.offer_snapshot(req)),
+            Request::LoadSnapshotChunk(req) => {
+                Response::LoadSnapshotChunk(self.load_snapshot_chunk(req))
+            },
+            Request::ApplySnapshotChunk(req) => {
+                Response::ApplySnapshotChunk(self.apply_snapshot_chunk(req))
+            },
+            Request::PrepareProposal(req) => Response::PrepareProposal(self.prepare_proposal(req)),
+            Request::ProcessProposal(req) => Response::ProcessProposal(self.process_proposal(req)),
+            Request::ExtendVote(req) => Response::ExtendVote(self.extend_vote(req)),
+            Request::VerifyVoteExtension(req) => {
+                Response::VerifyVoteExtension(self.verify_vote_extension(req))
+            },
+            Request::FinalizeBlock(req) => Response::FinalizeBlock(self.finalize_block(req)),
         }
     }
 }
+
+/// The response to an `Info` request for an application that has not yet
+/// committed any blocks.
+pub(crate) fn default_info() -> response::Info {
+    response::Info {
+        data: Default::default(),
+        version: Default::default(),
+        app_version: 0,
+        last_block_height: 0_u32.into(),
+        last_block_app_hash: Default::default(),
+    }
+}
+
+/// The response to a `FinalizeBlock` request that executes none of the
+/// block's transactions and leaves the application hash empty.
+pub(crate) fn default_finalize_block(request: request::FinalizeBlock) -> response::FinalizeBlock {
+    response::FinalizeBlock {
+        events: vec![],
+        tx_results: vec![Default::default(); request.txs.len()],
+        validator_updates: vec![],
+        consensus_param_updates: None,
+        app_hash: Default::default(),
+    }
+}
+
+/// The response to a `PrepareProposal` request that proposes the given
+/// transactions as they are, up to the request's `max_tx_bytes`.
+pub(crate) fn default_prepare_proposal(
+    request: request::PrepareProposal,
+) -> response::PrepareProposal {
+    let max_tx_bytes = request.max_tx_bytes;
+    let mut total_bytes = 0;
+    let txs = request
+        .txs
+        .into_iter()
+        .take_while(|tx| {
+            total_bytes += tx.len() as i64;
+            total_bytes <= max_tx_bytes
+        })
+        .collect();
+    response::PrepareProposal { txs }
+}
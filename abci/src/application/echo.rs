@@ -1,9 +1,15 @@
 //! Trivial ABCI echo application
 
-use crate::Application;
+use crate::{Consensus, Info, Mempool, Snapshot};
 
 /// Trivial echo application, mainly for testing purposes.
 #[derive(Clone, Default)]
 pub struct EchoApp;
 
-impl Application for EchoApp {}
+impl Consensus for EchoApp {}
+
+impl Mempool for EchoApp {}
+
+impl Info for EchoApp {}
+
+impl Snapshot for EchoApp {}
@@ -1,23 +1,51 @@
-//! In-memory key/value store ABCI application.
+//! Key/value store ABCI application.
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, VecDeque},
+    convert::{TryFrom, TryInto},
+    fs,
+    io::{self, Write},
+    path::PathBuf,
     sync::mpsc::{channel, Receiver, Sender},
 };
 
-use bytes::BytesMut;
-use tendermint_proto::abci::{
-    Event, EventAttribute, RequestCheckTx, RequestDeliverTx, RequestInfo, RequestQuery,
-    ResponseCheckTx, ResponseCommit, ResponseDeliverTx, ResponseInfo, ResponseQuery,
+use bytes::{Buf, Bytes, BytesMut};
+use sha2::{Digest, Sha256};
+use subtle_encoding::base64;
+use tendermint::{
+    abci::{
+        request, response,
+        response::{ApplySnapshotChunkResult, OfferSnapshot},
+        types, Event, EventAttributeIndexExt,
+    },
+    merkle::{
+        proof::ProofOps, proofs_from_byte_vectors, simple_hash_from_byte_vectors, ProofOperator,
+        ValueOp,
+    },
+    vote::Power,
+    PublicKey,
 };
-use tracing::{debug, info};
+use tracing::{debug, error, info, warn};
 
-use crate::{codec::MAX_VARINT_LENGTH, Application, Error};
+use crate::{Consensus, Error, Info, Mempool, Snapshot};
 
 /// In-memory, hashmap-backed key/value store ABCI application.
 ///
 /// This structure effectively just serves as a handle to the actual key/value
-/// store - the [`KeyValueStoreDriver`].
+/// store - the [`KeyValueStoreDriver`]. The driver keeps its state in memory,
+/// but can optionally persist each committed state to disk (see
+/// [`KeyValueStoreDriver::persist_to`]).
+///
+/// The application's hash is the root of a simple Merkle tree whose leaves are
+/// the store's key/value pairs, ordered by key. Queries with `prove` set are
+/// answered from the last committed state, along with a [`PROOF_OP_VALUE`]
+/// proof of the value against that state's application hash.
+///
+/// As with Tendermint's Go-based `persistent_kvstore`, transactions of the
+/// form `val:<pubkey>!<power>`, where `<pubkey>` is a base64-encoded Ed25519
+/// public key, update the validator set. A power of 0 removes the validator.
+/// The current validators are kept in the store under `val:<pubkey>` keys, and
+/// the updates delivered in each block are returned from `EndBlock`.
 ///
 /// ## Example
 /// ```rust
@@ -27,8 +55,12 @@ use crate::{codec::MAX_VARINT_LENGTH, Application, Error};
 /// // Create our key/value store application
 /// let (app, driver) = KeyValueStoreApp::new();
 /// // Create our server, binding it to TCP port 26658 on localhost and
-/// // supplying it with our key/value store application
-/// let server = ServerBuilder::default().bind("127.0.0.1:26658", app).unwrap();
+/// // supplying it with our key/value store application. Our single client
+/// // connection issues requests of all kinds, unlike Tendermint's.
+/// let server = ServerBuilder::default()
+///     .enforce_connection_roles(false)
+///     .bind("127.0.0.1:26658", app)
+///     .unwrap();
 /// let server_addr = server.local_addr();
 ///
 /// // We want the driver and the server to run in the background while we
@@ -70,9 +102,18 @@ pub struct KeyValueStoreApp {
 
 impl KeyValueStoreApp {
     /// Constructor.
+    ///
+    /// The resulting driver does not take any snapshots, but can still be
+    /// restored from snapshots taken by other instances.
     pub fn new() -> (Self, KeyValueStoreDriver) {
+        Self::with_snapshot_config(SnapshotConfig::default())
+    }
+
+    /// Constructor for an application whose driver periodically takes
+    /// snapshots of its state, as configured.
+    pub fn with_snapshot_config(config: SnapshotConfig) -> (Self, KeyValueStoreDriver) {
         let (cmd_tx, cmd_rx) = channel();
-        (Self { cmd_tx }, KeyValueStoreDriver::new(cmd_rx))
+        (Self { cmd_tx }, KeyValueStoreDriver::new(cmd_rx, config))
     }
 
     /// Attempt to retrieve the value associated with the given key.
@@ -88,6 +129,24 @@ impl KeyValueStoreApp {
         channel_recv(&result_rx)
     }
 
+    /// Attempt to retrieve the value associated with the given key as of the
+    /// last commit, along with a proof of its inclusion in the committed
+    /// state if it exists.
+    pub fn get_with_proof<K: AsRef<str>>(
+        &self,
+        key: K,
+    ) -> Result<(i64, Option<(String, ProofOps)>), Error> {
+        let (result_tx, result_rx) = channel();
+        channel_send(
+            &self.cmd_tx,
+            Command::GetWithProof {
+                key: key.as_ref().to_string(),
+                result_tx,
+            },
+        )?;
+        channel_recv(&result_rx)
+    }
+
     /// Attempt to set the value associated with the given key.
     ///
     /// Optionally returns any pre-existing value associated with the given
@@ -108,10 +167,39 @@ impl KeyValueStoreApp {
         )?;
         channel_recv(&result_rx)
     }
+
+    /// Apply the given validator update to the validators in the store.
+    ///
+    /// Returns `false` if the update attempts to remove a validator that is
+    /// not in the store.
+    fn update_validator(&self, update: types::ValidatorUpdate) -> Result<bool, Error> {
+        let (result_tx, result_rx) = channel();
+        channel_send(&self.cmd_tx, Command::UpdateValidator { update, result_tx })?;
+        channel_recv(&result_rx)
+    }
+
+    /// Take the validator updates applied since the last call.
+    fn take_validator_updates(&self) -> Result<Vec<types::ValidatorUpdate>, Error> {
+        let (result_tx, result_rx) = channel();
+        channel_send(&self.cmd_tx, Command::TakeValidatorUpdates { result_tx })?;
+        channel_recv(&result_rx)
+    }
+
+    fn deliver_validator_tx(&self, update: types::ValidatorUpdate) -> response::DeliverTx {
+        let pub_key = encode_pub_key(&update.pub_key);
+        if !self.update_validator(update).unwrap() {
+            return response::DeliverTx {
+                code: CODE_TYPE_UNAUTHORIZED,
+                log: format!("Cannot remove non-existent validator {}", pub_key),
+                ..Default::default()
+            };
+        }
+        Default::default()
+    }
 }
 
-impl Application for KeyValueStoreApp {
-    fn info(&self, request: RequestInfo) -> ResponseInfo {
+impl Info for KeyValueStoreApp {
+    fn info(&self, request: request::Info) -> response::Info {
         debug!(
             "Got info request. Tendermint version: {}; Block version: {}; P2P version: {}",
             request.version, request.block_version, request.p2p_version
@@ -121,67 +209,104 @@ impl Application for KeyValueStoreApp {
         channel_send(&self.cmd_tx, Command::GetInfo { result_tx }).unwrap();
         let (last_block_height, last_block_app_hash) = channel_recv(&result_rx).unwrap();
 
-        ResponseInfo {
+        response::Info {
             data: "kvstore-rs".to_string(),
             version: "0.1.0".to_string(),
             app_version: 1,
-            last_block_height,
+            last_block_height: last_block_height.try_into().unwrap(),
             last_block_app_hash: last_block_app_hash.into(),
         }
     }
 
-    fn query(&self, request: RequestQuery) -> ResponseQuery {
+    fn query(&self, request: request::Query) -> response::Query {
         let key = match std::str::from_utf8(&request.data) {
             Ok(s) => s,
             Err(e) => panic!("Failed to intepret key as UTF-8: {}", e),
         };
         debug!("Attempting to get key: {}", key);
-        match self.get(key) {
-            Ok((height, value_opt)) => match value_opt {
-                Some(value) => ResponseQuery {
+        let result = if request.prove {
+            self.get_with_proof(key)
+                .map(|(height, value_opt)| match value_opt {
+                    Some((value, proof)) => (height, Some(value), Some(proof)),
+                    None => (height, None, None),
+                })
+        } else {
+            self.get(key)
+                .map(|(height, value_opt)| (height, value_opt, None))
+        };
+        match result {
+            Ok((height, value_opt, proof)) => match value_opt {
+                Some(value) => response::Query {
                     code: 0,
                     log: "exists".to_string(),
                     info: "".to_string(),
                     index: 0,
                     key: request.data,
                     value: value.into_bytes().into(),
-                    proof_ops: None,
-                    height,
+                    proof,
+                    height: height.try_into().unwrap(),
                     codespace: "".to_string(),
                 },
-                None => ResponseQuery {
+                None => response::Query {
                     code: 0,
                     log: "does not exist".to_string(),
                     info: "".to_string(),
                     index: 0,
                     key: request.data,
                     value: Default::default(),
-                    proof_ops: None,
-                    height,
+                    proof: None,
+                    height: height.try_into().unwrap(),
                     codespace: "".to_string(),
                 },
             },
             Err(e) => panic!("Failed to get key \"{}\": {:?}", key, e),
         }
     }
+}
 
-    fn check_tx(&self, _request: RequestCheckTx) -> ResponseCheckTx {
-        ResponseCheckTx {
-            code: 0,
+impl Mempool for KeyValueStoreApp {
+    fn check_tx(&self, request: request::CheckTx) -> response::CheckTx {
+        let (code, log) = match parse_validator_tx(&request.tx) {
+            Some(Err(e)) => (CODE_TYPE_ENCODING_ERROR, e),
+            _ => (0, "".to_string()),
+        };
+        response::CheckTx {
+            code,
             data: Default::default(),
-            log: "".to_string(),
+            log,
             info: "".to_string(),
             gas_wanted: 1,
             gas_used: 0,
             events: vec![],
             codespace: "".to_string(),
-            mempool_error: "".to_string(),
             priority: 0,
             sender: "".to_string(),
         }
     }
+}
+
+impl Consensus for KeyValueStoreApp {
+    fn init_chain(&self, request: request::InitChain) -> response::InitChain {
+        for update in request.validators {
+            self.update_validator(update).unwrap();
+        }
+        // The genesis validators are not updates to the validator set
+        let _ = self.take_validator_updates().unwrap();
+        Default::default()
+    }
 
-    fn deliver_tx(&self, request: RequestDeliverTx) -> ResponseDeliverTx {
+    fn deliver_tx(&self, request: request::DeliverTx) -> response::DeliverTx {
+        match parse_validator_tx(&request.tx) {
+            Some(Ok(update)) => return self.deliver_validator_tx(update),
+            Some(Err(e)) => {
+                return response::DeliverTx {
+                    code: CODE_TYPE_ENCODING_ERROR,
+                    log: e,
+                    ..Default::default()
+                }
+            },
+            None => {},
+        }
         let tx = std::str::from_utf8(&request.tx).unwrap();
         let tx_parts = tx.split('=').collect::<Vec<&str>>();
         let (key, value) = if tx_parts.len() == 2 {
@@ -190,66 +315,250 @@ impl Application for KeyValueStoreApp {
             (tx, tx)
         };
         let _ = self.set(key, value).unwrap();
-        ResponseDeliverTx {
+        response::DeliverTx {
             code: 0,
             data: Default::default(),
             log: "".to_string(),
             info: "".to_string(),
             gas_wanted: 0,
             gas_used: 0,
-            events: vec![Event {
-                r#type: "app".to_string(),
-                attributes: vec![
-                    EventAttribute {
-                        key: "key".to_string(),
-                        value: key.to_string(),
-                        index: true,
-                    },
-                    EventAttribute {
-                        key: "index_key".to_string(),
-                        value: "index is working".to_string(),
-                        index: true,
-                    },
-                    EventAttribute {
-                        key: "noindex_key".to_string(),
-                        value: "index is working".to_string(),
-                        index: false,
-                    },
+            events: vec![Event::new(
+                "app",
+                vec![
+                    ("key", key).index(),
+                    ("index_key", "index is working").index(),
+                    ("noindex_key", "index is working").no_index(),
                 ],
-            }],
+            )],
             codespace: "".to_string(),
         }
     }
 
-    fn commit(&self) -> ResponseCommit {
+    fn end_block(&self, _request: request::EndBlock) -> response::EndBlock {
+        response::EndBlock {
+            validator_updates: self.take_validator_updates().unwrap(),
+            consensus_param_updates: None,
+            events: vec![],
+        }
+    }
+
+    fn finalize_block(&self, request: request::FinalizeBlock) -> response::FinalizeBlock {
+        let tx_results = request
+            .txs
+            .into_iter()
+            .map(|tx| self.deliver_tx(request::DeliverTx { tx }).into())
+            .collect();
+        // The hash of the block's resulting state is reported right away, and
+        // becomes the committed application hash once the block is committed
+        let (result_tx, result_rx) = channel();
+        channel_send(&self.cmd_tx, Command::GetAppHash { result_tx }).unwrap();
+        response::FinalizeBlock {
+            events: vec![],
+            tx_results,
+            validator_updates: self.take_validator_updates().unwrap(),
+            consensus_param_updates: None,
+            app_hash: channel_recv(&result_rx).unwrap().into(),
+        }
+    }
+
+    fn commit(&self) -> response::Commit {
         let (result_tx, result_rx) = channel();
         channel_send(&self.cmd_tx, Command::Commit { result_tx }).unwrap();
-        let (height, app_hash) = channel_recv(&result_rx).unwrap();
+        let (height, app_hash, retain_height) = channel_recv(&result_rx).unwrap();
         info!("Committed height {}", height);
-        ResponseCommit {
+        response::Commit {
             data: app_hash.into(),
-            retain_height: height - 1,
+            retain_height: retain_height.try_into().unwrap(),
+        }
+    }
+}
+
+impl Snapshot for KeyValueStoreApp {
+    fn list_snapshots(&self) -> response::ListSnapshots {
+        let (result_tx, result_rx) = channel();
+        channel_send(&self.cmd_tx, Command::ListSnapshots { result_tx }).unwrap();
+        response::ListSnapshots {
+            snapshots: channel_recv(&result_rx).unwrap(),
+        }
+    }
+
+    fn offer_snapshot(&self, request: request::OfferSnapshot) -> response::OfferSnapshot {
+        let (result_tx, result_rx) = channel();
+        channel_send(
+            &self.cmd_tx,
+            Command::OfferSnapshot {
+                snapshot: request.snapshot,
+                app_hash: request.app_hash,
+                result_tx,
+            },
+        )
+        .unwrap();
+        channel_recv(&result_rx).unwrap()
+    }
+
+    fn load_snapshot_chunk(
+        &self,
+        request: request::LoadSnapshotChunk,
+    ) -> response::LoadSnapshotChunk {
+        let (result_tx, result_rx) = channel();
+        channel_send(
+            &self.cmd_tx,
+            Command::LoadSnapshotChunk {
+                height: request.height.value(),
+                format: request.format,
+                chunk: request.chunk,
+                result_tx,
+            },
+        )
+        .unwrap();
+        response::LoadSnapshotChunk {
+            chunk: channel_recv(&result_rx).unwrap(),
+        }
+    }
+
+    fn apply_snapshot_chunk(
+        &self,
+        request: request::ApplySnapshotChunk,
+    ) -> response::ApplySnapshotChunk {
+        let (result_tx, result_rx) = channel();
+        channel_send(
+            &self.cmd_tx,
+            Command::ApplySnapshotChunk {
+                index: request.index,
+                chunk: request.chunk,
+                sender: request.sender,
+                result_tx,
+            },
+        )
+        .unwrap();
+        channel_recv(&result_rx).unwrap()
+    }
+}
+
+/// The prefix of transactions that update the validator set, and of the keys
+/// under which the current validators are stored.
+pub const VALIDATOR_TX_PREFIX: &str = "val:";
+
+/// The code of responses to malformed validator update transactions.
+pub const CODE_TYPE_ENCODING_ERROR: u32 = 1;
+
+/// The code of responses to transactions that attempt to remove a validator
+/// that does not exist.
+pub const CODE_TYPE_UNAUTHORIZED: u32 = 3;
+
+/// The only snapshot format produced and understood by the key/value store.
+///
+/// The store's contents are ordered by key, and each key and value is encoded
+/// with a varint length prefix. The resulting byte stream is then split into
+/// chunks. A snapshot's hash is the SHA-256 hash of the byte stream, and its
+/// metadata is the concatenation of the SHA-256 hashes of its chunks.
+pub const SNAPSHOT_FORMAT: u32 = 1;
+
+const CHUNK_HASH_SIZE: usize = 32;
+
+/// The type of the [`ProofOp`](tendermint::merkle::proof::ProofOp)s produced
+/// by the key/value store, which can be
+/// verified by the default [`ProofRuntime`](tendermint::merkle::ProofRuntime).
+pub use tendermint::merkle::PROOF_OP_VALUE;
+
+/// Configures the snapshots taken by the [`KeyValueStoreDriver`].
+///
+/// While snapshots are enabled, the application only lets Tendermint prune
+/// blocks below the height of the oldest retained snapshot, so that nodes
+/// restoring it can fetch the blocks they need to catch up.
+#[derive(Debug, Clone, Copy)]
+pub struct SnapshotConfig {
+    /// Take a snapshot every `interval` blocks. Snapshots are disabled if this
+    /// is 0.
+    pub interval: u64,
+    /// The maximum size, in bytes, of each snapshot chunk.
+    pub chunk_size: usize,
+    /// The number of most recent snapshots to retain.
+    pub keep_recent: usize,
+}
+
+impl Default for SnapshotConfig {
+    fn default() -> Self {
+        Self {
+            interval: 0,
+            chunk_size: 64 * 1024,
+            keep_recent: 2,
         }
     }
 }
 
+/// A snapshot taken by the driver, along with its chunks.
+#[derive(Debug)]
+struct StoredSnapshot {
+    snapshot: types::Snapshot,
+    chunks: Vec<Bytes>,
+}
+
+/// A snapshot restoration in progress.
+#[derive(Debug)]
+struct Restoration {
+    snapshot: types::Snapshot,
+    app_hash: Bytes,
+    chunks: Vec<Option<Bytes>>,
+}
+
 /// Manages key/value store state.
 #[derive(Debug)]
 pub struct KeyValueStoreDriver {
-    store: HashMap<String, String>,
+    store: BTreeMap<String, String>,
+    committed: BTreeMap<String, String>,
     height: i64,
     app_hash: Vec<u8>,
     cmd_rx: Receiver<Command>,
+    snapshot_config: SnapshotConfig,
+    snapshots: VecDeque<StoredSnapshot>,
+    restoration: Option<Restoration>,
+    validator_updates: Vec<types::ValidatorUpdate>,
+    state_path: Option<PathBuf>,
 }
 
 impl KeyValueStoreDriver {
-    fn new(cmd_rx: Receiver<Command>) -> Self {
+    fn new(cmd_rx: Receiver<Command>, snapshot_config: SnapshotConfig) -> Self {
         Self {
-            store: HashMap::new(),
+            store: BTreeMap::new(),
+            committed: BTreeMap::new(),
             height: 0,
-            app_hash: vec![0_u8; MAX_VARINT_LENGTH],
+            app_hash: app_hash(&BTreeMap::new()),
             cmd_rx,
+            snapshot_config,
+            snapshots: VecDeque::new(),
+            restoration: None,
+            validator_updates: Vec::new(),
+            state_path: None,
+        }
+    }
+
+    /// Persist each committed state to the file at the given path, so that the
+    /// application survives restarts.
+    ///
+    /// If the file already exists, the driver's state is loaded from it.
+    /// Failures to write the file are logged, and leave the driver running.
+    pub fn persist_to<P: Into<PathBuf>>(mut self, path: P) -> Result<Self, Error> {
+        let path = path.into();
+        match fs::read(&path) {
+            Ok(data) => {
+                let (height, store) = decode_state(data.into()).ok_or_else(|| {
+                    Error::io(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("corrupted key/value store state in {}", path.display()),
+                    ))
+                })?;
+                info!("Loaded state at height {} from {}", height, path.display());
+                self.app_hash = app_hash(&store);
+                self.committed = store.clone();
+                self.store = store;
+                self.height = height;
+            },
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {},
+            Err(e) => return Err(e.into()),
         }
+        self.state_path = Some(path);
+        Ok(self)
     }
 
     /// Run the driver in the current thread (blocking).
@@ -262,10 +571,11 @@ impl KeyValueStoreDriver {
                 },
                 Command::Get { key, result_tx } => {
                     debug!("Getting value for \"{}\"", key);
-                    channel_send(
-                        &result_tx,
-                        (self.height, self.store.get(&key).map(Clone::clone)),
-                    )?;
+                    channel_send(&result_tx, (self.height, self.store.get(&key).cloned()))?;
+                },
+                Command::GetWithProof { key, result_tx } => {
+                    debug!("Getting value and proof for \"{}\"", key);
+                    channel_send(&result_tx, (self.height, self.get_with_proof(&key)))?;
                 },
                 Command::Set {
                     key,
@@ -275,19 +585,326 @@ impl KeyValueStoreDriver {
                     debug!("Setting \"{}\" = \"{}\"", key, value);
                     channel_send(&result_tx, self.store.insert(key, value))?;
                 },
+                Command::UpdateValidator { update, result_tx } => {
+                    channel_send(&result_tx, self.update_validator(update))?
+                },
+                Command::TakeValidatorUpdates { result_tx } => {
+                    channel_send(&result_tx, std::mem::take(&mut self.validator_updates))?
+                },
+                Command::GetAppHash { result_tx } => {
+                    channel_send(&result_tx, app_hash(&self.store))?
+                },
                 Command::Commit { result_tx } => self.commit(result_tx)?,
+                Command::ListSnapshots { result_tx } => channel_send(
+                    &result_tx,
+                    self.snapshots.iter().map(|s| s.snapshot.clone()).collect(),
+                )?,
+                Command::OfferSnapshot {
+                    snapshot,
+                    app_hash,
+                    result_tx,
+                } => channel_send(&result_tx, self.offer_snapshot(snapshot, app_hash))?,
+                Command::LoadSnapshotChunk {
+                    height,
+                    format,
+                    chunk,
+                    result_tx,
+                } => channel_send(&result_tx, self.load_snapshot_chunk(height, format, chunk))?,
+                Command::ApplySnapshotChunk {
+                    index,
+                    chunk,
+                    sender,
+                    result_tx,
+                } => channel_send(&result_tx, self.apply_snapshot_chunk(index, chunk, sender))?,
             }
         }
     }
 
-    fn commit(&mut self, result_tx: Sender<(i64, Vec<u8>)>) -> Result<(), Error> {
-        // As in the Go-based key/value store, simply encode the number of
-        // items as the "app hash"
-        let mut app_hash = BytesMut::with_capacity(MAX_VARINT_LENGTH);
-        prost::encoding::encode_varint(self.store.len() as u64, &mut app_hash);
-        self.app_hash = app_hash.to_vec();
+    fn get_with_proof(&self, key: &str) -> Option<(String, ProofOps)> {
+        let index = self.committed.keys().position(|k| k == key)?;
+        let value = self.committed[key].clone();
+        let (_, mut proofs) = proofs_from_byte_vectors(merkle_leaves(&self.committed));
+        let op = ValueOp::new(key.as_bytes().to_vec(), proofs.swap_remove(index));
+        Some((
+            value,
+            ProofOps {
+                ops: vec![op.proof_op()],
+            },
+        ))
+    }
+
+    fn update_validator(&mut self, update: types::ValidatorUpdate) -> bool {
+        let key = format!("{}{}", VALIDATOR_TX_PREFIX, encode_pub_key(&update.pub_key));
+        if update.power.value() == 0 {
+            if self.store.remove(&key).is_none() {
+                return false;
+            }
+            debug!("Removing validator \"{}\"", key);
+        } else {
+            debug!("Setting validator \"{}\" power to {}", key, update.power);
+            self.store.insert(key, update.power.to_string());
+        }
+        self.validator_updates.push(update);
+        true
+    }
+
+    fn commit(&mut self, result_tx: Sender<(i64, Vec<u8>, i64)>) -> Result<(), Error> {
+        self.app_hash = app_hash(&self.store);
+        self.committed = self.store.clone();
         self.height += 1;
-        channel_send(&result_tx, (self.height, self.app_hash.clone()))
+        // The committed state is still served from memory, and persisted
+        // again on the next commit
+        if let Err(e) = self.save_state() {
+            error!("Failed to persist state at height {}: {}", self.height, e);
+        }
+        let interval = self.snapshot_config.interval;
+        if interval > 0 && self.height as u64 % interval == 0 {
+            self.take_snapshot();
+        }
+        channel_send(
+            &result_tx,
+            (self.height, self.app_hash.clone(), self.retain_height()),
+        )
+    }
+
+    // Nodes restoring one of our snapshots need the blocks since its height to
+    // catch up, so blocks are only pruned up to the oldest snapshot we keep,
+    // and not at all until we have one
+    fn retain_height(&self) -> i64 {
+        if self.snapshot_config.interval == 0 {
+            return self.height - 1;
+        }
+        self.snapshots
+            .front()
+            .map_or(0, |s| s.snapshot.height.value() as i64)
+    }
+
+    // Atomically replaces the persisted state, if any, with the committed state
+    fn save_state(&self) -> Result<(), Error> {
+        let path = match &self.state_path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        let mut data = BytesMut::new();
+        prost::encoding::encode_varint(self.height as u64, &mut data);
+        data.extend_from_slice(&encode_store(&self.committed));
+        let tmp_path = path.with_extension("tmp");
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(&data)?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    fn take_snapshot(&mut self) {
+        let data = encode_store(&self.committed);
+        let chunk_size = self.snapshot_config.chunk_size.max(1);
+        // A snapshot must consist of at least one chunk, even if it is empty.
+        let chunks = if data.is_empty() {
+            vec![data.clone()]
+        } else {
+            (0..data.len())
+                .step_by(chunk_size)
+                .map(|start| data.slice(start..(start + chunk_size).min(data.len())))
+                .collect::<Vec<_>>()
+        };
+        let metadata = chunks
+            .iter()
+            .flat_map(|chunk| Sha256::digest(chunk).to_vec())
+            .collect::<Vec<u8>>();
+        let snapshot = types::Snapshot {
+            height: self.height.try_into().unwrap(),
+            format: SNAPSHOT_FORMAT,
+            chunks: chunks.len() as u32,
+            hash: Sha256::digest(&data).to_vec().into(),
+            metadata: metadata.into(),
+        };
+        info!(
+            "Took snapshot at height {} with {} chunk(s)",
+            self.height,
+            chunks.len()
+        );
+        self.snapshots
+            .push_back(StoredSnapshot { snapshot, chunks });
+        while self.snapshots.len() > self.snapshot_config.keep_recent {
+            self.snapshots.pop_front();
+        }
+    }
+
+    fn offer_snapshot(&mut self, snapshot: types::Snapshot, app_hash: Bytes) -> OfferSnapshot {
+        if snapshot.format != SNAPSHOT_FORMAT {
+            return OfferSnapshot::RejectFormat;
+        }
+        if snapshot.chunks == 0
+            || snapshot.metadata.len() != snapshot.chunks as usize * CHUNK_HASH_SIZE
+        {
+            return OfferSnapshot::Reject;
+        }
+        info!("Restoring snapshot at height {}", snapshot.height);
+        self.restoration = Some(Restoration {
+            chunks: vec![None; snapshot.chunks as usize],
+            snapshot,
+            app_hash,
+        });
+        OfferSnapshot::Accept
+    }
+
+    fn load_snapshot_chunk(&self, height: u64, format: u32, chunk: u32) -> Bytes {
+        self.snapshots
+            .iter()
+            .find(|s| s.snapshot.height.value() == height && s.snapshot.format == format)
+            .and_then(|s| s.chunks.get(chunk as usize).cloned())
+            .unwrap_or_default()
+    }
+
+    fn apply_snapshot_chunk(
+        &mut self,
+        index: u32,
+        chunk: Bytes,
+        sender: String,
+    ) -> response::ApplySnapshotChunk {
+        let restoration = match self.restoration.as_mut() {
+            Some(r) => r,
+            None => return apply_snapshot_chunk_result(ApplySnapshotChunkResult::Abort),
+        };
+        let index = index as usize;
+        if index >= restoration.chunks.len() {
+            self.restoration = None;
+            return apply_snapshot_chunk_result(ApplySnapshotChunkResult::RejectSnapshot);
+        }
+        let expected_hash =
+            &restoration.snapshot.metadata[index * CHUNK_HASH_SIZE..(index + 1) * CHUNK_HASH_SIZE];
+        if Sha256::digest(&chunk).as_slice() != expected_hash {
+            warn!(
+                "Snapshot chunk {} from {:?} failed verification",
+                index, sender
+            );
+            return response::ApplySnapshotChunk {
+                result: ApplySnapshotChunkResult::Retry,
+                refetch_chunks: vec![index as u32],
+                reject_senders: vec![sender],
+            };
+        }
+        restoration.chunks[index] = Some(chunk);
+        if restoration.chunks.iter().any(Option::is_none) {
+            return apply_snapshot_chunk_result(ApplySnapshotChunkResult::Accept);
+        }
+
+        // We have all of the chunks, so we can attempt to restore our state
+        let restoration = self.restoration.take().unwrap();
+        let mut data = BytesMut::new();
+        for chunk in restoration.chunks.into_iter().flatten() {
+            data.extend_from_slice(&chunk);
+        }
+        let store = match decode_snapshot(data.freeze()) {
+            Some(store) => store,
+            None => return apply_snapshot_chunk_result(ApplySnapshotChunkResult::RejectSnapshot),
+        };
+        let restored_app_hash = app_hash(&store);
+        if restored_app_hash != restoration.app_hash.as_ref() {
+            warn!("Restored snapshot does not match the expected app hash");
+            return apply_snapshot_chunk_result(ApplySnapshotChunkResult::RejectSnapshot);
+        }
+        self.committed = store.clone();
+        self.store = store;
+        self.height = restoration.snapshot.height.value() as i64;
+        self.app_hash = restored_app_hash;
+        if let Err(e) = self.save_state() {
+            warn!("Failed to persist restored state: {}", e);
+            return apply_snapshot_chunk_result(ApplySnapshotChunkResult::Abort);
+        }
+        info!("Restored snapshot at height {}", self.height);
+        apply_snapshot_chunk_result(ApplySnapshotChunkResult::Accept)
+    }
+}
+
+// The root of the simple Merkle tree over the given key/value pairs
+fn app_hash(store: &BTreeMap<String, String>) -> Vec<u8> {
+    simple_hash_from_byte_vectors(merkle_leaves(store)).to_vec()
+}
+
+// As in Go's `merkle.KVPair`, each leaf is the length-prefixed key followed by
+// the length-prefixed SHA-256 hash of the value
+fn merkle_leaves(store: &BTreeMap<String, String>) -> Vec<Vec<u8>> {
+    store
+        .iter()
+        .map(|(key, value)| ValueOp::leaf(key.as_bytes(), value.as_bytes()))
+        .collect()
+}
+
+// Parses a `val:<pubkey>!<power>` transaction, returning `None` if the
+// transaction is not a validator update transaction at all
+fn parse_validator_tx(tx: &[u8]) -> Option<Result<types::ValidatorUpdate, String>> {
+    let update = tx.strip_prefix(VALIDATOR_TX_PREFIX.as_bytes())?;
+    Some(decode_validator_update(update))
+}
+
+fn decode_validator_update(update: &[u8]) -> Result<types::ValidatorUpdate, String> {
+    let update = std::str::from_utf8(update).map_err(|e| e.to_string())?;
+    let (pub_key, power) = update.split_once('!').ok_or_else(|| {
+        format!(
+            "Expected \"{}<pubkey>!<power>\", got \"{}{}\"",
+            VALIDATOR_TX_PREFIX, VALIDATOR_TX_PREFIX, update
+        )
+    })?;
+    let pub_key = base64::decode(pub_key)
+        .ok()
+        .and_then(|bytes| PublicKey::from_raw_ed25519(&bytes))
+        .ok_or_else(|| format!("Invalid Ed25519 public key \"{}\"", pub_key))?;
+    let power = power
+        .parse::<u64>()
+        .ok()
+        .and_then(|power| Power::try_from(power).ok())
+        .ok_or_else(|| format!("Invalid voting power \"{}\"", power))?;
+    Ok(types::ValidatorUpdate { pub_key, power })
+}
+
+fn encode_pub_key(pub_key: &PublicKey) -> String {
+    String::from_utf8(base64::encode(pub_key.to_bytes())).unwrap()
+}
+
+// Each key and value is encoded with a varint length prefix, ordered by key
+fn encode_store(store: &BTreeMap<String, String>) -> Bytes {
+    let mut data = BytesMut::new();
+    for (key, value) in store {
+        prost::encoding::encode_varint(key.len() as u64, &mut data);
+        data.extend_from_slice(key.as_bytes());
+        prost::encoding::encode_varint(value.len() as u64, &mut data);
+        data.extend_from_slice(value.as_bytes());
+    }
+    data.freeze()
+}
+
+// The persisted state is the varint-encoded height of the last commit,
+// followed by the committed store in the snapshot format
+fn decode_state(mut data: Bytes) -> Option<(i64, BTreeMap<String, String>)> {
+    let height = prost::encoding::decode_varint(&mut data).ok()?;
+    let height = i64::try_from(height).ok()?;
+    Some((height, decode_snapshot(data)?))
+}
+
+fn decode_snapshot(mut data: Bytes) -> Option<BTreeMap<String, String>> {
+    fn decode_string(data: &mut Bytes) -> Option<String> {
+        let len = prost::encoding::decode_varint(data).ok()? as usize;
+        if data.remaining() < len {
+            return None;
+        }
+        String::from_utf8(data.split_to(len).to_vec()).ok()
+    }
+
+    let mut store = BTreeMap::new();
+    while data.has_remaining() {
+        let key = decode_string(&mut data)?;
+        let value = decode_string(&mut data)?;
+        store.insert(key, value);
+    }
+    Some(store)
+}
+
+fn apply_snapshot_chunk_result(result: ApplySnapshotChunkResult) -> response::ApplySnapshotChunk {
+    response::ApplySnapshotChunk {
+        result,
+        ..Default::default()
     }
 }
 
@@ -300,15 +917,59 @@ enum Command {
         key: String,
         result_tx: Sender<(i64, Option<String>)>,
     },
+    /// Get the committed value associated with `key`, along with a proof of
+    /// its inclusion in the committed state.
+    GetWithProof {
+        key: String,
+        result_tx: Sender<(i64, Option<(String, ProofOps)>)>,
+    },
     /// Set the value of `key` to to `value`.
     Set {
         key: String,
         value: String,
         result_tx: Sender<Option<String>>,
     },
+    /// Apply a validator update, returning whether it could be applied.
+    UpdateValidator {
+        update: types::ValidatorUpdate,
+        result_tx: Sender<bool>,
+    },
+    /// Take the validator updates applied since they were last taken.
+    TakeValidatorUpdates {
+        result_tx: Sender<Vec<types::ValidatorUpdate>>,
+    },
+    /// Compute the application's hash of its current, uncommitted state.
+    GetAppHash { result_tx: Sender<Vec<u8>> },
     /// Commit the current state of the application, which involves recomputing
-    /// the application's hash.
-    Commit { result_tx: Sender<(i64, Vec<u8>)> },
+    /// the application's hash, and determine the height below which blocks
+    /// may be pruned.
+    Commit {
+        result_tx: Sender<(i64, Vec<u8>, i64)>,
+    },
+    /// List the snapshots currently available.
+    ListSnapshots {
+        result_tx: Sender<Vec<types::Snapshot>>,
+    },
+    /// Begin restoring the given snapshot.
+    OfferSnapshot {
+        snapshot: types::Snapshot,
+        app_hash: Bytes,
+        result_tx: Sender<OfferSnapshot>,
+    },
+    /// Load a chunk of one of our snapshots.
+    LoadSnapshotChunk {
+        height: u64,
+        format: u32,
+        chunk: u32,
+        result_tx: Sender<Bytes>,
+    },
+    /// Apply a chunk of the snapshot currently being restored.
+    ApplySnapshotChunk {
+        index: u32,
+        chunk: Bytes,
+        sender: String,
+        result_tx: Sender<response::ApplySnapshotChunk>,
+    },
 }
 
 fn channel_send<T>(tx: &Sender<T>, value: T) -> Result<(), Error> {
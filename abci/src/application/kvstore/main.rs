@@ -1,7 +1,13 @@
-//! In-memory key/value store application for Tendermint.
+//! Key/value store application for Tendermint.
 
+use std::path::PathBuf;
 use structopt::StructOpt;
-use tendermint_abci::{KeyValueStoreApp, ServerBuilder};
+
+use tendermint_abci::{
+    Application, Error, KeyValueStoreApp, ProtocolVersion, RecordingApp, ServerBuilder,
+    SnapshotConfig,
+};
+use tendermint_config::net::Address;
 use tracing_subscriber::filter::LevelFilter;
 
 #[derive(Debug, StructOpt)]
@@ -14,11 +20,43 @@ struct Opt {
     #[structopt(short, long, default_value = "26658")]
     port: u16,
 
+    /// Bind the server to this `tcp://` or `unix://` address instead
+    /// (overrides --host and --port).
+    #[structopt(short, long)]
+    addr: Option<Address>,
+
     /// The default server read buffer size, in bytes, for each incoming client
     /// connection.
     #[structopt(short, long, default_value = "1048576")]
     read_buf_size: usize,
 
+    /// The version of the ABCI wire protocol to speak, which must match the
+    /// version of the Tendermint or CometBFT node (0.34, 0.35, 0.37 or 0.38).
+    #[structopt(long, default_value = "0.35", parse(try_from_str = parse_protocol_version))]
+    protocol_version: ProtocolVersion,
+
+    /// Take a state sync snapshot every this many blocks (disabled if 0).
+    #[structopt(long, default_value = "0")]
+    snapshot_interval: u64,
+
+    /// The maximum size, in bytes, of each state sync snapshot chunk.
+    #[structopt(long, default_value = "65536")]
+    snapshot_chunk_size: usize,
+
+    /// The number of most recent state sync snapshots to retain.
+    #[structopt(long, default_value = "2")]
+    snapshot_keep_recent: usize,
+
+    /// Persist the application's committed state to this file, and restore it
+    /// from the file on startup if it exists.
+    #[structopt(long, parse(from_os_str))]
+    db: Option<PathBuf>,
+
+    /// Record all requests and responses to this file, for subsequent replay
+    /// with `kvstore-replay`.
+    #[structopt(long, parse(from_os_str))]
+    record: Option<PathBuf>,
+
     /// Increase output logging verbosity to DEBUG level.
     #[structopt(short, long)]
     verbose: bool,
@@ -39,10 +77,35 @@ fn main() {
     };
     tracing_subscriber::fmt().with_max_level(log_level).init();
 
-    let (app, driver) = KeyValueStoreApp::new();
-    let server = ServerBuilder::new(opt.read_buf_size)
-        .bind(format!("{}:{}", opt.host, opt.port), app)
-        .unwrap();
+    let (app, driver) = KeyValueStoreApp::with_snapshot_config(SnapshotConfig {
+        interval: opt.snapshot_interval,
+        chunk_size: opt.snapshot_chunk_size,
+        keep_recent: opt.snapshot_keep_recent,
+    });
+    let driver = match &opt.db {
+        Some(path) => driver.persist_to(path).unwrap(),
+        None => driver,
+    };
     std::thread::spawn(move || driver.run());
+    match &opt.record {
+        Some(path) => serve(
+            &opt,
+            RecordingApp::create(app, path, opt.protocol_version).unwrap(),
+        ),
+        None => serve(&opt, app),
+    }
+}
+
+fn serve<App: Application>(opt: &Opt, app: App) {
+    let builder = ServerBuilder::new(opt.read_buf_size).protocol_version(opt.protocol_version);
+    let server = match &opt.addr {
+        Some(addr) => builder.bind_address(addr, app),
+        None => builder.bind(format!("{}:{}", opt.host, opt.port), app),
+    }
+    .unwrap();
     server.listen().unwrap();
 }
+
+fn parse_protocol_version(s: &str) -> Result<ProtocolVersion, String> {
+    s.parse().map_err(|e: Error| e.detail().to_string())
+}
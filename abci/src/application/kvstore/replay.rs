@@ -0,0 +1,67 @@
+//! Replays a session recorded by `kvstore-rs --record` against a fresh
+//! instance of the key/value store application, over the protocol version the
+//! session was recorded with, reporting any responses that differ from the
+//! recorded ones.
+
+use std::{fs::File, path::PathBuf, process};
+
+use structopt::StructOpt;
+use tendermint_abci::{recorder::replay, KeyValueStoreApp, SnapshotConfig};
+use tracing_subscriber::filter::LevelFilter;
+
+#[derive(Debug, StructOpt)]
+struct Opt {
+    /// The recording to replay.
+    #[structopt(parse(from_os_str))]
+    recording: PathBuf,
+
+    /// Take a state sync snapshot every this many blocks (disabled if 0). Must
+    /// match the setting used during recording.
+    #[structopt(long, default_value = "0")]
+    snapshot_interval: u64,
+
+    /// The maximum size, in bytes, of each state sync snapshot chunk. Must
+    /// match the setting used during recording.
+    #[structopt(long, default_value = "65536")]
+    snapshot_chunk_size: usize,
+
+    /// The number of most recent state sync snapshots to retain. Must match
+    /// the setting used during recording.
+    #[structopt(long, default_value = "2")]
+    snapshot_keep_recent: usize,
+
+    /// Increase output logging verbosity to DEBUG level.
+    #[structopt(short, long)]
+    verbose: bool,
+}
+
+fn main() {
+    let opt: Opt = Opt::from_args();
+    let log_level = if opt.verbose {
+        LevelFilter::DEBUG
+    } else {
+        LevelFilter::WARN
+    };
+    tracing_subscriber::fmt().with_max_level(log_level).init();
+
+    let (app, driver) = KeyValueStoreApp::with_snapshot_config(SnapshotConfig {
+        interval: opt.snapshot_interval,
+        chunk_size: opt.snapshot_chunk_size,
+        keep_recent: opt.snapshot_keep_recent,
+    });
+    std::thread::spawn(move || driver.run());
+
+    let recording = File::open(&opt.recording).unwrap();
+    let mismatches = replay(&app, recording).unwrap();
+    for mismatch in &mismatches {
+        println!("Mismatch for request #{}:", mismatch.index);
+        println!("  request:  {:?}", mismatch.request);
+        println!("  expected: {:?}", mismatch.expected);
+        println!("  actual:   {:?}", mismatch.actual);
+    }
+    if !mismatches.is_empty() {
+        println!("{} mismatch(es) found", mismatches.len());
+        process::exit(1);
+    }
+    println!("Replay matched the recording");
+}
@@ -0,0 +1,194 @@
+//! Asynchronous ABCI application interface.
+
+use async_trait::async_trait;
+use tendermint::abci::{request, response, Request, Response};
+
+use crate::application::{default_finalize_block, default_info, default_prepare_proposal};
+
+/// An asynchronous ABCI application.
+///
+/// As with the blocking [`Application`], methods take and return the validated
+/// domain types from [`tendermint::abci`].
+///
+/// Unlike the blocking [`Application`], asynchronous applications are not
+/// cloned for each incoming connection. Instead, a single instance is shared
+/// (via an [`Arc`]) between all of the connections to the [`AsyncServer`],
+/// which is why applications must be `Sync`. Shared state can therefore be
+/// kept directly in the application, guarded by whichever (ideally async-aware)
+/// synchronization primitives the application developer prefers.
+///
+/// [`Application`]: crate::Application
+/// [`Arc`]: std::sync::Arc
+/// [`AsyncServer`]: crate::AsyncServer
+#[async_trait]
+pub trait AsyncApplication: Send + Sync + 'static {
+    /// Echo back the same message as provided in the request.
+    async fn echo(&self, request: request::Echo) -> response::Echo {
+        response::Echo {
+            message: request.message,
+        }
+    }
+
+    /// Provide information about the ABCI application.
+    async fn info(&self, _request: request::Info) -> response::Info {
+        default_info()
+    }
+
+    /// Called once upon genesis.
+    async fn init_chain(&self, _request: request::InitChain) -> response::InitChain {
+        Default::default()
+    }
+
+    /// Query the application for data at the current or past height.
+    async fn query(&self, _request: request::Query) -> response::Query {
+        Default::default()
+    }
+
+    /// Check the given transaction before putting it into the local mempool.
+    async fn check_tx(&self, _request: request::CheckTx) -> response::CheckTx {
+        Default::default()
+    }
+
+    /// Signals the beginning of a new block, prior to any `DeliverTx` calls.
+    async fn begin_block(&self, _request: request::BeginBlock) -> response::BeginBlock {
+        Default::default()
+    }
+
+    /// Apply a transaction to the application's state.
+    async fn deliver_tx(&self, _request: request::DeliverTx) -> response::DeliverTx {
+        Default::default()
+    }
+
+    /// Signals the end of a block.
+    async fn end_block(&self, _request: request::EndBlock) -> response::EndBlock {
+        Default::default()
+    }
+
+    /// Commit the current state at the current height.
+    async fn commit(&self) -> response::Commit {
+        Default::default()
+    }
+
+    /// Prepare the transactions of a block that this node is proposing.
+    ///
+    /// By default, the transactions are proposed as given, up to the
+    /// request's `max_tx_bytes`.
+    async fn prepare_proposal(
+        &self,
+        request: request::PrepareProposal,
+    ) -> response::PrepareProposal {
+        default_prepare_proposal(request)
+    }
+
+    /// Validate a block proposed by another node. Accepts every proposal by
+    /// default.
+    async fn process_proposal(
+        &self,
+        _request: request::ProcessProposal,
+    ) -> response::ProcessProposal {
+        response::ProcessProposal::Accept
+    }
+
+    /// Provide the data to attach to this node's precommit vote. Empty by
+    /// default.
+    async fn extend_vote(&self, _request: request::ExtendVote) -> response::ExtendVote {
+        Default::default()
+    }
+
+    /// Verify the vote extension of another validator. Accepts every vote
+    /// extension by default.
+    async fn verify_vote_extension(
+        &self,
+        _request: request::VerifyVoteExtension,
+    ) -> response::VerifyVoteExtension {
+        response::VerifyVoteExtension::Accept
+    }
+
+    /// Execute a decided block, returning the hash of the application's
+    /// resulting state.
+    ///
+    /// The default returns a default result for each of the block's
+    /// transactions and an empty application hash, as with
+    /// [`Consensus::finalize_block`].
+    ///
+    /// [`Consensus::finalize_block`]: crate::Consensus::finalize_block
+    async fn finalize_block(&self, request: request::FinalizeBlock) -> response::FinalizeBlock {
+        default_finalize_block(request)
+    }
+
+    /// Used during state sync to discover available snapshots on peers.
+    async fn list_snapshots(&self) -> response::ListSnapshots {
+        Default::default()
+    }
+
+    /// Called when bootstrapping the node using state sync.
+    async fn offer_snapshot(&self, _request: request::OfferSnapshot) -> response::OfferSnapshot {
+        Default::default()
+    }
+
+    /// Used during state sync to retrieve chunks of snapshots from peers.
+    async fn load_snapshot_chunk(
+        &self,
+        _request: request::LoadSnapshotChunk,
+    ) -> response::LoadSnapshotChunk {
+        Default::default()
+    }
+
+    /// Apply the given snapshot chunk to the application's state.
+    async fn apply_snapshot_chunk(
+        &self,
+        _request: request::ApplySnapshotChunk,
+    ) -> response::ApplySnapshotChunk {
+        Default::default()
+    }
+}
+
+/// Provides a mechanism for the [`AsyncServer`] to execute incoming requests
+/// while expecting the correct response types.
+///
+/// [`AsyncServer`]: crate::AsyncServer
+#[async_trait]
+pub trait AsyncRequestDispatcher {
+    /// Executes the relevant application method based on the type of the
+    /// request, and produces the corresponding response.
+    async fn handle(&self, request: Request) -> Response;
+}
+
+// Implement `AsyncRequestDispatcher` for all `AsyncApplication`s.
+#[async_trait]
+impl<A: AsyncApplication> AsyncRequestDispatcher for A {
+    async fn handle(&self, request: Request) -> Response {
+        tracing::debug!("Incoming request: {:?}", request);
+        match request {
+            Request::Echo(req) => Response::Echo(self.echo(req).await),
+            Request::Flush => Response::Flush,
+            Request::Info(req) => Response::Info(self.info(req).await),
+            Request::InitChain(req) => Response::InitChain(self.init_chain(req).await),
+            Request::Query(req) => Response::Query(self.query(req).await),
+            Request::BeginBlock(req) => Response::BeginBlock(self.begin_block(req).await),
+            Request::CheckTx(req) => Response::CheckTx(self.check_tx(req).await),
+            Request::DeliverTx(req) => Response::DeliverTx(self.deliver_tx(req).await),
+            Request::EndBlock(req) => Response::EndBlock(self.end_block(req).await),
+            Request::Commit => Response::Commit(self.commit().await),
+            Request::ListSnapshots => Response::ListSnapshots(self.list_snapshots().await),
+            Request::OfferSnapshot(req) => Response::OfferSnapshot(self.offer_snapshot(req).await),
+            Request::LoadSnapshotChunk(req) => {
+                Response::LoadSnapshotChunk(self.load_snapshot_chunk(req).await)
+            },
+            Request::ApplySnapshotChunk(req) => {
+                Response::ApplySnapshotChunk(self.apply_snapshot_chunk(req).await)
+            },
+            Request::PrepareProposal(req) => {
+                Response::PrepareProposal(self.prepare_proposal(req).await)
+            },
+            Request::ProcessProposal(req) => {
+                Response::ProcessProposal(self.process_proposal(req).await)
+            },
+            Request::ExtendVote(req) => Response::ExtendVote(self.extend_vote(req).await),
+            Request::VerifyVoteExtension(req) => {
+                Response::VerifyVoteExtension(self.verify_vote_extension(req).await)
+            },
+            Request::FinalizeBlock(req) => Response::FinalizeBlock(self.finalize_block(req).await),
+        }
+    }
+}
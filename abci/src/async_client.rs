@@ -0,0 +1,642 @@
+//! Pipelined, Tokio-based ABCI client.
+
+use std::{
+    collections::VecDeque,
+    future::Future,
+    marker::PhantomData,
+    path::Path,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use futures::{future::poll_fn, Sink, SinkExt, StreamExt};
+use tendermint_config::net::Address;
+#[cfg(not(unix))]
+use tendermint_config::net::UNIX_PREFIX;
+use tendermint_proto::{
+    abci::{
+        RequestApplySnapshotChunk, RequestBeginBlock, RequestCheckTx, RequestCommit,
+        RequestDeliverTx, RequestEcho, RequestEndBlock, RequestFlush, RequestInfo,
+        RequestInitChain, RequestListSnapshots, RequestLoadSnapshotChunk, RequestOfferSnapshot,
+        RequestQuery, ResponseApplySnapshotChunk, ResponseBeginBlock, ResponseCheckTx,
+        ResponseCommit, ResponseDeliverTx, ResponseEcho, ResponseEndBlock, ResponseFlush,
+        ResponseInfo, ResponseInitChain, ResponseListSnapshots, ResponseLoadSnapshotChunk,
+        ResponseOfferSnapshot, ResponseQuery,
+    },
+    v0_35,
+    v0_38::{
+        self,
+        abci::{
+            RequestExtendVote, RequestFinalizeBlock, RequestPrepareProposal,
+            RequestProcessProposal, RequestVerifyVoteExtension, ResponseExtendVote,
+            ResponseFinalizeBlock, ResponsePrepareProposal, ResponseProcessProposal,
+            ResponseVerifyVoteExtension,
+        },
+    },
+};
+#[cfg(unix)]
+use tokio::net::UnixStream;
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::{TcpStream, ToSocketAddrs},
+    sync::oneshot,
+};
+use tokio_util::codec::Framed;
+use tracing::{debug, error};
+
+use crate::{
+    client::DEFAULT_CLIENT_READ_BUF_SIZE,
+    codec::{AsyncClientCodec, DEFAULT_MAX_MESSAGE_SIZE},
+    protocol::{
+        ClientRequest, Protocol, ProtocolV0_34, ProtocolV0_35, ProtocolV0_37, ProtocolV0_38,
+        ServerResponse, Translate,
+    },
+    Error, ProtocolVersion,
+};
+
+/// Builder for a pipelined, asynchronous ABCI client.
+pub struct AsyncClientBuilder {
+    read_buf_size: usize,
+    max_message_size: usize,
+    protocol_version: ProtocolVersion,
+}
+
+impl AsyncClientBuilder {
+    /// Builder constructor.
+    pub fn new(read_buf_size: usize) -> Self {
+        Self {
+            read_buf_size,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            protocol_version: ProtocolVersion::default(),
+        }
+    }
+
+    /// Limit the size of responses received from the server (100MB by
+    /// default).
+    pub fn max_message_size(mut self, max: usize) -> Self {
+        self.max_message_size = max;
+        self
+    }
+
+    /// The version of the ABCI wire protocol spoken by the server
+    /// ([`ProtocolVersion::V0_35`] by default).
+    ///
+    /// As with [`ClientBuilder::protocol_version`], requests that are not
+    /// part of the protocol fail without being sent.
+    ///
+    /// [`ClientBuilder::protocol_version`]: crate::ClientBuilder::protocol_version
+    pub fn protocol_version(mut self, version: ProtocolVersion) -> Self {
+        self.protocol_version = version;
+        self
+    }
+
+    /// Client constructor that attempts to connect to the given network
+    /// address.
+    ///
+    /// Must be called from within a Tokio runtime.
+    pub async fn connect<A: ToSocketAddrs>(self, addr: A) -> Result<AsyncClient, Error> {
+        let stream = TcpStream::connect(addr).await.map_err(Error::io)?;
+        Ok(self.client(stream))
+    }
+
+    /// Client constructor that attempts to connect to the Unix domain socket
+    /// at the given path. Only supported on Unix platforms.
+    ///
+    /// Must be called from within a Tokio runtime.
+    #[cfg(unix)]
+    pub async fn connect_unix<P: AsRef<Path>>(self, path: P) -> Result<AsyncClient, Error> {
+        let stream = UnixStream::connect(path).await.map_err(Error::io)?;
+        Ok(self.client(stream))
+    }
+
+    /// Client constructor that attempts to connect to the Unix domain socket
+    /// at the given path. Only supported on Unix platforms.
+    #[cfg(not(unix))]
+    pub async fn connect_unix<P: AsRef<Path>>(self, _path: P) -> Result<AsyncClient, Error> {
+        Err(Error::unsupported_transport(UNIX_PREFIX.to_string()))
+    }
+
+    /// Client constructor that attempts to connect to the given `tcp://` or
+    /// `unix://` address.
+    ///
+    /// Must be called from within a Tokio runtime.
+    pub async fn connect_address(self, addr: &Address) -> Result<AsyncClient, Error> {
+        match addr {
+            Address::Tcp { host, port, .. } => self.connect((host.as_str(), *port)).await,
+            Address::Unix { path } => self.connect_unix(path).await,
+        }
+    }
+
+    fn client<S>(self, stream: S) -> AsyncClient
+    where
+        S: AsyncRead + AsyncWrite + Send + 'static,
+    {
+        match self.protocol_version {
+            ProtocolVersion::V0_34 => self.protocol_client::<S, ProtocolV0_34>(stream),
+            ProtocolVersion::V0_35 => self.protocol_client::<S, ProtocolV0_35>(stream),
+            ProtocolVersion::V0_37 => self.protocol_client::<S, ProtocolV0_37>(stream),
+            ProtocolVersion::V0_38 => self.protocol_client::<S, ProtocolV0_38>(stream),
+        }
+    }
+
+    fn protocol_client<S, P>(self, stream: S) -> AsyncClient
+    where
+        S: AsyncRead + AsyncWrite + Send + 'static,
+        P: Protocol + 'static,
+        ServerResponse: From<P::Response>,
+    {
+        let codec = AsyncClientCodec::<P>::new(self.max_message_size);
+        let framed = Framed::with_capacity(stream, codec, self.read_buf_size);
+        let (sink, stream) = framed.split();
+        let sink = ProtocolSink::<_, P> {
+            inner: sink,
+            _protocol: PhantomData,
+        };
+        let stream = stream.map(|res| res.map(ServerResponse::from));
+        let pending = Arc::new(Mutex::new(Pending::default()));
+        tokio::spawn(receive_responses(stream, pending.clone()));
+        AsyncClient {
+            sink: Box::pin(sink),
+            pending,
+        }
+    }
+}
+
+impl Default for AsyncClientBuilder {
+    fn default() -> Self {
+        Self::new(DEFAULT_CLIENT_READ_BUF_SIZE)
+    }
+}
+
+/// Pipelined, asynchronous ABCI client.
+///
+/// As with Tendermint's own socket client, requests are queued without waiting
+/// for their responses, and are only guaranteed to be sent to the server once
+/// [`flush`](AsyncClient::flush) is called. Each queueing method produces a
+/// [`ResponseFuture`], which resolves once the server has responded to that
+/// specific request. Responses are matched to requests in the order in which
+/// the requests were queued.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// # async fn example() -> Result<(), tendermint_abci::Error> {
+/// use tendermint_abci::AsyncClientBuilder;
+/// use tendermint_proto::abci::RequestDeliverTx;
+///
+/// let mut client = AsyncClientBuilder::default()
+///     .connect("127.0.0.1:26658")
+///     .await?;
+/// let mut responses = Vec::new();
+/// for i in 0..100 {
+///     let tx = format!("key{}=value{}", i, i);
+///     responses.push(client.deliver_tx(RequestDeliverTx { tx: tx.into() }).await?);
+/// }
+/// // Wait for the server to process all of the queued requests
+/// client.flush().await?;
+/// for response in responses {
+///     assert_eq!(response.await?.code, 0);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct AsyncClient {
+    sink: Pin<Box<dyn Sink<ClientRequest, Error = Error> + Send>>,
+    pending: Arc<Mutex<Pending>>,
+}
+
+macro_rules! queue {
+    ($self:expr, $version:ident, $type:ident, $req:expr) => {{
+        let request = $version::abci::Request {
+            value: Some($version::abci::request::Value::$type($req)),
+        };
+        $self
+            .queue(request.into(), |response| {
+                let response: $version::abci::Response = response.translate()?;
+                match response
+                    .value
+                    .ok_or_else(Error::malformed_server_response)?
+                {
+                    $version::abci::response::Value::$type(r) => Ok(r),
+                    r => Err(Error::unexpected_server_response_type(
+                        stringify!($type).to_string(),
+                        format!("{:?}", r),
+                    )),
+                }
+            })
+            .await
+    }};
+}
+
+impl AsyncClient {
+    /// Queue a request for the ABCI server to echo back a message.
+    pub async fn echo(&mut self, req: RequestEcho) -> Result<ResponseFuture<ResponseEcho>, Error> {
+        queue!(self, v0_35, Echo, req)
+    }
+
+    /// Queue a request for information about the ABCI application.
+    pub async fn info(&mut self, req: RequestInfo) -> Result<ResponseFuture<ResponseInfo>, Error> {
+        queue!(self, v0_35, Info, req)
+    }
+
+    /// Queue a genesis request, to be called once upon genesis.
+    pub async fn init_chain(
+        &mut self,
+        req: RequestInitChain,
+    ) -> Result<ResponseFuture<ResponseInitChain>, Error> {
+        queue!(self, v0_35, InitChain, req)
+    }
+
+    /// Queue a query for data at the current or past height.
+    pub async fn query(
+        &mut self,
+        req: RequestQuery,
+    ) -> Result<ResponseFuture<ResponseQuery>, Error> {
+        queue!(self, v0_35, Query, req)
+    }
+
+    /// Queue a check of the given transaction before putting it into the local
+    /// mempool.
+    pub async fn check_tx(
+        &mut self,
+        req: RequestCheckTx,
+    ) -> Result<ResponseFuture<ResponseCheckTx>, Error> {
+        queue!(self, v0_35, CheckTx, req)
+    }
+
+    /// Queue a signal for the beginning of a new block, prior to any
+    /// `DeliverTx` calls.
+    pub async fn begin_block(
+        &mut self,
+        req: RequestBeginBlock,
+    ) -> Result<ResponseFuture<ResponseBeginBlock>, Error> {
+        queue!(self, v0_35, BeginBlock, req)
+    }
+
+    /// Queue a transaction to be applied to the application's state.
+    pub async fn deliver_tx(
+        &mut self,
+        req: RequestDeliverTx,
+    ) -> Result<ResponseFuture<ResponseDeliverTx>, Error> {
+        queue!(self, v0_35, DeliverTx, req)
+    }
+
+    /// Queue a signal for the end of a block.
+    pub async fn end_block(
+        &mut self,
+        req: RequestEndBlock,
+    ) -> Result<ResponseFuture<ResponseEndBlock>, Error> {
+        queue!(self, v0_35, EndBlock, req)
+    }
+
+    /// Queue a request to commit the current state at the current height.
+    pub async fn commit(&mut self) -> Result<ResponseFuture<ResponseCommit>, Error> {
+        queue!(self, v0_35, Commit, RequestCommit {})
+    }
+
+    /// Queue a request to discover available snapshots.
+    pub async fn list_snapshots(&mut self) -> Result<ResponseFuture<ResponseListSnapshots>, Error> {
+        queue!(self, v0_35, ListSnapshots, RequestListSnapshots {})
+    }
+
+    /// Queue a snapshot offer, as when bootstrapping the node using state sync.
+    pub async fn offer_snapshot(
+        &mut self,
+        req: RequestOfferSnapshot,
+    ) -> Result<ResponseFuture<ResponseOfferSnapshot>, Error> {
+        queue!(self, v0_35, OfferSnapshot, req)
+    }
+
+    /// Queue a request for a chunk of a snapshot.
+    pub async fn load_snapshot_chunk(
+        &mut self,
+        req: RequestLoadSnapshotChunk,
+    ) -> Result<ResponseFuture<ResponseLoadSnapshotChunk>, Error> {
+        queue!(self, v0_35, LoadSnapshotChunk, req)
+    }
+
+    /// Queue the given snapshot chunk to be applied to the application's state.
+    pub async fn apply_snapshot_chunk(
+        &mut self,
+        req: RequestApplySnapshotChunk,
+    ) -> Result<ResponseFuture<ResponseApplySnapshotChunk>, Error> {
+        queue!(self, v0_35, ApplySnapshotChunk, req)
+    }
+
+    /// Queue a request for the application to prepare a block proposal from
+    /// the given transactions. Only part of the v0.37 and v0.38 protocols.
+    pub async fn prepare_proposal(
+        &mut self,
+        req: RequestPrepareProposal,
+    ) -> Result<ResponseFuture<ResponsePrepareProposal>, Error> {
+        queue!(self, v0_38, PrepareProposal, req)
+    }
+
+    /// Queue a request for the application to validate a block proposed by
+    /// another node. Only part of the v0.37 and v0.38 protocols.
+    pub async fn process_proposal(
+        &mut self,
+        req: RequestProcessProposal,
+    ) -> Result<ResponseFuture<ResponseProcessProposal>, Error> {
+        queue!(self, v0_38, ProcessProposal, req)
+    }
+
+    /// Queue a request for the data to attach to this node's precommit vote.
+    /// Only part of the v0.38 protocol.
+    pub async fn extend_vote(
+        &mut self,
+        req: RequestExtendVote,
+    ) -> Result<ResponseFuture<ResponseExtendVote>, Error> {
+        queue!(self, v0_38, ExtendVote, req)
+    }
+
+    /// Queue the verification of the vote extension of another validator.
+    /// Only part of the v0.38 protocol.
+    pub async fn verify_vote_extension(
+        &mut self,
+        req: RequestVerifyVoteExtension,
+    ) -> Result<ResponseFuture<ResponseVerifyVoteExtension>, Error> {
+        queue!(self, v0_38, VerifyVoteExtension, req)
+    }
+
+    /// Queue a decided block to be executed. Only part of the v0.38 protocol,
+    /// in which it replaces `BeginBlock`, `DeliverTx` and `EndBlock`.
+    pub async fn finalize_block(
+        &mut self,
+        req: RequestFinalizeBlock,
+    ) -> Result<ResponseFuture<ResponseFinalizeBlock>, Error> {
+        queue!(self, v0_38, FinalizeBlock, req)
+    }
+
+    /// Send all queued requests to the server, followed by a `Flush` request,
+    /// and wait for the server to respond to all of them.
+    ///
+    /// Once this method returns, all previously produced [`ResponseFuture`]s
+    /// are guaranteed to be ready.
+    pub async fn flush(&mut self) -> Result<ResponseFlush, Error> {
+        let response = queue!(self, v0_35, Flush, RequestFlush {})?;
+        self.sink.flush().await?;
+        response.await
+    }
+
+    async fn queue<T>(
+        &mut self,
+        req: ClientRequest,
+        extract: fn(ServerResponse) -> Result<T, Error>,
+    ) -> Result<ResponseFuture<T>, Error> {
+        // Only waits if the write buffer is full. Nothing has been queued yet,
+        // so dropping this future while waiting leaves no trace of the
+        // request.
+        poll_fn(|cx| self.sink.poll_ready_unpin(cx)).await?;
+        let (tx, rx) = oneshot::channel();
+        let mut pending = self.pending.lock().unwrap();
+        if pending.closed {
+            return Err(Error::server_connection_terminated());
+        }
+        // Buffers the request without waiting, so that its sender is queued
+        // if and only if the request is.
+        self.sink.start_send_unpin(req)?;
+        pending.queue.push_back(tx);
+        Ok(ResponseFuture { rx, extract })
+    }
+}
+
+/// A response to a request queued by an [`AsyncClient`].
+///
+/// Only resolves once the request has been sent to the server, which is only
+/// guaranteed to happen once [`AsyncClient::flush`] has been called.
+pub struct ResponseFuture<T> {
+    rx: oneshot::Receiver<ServerResponse>,
+    extract: fn(ServerResponse) -> Result<T, Error>,
+}
+
+impl<T> Future for ResponseFuture<T> {
+    type Output = Result<T, Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let extract = self.extract;
+        Pin::new(&mut self.rx).poll(cx).map(|result| {
+            let response = result.map_err(|_| Error::server_connection_terminated())?;
+            extract(response)
+        })
+    }
+}
+
+/// Converts the requests made through the client into the wire format of the
+/// protocol version `P` as they are sent, so that requests that are not part
+/// of the protocol fail right away.
+struct ProtocolSink<S, P> {
+    inner: S,
+    _protocol: PhantomData<fn() -> P>,
+}
+
+impl<S, P> Sink<ClientRequest> for ProtocolSink<S, P>
+where
+    S: Sink<P::Request, Error = Error> + Unpin,
+    P: Protocol,
+{
+    type Error = Error;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Pin::new(&mut self.inner).poll_ready(cx)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: ClientRequest) -> Result<(), Error> {
+        let request = P::encode_request(item)?;
+        Pin::new(&mut self.inner).start_send(request)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Pin::new(&mut self.inner).poll_close(cx)
+    }
+}
+
+/// Requests for which we are still awaiting responses, in the order in which
+/// they were sent.
+#[derive(Default)]
+struct Pending {
+    queue: VecDeque<oneshot::Sender<ServerResponse>>,
+    closed: bool,
+}
+
+async fn receive_responses<S>(mut stream: S, pending: Arc<Mutex<Pending>>)
+where
+    S: futures::Stream<Item = Result<ServerResponse, Error>> + Unpin,
+{
+    while let Some(result) = stream.next().await {
+        let response = match result {
+            Ok(r) => r,
+            Err(e) => {
+                error!("Failed to read response from server: {:?}", e);
+                break;
+            },
+        };
+        let tx = pending.lock().unwrap().queue.pop_front();
+        match tx {
+            // The caller may have dropped the response future, which is fine.
+            Some(tx) => {
+                let _ = tx.send(response);
+            },
+            None => {
+                error!("Received unsolicited response from server: {:?}", response);
+                break;
+            },
+        }
+    }
+    debug!("Server connection terminated");
+    let mut pending = pending.lock().unwrap();
+    pending.closed = true;
+    // Dropping the senders fails all outstanding response futures.
+    pending.queue.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        pin::Pin,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex,
+        },
+        task::{Context, Poll},
+    };
+
+    use futures::{stream, FutureExt, Sink};
+    use tendermint_proto::abci::{
+        response, RequestDeliverTx, RequestEcho, RequestInfo, Response, ResponseDeliverTx,
+        ResponseInfo,
+    };
+
+    use super::{receive_responses, AsyncClient, Pending};
+    use crate::{
+        protocol::{ClientRequest, ServerResponse},
+        Error,
+    };
+
+    /// Fails to send the first request, then accepts all others.
+    struct FailingSink {
+        failed: bool,
+    }
+
+    impl Sink<ClientRequest> for FailingSink {
+        type Error = Error;
+
+        fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn start_send(mut self: Pin<&mut Self>, _item: ClientRequest) -> Result<(), Error> {
+            if self.failed {
+                return Ok(());
+            }
+            self.failed = true;
+            Err(Error::server_connection_terminated())
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    /// Accepts requests only while it is ready, as if the write buffer were
+    /// full otherwise.
+    struct GatedSink {
+        ready: Arc<AtomicBool>,
+    }
+
+    impl Sink<ClientRequest> for GatedSink {
+        type Error = Error;
+
+        fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+            if self.ready.load(Ordering::SeqCst) {
+                Poll::Ready(Ok(()))
+            } else {
+                Poll::Pending
+            }
+        }
+
+        fn start_send(self: Pin<&mut Self>, _item: ClientRequest) -> Result<(), Error> {
+            assert!(self.ready.load(Ordering::SeqCst));
+            Ok(())
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn deliver_tx(tx: &'static str) -> RequestDeliverTx {
+        RequestDeliverTx { tx: tx.into() }
+    }
+
+    fn deliver_tx_response(data: &'static str) -> Result<ServerResponse, Error> {
+        Ok(ServerResponse::V0_35(Response {
+            value: Some(response::Value::DeliverTx(ResponseDeliverTx {
+                data: data.into(),
+                ..Default::default()
+            })),
+        }))
+    }
+
+    #[tokio::test]
+    async fn requests_dropped_while_waiting_to_be_sent_are_not_left_pending() {
+        let ready = Arc::new(AtomicBool::new(false));
+        let pending = Arc::new(Mutex::new(Pending::default()));
+        let mut client = AsyncClient {
+            sink: Box::pin(GatedSink {
+                ready: ready.clone(),
+            }),
+            pending: pending.clone(),
+        };
+
+        // Gives up on a request while waiting for room in the write buffer,
+        // e.g. upon a timeout
+        assert!(client.deliver_tx(deliver_tx("x")).now_or_never().is_none());
+        assert!(pending.lock().unwrap().queue.is_empty());
+
+        // Later responses go to the requests they answer
+        ready.store(true, Ordering::SeqCst);
+        let a = client.deliver_tx(deliver_tx("a")).await.unwrap();
+        let b = client.deliver_tx(deliver_tx("b")).await.unwrap();
+        let responses = vec![deliver_tx_response("a"), deliver_tx_response("b")];
+        receive_responses(stream::iter(responses), pending).await;
+        assert_eq!(a.await.unwrap().data, "a");
+        assert_eq!(b.await.unwrap().data, "b");
+    }
+
+    #[tokio::test]
+    async fn failed_requests_are_not_left_pending() {
+        let pending = Arc::new(Mutex::new(Pending::default()));
+        let mut client = AsyncClient {
+            sink: Box::pin(FailingSink { failed: false }),
+            pending: pending.clone(),
+        };
+
+        assert!(client.echo(RequestEcho::default()).await.is_err());
+        assert!(pending.lock().unwrap().queue.is_empty());
+
+        // The next response goes to the next request
+        let info = client.info(RequestInfo::default()).await.unwrap();
+        let response = ServerResponse::V0_35(Response {
+            value: Some(response::Value::Info(ResponseInfo {
+                last_block_height: 7,
+                ..Default::default()
+            })),
+        });
+        receive_responses(stream::iter(vec![Ok(response)]), pending).await;
+        assert_eq!(info.await.unwrap().last_block_height, 7);
+    }
+}
@@ -0,0 +1,394 @@
+//! Asynchronous, Tokio-based ABCI application server interface.
+
+use std::{future::Future, io, panic::AssertUnwindSafe, path::Path, sync::Arc};
+
+use futures::{FutureExt, SinkExt, StreamExt};
+use tendermint_config::net::{Address, UNIX_PREFIX};
+#[cfg(unix)]
+use tokio::net::UnixListener;
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::{TcpListener, ToSocketAddrs},
+};
+use tokio_util::codec::Framed;
+use tracing::{error, info, warn};
+
+#[cfg(unix)]
+use crate::net::SocketPath;
+use crate::{
+    async_application::AsyncRequestDispatcher,
+    codec::{AsyncServerCodec, DEFAULT_MAX_MESSAGE_SIZE},
+    connection::ConnectionGuard,
+    error::Error,
+    net::is_transient_accept_error,
+    protocol::{
+        Protocol, ProtocolV0_34, ProtocolV0_35, ProtocolV0_37, ProtocolV0_38, ProtocolVersion,
+    },
+    server::{panic_response, ACCEPT_RETRY_DELAY, DEFAULT_SERVER_READ_BUF_SIZE},
+    AsyncApplication,
+};
+
+/// Allows us to configure and construct an asynchronous ABCI server.
+pub struct AsyncServerBuilder {
+    read_buf_size: usize,
+    max_message_size: usize,
+    enforce_connection_roles: bool,
+    protocol_version: ProtocolVersion,
+}
+
+impl AsyncServerBuilder {
+    /// Builder constructor.
+    ///
+    /// Allows you to specify the initial capacity of the read buffer used for
+    /// each incoming connection. This needs to be tuned for your application.
+    pub fn new(read_buf_size: usize) -> Self {
+        Self {
+            read_buf_size,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            enforce_connection_roles: true,
+            protocol_version: ProtocolVersion::default(),
+        }
+    }
+
+    /// Limit the size of incoming messages (100MB by default).
+    ///
+    /// Connections on which a larger message is received are closed.
+    pub fn max_message_size(mut self, max: usize) -> Self {
+        self.max_message_size = max;
+        self
+    }
+
+    /// Whether or not to enforce the role of each incoming connection
+    /// (enabled by default).
+    ///
+    /// When enabled, the role of each connection (consensus, mempool, info or
+    /// snapshot) is determined by the first request received on it, and
+    /// requests belonging to any other role are answered with an exception.
+    /// `Echo` and `Flush` requests are allowed on all connections. On the
+    /// consensus connection, the server also enforces the order of
+    /// `InitChain`, and `BeginBlock`, `DeliverTx`, `EndBlock` and `Commit` for
+    /// each block.
+    ///
+    /// Disabling this allows a single client connection to issue requests of
+    /// any kind, e.g. for testing.
+    pub fn enforce_connection_roles(mut self, enforce: bool) -> Self {
+        self.enforce_connection_roles = enforce;
+        self
+    }
+
+    /// The version of the ABCI wire protocol spoken by the server
+    /// ([`ProtocolVersion::V0_35`] by default).
+    ///
+    /// This must match the version of the Tendermint or CometBFT node
+    /// connecting to the server.
+    pub fn protocol_version(mut self, version: ProtocolVersion) -> Self {
+        self.protocol_version = version;
+        self
+    }
+
+    /// Constructor for an asynchronous ABCI server.
+    ///
+    /// Binds the server to the given address. You must subsequently call the
+    /// [`AsyncServer::listen`] method in order for incoming connections'
+    /// requests to be routed to the specified ABCI application.
+    pub async fn bind<Addr, App>(self, addr: Addr, app: App) -> Result<AsyncServer<App>, Error>
+    where
+        Addr: ToSocketAddrs,
+        App: AsyncApplication,
+    {
+        let listener = TcpListener::bind(addr).await.map_err(Error::io)?;
+        self.serve(AsyncListener::Tcp(listener), app)
+    }
+
+    /// Constructor for an asynchronous ABCI server listening on a Unix domain
+    /// socket.
+    ///
+    /// Binds the server to a socket at the given path, which must not already
+    /// exist, and which is removed once the server is dropped. Only supported
+    /// on Unix platforms.
+    pub async fn bind_unix<P, App>(self, path: P, app: App) -> Result<AsyncServer<App>, Error>
+    where
+        P: AsRef<Path>,
+        App: AsyncApplication,
+    {
+        let listener = AsyncListener::bind_unix(path.as_ref())?;
+        self.serve(listener, app)
+    }
+
+    /// Constructor for an asynchronous ABCI server bound to the given `tcp://`
+    /// or `unix://` address.
+    pub async fn bind_address<App>(
+        self,
+        addr: &Address,
+        app: App,
+    ) -> Result<AsyncServer<App>, Error>
+    where
+        App: AsyncApplication,
+    {
+        let listener = AsyncListener::bind(addr).await?;
+        self.serve(listener, app)
+    }
+
+    fn serve<App>(self, listener: AsyncListener, app: App) -> Result<AsyncServer<App>, Error>
+    where
+        App: AsyncApplication,
+    {
+        let local_addr = listener.local_addr()?;
+        info!("Async ABCI server running at {}", local_addr);
+        Ok(AsyncServer {
+            app: Arc::new(app),
+            listener,
+            local_addr,
+            read_buf_size: self.read_buf_size,
+            max_message_size: self.max_message_size,
+            enforce_connection_roles: self.enforce_connection_roles,
+            protocol_version: self.protocol_version,
+        })
+    }
+}
+
+impl Default for AsyncServerBuilder {
+    fn default() -> Self {
+        Self::new(DEFAULT_SERVER_READ_BUF_SIZE)
+    }
+}
+
+/// A Tokio-based server for serving a specific asynchronous ABCI application.
+///
+/// Each incoming connection is handled in a separate task. A single instance
+/// of the ABCI application is shared between all of these tasks.
+///
+/// A panic in any of the application's methods is caught and reported to the
+/// client as an exception, leaving the connection (and the rest of the server)
+/// running.
+pub struct AsyncServer<App> {
+    app: Arc<App>,
+    listener: AsyncListener,
+    local_addr: String,
+    read_buf_size: usize,
+    max_message_size: usize,
+    enforce_connection_roles: bool,
+    protocol_version: ProtocolVersion,
+}
+
+impl<App: AsyncApplication> AsyncServer<App> {
+    /// Listen for incoming connections, spawning a new task to handle each of
+    /// them.
+    ///
+    /// Failures to accept individual connections are logged and otherwise
+    /// ignored. Only returns if the listener itself fails.
+    ///
+    /// Must be called from within a Tokio runtime.
+    pub async fn listen(self) -> Result<(), Error> {
+        loop {
+            match &self.listener {
+                AsyncListener::Tcp(listener) => {
+                    let (stream, addr) = accept_retrying(|| listener.accept()).await?;
+                    self.spawn_client_handler(stream, addr.to_string());
+                },
+                #[cfg(unix)]
+                AsyncListener::Unix { listener, path } => {
+                    let (stream, _) = accept_retrying(|| listener.accept()).await?;
+                    self.spawn_client_handler(stream, format!("{}{}", UNIX_PREFIX, path));
+                },
+            }
+        }
+    }
+
+    /// Getter for this server's local address.
+    ///
+    /// For TCP servers this is of the form `host:port`, and for Unix domain
+    /// socket servers it is the socket path prefixed with `unix://`.
+    pub fn local_addr(&self) -> String {
+        self.local_addr.clone()
+    }
+
+    fn spawn_client_handler<S>(&self, stream: S, addr: String)
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        info!("Incoming connection from: {}", addr);
+        let app = self.app.clone();
+        let guard = ConnectionGuard::new(self.enforce_connection_roles);
+        let (read_buf_size, max_message_size) = (self.read_buf_size, self.max_message_size);
+        match self.protocol_version {
+            ProtocolVersion::V0_34 => tokio::spawn(Self::handle_client::<S, ProtocolV0_34>(
+                stream,
+                addr,
+                app,
+                read_buf_size,
+                max_message_size,
+                guard,
+            )),
+            ProtocolVersion::V0_35 => tokio::spawn(Self::handle_client::<S, ProtocolV0_35>(
+                stream,
+                addr,
+                app,
+                read_buf_size,
+                max_message_size,
+                guard,
+            )),
+            ProtocolVersion::V0_37 => tokio::spawn(Self::handle_client::<S, ProtocolV0_37>(
+                stream,
+                addr,
+                app,
+                read_buf_size,
+                max_message_size,
+                guard,
+            )),
+            ProtocolVersion::V0_38 => tokio::spawn(Self::handle_client::<S, ProtocolV0_38>(
+                stream,
+                addr,
+                app,
+                read_buf_size,
+                max_message_size,
+                guard,
+            )),
+        };
+    }
+
+    async fn handle_client<S, P>(
+        stream: S,
+        addr: String,
+        app: Arc<App>,
+        read_buf_size: usize,
+        max_message_size: usize,
+        mut guard: ConnectionGuard,
+    ) where
+        S: AsyncRead + AsyncWrite + Unpin,
+        P: Protocol,
+    {
+        let codec = AsyncServerCodec::<P>::new(max_message_size);
+        let mut framed = Framed::with_capacity(stream, codec, read_buf_size);
+        info!("Listening for incoming requests from {}", addr);
+        loop {
+            let request = match framed.next().await {
+                Some(result) => match result {
+                    Ok(r) => r,
+                    Err(e) => {
+                        error!(
+                            "Failed to read incoming request from client {}: {:?}",
+                            addr, e
+                        );
+                        return;
+                    },
+                },
+                None => {
+                    info!("Client {} terminated stream", addr);
+                    return;
+                },
+            };
+            let response = match P::parse_request(request) {
+                Ok(request) => P::encode_response(match guard.check(request) {
+                    Ok(request) => AssertUnwindSafe(app.handle(request))
+                        .catch_unwind()
+                        .await
+                        .unwrap_or_else(|payload| panic_response(payload.as_ref())),
+                    Err(exception) => exception,
+                }),
+                Err(response) => response,
+            };
+            if let Err(e) = framed.send(response).await {
+                error!("Failed sending response to client {}: {:?}", addr, e);
+                return;
+            }
+        }
+    }
+}
+
+/// Accept a connection via the given function, retrying after failures that
+/// do not affect the listener itself (see [`is_transient_accept_error`]).
+async fn accept_retrying<T, F, Fut>(mut accept: F) -> Result<T, Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = io::Result<T>>,
+{
+    loop {
+        match accept().await {
+            Ok(accepted) => return Ok(accepted),
+            Err(e) if is_transient_accept_error(&e) => {
+                warn!("Failed to accept incoming connection: {}", e);
+                tokio::time::sleep(ACCEPT_RETRY_DELAY).await;
+            },
+            Err(e) => {
+                error!("Failed to accept incoming connections: {}", e);
+                return Err(Error::io(e));
+            },
+        }
+    }
+}
+
+/// A Tokio listener for incoming connections, either over TCP or via a Unix
+/// domain socket.
+pub(crate) enum AsyncListener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix {
+        listener: UnixListener,
+        path: SocketPath,
+    },
+}
+
+impl AsyncListener {
+    /// Bind to the given address.
+    pub(crate) async fn bind(addr: &Address) -> Result<Self, Error> {
+        match addr {
+            Address::Tcp { host, port, .. } => Ok(Self::Tcp(
+                TcpListener::bind((host.as_str(), *port))
+                    .await
+                    .map_err(Error::io)?,
+            )),
+            Address::Unix { path } => Self::bind_unix(Path::new(path)),
+        }
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn bind_unix(path: &Path) -> Result<Self, Error> {
+        Ok(Self::Unix {
+            listener: UnixListener::bind(path).map_err(Error::io)?,
+            path: SocketPath::new(path),
+        })
+    }
+
+    #[cfg(not(unix))]
+    pub(crate) fn bind_unix(_path: &Path) -> Result<Self, Error> {
+        Err(Error::unsupported_transport(UNIX_PREFIX.to_string()))
+    }
+
+    /// The address to which this listener is bound.
+    ///
+    /// TCP addresses are rendered as `host:port`, while Unix domain socket
+    /// addresses are rendered as `unix://path`.
+    pub(crate) fn local_addr(&self) -> Result<String, Error> {
+        match self {
+            Self::Tcp(listener) => Ok(listener.local_addr().map_err(Error::io)?.to_string()),
+            #[cfg(unix)]
+            Self::Unix { path, .. } => Ok(format!("{}{}", UNIX_PREFIX, path)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::VecDeque, io};
+
+    use super::accept_retrying;
+
+    #[tokio::test]
+    async fn accepting_is_retried_after_transient_errors_only() {
+        let mut results = VecDeque::from(vec![
+            Err(io::Error::from(io::ErrorKind::ConnectionAborted)),
+            #[cfg(unix)]
+            Err(io::Error::from_raw_os_error(libc::EMFILE)),
+            Ok("connection"),
+            Err(io::Error::from(io::ErrorKind::InvalidInput)),
+        ]);
+        let mut accept = || {
+            let result = results.pop_front().unwrap();
+            async move { result }
+        };
+
+        assert_eq!(accept_retrying(&mut accept).await.unwrap(), "connection");
+        assert!(accept_retrying(&mut accept).await.is_err());
+    }
+}
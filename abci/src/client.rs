@@ -1,18 +1,42 @@
 //! Blocking ABCI client.
 
-use std::net::{TcpStream, ToSocketAddrs};
-
-use tendermint_proto::abci::{
-    request, response, Request, RequestApplySnapshotChunk, RequestBeginBlock, RequestCheckTx,
-    RequestCommit, RequestDeliverTx, RequestEcho, RequestEndBlock, RequestFlush, RequestInfo,
-    RequestInitChain, RequestListSnapshots, RequestLoadSnapshotChunk, RequestOfferSnapshot,
-    RequestQuery, ResponseApplySnapshotChunk, ResponseBeginBlock, ResponseCheckTx, ResponseCommit,
-    ResponseDeliverTx, ResponseEcho, ResponseEndBlock, ResponseFlush, ResponseInfo,
-    ResponseInitChain, ResponseListSnapshots, ResponseLoadSnapshotChunk, ResponseOfferSnapshot,
-    ResponseQuery,
+use std::{
+    net::{TcpStream, ToSocketAddrs},
+    path::Path,
 };
 
-use crate::{codec::ClientCodec, Error};
+use tendermint_config::net::Address;
+use tendermint_proto::{
+    abci::{
+        RequestApplySnapshotChunk, RequestBeginBlock, RequestCheckTx, RequestCommit,
+        RequestDeliverTx, RequestEcho, RequestEndBlock, RequestFlush, RequestInfo,
+        RequestInitChain, RequestListSnapshots, RequestLoadSnapshotChunk, RequestOfferSnapshot,
+        RequestQuery, ResponseApplySnapshotChunk, ResponseBeginBlock, ResponseCheckTx,
+        ResponseCommit, ResponseDeliverTx, ResponseEcho, ResponseEndBlock, ResponseFlush,
+        ResponseInfo, ResponseInitChain, ResponseListSnapshots, ResponseLoadSnapshotChunk,
+        ResponseOfferSnapshot, ResponseQuery,
+    },
+    v0_35,
+    v0_38::{
+        self,
+        abci::{
+            RequestExtendVote, RequestFinalizeBlock, RequestPrepareProposal,
+            RequestProcessProposal, RequestVerifyVoteExtension, ResponseExtendVote,
+            ResponseFinalizeBlock, ResponsePrepareProposal, ResponseProcessProposal,
+            ResponseVerifyVoteExtension,
+        },
+    },
+};
+
+use crate::{
+    codec::{ClientCodec, Codec, DEFAULT_MAX_MESSAGE_SIZE},
+    net::Stream,
+    protocol::{
+        ClientRequest, Protocol, ProtocolV0_34, ProtocolV0_35, ProtocolV0_37, ProtocolV0_38,
+        ServerResponse, Translate,
+    },
+    Error, ProtocolVersion,
+};
 
 /// The size of the read buffer for the client in its receiving of responses
 /// from the server.
@@ -21,101 +45,177 @@ pub const DEFAULT_CLIENT_READ_BUF_SIZE: usize = 1024;
 /// Builder for a blocking ABCI client.
 pub struct ClientBuilder {
     read_buf_size: usize,
+    max_message_size: usize,
+    protocol_version: ProtocolVersion,
 }
 
 impl ClientBuilder {
     /// Builder constructor.
     pub fn new(read_buf_size: usize) -> Self {
-        Self { read_buf_size }
+        Self {
+            read_buf_size,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            protocol_version: ProtocolVersion::default(),
+        }
+    }
+
+    /// Limit the size of responses received from the server (100MB by
+    /// default).
+    pub fn max_message_size(mut self, max: usize) -> Self {
+        self.max_message_size = max;
+        self
+    }
+
+    /// The version of the ABCI wire protocol spoken by the server
+    /// ([`ProtocolVersion::V0_35`] by default).
+    ///
+    /// Requests of the same version as the protocol are sent as they are,
+    /// and others are translated into it. Requests that are not part of the
+    /// protocol, such as ABCI++ requests over the v0.35 protocol, fail without
+    /// being sent.
+    pub fn protocol_version(mut self, version: ProtocolVersion) -> Self {
+        self.protocol_version = version;
+        self
     }
 
     /// Client constructor that attempts to connect to the given network
     /// address.
     pub fn connect<A: ToSocketAddrs>(self, addr: A) -> Result<Client, Error> {
         let stream = TcpStream::connect(addr).map_err(Error::io)?;
-        Ok(Client {
-            codec: ClientCodec::new(stream, self.read_buf_size),
-        })
+        Ok(self.client(Stream::Tcp(stream)))
+    }
+
+    /// Client constructor that attempts to connect to the Unix domain socket
+    /// at the given path. Only supported on Unix platforms.
+    pub fn connect_unix<P: AsRef<Path>>(self, path: P) -> Result<Client, Error> {
+        let stream = Stream::connect_unix(path.as_ref())?;
+        Ok(self.client(stream))
+    }
+
+    /// Client constructor that attempts to connect to the given `tcp://` or
+    /// `unix://` address.
+    pub fn connect_address(self, addr: &Address) -> Result<Client, Error> {
+        let stream = Stream::connect(addr)?;
+        Ok(self.client(stream))
+    }
+
+    fn client(self, stream: Stream) -> Client {
+        let (read_buf_size, max_message_size) = (self.read_buf_size, self.max_message_size);
+        let codec = match self.protocol_version {
+            ProtocolVersion::V0_34 => {
+                ProtocolCodec::V0_34(Codec::new(stream, read_buf_size, max_message_size))
+            },
+            ProtocolVersion::V0_35 => {
+                ProtocolCodec::V0_35(Codec::new(stream, read_buf_size, max_message_size))
+            },
+            ProtocolVersion::V0_37 => {
+                ProtocolCodec::V0_37(Codec::new(stream, read_buf_size, max_message_size))
+            },
+            ProtocolVersion::V0_38 => {
+                ProtocolCodec::V0_38(Codec::new(stream, read_buf_size, max_message_size))
+            },
+        };
+        Client { codec }
     }
 }
 
 impl Default for ClientBuilder {
     fn default() -> Self {
-        Self {
-            read_buf_size: DEFAULT_CLIENT_READ_BUF_SIZE,
-        }
+        Self::new(DEFAULT_CLIENT_READ_BUF_SIZE)
     }
 }
 
 /// Blocking ABCI client.
+///
+/// Requests take the messages of the v0.35 protocol, other than the ABCI++
+/// requests, which take those of the v0.38 protocol. They can be sent over
+/// any version of the protocol they are part of (see
+/// [`ClientBuilder::protocol_version`]).
 pub struct Client {
-    codec: ClientCodec<TcpStream>,
+    codec: ProtocolCodec,
+}
+
+/// The codec of a client, in the wire format of the protocol version spoken by
+/// the server.
+enum ProtocolCodec {
+    V0_34(ClientCodec<Stream, ProtocolV0_34>),
+    V0_35(ClientCodec<Stream, ProtocolV0_35>),
+    V0_37(ClientCodec<Stream, ProtocolV0_37>),
+    V0_38(ClientCodec<Stream, ProtocolV0_38>),
 }
 
 macro_rules! perform {
-    ($self:expr, $type:ident, $req:expr) => {
-        match $self.perform(request::Value::$type($req))? {
-            response::Value::$type(r) => Ok(r),
-            r => {
-                Err(Error::unexpected_server_response_type(stringify!($type).to_string(), r).into())
-            },
+    ($self:expr, $version:ident, $type:ident, $req:expr) => {{
+        let request = $version::abci::Request {
+            value: Some($version::abci::request::Value::$type($req)),
+        };
+        let response: $version::abci::Response = $self.perform(request.into())?.translate()?;
+        match response
+            .value
+            .ok_or_else(Error::malformed_server_response)?
+        {
+            $version::abci::response::Value::$type(r) => Ok(r),
+            r => Err(Error::unexpected_server_response_type(
+                stringify!($type).to_string(),
+                format!("{:?}", r),
+            )),
         }
-    };
+    }};
 }
 
 impl Client {
     /// Ask the ABCI server to echo back a message.
     pub fn echo(&mut self, req: RequestEcho) -> Result<ResponseEcho, Error> {
-        perform!(self, Echo, req)
+        perform!(self, v0_35, Echo, req)
     }
 
     /// Request information about the ABCI application.
     pub fn info(&mut self, req: RequestInfo) -> Result<ResponseInfo, Error> {
-        perform!(self, Info, req)
+        perform!(self, v0_35, Info, req)
     }
 
     /// To be called once upon genesis.
     pub fn init_chain(&mut self, req: RequestInitChain) -> Result<ResponseInitChain, Error> {
-        perform!(self, InitChain, req)
+        perform!(self, v0_35, InitChain, req)
     }
 
     /// Query the application for data at the current or past height.
     pub fn query(&mut self, req: RequestQuery) -> Result<ResponseQuery, Error> {
-        perform!(self, Query, req)
+        perform!(self, v0_35, Query, req)
     }
 
     /// Check the given transaction before putting it into the local mempool.
     pub fn check_tx(&mut self, req: RequestCheckTx) -> Result<ResponseCheckTx, Error> {
-        perform!(self, CheckTx, req)
+        perform!(self, v0_35, CheckTx, req)
     }
 
     /// Signal the beginning of a new block, prior to any `DeliverTx` calls.
     pub fn begin_block(&mut self, req: RequestBeginBlock) -> Result<ResponseBeginBlock, Error> {
-        perform!(self, BeginBlock, req)
+        perform!(self, v0_35, BeginBlock, req)
     }
 
     /// Apply a transaction to the application's state.
     pub fn deliver_tx(&mut self, req: RequestDeliverTx) -> Result<ResponseDeliverTx, Error> {
-        perform!(self, DeliverTx, req)
+        perform!(self, v0_35, DeliverTx, req)
     }
 
     /// Signal the end of a block.
     pub fn end_block(&mut self, req: RequestEndBlock) -> Result<ResponseEndBlock, Error> {
-        perform!(self, EndBlock, req)
+        perform!(self, v0_35, EndBlock, req)
     }
 
     pub fn flush(&mut self) -> Result<ResponseFlush, Error> {
-        perform!(self, Flush, RequestFlush {})
+        perform!(self, v0_35, Flush, RequestFlush {})
     }
 
     /// Commit the current state at the current height.
     pub fn commit(&mut self) -> Result<ResponseCommit, Error> {
-        perform!(self, Commit, RequestCommit {})
+        perform!(self, v0_35, Commit, RequestCommit {})
     }
 
     /// Used during state sync to discover available snapshots on peers.
     pub fn list_snapshots(&mut self) -> Result<ResponseListSnapshots, Error> {
-        perform!(self, ListSnapshots, RequestListSnapshots {})
+        perform!(self, v0_35, ListSnapshots, RequestListSnapshots {})
     }
 
     /// Called when bootstrapping the node using state sync.
@@ -123,7 +223,7 @@ impl Client {
         &mut self,
         req: RequestOfferSnapshot,
     ) -> Result<ResponseOfferSnapshot, Error> {
-        perform!(self, OfferSnapshot, req)
+        perform!(self, v0_35, OfferSnapshot, req)
     }
 
     /// Used during state sync to retrieve chunks of snapshots from peers.
@@ -131,7 +231,7 @@ impl Client {
         &mut self,
         req: RequestLoadSnapshotChunk,
     ) -> Result<ResponseLoadSnapshotChunk, Error> {
-        perform!(self, LoadSnapshotChunk, req)
+        perform!(self, v0_35, LoadSnapshotChunk, req)
     }
 
     /// Apply the given snapshot chunk to the application's state.
@@ -139,15 +239,72 @@ impl Client {
         &mut self,
         req: RequestApplySnapshotChunk,
     ) -> Result<ResponseApplySnapshotChunk, Error> {
-        perform!(self, ApplySnapshotChunk, req)
+        perform!(self, v0_35, ApplySnapshotChunk, req)
+    }
+
+    /// Ask the application to prepare a block proposal from the given
+    /// transactions. Only part of the v0.37 and v0.38 protocols.
+    pub fn prepare_proposal(
+        &mut self,
+        req: RequestPrepareProposal,
+    ) -> Result<ResponsePrepareProposal, Error> {
+        perform!(self, v0_38, PrepareProposal, req)
+    }
+
+    /// Ask the application to validate a block proposed by another node. Only
+    /// part of the v0.37 and v0.38 protocols.
+    pub fn process_proposal(
+        &mut self,
+        req: RequestProcessProposal,
+    ) -> Result<ResponseProcessProposal, Error> {
+        perform!(self, v0_38, ProcessProposal, req)
+    }
+
+    /// Request the data to attach to this node's precommit vote. Only part of
+    /// the v0.38 protocol.
+    pub fn extend_vote(&mut self, req: RequestExtendVote) -> Result<ResponseExtendVote, Error> {
+        perform!(self, v0_38, ExtendVote, req)
+    }
+
+    /// Verify the vote extension of another validator. Only part of the v0.38
+    /// protocol.
+    pub fn verify_vote_extension(
+        &mut self,
+        req: RequestVerifyVoteExtension,
+    ) -> Result<ResponseVerifyVoteExtension, Error> {
+        perform!(self, v0_38, VerifyVoteExtension, req)
+    }
+
+    /// Execute a decided block. Only part of the v0.38 protocol, in which it
+    /// replaces `BeginBlock`, `DeliverTx` and `EndBlock`.
+    pub fn finalize_block(
+        &mut self,
+        req: RequestFinalizeBlock,
+    ) -> Result<ResponseFinalizeBlock, Error> {
+        perform!(self, v0_38, FinalizeBlock, req)
     }
 
-    fn perform(&mut self, req: request::Value) -> Result<response::Value, Error> {
-        self.codec.send(Request { value: Some(req) })?;
-        let res = self
-            .codec
-            .next()
-            .ok_or_else(Error::server_connection_terminated)??;
-        res.value.ok_or_else(Error::malformed_server_response)
+    fn perform(&mut self, req: ClientRequest) -> Result<ServerResponse, Error> {
+        match &mut self.codec {
+            ProtocolCodec::V0_34(codec) => exchange::<ProtocolV0_34>(codec, req),
+            ProtocolCodec::V0_35(codec) => exchange::<ProtocolV0_35>(codec, req),
+            ProtocolCodec::V0_37(codec) => exchange::<ProtocolV0_37>(codec, req),
+            ProtocolCodec::V0_38(codec) => exchange::<ProtocolV0_38>(codec, req),
+        }
     }
 }
+
+fn exchange<P>(
+    codec: &mut ClientCodec<Stream, P>,
+    req: ClientRequest,
+) -> Result<ServerResponse, Error>
+where
+    P: Protocol,
+    ServerResponse: From<P::Response>,
+{
+    codec.send(P::encode_request(req)?)?;
+    let res = codec
+        .next()
+        .ok_or_else(Error::server_connection_terminated)??;
+    Ok(res.into())
+}
@@ -11,20 +11,25 @@ use std::{
 
 use bytes::{Buf, BufMut, BytesMut};
 use prost::Message;
-use tendermint_proto::abci::{Request, Response};
 
-use crate::error::Error;
+use crate::{error::Error, protocol::Protocol};
 
-/// The maximum number of bytes we expect in a varint. We use this to check if
-/// we're encountering a decoding error for a varint.
-pub const MAX_VARINT_LENGTH: usize = 16;
+/// The maximum number of bytes in a varint encoding a `u64`. We use this to
+/// distinguish partially received length delimiters from malformed ones.
+pub const MAX_VARINT_LENGTH: usize = 10;
 
-/// The server receives incoming requests, and sends outgoing responses.
-pub type ServerCodec<S> = Codec<S, Request, Response>;
+/// The default maximum size of an incoming message, excluding its length
+/// delimiter (100MB), as in Tendermint's own socket protocol implementation.
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 100 * 1024 * 1024;
+
+/// The server receives incoming requests, and sends outgoing responses, in the
+/// wire format of the protocol version `P`.
+pub type ServerCodec<S, P> = Codec<S, <P as Protocol>::Request, <P as Protocol>::Response>;
 
 #[cfg(feature = "client")]
-/// The client sends outgoing requests, and receives incoming responses.
-pub type ClientCodec<S> = Codec<S, Response, Request>;
+/// The client sends outgoing requests, and receives incoming responses, in the
+/// wire format of the protocol version `P`.
+pub type ClientCodec<S, P> = Codec<S, <P as Protocol>::Response, <P as Protocol>::Request>;
 
 /// Allows for iteration over `S` to produce instances of `I`, as well as
 /// sending instances of `O`.
@@ -35,6 +40,9 @@ pub struct Codec<S, I, O> {
     // Fixed-length read window
     read_window: Vec<u8>,
     write_buf: BytesMut,
+    max_message_size: usize,
+    // Set once the incoming stream can no longer be decoded
+    failed: bool,
     _incoming: PhantomData<I>,
     _outgoing: PhantomData<O>,
 }
@@ -46,19 +54,22 @@ where
     O: Message,
 {
     /// Constructor.
-    pub fn new(stream: S, read_buf_size: usize) -> Self {
+    pub fn new(stream: S, read_buf_size: usize, max_message_size: usize) -> Self {
         Self {
             stream,
             read_buf: BytesMut::new(),
             read_window: vec![0_u8; read_buf_size],
             write_buf: BytesMut::new(),
+            max_message_size,
+            failed: false,
             _incoming: Default::default(),
             _outgoing: Default::default(),
         }
     }
 }
 
-// Iterating over a codec produces instances of `Result<I>`.
+// Iterating over a codec produces instances of `Result<I>`. Iteration ends
+// after the first error, since we cannot resynchronize with the stream.
 impl<S, I, O> Iterator for Codec<S, I, O>
 where
     S: Read,
@@ -67,9 +78,24 @@ where
     type Item = Result<I, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let result = self.read_next();
+        self.failed = matches!(result, Some(Err(_)));
+        result
+    }
+}
+
+impl<S, I, O> Codec<S, I, O>
+where
+    S: Read,
+    I: Message + Default,
+{
+    fn read_next(&mut self) -> Option<Result<I, Error>> {
         loop {
             // Try to decode an incoming message from our buffer first
-            match decode_length_delimited::<I>(&mut self.read_buf) {
+            match decode_length_delimited::<I>(&mut self.read_buf, self.max_message_size) {
                 Ok(Some(incoming)) => return Some(Ok(incoming)),
                 Err(e) => return Some(Err(e)),
                 _ => (), // not enough data to decode a message, let's continue.
@@ -82,7 +108,11 @@ where
                 Err(e) => return Some(Err(Error::io(e))),
             };
             if bytes_read == 0 {
-                // The underlying stream terminated
+                // The underlying stream terminated, possibly in the middle of
+                // a message
+                if !self.read_buf.is_empty() {
+                    return Some(Err(Error::truncated_message(self.read_buf.len())));
+                }
                 return None;
             }
             self.read_buf
@@ -120,6 +150,83 @@ where
     }
 }
 
+/// The async server receives incoming requests, and sends outgoing responses,
+/// in the wire format of the protocol version `P`.
+#[cfg(feature = "async")]
+pub type AsyncServerCodec<P> = AsyncCodec<<P as Protocol>::Request, <P as Protocol>::Response>;
+
+/// The async client sends outgoing requests, and receives incoming responses,
+/// in the wire format of the protocol version `P`.
+#[cfg(all(feature = "client", feature = "async"))]
+pub type AsyncClientCodec<P> = AsyncCodec<<P as Protocol>::Response, <P as Protocol>::Request>;
+
+/// A [`tokio_util::codec`]-compatible codec for the Tendermint Socket
+/// Protocol, producing instances of `I` and accepting instances of `O`.
+///
+/// Intended to be used with [`tokio_util::codec::Framed`].
+#[cfg(feature = "async")]
+pub struct AsyncCodec<I, O> {
+    max_message_size: usize,
+    _incoming: PhantomData<I>,
+    _outgoing: PhantomData<O>,
+}
+
+#[cfg(feature = "async")]
+impl<I, O> AsyncCodec<I, O> {
+    /// Constructor.
+    pub fn new(max_message_size: usize) -> Self {
+        Self {
+            max_message_size,
+            _incoming: Default::default(),
+            _outgoing: Default::default(),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<I, O> Default for AsyncCodec<I, O> {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_MESSAGE_SIZE)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<I, O> tokio_util::codec::Decoder for AsyncCodec<I, O>
+where
+    I: Message + Default,
+{
+    type Item = I;
+    type Error = Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        decode_length_delimited(src, self.max_message_size)
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self.decode(src)? {
+            Some(item) => Ok(Some(item)),
+            None if src.is_empty() => Ok(None),
+            None => {
+                let remaining = src.len();
+                src.clear();
+                Err(Error::truncated_message(remaining))
+            },
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<I, O> tokio_util::codec::Encoder<O> for AsyncCodec<I, O>
+where
+    O: Message,
+{
+    type Error = Error;
+
+    fn encode(&mut self, item: O, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        encode_length_delimited(item, dst)
+    }
+}
+
 /// Encode the given message with a length prefix.
 pub fn encode_length_delimited<M, B>(message: M, mut dst: &mut B) -> Result<(), Error>
 where
@@ -136,31 +243,163 @@ where
 }
 
 /// Attempt to decode a message of type `M` from the given source buffer.
-pub fn decode_length_delimited<M>(src: &mut BytesMut) -> Result<Option<M>, Error>
+///
+/// Returns `Ok(None)` if the buffer does not yet contain an entire message.
+/// Fails without waiting for the rest of the message if its length delimiter
+/// is malformed, or if the message is larger than `max_message_size` bytes.
+pub fn decode_length_delimited<M>(
+    src: &mut BytesMut,
+    max_message_size: usize,
+) -> Result<Option<M>, Error>
 where
     M: Message + Default,
 {
-    let src_len = src.len();
     let mut tmp = src.clone().freeze();
     let encoded_len = match prost::encoding::decode_varint(&mut tmp) {
         Ok(len) => len,
-        // We've potentially only received a partial length delimiter
-        Err(_) if src_len <= MAX_VARINT_LENGTH => return Ok(None),
+        // We've potentially only received a partial length delimiter, in
+        // which case every byte received so far has its continuation bit set.
+        Err(_) if src.len() < MAX_VARINT_LENGTH && src.iter().all(|b| b & 0x80 != 0) => {
+            return Ok(None)
+        },
         Err(e) => return Err(Error::decode(e)),
     };
-    let remaining = tmp.remaining() as u64;
-    if remaining < encoded_len {
+    if encoded_len > max_message_size as u64 {
+        return Err(Error::message_too_large(encoded_len, max_message_size));
+    }
+    let encoded_len = encoded_len as usize;
+    if tmp.remaining() < encoded_len {
         // We don't have enough data yet to decode the entire message
-        Ok(None)
-    } else {
-        let delim_len = src_len - tmp.remaining();
-        // We only advance the source buffer once we're sure we have enough
-        // data to try to decode the result.
-        src.advance(delim_len + (encoded_len as usize));
+        return Ok(None);
+    }
+    let delim_len = src.len() - tmp.remaining();
+    // We only advance the source buffer once we're sure we have enough data
+    // to try to decode the result.
+    src.advance(delim_len + encoded_len);
+    M::decode(tmp.split_to(encoded_len))
+        .map(Some)
+        .map_err(Error::decode)
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use proptest::prelude::*;
+    use tendermint_proto::abci::{request, Request, RequestEcho};
+
+    use super::*;
+    use crate::{error::ErrorDetail, protocol::ProtocolV0_35};
+
+    fn echo(message: String) -> Request {
+        Request {
+            value: Some(request::Value::Echo(RequestEcho { message })),
+        }
+    }
+
+    fn encode_all(messages: &[Request]) -> Vec<u8> {
+        let mut buf = BytesMut::new();
+        for message in messages {
+            encode_length_delimited(message.clone(), &mut buf).unwrap();
+        }
+        buf.to_vec()
+    }
+
+    /// Decodes all messages from the given byte stream, read in chunks of the
+    /// given size.
+    fn decode_all(
+        bytes: Vec<u8>,
+        read_buf_size: usize,
+        max_message_size: usize,
+    ) -> Vec<Result<Request, Error>> {
+        ServerCodec::<_, ProtocolV0_35>::new(Cursor::new(bytes), read_buf_size, max_message_size)
+            .collect()
+    }
+
+    #[test]
+    fn partial_length_delimiters_are_awaited() {
+        let mut buf = BytesMut::from(&[0x80, 0x80][..]);
+        assert!(decode_length_delimited::<Request>(&mut buf, 1024)
+            .unwrap()
+            .is_none());
+        assert_eq!(buf.len(), 2);
+    }
+
+    #[test]
+    fn overlong_length_delimiters_are_rejected() {
+        let mut buf = BytesMut::from(&[0xff; MAX_VARINT_LENGTH + 1][..]);
+        let err = decode_length_delimited::<Request>(&mut buf, usize::MAX).unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::Decode(_)));
+    }
+
+    #[test]
+    fn oversized_messages_are_rejected_before_being_received() {
+        let bytes = encode_all(&[echo("a".repeat(100))]);
+        let mut buf = BytesMut::from(&bytes[..2]);
+        let err = decode_length_delimited::<Request>(&mut buf, 64).unwrap_err();
+        match err.detail() {
+            ErrorDetail::MessageTooLarge(e) => assert_eq!(e.max, 64),
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
 
-        let mut result_bytes = BytesMut::from(tmp.split_to(encoded_len as usize).as_ref());
-        let res = M::decode(&mut result_bytes).map_err(Error::decode)?;
+    #[test]
+    fn truncated_streams_produce_an_error() {
+        let mut bytes = encode_all(&[echo("first".to_string()), echo("second".to_string())]);
+        bytes.pop();
+        let results = decode_all(bytes, 4, 1024);
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        assert!(matches!(
+            results[1].as_ref().unwrap_err().detail(),
+            ErrorDetail::TruncatedMessage(_)
+        ));
+    }
 
-        Ok(Some(res))
+    proptest! {
+        #[test]
+        fn messages_survive_arbitrary_chunking(
+            messages in prop::collection::vec(".{0,200}", 0..10),
+            read_buf_size in 1..64_usize,
+        ) {
+            let messages = messages.into_iter().map(echo).collect::<Vec<_>>();
+            let decoded = decode_all(encode_all(&messages), read_buf_size, 1024)
+                .into_iter()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            prop_assert_eq!(decoded, messages);
+        }
+
+        #[test]
+        fn arbitrary_streams_are_decoded_without_panicking(
+            bytes in prop::collection::vec(any::<u8>(), 0..512),
+            read_buf_size in 1..64_usize,
+            max_message_size in 0..256_usize,
+        ) {
+            let results = decode_all(bytes.clone(), read_buf_size, max_message_size);
+            // Each successfully decoded message consumes at least one byte,
+            // and iteration ends after the first error.
+            prop_assert!(results.len() <= bytes.len());
+            for (i, result) in results.iter().enumerate() {
+                if result.is_err() {
+                    prop_assert_eq!(i, results.len() - 1);
+                }
+            }
+        }
+
+        #[test]
+        fn decoded_messages_respect_the_size_limit(
+            bytes in prop::collection::vec(any::<u8>(), 0..512),
+            max_message_size in 0..256_usize,
+        ) {
+            let mut buf = BytesMut::from(&bytes[..]);
+            let mut remaining = buf.len();
+            while let Ok(Some(_)) = decode_length_delimited::<Request>(&mut buf, max_message_size) {
+                let consumed = remaining - buf.len();
+                prop_assert!(consumed > 0);
+                prop_assert!(consumed <= max_message_size + MAX_VARINT_LENGTH);
+                remaining = buf.len();
+            }
+        }
     }
 }
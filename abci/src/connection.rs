@@ -0,0 +1,147 @@
+//! Per-connection request validation.
+//!
+//! Tendermint opens one ABCI connection for each [`MethodKind`] (other than
+//! [`MethodKind::Flush`]). A [`ConnectionGuard`] infers the role of a
+//! connection from the first request received on it, and rejects any
+//! subsequent requests that belong to a different role. On the consensus
+//! connection, it additionally enforces the order in which Tendermint calls
+//! the consensus methods.
+
+use tendermint::abci::{response, MethodKind, Request, Response};
+
+/// Tracks the role of a single ABCI connection and the position of the
+/// consensus connection within the block execution cycle.
+pub(crate) struct ConnectionGuard {
+    enforce: bool,
+    kind: Option<MethodKind>,
+    consensus: ConsensusState,
+}
+
+/// The consensus requests we expect next on the consensus connection.
+///
+/// The ABCI++ `PrepareProposal`, `ProcessProposal`, `ExtendVote` and
+/// `VerifyVoteExtension` requests may arrive any number of times while a block
+/// is being decided, i.e. before the block is executed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ConsensusState {
+    /// Either `InitChain` (upon genesis) or the execution of a block (when the
+    /// application already has state).
+    Start,
+    /// `BeginBlock` or `FinalizeBlock`, having executed `InitChain` or
+    /// committed a block.
+    AwaitingBlock,
+    /// `DeliverTx` or `EndBlock`.
+    ExecutingBlock,
+    /// `Commit`.
+    AwaitingCommit,
+}
+
+impl ConnectionGuard {
+    /// Constructor. If `enforce` is `false`, all requests are allowed through.
+    pub(crate) fn new(enforce: bool) -> Self {
+        Self {
+            enforce,
+            kind: None,
+            consensus: ConsensusState::Start,
+        }
+    }
+
+    /// Check whether the given request is allowed on this connection.
+    ///
+    /// `Flush` and `Echo` requests are always allowed and never establish the
+    /// role of a connection. On rejection, produces the exception with which
+    /// to respond to the client.
+    pub(crate) fn check(&mut self, request: Request) -> Result<Request, Response> {
+        if !self.enforce {
+            return Ok(request);
+        }
+        if let Request::Flush | Request::Echo(_) = request {
+            return Ok(request);
+        }
+        let kind = request.kind();
+        match self.kind {
+            None => self.kind = Some(kind),
+            Some(expected) if expected != kind => {
+                return Err(exception(format!(
+                    "{} request not allowed on {:?} connection",
+                    request_name(&request),
+                    expected
+                )))
+            },
+            _ => (),
+        }
+        if kind == MethodKind::Consensus {
+            self.advance_consensus(&request)?;
+        }
+        Ok(request)
+    }
+
+    fn advance_consensus(&mut self, request: &Request) -> Result<(), Response> {
+        use ConsensusState::*;
+
+        let next = match (self.consensus, request) {
+            (Start, Request::InitChain(_)) => AwaitingBlock,
+            (
+                Start | AwaitingBlock,
+                Request::PrepareProposal(_)
+                | Request::ProcessProposal(_)
+                | Request::ExtendVote(_)
+                | Request::VerifyVoteExtension(_),
+            ) => AwaitingBlock,
+            (Start | AwaitingBlock, Request::BeginBlock(_)) => ExecutingBlock,
+            (Start | AwaitingBlock, Request::FinalizeBlock(_)) => AwaitingCommit,
+            (ExecutingBlock, Request::DeliverTx(_)) => ExecutingBlock,
+            (ExecutingBlock, Request::EndBlock(_)) => AwaitingCommit,
+            (AwaitingCommit, Request::Commit) => AwaitingBlock,
+            (state, _) => {
+                return Err(exception(format!(
+                    "out of order {} request on consensus connection: expected {}",
+                    request_name(request),
+                    state.expected()
+                )))
+            },
+        };
+        self.consensus = next;
+        Ok(())
+    }
+}
+
+impl ConsensusState {
+    fn expected(&self) -> &'static str {
+        match self {
+            ConsensusState::Start => "InitChain, BeginBlock or FinalizeBlock",
+            ConsensusState::AwaitingBlock => "BeginBlock or FinalizeBlock",
+            ConsensusState::ExecutingBlock => "DeliverTx or EndBlock",
+            ConsensusState::AwaitingCommit => "Commit",
+        }
+    }
+}
+
+pub(crate) fn request_name(request: &Request) -> &'static str {
+    match request {
+        Request::Echo(_) => "Echo",
+        Request::Flush => "Flush",
+        Request::Info(_) => "Info",
+        Request::InitChain(_) => "InitChain",
+        Request::Query(_) => "Query",
+        Request::BeginBlock(_) => "BeginBlock",
+        Request::CheckTx(_) => "CheckTx",
+        Request::DeliverTx(_) => "DeliverTx",
+        Request::EndBlock(_) => "EndBlock",
+        Request::Commit => "Commit",
+        Request::ListSnapshots => "ListSnapshots",
+        Request::OfferSnapshot(_) => "OfferSnapshot",
+        Request::LoadSnapshotChunk(_) => "LoadSnapshotChunk",
+        Request::ApplySnapshotChunk(_) => "ApplySnapshotChunk",
+        Request::PrepareProposal(_) => "PrepareProposal",
+        Request::ProcessProposal(_) => "ProcessProposal",
+        Request::ExtendVote(_) => "ExtendVote",
+        Request::VerifyVoteExtension(_) => "VerifyVoteExtension",
+        Request::FinalizeBlock(_) => "FinalizeBlock",
+    }
+}
+
+fn exception(error: String) -> Response {
+    tracing::error!("Rejecting request: {}", error);
+    Response::Exception(response::Exception { error })
+}
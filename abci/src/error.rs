@@ -1,7 +1,6 @@
 //! tendermint-abci errors
 
 use flex_error::{define_error, DisplayError};
-use tendermint_proto::abci::response::Value;
 
 define_error! {
     Error {
@@ -17,6 +16,23 @@ define_error! {
             [ DisplayError<prost::DecodeError> ]
             | _ | { "error encoding protocol buffer" },
 
+        MessageTooLarge
+            {
+                size: u64,
+                max: usize,
+            }
+            | e | {
+                format_args!("message of {0} bytes exceeds the maximum message size of {1} bytes",
+                    e.size, e.max)
+            },
+
+        TruncatedMessage
+            { remaining: usize }
+            | e | {
+                format_args!("stream terminated with {} byte(s) of an incomplete message",
+                    e.remaining)
+            },
+
         ServerConnectionTerminated
             | _ | { "server connection terminated" },
 
@@ -26,13 +42,59 @@ define_error! {
         UnexpectedServerResponseType
             {
                 expected: String,
-                got: Value,
+                got: String,
             }
             | e | {
-                format_args!("unexpected server response type: expected {0}, but got {1:?}",
+                format_args!("unexpected server response type: expected {0}, but got {1}",
                     e.expected, e.got)
             },
 
+        InvalidRequest
+            [ tendermint::Error ]
+            | _ | { "invalid request" },
+
+        InvalidServerResponse
+            [ tendermint::Error ]
+            | _ | { "invalid server response" },
+
+        UnsupportedTransport
+            { scheme: String }
+            | e | { format_args!("unsupported transport on this platform: {}", e.scheme) },
+
+        GrpcTransport
+            { reason: String }
+            | e | { format_args!("gRPC transport error: {}", e.reason) },
+
+        GrpcStatus
+            {
+                code: String,
+                message: String,
+            }
+            | e | { format_args!("gRPC request failed with status {}: {}", e.code, e.message) },
+
+        TruncatedRecording
+            { records: usize }
+            | e | { format_args!("recording truncated after {} complete record(s)", e.records) },
+
+        InvalidRecording
+            { reason: String }
+            | e | { format_args!("invalid recording: {}", e.reason) },
+
+        UnsupportedProtocolVersion
+            { version: String }
+            | e | { format_args!("unsupported ABCI protocol version: {}", e.version) },
+
+        InvalidGenesis
+            { reason: String }
+            | e | { format_args!("invalid genesis: {}", e.reason) },
+
+        InvalidValidatorUpdates
+            { reason: String }
+            | e | { format_args!("invalid validator updates: {}", e.reason) },
+
+        NodeHalted
+            | _ | { "node halted due to a previous error" },
+
         ChannelSend
             | _ | { "channel send error" },
 
@@ -42,6 +104,12 @@ define_error! {
     }
 }
 
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::io(e)
+    }
+}
+
 impl Error {
     pub fn send<T>(_e: std::sync::mpsc::SendError<T>) -> Error {
         Error::channel_send()
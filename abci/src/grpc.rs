@@ -0,0 +1,720 @@
+//! gRPC transport for ABCI applications.
+//!
+//! Tendermint nodes configured with `abci = "grpc"` communicate with their
+//! application via the `tendermint.abci.ABCIApplication` gRPC service instead
+//! of the socket protocol (or the `tendermint.abci.ABCI` service, for CometBFT
+//! v0.38). As with the socket protocol, the messages of the service depend on
+//! the [`ProtocolVersion`] spoken by the node.
+
+use std::{
+    convert::TryFrom,
+    marker::PhantomData,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use tendermint::abci::Request;
+use tendermint_config::net::Address;
+#[cfg(not(unix))]
+use tendermint_config::net::UNIX_PREFIX;
+use tendermint_proto::{
+    abci::{
+        RequestApplySnapshotChunk, RequestBeginBlock, RequestCheckTx, RequestDeliverTx,
+        RequestEcho, RequestEndBlock, RequestInfo, RequestInitChain, RequestLoadSnapshotChunk,
+        RequestOfferSnapshot, RequestQuery, ResponseApplySnapshotChunk, ResponseBeginBlock,
+        ResponseCheckTx, ResponseCommit, ResponseDeliverTx, ResponseEcho, ResponseEndBlock,
+        ResponseFlush, ResponseInfo, ResponseInitChain, ResponseListSnapshots,
+        ResponseLoadSnapshotChunk, ResponseOfferSnapshot, ResponseQuery,
+    },
+    v0_34, v0_35, v0_37,
+    v0_38::{
+        self,
+        abci::{
+            RequestExtendVote, RequestFinalizeBlock, RequestPrepareProposal,
+            RequestProcessProposal, RequestVerifyVoteExtension, ResponseExtendVote,
+            ResponseFinalizeBlock, ResponsePrepareProposal, ResponseProcessProposal,
+            ResponseVerifyVoteExtension,
+        },
+    },
+};
+#[cfg(unix)]
+use tokio::net::UnixStream;
+use tokio::net::{TcpListener, ToSocketAddrs};
+use tokio_stream::wrappers::TcpListenerStream;
+#[cfg(unix)]
+use tokio_stream::wrappers::UnixListenerStream;
+use tonic::{
+    transport::{Channel, Endpoint},
+    Status,
+};
+use tracing::info;
+
+use crate::{
+    application::RequestDispatcher,
+    async_server::AsyncListener,
+    protocol::{
+        Protocol, ProtocolV0_34, ProtocolV0_35, ProtocolV0_37, ProtocolV0_38, ServerResponse,
+        Translate,
+    },
+    Application, Error, ProtocolVersion,
+};
+
+/// Serves the given gRPC service on a listener until it fails.
+///
+/// The router of each service has a different type, hence the macro.
+macro_rules! serve {
+    ($listener:expr, $service:expr) => {{
+        let router = tonic::transport::Server::builder().add_service($service);
+        match $listener {
+            AsyncListener::Tcp(listener) => {
+                router
+                    .serve_with_incoming(TcpListenerStream::new(listener))
+                    .await
+            },
+            #[cfg(unix)]
+            // The socket file is removed once `_path` is dropped, after the
+            // server has stopped.
+            AsyncListener::Unix {
+                listener,
+                path: _path,
+            } => {
+                router
+                    .serve_with_incoming(UnixListenerStream::new(listener))
+                    .await
+            },
+        }
+        .map_err(|e| Error::grpc_transport(e.to_string()))
+    }};
+}
+
+/// Allows us to configure and construct a gRPC ABCI server.
+#[derive(Default)]
+pub struct GrpcServerBuilder {
+    protocol_version: ProtocolVersion,
+}
+
+impl GrpcServerBuilder {
+    /// The version of the ABCI protocol to serve
+    /// ([`ProtocolVersion::V0_35`] by default).
+    ///
+    /// This must match the version of the Tendermint or CometBFT node
+    /// connecting to the server. Only the requests that are part of the given
+    /// protocol reach the application (see [`ServerBuilder::protocol_version`]).
+    ///
+    /// [`ServerBuilder::protocol_version`]: crate::ServerBuilder::protocol_version
+    pub fn protocol_version(mut self, version: ProtocolVersion) -> Self {
+        self.protocol_version = version;
+        self
+    }
+
+    /// Constructor for a gRPC ABCI server.
+    ///
+    /// Binds the server to the given address. You must subsequently call the
+    /// [`GrpcServer::listen`] method in order for incoming requests to be
+    /// routed to the specified ABCI application.
+    pub async fn bind<Addr, App>(self, addr: Addr, app: App) -> Result<GrpcServer<App>, Error>
+    where
+        Addr: ToSocketAddrs,
+        App: Application,
+    {
+        let listener = TcpListener::bind(addr).await.map_err(Error::io)?;
+        self.serve(AsyncListener::Tcp(listener), app)
+    }
+
+    /// Constructor for a gRPC ABCI server listening on a Unix domain socket.
+    ///
+    /// Binds the server to a socket at the given path, which must not already
+    /// exist, and which is removed once the server is dropped. Only supported
+    /// on Unix platforms.
+    pub async fn bind_unix<P, App>(self, path: P, app: App) -> Result<GrpcServer<App>, Error>
+    where
+        P: AsRef<Path>,
+        App: Application,
+    {
+        let listener = AsyncListener::bind_unix(path.as_ref())?;
+        self.serve(listener, app)
+    }
+
+    /// Constructor for a gRPC ABCI server bound to the given `tcp://` or
+    /// `unix://` address.
+    pub async fn bind_address<App>(self, addr: &Address, app: App) -> Result<GrpcServer<App>, Error>
+    where
+        App: Application,
+    {
+        let listener = AsyncListener::bind(addr).await?;
+        self.serve(listener, app)
+    }
+
+    fn serve<App>(self, listener: AsyncListener, app: App) -> Result<GrpcServer<App>, Error>
+    where
+        App: Application,
+    {
+        let local_addr = listener.local_addr()?;
+        info!("gRPC ABCI server running at {}", local_addr);
+        Ok(GrpcServer {
+            app,
+            listener,
+            local_addr,
+            protocol_version: self.protocol_version,
+        })
+    }
+}
+
+/// A gRPC server for serving a specific ABCI application.
+///
+/// Unlike the socket protocol, gRPC does not distinguish between Tendermint's
+/// ABCI connections, so all requests are handled by a single instance of the
+/// application, one request at a time (as with Tendermint's own local client).
+pub struct GrpcServer<App> {
+    app: App,
+    listener: AsyncListener,
+    local_addr: String,
+    protocol_version: ProtocolVersion,
+}
+
+impl<App: Application> GrpcServer<App> {
+    /// Getter for this server's local address.
+    ///
+    /// For TCP servers this is of the form `host:port`, and for Unix domain
+    /// socket servers it is the socket path prefixed with `unix://`.
+    pub fn local_addr(&self) -> String {
+        self.local_addr.clone()
+    }
+
+    /// Serve incoming gRPC requests.
+    ///
+    /// Must be called from within a Tokio runtime.
+    pub async fn listen(self) -> Result<(), Error> {
+        let app = Arc::new(Mutex::new(self.app));
+        match self.protocol_version {
+            ProtocolVersion::V0_34 => serve!(
+                self.listener,
+                v0_34::abci::abci_application_server::AbciApplicationServer::new(
+                    GrpcApplication::<_, ProtocolV0_34>::new(app)
+                )
+            ),
+            ProtocolVersion::V0_35 => serve!(
+                self.listener,
+                v0_35::abci::abci_application_server::AbciApplicationServer::new(
+                    GrpcApplication::<_, ProtocolV0_35>::new(app)
+                )
+            ),
+            ProtocolVersion::V0_37 => serve!(
+                self.listener,
+                v0_37::abci::abci_application_server::AbciApplicationServer::new(
+                    GrpcApplication::<_, ProtocolV0_37>::new(app)
+                )
+            ),
+            ProtocolVersion::V0_38 => serve!(
+                self.listener,
+                v0_38::abci::abci_server::AbciServer::new(
+                    GrpcApplication::<_, ProtocolV0_38>::new(app)
+                )
+            ),
+        }
+    }
+}
+
+/// Adapts an [`Application`] to the generated gRPC service of the protocol
+/// version `P`.
+struct GrpcApplication<App, P> {
+    app: Arc<Mutex<App>>,
+    _protocol: PhantomData<fn() -> P>,
+}
+
+impl<App, P> GrpcApplication<App, P> {
+    fn new(app: Arc<Mutex<App>>) -> Self {
+        Self {
+            app,
+            _protocol: PhantomData,
+        }
+    }
+}
+
+impl<App: Application, P: Protocol> GrpcApplication<App, P>
+where
+    Request: TryFrom<P::Request, Error = tendermint::Error>,
+{
+    async fn handle<T>(
+        &self,
+        request: P::Request,
+        extract: fn(P::Response) -> Option<T>,
+    ) -> Result<tonic::Response<T>, Status> {
+        let request = Request::try_from(request)
+            .map_err(|e| Status::invalid_argument(format!("invalid request: {}", e)))?;
+        let app = self.app.clone();
+        let response = tokio::task::spawn_blocking(move || {
+            // Keep serving requests even if the application panicked while
+            // handling a previous one, as the socket server does.
+            let app = app.lock().unwrap_or_else(|e| e.into_inner());
+            app.handle(request)
+        })
+        .await
+        .map_err(|e| Status::internal(format!("application panicked: {}", e)))?;
+        // Responses to the requests of this protocol version always convert.
+        extract(P::encode_response(response))
+            .map(tonic::Response::new)
+            .ok_or_else(|| Status::internal("unexpected response type from application"))
+    }
+}
+
+/// Implements the generated gRPC service of a protocol version for
+/// [`GrpcApplication`], by handling each of the listed methods as the request
+/// of the given type.
+macro_rules! grpc_service {
+    (
+        $service:path, $protocol:ty, $version:ident {
+            $($method:ident($type:ident): $req:ident -> $res:ident,)+
+        }
+        $($extra:item)*
+    ) => {
+        #[tonic::async_trait]
+        impl<App: Application> $service for GrpcApplication<App, $protocol> {
+            $(
+                async fn $method(
+                    &self,
+                    request: tonic::Request<$version::abci::$req>,
+                ) -> Result<tonic::Response<$version::abci::$res>, Status> {
+                    self.handle(
+                        $version::abci::Request {
+                            value: Some($version::abci::request::Value::$type(
+                                request.into_inner(),
+                            )),
+                        },
+                        |response| match response.value {
+                            Some($version::abci::response::Value::$type(r)) => Some(r),
+                            _ => None,
+                        },
+                    )
+                    .await
+                }
+            )+
+
+            $($extra)*
+        }
+    };
+}
+
+grpc_service!(
+    v0_34::abci::abci_application_server::AbciApplication, ProtocolV0_34, v0_34 {
+        echo(Echo): RequestEcho -> ResponseEcho,
+        flush(Flush): RequestFlush -> ResponseFlush,
+        info(Info): RequestInfo -> ResponseInfo,
+        deliver_tx(DeliverTx): RequestDeliverTx -> ResponseDeliverTx,
+        check_tx(CheckTx): RequestCheckTx -> ResponseCheckTx,
+        query(Query): RequestQuery -> ResponseQuery,
+        commit(Commit): RequestCommit -> ResponseCommit,
+        init_chain(InitChain): RequestInitChain -> ResponseInitChain,
+        begin_block(BeginBlock): RequestBeginBlock -> ResponseBeginBlock,
+        end_block(EndBlock): RequestEndBlock -> ResponseEndBlock,
+        list_snapshots(ListSnapshots): RequestListSnapshots -> ResponseListSnapshots,
+        offer_snapshot(OfferSnapshot): RequestOfferSnapshot -> ResponseOfferSnapshot,
+        load_snapshot_chunk(LoadSnapshotChunk):
+            RequestLoadSnapshotChunk -> ResponseLoadSnapshotChunk,
+        apply_snapshot_chunk(ApplySnapshotChunk):
+            RequestApplySnapshotChunk -> ResponseApplySnapshotChunk,
+    }
+
+    // As over the socket protocol, `SetOption` is acknowledged without
+    // involving the application.
+    async fn set_option(
+        &self,
+        _request: tonic::Request<v0_34::abci::RequestSetOption>,
+    ) -> Result<tonic::Response<v0_34::abci::ResponseSetOption>, Status> {
+        Ok(tonic::Response::new(Default::default()))
+    }
+);
+
+grpc_service!(
+    v0_35::abci::abci_application_server::AbciApplication, ProtocolV0_35, v0_35 {
+        echo(Echo): RequestEcho -> ResponseEcho,
+        flush(Flush): RequestFlush -> ResponseFlush,
+        info(Info): RequestInfo -> ResponseInfo,
+        deliver_tx(DeliverTx): RequestDeliverTx -> ResponseDeliverTx,
+        check_tx(CheckTx): RequestCheckTx -> ResponseCheckTx,
+        query(Query): RequestQuery -> ResponseQuery,
+        commit(Commit): RequestCommit -> ResponseCommit,
+        init_chain(InitChain): RequestInitChain -> ResponseInitChain,
+        begin_block(BeginBlock): RequestBeginBlock -> ResponseBeginBlock,
+        end_block(EndBlock): RequestEndBlock -> ResponseEndBlock,
+        list_snapshots(ListSnapshots): RequestListSnapshots -> ResponseListSnapshots,
+        offer_snapshot(OfferSnapshot): RequestOfferSnapshot -> ResponseOfferSnapshot,
+        load_snapshot_chunk(LoadSnapshotChunk):
+            RequestLoadSnapshotChunk -> ResponseLoadSnapshotChunk,
+        apply_snapshot_chunk(ApplySnapshotChunk):
+            RequestApplySnapshotChunk -> ResponseApplySnapshotChunk,
+    }
+);
+
+grpc_service!(
+    v0_37::abci::abci_application_server::AbciApplication, ProtocolV0_37, v0_37 {
+        echo(Echo): RequestEcho -> ResponseEcho,
+        flush(Flush): RequestFlush -> ResponseFlush,
+        info(Info): RequestInfo -> ResponseInfo,
+        deliver_tx(DeliverTx): RequestDeliverTx -> ResponseDeliverTx,
+        check_tx(CheckTx): RequestCheckTx -> ResponseCheckTx,
+        query(Query): RequestQuery -> ResponseQuery,
+        commit(Commit): RequestCommit -> ResponseCommit,
+        init_chain(InitChain): RequestInitChain -> ResponseInitChain,
+        begin_block(BeginBlock): RequestBeginBlock -> ResponseBeginBlock,
+        end_block(EndBlock): RequestEndBlock -> ResponseEndBlock,
+        list_snapshots(ListSnapshots): RequestListSnapshots -> ResponseListSnapshots,
+        offer_snapshot(OfferSnapshot): RequestOfferSnapshot -> ResponseOfferSnapshot,
+        load_snapshot_chunk(LoadSnapshotChunk):
+            RequestLoadSnapshotChunk -> ResponseLoadSnapshotChunk,
+        apply_snapshot_chunk(ApplySnapshotChunk):
+            RequestApplySnapshotChunk -> ResponseApplySnapshotChunk,
+        prepare_proposal(PrepareProposal): RequestPrepareProposal -> ResponsePrepareProposal,
+        process_proposal(ProcessProposal): RequestProcessProposal -> ResponseProcessProposal,
+    }
+);
+
+grpc_service!(
+    v0_38::abci::abci_server::Abci, ProtocolV0_38, v0_38 {
+        echo(Echo): RequestEcho -> ResponseEcho,
+        flush(Flush): RequestFlush -> ResponseFlush,
+        info(Info): RequestInfo -> ResponseInfo,
+        check_tx(CheckTx): RequestCheckTx -> ResponseCheckTx,
+        query(Query): RequestQuery -> ResponseQuery,
+        commit(Commit): RequestCommit -> ResponseCommit,
+        init_chain(InitChain): RequestInitChain -> ResponseInitChain,
+        list_snapshots(ListSnapshots): RequestListSnapshots -> ResponseListSnapshots,
+        offer_snapshot(OfferSnapshot): RequestOfferSnapshot -> ResponseOfferSnapshot,
+        load_snapshot_chunk(LoadSnapshotChunk):
+            RequestLoadSnapshotChunk -> ResponseLoadSnapshotChunk,
+        apply_snapshot_chunk(ApplySnapshotChunk):
+            RequestApplySnapshotChunk -> ResponseApplySnapshotChunk,
+        prepare_proposal(PrepareProposal): RequestPrepareProposal -> ResponsePrepareProposal,
+        process_proposal(ProcessProposal): RequestProcessProposal -> ResponseProcessProposal,
+        extend_vote(ExtendVote): RequestExtendVote -> ResponseExtendVote,
+        verify_vote_extension(VerifyVoteExtension):
+            RequestVerifyVoteExtension -> ResponseVerifyVoteExtension,
+        finalize_block(FinalizeBlock): RequestFinalizeBlock -> ResponseFinalizeBlock,
+    }
+);
+
+/// Allows us to configure and construct a gRPC ABCI client.
+#[derive(Default)]
+pub struct GrpcClientBuilder {
+    protocol_version: ProtocolVersion,
+}
+
+impl GrpcClientBuilder {
+    /// The version of the ABCI protocol spoken by the server
+    /// ([`ProtocolVersion::V0_35`] by default).
+    ///
+    /// Requests that are not part of the given protocol fail without being
+    /// sent.
+    pub fn protocol_version(mut self, version: ProtocolVersion) -> Self {
+        self.protocol_version = version;
+        self
+    }
+
+    /// Client constructor that attempts to connect to the gRPC server at the
+    /// given `host:port` network address.
+    pub async fn connect<A: AsRef<str>>(self, addr: A) -> Result<GrpcClient, Error> {
+        let channel = Endpoint::from_shared(format!("http://{}", addr.as_ref()))
+            .map_err(|e| Error::grpc_transport(e.to_string()))?
+            .connect()
+            .await
+            .map_err(|e| Error::grpc_transport(e.to_string()))?;
+        Ok(self.client(channel))
+    }
+
+    /// Client constructor that attempts to connect to the gRPC server
+    /// listening on the Unix domain socket at the given path. Only supported
+    /// on Unix platforms.
+    #[cfg(unix)]
+    pub async fn connect_unix<P: AsRef<Path>>(self, path: P) -> Result<GrpcClient, Error> {
+        let path = path.as_ref().to_path_buf();
+        // The endpoint's URI is required, but ignored by the connector.
+        let channel = Endpoint::from_static("http://[::]:0")
+            .connect_with_connector(tower::service_fn(move |_| {
+                UnixStream::connect(path.clone())
+            }))
+            .await
+            .map_err(|e| Error::grpc_transport(e.to_string()))?;
+        Ok(self.client(channel))
+    }
+
+    /// Client constructor that attempts to connect to the gRPC server
+    /// listening on the Unix domain socket at the given path. Only supported
+    /// on Unix platforms.
+    #[cfg(not(unix))]
+    pub async fn connect_unix<P: AsRef<Path>>(self, _path: P) -> Result<GrpcClient, Error> {
+        Err(Error::unsupported_transport(UNIX_PREFIX.to_string()))
+    }
+
+    /// Client constructor that attempts to connect to the gRPC server at the
+    /// given `tcp://` or `unix://` address.
+    pub async fn connect_address(self, addr: &Address) -> Result<GrpcClient, Error> {
+        match addr {
+            Address::Tcp { host, port, .. } => self.connect(format!("{}:{}", host, port)).await,
+            Address::Unix { path } => self.connect_unix(path).await,
+        }
+    }
+
+    fn client(self, channel: Channel) -> GrpcClient {
+        GrpcClient {
+            inner: ProtocolClient::new(channel, self.protocol_version),
+            protocol_version: self.protocol_version,
+        }
+    }
+}
+
+/// A gRPC client for an ABCI application.
+///
+/// Requests take the messages of the v0.35 protocol, other than the ABCI++
+/// requests, which take those of the v0.38 protocol. They can be sent over
+/// any version of the protocol they are part of (see
+/// [`GrpcClientBuilder::protocol_version`]).
+pub struct GrpcClient {
+    protocol_version: ProtocolVersion,
+    inner: ProtocolClient,
+}
+
+/// The generated gRPC client of the protocol version spoken by the server.
+enum ProtocolClient {
+    V0_34(v0_34::abci::abci_application_client::AbciApplicationClient<Channel>),
+    V0_35(v0_35::abci::abci_application_client::AbciApplicationClient<Channel>),
+    V0_37(v0_37::abci::abci_application_client::AbciApplicationClient<Channel>),
+    V0_38(v0_38::abci::abci_client::AbciClient<Channel>),
+}
+
+impl ProtocolClient {
+    fn new(channel: Channel, version: ProtocolVersion) -> Self {
+        match version {
+            ProtocolVersion::V0_34 => Self::V0_34(
+                v0_34::abci::abci_application_client::AbciApplicationClient::new(channel),
+            ),
+            ProtocolVersion::V0_35 => Self::V0_35(
+                v0_35::abci::abci_application_client::AbciApplicationClient::new(channel),
+            ),
+            ProtocolVersion::V0_37 => Self::V0_37(
+                v0_37::abci::abci_application_client::AbciApplicationClient::new(channel),
+            ),
+            ProtocolVersion::V0_38 => {
+                Self::V0_38(v0_38::abci::abci_client::AbciClient::new(channel))
+            },
+        }
+    }
+}
+
+/// Calls the given method of the server, over the listed protocol versions
+/// that it is part of (by default, all of them). The request is translated
+/// from the given protocol version into that of the server, and its response
+/// back.
+macro_rules! perform {
+    ($self:expr, $version:ident, $method:ident, $type:ident, $req:expr) => {
+        perform!(
+            $self, $version, $method, $type, $req,
+            [V0_34 => v0_34, V0_35 => v0_35, V0_37 => v0_37, V0_38 => v0_38]
+        )
+    };
+    (
+        $self:expr, $version:ident, $method:ident, $type:ident, $req:expr,
+        [$($variant:ident => $to:ident),+]
+    ) => {{
+        let request = $version::abci::Request {
+            value: Some($version::abci::request::Value::$type($req)),
+        };
+        let response: ServerResponse = match &mut $self.inner {
+            $(
+                ProtocolClient::$variant(client) => {
+                    let request: $to::abci::Request = request.translate()?;
+                    let request = match request.value {
+                        Some($to::abci::request::Value::$type(r)) => r,
+                        _ => unreachable!("requests are translated into requests of the same type"),
+                    };
+                    let response = client
+                        .$method(request)
+                        .await
+                        .map_err(|s| {
+                            Error::grpc_status(format!("{:?}", s.code()), s.message().to_string())
+                        })?
+                        .into_inner();
+                    $to::abci::Response {
+                        value: Some($to::abci::response::Value::$type(response)),
+                    }
+                    .into()
+                },
+            )+
+            #[allow(unreachable_patterns)]
+            _ => return Err(unsupported(stringify!($type), $self.protocol_version)),
+        };
+        let response: $version::abci::Response = response.translate()?;
+        match response
+            .value
+            .ok_or_else(Error::malformed_server_response)?
+        {
+            $version::abci::response::Value::$type(r) => Ok(r),
+            r => Err(Error::unexpected_server_response_type(
+                stringify!($type).to_string(),
+                format!("{:?}", r),
+            )),
+        }
+    }};
+}
+
+/// The error for a request that is not part of the given protocol version.
+fn unsupported(method: &str, version: ProtocolVersion) -> Error {
+    Error::invalid_request(tendermint::Error::unsupported_abci_method(
+        method.to_string(),
+        version.to_string(),
+    ))
+}
+
+impl GrpcClient {
+    /// Ask the ABCI server to echo back a message.
+    pub async fn echo(&mut self, req: RequestEcho) -> Result<ResponseEcho, Error> {
+        perform!(self, v0_35, echo, Echo, req)
+    }
+
+    /// Request information about the ABCI application.
+    pub async fn info(&mut self, req: RequestInfo) -> Result<ResponseInfo, Error> {
+        perform!(self, v0_35, info, Info, req)
+    }
+
+    /// To be called once upon genesis.
+    pub async fn init_chain(&mut self, req: RequestInitChain) -> Result<ResponseInitChain, Error> {
+        perform!(self, v0_35, init_chain, InitChain, req)
+    }
+
+    /// Query the application for data at the current or past height.
+    pub async fn query(&mut self, req: RequestQuery) -> Result<ResponseQuery, Error> {
+        perform!(self, v0_35, query, Query, req)
+    }
+
+    /// Check the given transaction before putting it into the local mempool.
+    pub async fn check_tx(&mut self, req: RequestCheckTx) -> Result<ResponseCheckTx, Error> {
+        perform!(self, v0_35, check_tx, CheckTx, req)
+    }
+
+    /// Signal the beginning of a new block, prior to any `DeliverTx` calls.
+    /// Not part of the v0.38 protocol.
+    pub async fn begin_block(
+        &mut self,
+        req: RequestBeginBlock,
+    ) -> Result<ResponseBeginBlock, Error> {
+        perform!(
+            self, v0_35, begin_block, BeginBlock, req,
+            [V0_34 => v0_34, V0_35 => v0_35, V0_37 => v0_37]
+        )
+    }
+
+    /// Apply a transaction to the application's state. Not part of the v0.38
+    /// protocol.
+    pub async fn deliver_tx(&mut self, req: RequestDeliverTx) -> Result<ResponseDeliverTx, Error> {
+        perform!(
+            self, v0_35, deliver_tx, DeliverTx, req,
+            [V0_34 => v0_34, V0_35 => v0_35, V0_37 => v0_37]
+        )
+    }
+
+    /// Signal the end of a block. Not part of the v0.38 protocol.
+    pub async fn end_block(&mut self, req: RequestEndBlock) -> Result<ResponseEndBlock, Error> {
+        perform!(
+            self, v0_35, end_block, EndBlock, req,
+            [V0_34 => v0_34, V0_35 => v0_35, V0_37 => v0_37]
+        )
+    }
+
+    /// Ask the application to flush any pending work.
+    pub async fn flush(&mut self) -> Result<ResponseFlush, Error> {
+        perform!(self, v0_35, flush, Flush, Default::default())
+    }
+
+    /// Commit the current state at the current height.
+    pub async fn commit(&mut self) -> Result<ResponseCommit, Error> {
+        perform!(self, v0_35, commit, Commit, Default::default())
+    }
+
+    /// Used during state sync to discover available snapshots on peers.
+    pub async fn list_snapshots(&mut self) -> Result<ResponseListSnapshots, Error> {
+        perform!(
+            self,
+            v0_35,
+            list_snapshots,
+            ListSnapshots,
+            Default::default()
+        )
+    }
+
+    /// Called when bootstrapping the node using state sync.
+    pub async fn offer_snapshot(
+        &mut self,
+        req: RequestOfferSnapshot,
+    ) -> Result<ResponseOfferSnapshot, Error> {
+        perform!(self, v0_35, offer_snapshot, OfferSnapshot, req)
+    }
+
+    /// Used during state sync to retrieve chunks of snapshots from peers.
+    pub async fn load_snapshot_chunk(
+        &mut self,
+        req: RequestLoadSnapshotChunk,
+    ) -> Result<ResponseLoadSnapshotChunk, Error> {
+        perform!(self, v0_35, load_snapshot_chunk, LoadSnapshotChunk, req)
+    }
+
+    /// Apply the given snapshot chunk to the application's state.
+    pub async fn apply_snapshot_chunk(
+        &mut self,
+        req: RequestApplySnapshotChunk,
+    ) -> Result<ResponseApplySnapshotChunk, Error> {
+        perform!(self, v0_35, apply_snapshot_chunk, ApplySnapshotChunk, req)
+    }
+
+    /// Ask the application to prepare a block proposal from the given
+    /// transactions. Only part of the v0.37 and v0.38 protocols.
+    pub async fn prepare_proposal(
+        &mut self,
+        req: RequestPrepareProposal,
+    ) -> Result<ResponsePrepareProposal, Error> {
+        perform!(
+            self, v0_38, prepare_proposal, PrepareProposal, req,
+            [V0_37 => v0_37, V0_38 => v0_38]
+        )
+    }
+
+    /// Ask the application to validate a block proposed by another node. Only
+    /// part of the v0.37 and v0.38 protocols.
+    pub async fn process_proposal(
+        &mut self,
+        req: RequestProcessProposal,
+    ) -> Result<ResponseProcessProposal, Error> {
+        perform!(
+            self, v0_38, process_proposal, ProcessProposal, req,
+            [V0_37 => v0_37, V0_38 => v0_38]
+        )
+    }
+
+    /// Request the data to attach to this node's precommit vote. Only part of
+    /// the v0.38 protocol.
+    pub async fn extend_vote(
+        &mut self,
+        req: RequestExtendVote,
+    ) -> Result<ResponseExtendVote, Error> {
+        perform!(self, v0_38, extend_vote, ExtendVote, req, [V0_38 => v0_38])
+    }
+
+    /// Verify the vote extension of another validator. Only part of the v0.38
+    /// protocol.
+    pub async fn verify_vote_extension(
+        &mut self,
+        req: RequestVerifyVoteExtension,
+    ) -> Result<ResponseVerifyVoteExtension, Error> {
+        perform!(
+            self, v0_38, verify_vote_extension, VerifyVoteExtension, req,
+            [V0_38 => v0_38]
+        )
+    }
+
+    /// Execute a decided block. Only part of the v0.38 protocol, in which it
+    /// replaces `BeginBlock`, `DeliverTx` and `EndBlock`.
+    pub async fn finalize_block(
+        &mut self,
+        req: RequestFinalizeBlock,
+    ) -> Result<ResponseFinalizeBlock, Error> {
+        perform!(self, v0_38, finalize_block, FinalizeBlock, req, [V0_38 => v0_38])
+    }
+}
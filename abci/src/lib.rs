@@ -3,10 +3,25 @@
 //! [Tendermint]: https://tendermint.com
 
 mod application;
+#[cfg(feature = "async")]
+mod async_application;
+#[cfg(all(feature = "client", feature = "async"))]
+mod async_client;
+#[cfg(feature = "async")]
+mod async_server;
 #[cfg(feature = "client")]
 mod client;
 mod codec;
+mod connection;
 pub mod error;
+#[cfg(feature = "grpc")]
+mod grpc;
+pub mod middleware;
+mod net;
+#[cfg(feature = "node")]
+pub mod node;
+mod protocol;
+pub mod recorder;
 mod server;
 
 // Common exports
@@ -14,9 +29,23 @@ mod server;
 #[cfg(feature = "echo-app")]
 pub use application::echo::EchoApp;
 #[cfg(feature = "kvstore-app")]
-pub use application::kvstore::{KeyValueStoreApp, KeyValueStoreDriver};
-pub use application::Application;
+pub use application::kvstore::{
+    KeyValueStoreApp, KeyValueStoreDriver, SnapshotConfig, CODE_TYPE_ENCODING_ERROR,
+    CODE_TYPE_UNAUTHORIZED, PROOF_OP_VALUE, SNAPSHOT_FORMAT, VALIDATOR_TX_PREFIX,
+};
+pub use application::{Application, Consensus, Info, Mempool, Snapshot};
+#[cfg(feature = "async")]
+pub use async_application::AsyncApplication;
+#[cfg(all(feature = "client", feature = "async"))]
+pub use async_client::{AsyncClient, AsyncClientBuilder, ResponseFuture};
+#[cfg(feature = "async")]
+pub use async_server::{AsyncServer, AsyncServerBuilder};
 #[cfg(feature = "client")]
 pub use client::{Client, ClientBuilder};
 pub use error::Error;
-pub use server::{Server, ServerBuilder};
+#[cfg(feature = "grpc")]
+pub use grpc::{GrpcClient, GrpcClientBuilder, GrpcServer, GrpcServerBuilder};
+pub use middleware::{ApplicationExt, Layered, Middleware};
+pub use protocol::ProtocolVersion;
+pub use recorder::RecordingApp;
+pub use server::{Server, ServerBuilder, ShutdownHandle};
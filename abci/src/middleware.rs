@@ -0,0 +1,215 @@
+//! Composable middleware for ABCI applications.
+//!
+//! A [`Middleware`] sees every request on its way to an application, and every
+//! response on its way back. Wrapping an application in a middleware produces a
+//! [`Layered`] application, which itself implements [`Application`] and can
+//! therefore be wrapped again, or served like any other application:
+//!
+//! ```rust,no_run
+//! use tendermint_abci::{
+//!     middleware::{Logging, Metrics, QueryCache, RateLimit},
+//!     ApplicationExt, Consensus, Info, Mempool, ServerBuilder, Snapshot,
+//! };
+//!
+//! #[derive(Clone)]
+//! struct MyApp;
+//!
+//! impl Consensus for MyApp {}
+//! impl Mempool for MyApp {}
+//! impl Info for MyApp {}
+//! impl Snapshot for MyApp {}
+//!
+//! let metrics = Metrics::new();
+//! let app = MyApp
+//!     .layer(QueryCache::new(1024))
+//!     .layer(RateLimit::new(100, 200))
+//!     .layer(metrics.clone())
+//!     .layer(Logging::new());
+//! let server = ServerBuilder::default().bind("127.0.0.1:26658", app).unwrap();
+//! server.listen().unwrap();
+//! ```
+//!
+//! Layers are applied from the inside out, so in the example above requests
+//! are logged and timed before they reach the rate limiter, and only then
+//! reach the query cache.
+
+mod logging;
+mod metrics;
+mod query_cache;
+mod rate_limit;
+
+pub use logging::Logging;
+pub use metrics::{LatencyStats, Metrics};
+pub use query_cache::QueryCache;
+pub use rate_limit::{RateLimit, RATE_LIMITED_CODE, RATE_LIMITED_CODESPACE};
+
+use tendermint::abci::{request, response, Request, Response};
+
+use crate::{application::RequestDispatcher, Application, Consensus, Info, Mempool, Snapshot};
+
+/// A cross-cutting concern that can be applied to any [`Application`].
+///
+/// Middleware is cloned along with the application it wraps, i.e. once for
+/// each incoming connection. As with applications, it is up to the
+/// implementation to manage state shared between clones.
+pub trait Middleware: Send + Clone + 'static {
+    /// Handle the given request, calling `next` to pass it on to the wrapped
+    /// application.
+    ///
+    /// The response must be of the same type as the request. A middleware may
+    /// respond without calling `next` at all, e.g. in order to reject or to
+    /// answer a request itself.
+    fn call(&self, request: Request, next: &dyn Fn(Request) -> Response) -> Response;
+}
+
+/// Extension methods for wrapping [`Application`]s in [`Middleware`].
+pub trait ApplicationExt: Application + Sized {
+    /// Wrap this application in the given middleware.
+    fn layer<M: Middleware>(self, middleware: M) -> Layered<M, Self> {
+        Layered::new(middleware, self)
+    }
+}
+
+impl<A: Application> ApplicationExt for A {}
+
+/// An [`Application`] wrapped in a [`Middleware`].
+#[derive(Clone)]
+pub struct Layered<M, A> {
+    middleware: M,
+    inner: A,
+}
+
+impl<M: Middleware, A: Application> Layered<M, A> {
+    /// Wrap the given application in the given middleware.
+    pub fn new(middleware: M, inner: A) -> Self {
+        Self { middleware, inner }
+    }
+
+    /// The middleware wrapping the application.
+    pub fn middleware(&self) -> &M {
+        &self.middleware
+    }
+
+    /// The wrapped application.
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    fn call(&self, request: Request) -> Response {
+        self.middleware
+            .call(request, &|request| self.inner.handle(request))
+    }
+}
+
+macro_rules! layered {
+    ($self:expr, $type:ident, $req:expr) => {
+        match $self.call(Request::$type($req)) {
+            Response::$type(r) => r,
+            r => unexpected_response(stringify!($type), r),
+        }
+    };
+    ($self:expr, $type:ident) => {
+        match $self.call(Request::$type) {
+            Response::$type(r) => r,
+            r => unexpected_response(stringify!($type), r),
+        }
+    };
+}
+
+// A middleware responding with the wrong type of response is a bug, which the
+// server reports like any other panic in the application.
+fn unexpected_response(request: &str, response: Response) -> ! {
+    panic!(
+        "middleware produced an unexpected response to a {} request: {:?}",
+        request, response
+    )
+}
+
+impl<M: Middleware, A: Application> Consensus for Layered<M, A> {
+    fn init_chain(&self, request: request::InitChain) -> response::InitChain {
+        layered!(self, InitChain, request)
+    }
+
+    fn begin_block(&self, request: request::BeginBlock) -> response::BeginBlock {
+        layered!(self, BeginBlock, request)
+    }
+
+    fn deliver_tx(&self, request: request::DeliverTx) -> response::DeliverTx {
+        layered!(self, DeliverTx, request)
+    }
+
+    fn end_block(&self, request: request::EndBlock) -> response::EndBlock {
+        layered!(self, EndBlock, request)
+    }
+
+    fn commit(&self) -> response::Commit {
+        layered!(self, Commit)
+    }
+
+    fn prepare_proposal(&self, request: request::PrepareProposal) -> response::PrepareProposal {
+        layered!(self, PrepareProposal, request)
+    }
+
+    fn process_proposal(&self, request: request::ProcessProposal) -> response::ProcessProposal {
+        layered!(self, ProcessProposal, request)
+    }
+
+    fn extend_vote(&self, request: request::ExtendVote) -> response::ExtendVote {
+        layered!(self, ExtendVote, request)
+    }
+
+    fn verify_vote_extension(
+        &self,
+        request: request::VerifyVoteExtension,
+    ) -> response::VerifyVoteExtension {
+        layered!(self, VerifyVoteExtension, request)
+    }
+
+    fn finalize_block(&self, request: request::FinalizeBlock) -> response::FinalizeBlock {
+        layered!(self, FinalizeBlock, request)
+    }
+}
+
+impl<M: Middleware, A: Application> Mempool for Layered<M, A> {
+    fn check_tx(&self, request: request::CheckTx) -> response::CheckTx {
+        layered!(self, CheckTx, request)
+    }
+}
+
+impl<M: Middleware, A: Application> Info for Layered<M, A> {
+    fn echo(&self, request: request::Echo) -> response::Echo {
+        layered!(self, Echo, request)
+    }
+
+    fn info(&self, request: request::Info) -> response::Info {
+        layered!(self, Info, request)
+    }
+
+    fn query(&self, request: request::Query) -> response::Query {
+        layered!(self, Query, request)
+    }
+}
+
+impl<M: Middleware, A: Application> Snapshot for Layered<M, A> {
+    fn list_snapshots(&self) -> response::ListSnapshots {
+        layered!(self, ListSnapshots)
+    }
+
+    fn offer_snapshot(&self, request: request::OfferSnapshot) -> response::OfferSnapshot {
+        layered!(self, OfferSnapshot, request)
+    }
+
+    fn load_snapshot_chunk(
+        &self,
+        request: request::LoadSnapshotChunk,
+    ) -> response::LoadSnapshotChunk {
+        layered!(self, LoadSnapshotChunk, request)
+    }
+
+    fn apply_snapshot_chunk(
+        &self,
+        request: request::ApplySnapshotChunk,
+    ) -> response::ApplySnapshotChunk {
+        layered!(self, ApplySnapshotChunk, request)
+    }
+}
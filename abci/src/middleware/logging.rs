@@ -0,0 +1,36 @@
+//! Request logging.
+
+use std::time::Instant;
+
+use tendermint::abci::{Request, Response};
+use tracing::{debug, info};
+
+use super::Middleware;
+use crate::connection::request_name;
+
+/// Logs every request handled by the wrapped application.
+///
+/// The type of each request and the time taken to handle it are logged at the
+/// `INFO` level, while the full requests and responses are logged at the
+/// `DEBUG` level.
+#[derive(Clone, Debug, Default)]
+pub struct Logging {}
+
+impl Logging {
+    /// Constructor.
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Middleware for Logging {
+    fn call(&self, request: Request, next: &dyn Fn(Request) -> Response) -> Response {
+        let name = request_name(&request);
+        debug!("{} request: {:?}", name, request);
+        let start = Instant::now();
+        let response = next(request);
+        info!("Handled {} request in {:?}", name, start.elapsed());
+        debug!("{} response: {:?}", name, response);
+        response
+    }
+}
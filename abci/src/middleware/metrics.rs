@@ -0,0 +1,89 @@
+//! Latency metrics.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tendermint::abci::{MethodKind, Request, Response};
+
+use super::Middleware;
+
+/// Records the time taken by the wrapped application to handle requests, per
+/// [`MethodKind`].
+///
+/// All clones of a `Metrics` middleware share the same statistics, so a clone
+/// kept aside before wrapping the application can be used to read them.
+#[derive(Clone, Debug, Default)]
+pub struct Metrics {
+    stats: Arc<Mutex<HashMap<MethodKind, LatencyStats>>>,
+}
+
+impl Metrics {
+    /// Constructor.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The statistics recorded so far for the given kind of method.
+    pub fn latency(&self, kind: MethodKind) -> LatencyStats {
+        self.lock().get(&kind).copied().unwrap_or_default()
+    }
+
+    /// The statistics recorded so far for all kinds of methods that have been
+    /// called at least once.
+    pub fn snapshot(&self) -> HashMap<MethodKind, LatencyStats> {
+        self.lock().clone()
+    }
+
+    /// Discard all statistics recorded so far.
+    pub fn reset(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<MethodKind, LatencyStats>> {
+        self.stats.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Middleware for Metrics {
+    fn call(&self, request: Request, next: &dyn Fn(Request) -> Response) -> Response {
+        let kind = request.kind();
+        let start = Instant::now();
+        let response = next(request);
+        self.lock().entry(kind).or_default().record(start.elapsed());
+        response
+    }
+}
+
+/// Latency statistics for a single kind of method.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LatencyStats {
+    /// The number of requests handled.
+    pub count: u64,
+    /// The total time taken to handle all requests.
+    pub total: Duration,
+    /// The longest time taken to handle a single request.
+    pub max: Duration,
+}
+
+impl LatencyStats {
+    /// The average time taken to handle a request, if any requests have been
+    /// handled.
+    pub fn mean(&self) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+        // Durations can only be divided by `u32`s.
+        Some(Duration::from_nanos(
+            (self.total.as_nanos() / u128::from(self.count)) as u64,
+        ))
+    }
+
+    fn record(&mut self, latency: Duration) {
+        self.count += 1;
+        self.total += latency;
+        self.max = self.max.max(latency);
+    }
+}
@@ -0,0 +1,167 @@
+//! Caching of query responses.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+};
+
+use bytes::Bytes;
+use tendermint::{
+    abci::{request, MethodKind, Request, Response},
+    block,
+};
+
+use super::Middleware;
+
+/// Caches the wrapped application's responses to `Query` requests.
+///
+/// Only successful responses are cached. As the results of queries may depend
+/// on any state of the application, including state that has not yet been
+/// committed, the cache is cleared whenever that state may change: upon every
+/// request on the consensus connection, and upon applying snapshot chunks.
+///
+/// Once the cache holds the given maximum number of responses, caching another
+/// response evicts the least recently used one.
+///
+/// All clones of a `QueryCache` middleware share the same cache.
+#[derive(Clone, Debug)]
+pub struct QueryCache {
+    capacity: usize,
+    cache: Arc<Mutex<Cache>>,
+}
+
+type QueryKey = (Bytes, String, block::Height, bool);
+
+#[derive(Debug, Default)]
+struct Cache {
+    entries: HashMap<QueryKey, Entry>,
+    // The keys of the entries by the time at which they were last used, in
+    // order to find the least recently used entry.
+    recency: BTreeMap<u64, QueryKey>,
+    // Incremented whenever an entry is used.
+    clock: u64,
+    // Incremented whenever the cache is cleared, so that responses computed
+    // from state that changed in the meantime are not cached.
+    generation: u64,
+}
+
+#[derive(Debug)]
+struct Entry {
+    response: Response,
+    last_used: u64,
+}
+
+impl Cache {
+    fn get(&mut self, key: &QueryKey) -> Option<Response> {
+        self.clock += 1;
+        let entry = self.entries.get_mut(key)?;
+        self.recency.remove(&entry.last_used);
+        self.recency.insert(self.clock, key.clone());
+        entry.last_used = self.clock;
+        Some(entry.response.clone())
+    }
+
+    fn insert(&mut self, key: QueryKey, response: Response, capacity: usize) {
+        if capacity == 0 {
+            return;
+        }
+        self.clock += 1;
+        // The same query may have been answered concurrently.
+        if let Some(entry) = self.entries.remove(&key) {
+            self.recency.remove(&entry.last_used);
+        }
+        while self.entries.len() >= capacity {
+            let oldest = match self.recency.keys().next() {
+                Some(&oldest) => oldest,
+                None => break,
+            };
+            if let Some(key) = self.recency.remove(&oldest) {
+                self.entries.remove(&key);
+            }
+        }
+        self.recency.insert(self.clock, key.clone());
+        self.entries.insert(
+            key,
+            Entry {
+                response,
+                last_used: self.clock,
+            },
+        );
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+        self.generation += 1;
+    }
+}
+
+impl QueryCache {
+    /// Cache up to `capacity` query responses.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            cache: Arc::new(Mutex::new(Cache::default())),
+        }
+    }
+
+    /// The number of query responses currently cached.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Whether no query responses are currently cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Discard all cached query responses.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Cache> {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn query(&self, query: request::Query, next: &dyn Fn(Request) -> Response) -> Response {
+        let key = (
+            query.data.clone(),
+            query.path.clone(),
+            query.height,
+            query.prove,
+        );
+        let generation = {
+            let mut cache = self.lock();
+            if let Some(response) = cache.get(&key) {
+                return response;
+            }
+            cache.generation
+        };
+        let response = next(Request::Query(query));
+        if matches!(&response, Response::Query(r) if r.code == 0) {
+            let mut cache = self.lock();
+            if cache.generation == generation {
+                cache.insert(key, response.clone(), self.capacity);
+            }
+        }
+        response
+    }
+}
+
+impl Middleware for QueryCache {
+    fn call(&self, request: Request, next: &dyn Fn(Request) -> Response) -> Response {
+        match request {
+            Request::Query(query) => self.query(query, next),
+            request => {
+                let invalidates = request.kind() == MethodKind::Consensus
+                    || matches!(request, Request::ApplySnapshotChunk(_));
+                let response = next(request);
+                if invalidates {
+                    self.clear();
+                }
+                response
+            },
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Rate limiting of `CheckTx` requests.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use tendermint::abci::{request::CheckTxKind, response, Request, Response};
+use tracing::debug;
+
+use super::Middleware;
+
+/// The code with which rate-limited `CheckTx` requests are rejected.
+pub const RATE_LIMITED_CODE: u32 = 1;
+
+/// The codespace in which [`RATE_LIMITED_CODE`] is defined.
+pub const RATE_LIMITED_CODESPACE: &str = "ratelimit";
+
+/// Limits the rate at which new transactions are checked by the wrapped
+/// application.
+///
+/// Implemented as a token bucket: each `CheckTx` request consumes a token, and
+/// tokens are replenished at a fixed rate, up to a maximum burst size. Requests
+/// made while the bucket is empty are rejected with [`RATE_LIMITED_CODE`]
+/// without reaching the application.
+///
+/// Rechecks of transactions already in the mempool are never rate limited, as
+/// rejecting them would evict valid transactions from the mempool. All other
+/// requests are passed through untouched.
+///
+/// All clones of a `RateLimit` middleware share the same bucket.
+#[derive(Clone, Debug)]
+pub struct RateLimit {
+    per_second: u32,
+    burst: u32,
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl RateLimit {
+    /// Allow up to `per_second` new transactions to be checked per second on
+    /// average, and up to `burst` transactions to be checked at once.
+    ///
+    /// The bucket starts out full. If `per_second` is zero, it is never
+    /// replenished.
+    pub fn new(per_second: u32, burst: u32) -> Self {
+        Self {
+            per_second,
+            burst,
+            bucket: Arc::new(Mutex::new(Bucket {
+                tokens: f64::from(burst),
+                updated: Instant::now(),
+            })),
+        }
+    }
+
+    fn try_acquire(&self) -> bool {
+        let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens =
+            (bucket.tokens + elapsed * f64::from(self.per_second)).min(f64::from(self.burst));
+        bucket.updated = now;
+        if bucket.tokens < 1.0 {
+            return false;
+        }
+        bucket.tokens -= 1.0;
+        true
+    }
+}
+
+impl Middleware for RateLimit {
+    fn call(&self, request: Request, next: &dyn Fn(Request) -> Response) -> Response {
+        match &request {
+            Request::CheckTx(req) if req.kind == CheckTxKind::New && !self.try_acquire() => {
+                debug!("Rate limit exceeded, rejecting transaction");
+                Response::CheckTx(response::CheckTx {
+                    code: RATE_LIMITED_CODE,
+                    log: "rate limit exceeded".to_string(),
+                    codespace: RATE_LIMITED_CODESPACE.to_string(),
+                    ..Default::default()
+                })
+            },
+            _ => next(request),
+        }
+    }
+}
@@ -0,0 +1,302 @@
+//! Transport-agnostic listeners and streams for the blocking ABCI server and
+//! client.
+
+#[cfg(unix)]
+use std::{
+    fmt, fs,
+    os::unix::net::{UnixListener, UnixStream},
+};
+use std::{
+    io::{self, Read, Write},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr, TcpListener, TcpStream},
+    path::{Path, PathBuf},
+};
+
+use tendermint_config::net::{Address, UNIX_PREFIX};
+
+#[cfg(unix)]
+use tracing::warn;
+
+use crate::Error;
+
+/// A listener for incoming connections, either over TCP or via a Unix domain
+/// socket.
+pub(crate) enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix {
+        listener: UnixListener,
+        path: SocketPath,
+    },
+}
+
+impl Listener {
+    /// Bind to the given address.
+    pub(crate) fn bind(addr: &Address) -> Result<Self, Error> {
+        match addr {
+            Address::Tcp { host, port, .. } => Ok(Self::Tcp(
+                TcpListener::bind((host.as_str(), *port)).map_err(Error::io)?,
+            )),
+            Address::Unix { path } => Self::bind_unix(Path::new(path)),
+        }
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn bind_unix(path: &Path) -> Result<Self, Error> {
+        Ok(Self::Unix {
+            listener: UnixListener::bind(path).map_err(Error::io)?,
+            path: SocketPath::new(path),
+        })
+    }
+
+    #[cfg(not(unix))]
+    pub(crate) fn bind_unix(_path: &Path) -> Result<Self, Error> {
+        Err(Error::unsupported_transport(UNIX_PREFIX.to_string()))
+    }
+
+    /// The address to which this listener is bound.
+    ///
+    /// TCP addresses are rendered as `host:port`, while Unix domain socket
+    /// addresses are rendered as `unix://path`.
+    pub(crate) fn local_addr(&self) -> Result<String, Error> {
+        match self {
+            Self::Tcp(listener) => Ok(listener.local_addr().map_err(Error::io)?.to_string()),
+            #[cfg(unix)]
+            Self::Unix { path, .. } => Ok(format!("{}{}", UNIX_PREFIX, path)),
+        }
+    }
+
+    /// An address through which this listener can be reached from the local
+    /// host.
+    ///
+    /// TCP listeners bound to an unspecified address (e.g. `0.0.0.0`) are
+    /// reached via the loopback address of the same family.
+    pub(crate) fn wake_addr(&self) -> Result<WakeAddr, Error> {
+        match self {
+            Self::Tcp(listener) => {
+                let mut addr = listener.local_addr().map_err(Error::io)?;
+                if addr.ip().is_unspecified() {
+                    addr.set_ip(match addr.ip() {
+                        IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::LOCALHOST),
+                        IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::LOCALHOST),
+                    });
+                }
+                Ok(WakeAddr::Tcp(addr))
+            },
+            #[cfg(unix)]
+            Self::Unix { path, .. } => Ok(WakeAddr::Unix(path.as_path().to_path_buf())),
+        }
+    }
+
+    /// Block until a new connection arrives, returning the stream along with a
+    /// description of the peer's address.
+    ///
+    /// Returns the underlying I/O error on failure, so that the caller can
+    /// tell whether it is worth retrying (see [`is_transient_accept_error`]).
+    pub(crate) fn accept(&self) -> io::Result<(Stream, String)> {
+        match self {
+            Self::Tcp(listener) => {
+                let (stream, addr) = listener.accept()?;
+                Ok((Stream::Tcp(stream), addr.to_string()))
+            },
+            #[cfg(unix)]
+            Self::Unix { listener, path } => {
+                let (stream, _) = listener.accept()?;
+                Ok((Stream::Unix(stream), format!("{}{}", UNIX_PREFIX, path)))
+            },
+        }
+    }
+}
+
+/// Whether the given error returned when accepting a connection only affects
+/// that connection, or is caused by a temporary lack of resources, such that
+/// the listener can keep accepting connections.
+pub(crate) fn is_transient_accept_error(e: &io::Error) -> bool {
+    match e.kind() {
+        io::ErrorKind::ConnectionAborted
+        | io::ErrorKind::ConnectionReset
+        | io::ErrorKind::ConnectionRefused
+        | io::ErrorKind::Interrupted
+        | io::ErrorKind::WouldBlock
+        | io::ErrorKind::TimedOut
+        | io::ErrorKind::OutOfMemory => true,
+        _ => is_resource_exhaustion(e),
+    }
+}
+
+/// Running out of file descriptors or buffer space has no dedicated error
+/// kind.
+#[cfg(unix)]
+fn is_resource_exhaustion(e: &io::Error) -> bool {
+    matches!(
+        e.raw_os_error(),
+        Some(libc::EMFILE | libc::ENFILE | libc::ENOBUFS)
+    )
+}
+
+#[cfg(not(unix))]
+fn is_resource_exhaustion(_e: &io::Error) -> bool {
+    false
+}
+
+/// The path of a Unix domain socket to which a listener is bound.
+///
+/// The socket file outlives the listener, and would prevent binding to the
+/// same path again, so it is removed when this is dropped along with the
+/// listener.
+#[cfg(unix)]
+pub(crate) struct SocketPath(PathBuf);
+
+#[cfg(unix)]
+impl SocketPath {
+    pub(crate) fn new(path: &Path) -> Self {
+        Self(path.to_path_buf())
+    }
+
+    pub(crate) fn as_path(&self) -> &Path {
+        &self.0
+    }
+}
+
+#[cfg(unix)]
+impl fmt::Display for SocketPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.display().fmt(f)
+    }
+}
+
+#[cfg(unix)]
+impl Drop for SocketPath {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_file(&self.0) {
+            warn!("Failed to remove socket file {}: {}", self, e);
+        }
+    }
+}
+
+/// The local address of a [`Listener`], through which a thread blocked in
+/// [`Listener::accept`] can be woken up by connecting to it.
+pub(crate) enum WakeAddr {
+    Tcp(SocketAddr),
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl WakeAddr {
+    /// Connect to the listener, immediately dropping the resulting stream.
+    pub(crate) fn wake(&self) -> Result<(), Error> {
+        match self {
+            Self::Tcp(addr) => TcpStream::connect(addr).map(drop),
+            #[cfg(unix)]
+            Self::Unix(path) => UnixStream::connect(path).map(drop),
+        }
+        .map_err(Error::io)
+    }
+}
+
+/// A bidirectional stream, either over TCP or via a Unix domain socket.
+pub(crate) enum Stream {
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+impl Stream {
+    /// Creates a new independently owned handle to the underlying socket.
+    pub(crate) fn try_clone(&self) -> Result<Self, Error> {
+        match self {
+            Self::Tcp(s) => s.try_clone().map(Self::Tcp),
+            #[cfg(unix)]
+            Self::Unix(s) => s.try_clone().map(Self::Unix),
+        }
+        .map_err(Error::io)
+    }
+
+    /// Shuts down the read half of this stream, causing any pending and
+    /// future reads to immediately return with end-of-stream.
+    pub(crate) fn shutdown_read(&self) -> Result<(), Error> {
+        match self {
+            Self::Tcp(s) => s.shutdown(Shutdown::Read),
+            #[cfg(unix)]
+            Self::Unix(s) => s.shutdown(Shutdown::Read),
+        }
+        .map_err(Error::io)
+    }
+}
+
+#[cfg(feature = "client")]
+impl Stream {
+    /// Connect to the given address.
+    pub(crate) fn connect(addr: &Address) -> Result<Self, Error> {
+        match addr {
+            Address::Tcp { host, port, .. } => Ok(Self::Tcp(
+                TcpStream::connect((host.as_str(), *port)).map_err(Error::io)?,
+            )),
+            Address::Unix { path } => Self::connect_unix(Path::new(path)),
+        }
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn connect_unix(path: &Path) -> Result<Self, Error> {
+        Ok(Self::Unix(UnixStream::connect(path).map_err(Error::io)?))
+    }
+
+    #[cfg(not(unix))]
+    pub(crate) fn connect_unix(_path: &Path) -> Result<Self, Error> {
+        Err(Error::unsupported_transport(UNIX_PREFIX.to_string()))
+    }
+}
+
+impl Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Tcp(s) => s.read(buf),
+            #[cfg(unix)]
+            Self::Unix(s) => s.read(buf),
+        }
+    }
+}
+
+impl Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Tcp(s) => s.write(buf),
+            #[cfg(unix)]
+            Self::Unix(s) => s.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Tcp(s) => s.flush(),
+            #[cfg(unix)]
+            Self::Unix(s) => s.flush(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accept_errors_are_classified() {
+        let transient = [
+            io::Error::from(io::ErrorKind::ConnectionAborted),
+            io::Error::from(io::ErrorKind::Interrupted),
+            #[cfg(unix)]
+            io::Error::from_raw_os_error(libc::EMFILE),
+        ];
+        for e in &transient {
+            assert!(is_transient_accept_error(e), "{:?}", e);
+        }
+        let fatal = [
+            io::Error::from(io::ErrorKind::InvalidInput),
+            #[cfg(unix)]
+            io::Error::from_raw_os_error(libc::EBADF),
+        ];
+        for e in &fatal {
+            assert!(!is_transient_accept_error(e), "{:?}", e);
+        }
+    }
+}
@@ -0,0 +1,575 @@
+//! In-process, single-validator consensus driver for testing ABCI
+//! applications without a Tendermint node.
+//!
+//! A [`Node`] drives any [`Application`] through its entire lifecycle: it
+//! initializes the chain from a [`Genesis`], keeps a mempool of transactions
+//! that passed `CheckTx`, and executes and commits blocks containing them,
+//! either on demand or on a timer. Validator and consensus parameter updates
+//! returned by the application take effect at the same heights as they would
+//! on a real network, and the headers of all committed blocks are recorded.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use tendermint::Genesis;
+//! use tendermint_abci::{node::Node, Consensus, Info, Mempool, Snapshot};
+//!
+//! #[derive(Clone)]
+//! struct MyApp;
+//!
+//! impl Consensus for MyApp {}
+//! impl Mempool for MyApp {}
+//! impl Info for MyApp {}
+//! impl Snapshot for MyApp {}
+//!
+//! # fn genesis() -> Genesis {
+//! #     serde_json::from_str(r#"{
+//! #         "genesis_time": "2022-01-01T00:00:00Z",
+//! #         "chain_id": "test-chain",
+//! #         "initial_height": "1",
+//! #         "consensus_params": {
+//! #             "block": { "max_bytes": "22020096", "max_gas": "-1", "time_iota_ms": "1000" },
+//! #             "evidence": {
+//! #                 "max_age_num_blocks": "100000",
+//! #                 "max_age_duration": "172800000000000",
+//! #                 "max_bytes": "1048576"
+//! #             },
+//! #             "validator": { "pub_key_types": ["ed25519"] }
+//! #         },
+//! #         "validators": [{
+//! #             "address": "B00A6323737F321EB0B8D59C6FD497A14B60938A",
+//! #             "pub_key": {
+//! #                 "type": "tendermint/PubKeyEd25519",
+//! #                 "value": "cOQZvh/h9ZioSeUMZB/1Vy1Xo5x2sjrVjlE/qHnYifM="
+//! #             },
+//! #             "power": "10",
+//! #             "name": ""
+//! #         }],
+//! #         "app_hash": ""
+//! #     }"#).unwrap()
+//! # }
+//! let node = Node::new(MyApp, &genesis()).unwrap();
+//! let response = node.check_tx("some-tx");
+//! assert_eq!(response.code, 0);
+//! let block = node.produce_block().unwrap();
+//! assert_eq!(block.txs.len(), 1);
+//! ```
+
+use std::{
+    collections::VecDeque,
+    convert::{TryFrom, TryInto},
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Arc, Mutex, MutexGuard,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use prost::Message;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use tendermint::{
+    abci::{
+        request::{self, CheckTxKind},
+        response,
+        types::{LastCommitInfo, Validator, ValidatorUpdate, VoteInfo},
+    },
+    account,
+    block::{self, header::Version},
+    chain, consensus,
+    hash::{Algorithm, AppHash, Hash},
+    merkle::simple_hash_from_byte_vectors,
+    public_key, validator, Genesis, Time,
+};
+use tendermint_proto::{abci as pb, types::HashedParams};
+use tracing::{debug, error, info};
+
+use crate::{Application, Error};
+
+/// The version of the block protocol implemented by the node.
+pub const BLOCK_PROTOCOL: u64 = 11;
+
+/// An in-process, single-validator node driving an [`Application`].
+///
+/// The node does not participate in any consensus protocol: every block it
+/// produces is immediately committed, as if signed by all of its validators.
+/// Blocks are proposed by the validator with the greatest voting power. Since
+/// no blocks are actually signed, headers do not contain a last commit hash.
+///
+/// All clones of a `Node` share the same state. Blocks are executed while
+/// holding a lock on that state, so transactions submitted concurrently are
+/// only checked once the block has been committed, as with Tendermint's own
+/// mempool.
+#[derive(Clone)]
+pub struct Node<A> {
+    app: A,
+    state: Arc<Mutex<State>>,
+}
+
+/// A block that has been executed and committed by a [`Node`], along with the
+/// application's responses.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CommittedBlock {
+    /// The block's header.
+    pub header: block::Header,
+    /// The transactions included in the block.
+    pub txs: Vec<Bytes>,
+    /// The application's responses to the block's transactions.
+    pub deliver_tx: Vec<response::DeliverTx>,
+    /// The application's response to the end of the block.
+    pub end_block: response::EndBlock,
+    /// The application's response to committing the block.
+    pub commit: response::Commit,
+}
+
+struct State {
+    chain_id: chain::Id,
+    height: block::Height,
+    last_block_id: Option<block::Id>,
+    last_block_time: Option<Time>,
+    genesis_time: Time,
+    app_hash: AppHash,
+    last_results_hash: Option<Hash>,
+    last_validators: Option<validator::Set>,
+    validators: validator::Set,
+    next_validators: validator::Set,
+    consensus_params: consensus::Params,
+    mempool: VecDeque<MempoolTx>,
+    blocks: Vec<CommittedBlock>,
+    halted: bool,
+}
+
+struct MempoolTx {
+    tx: Bytes,
+    gas_wanted: i64,
+}
+
+impl<A: Application> Node<A> {
+    /// Initialize the given application's chain from the given genesis, and
+    /// start a node which drives it.
+    pub fn new<S: Serialize>(app: A, genesis: &Genesis<S>) -> Result<Self, Error> {
+        let initial_height = block::Height::try_from(genesis.initial_height.max(1))
+            .map_err(|e| Error::invalid_genesis(e.to_string()))?;
+        let app_state_bytes = serde_json::to_vec(&genesis.app_state)
+            .map_err(|e| Error::invalid_genesis(e.to_string()))?;
+        let genesis_validators = genesis
+            .validators
+            .iter()
+            .map(|v| ValidatorUpdate {
+                pub_key: v.pub_key,
+                power: v.power,
+            })
+            .collect::<Vec<_>>();
+
+        let response = app.init_chain(request::InitChain {
+            time: genesis.genesis_time,
+            chain_id: genesis.chain_id.to_string(),
+            consensus_params: genesis.consensus_params.clone(),
+            validators: genesis_validators.clone(),
+            app_state_bytes: app_state_bytes.into(),
+            initial_height,
+        });
+
+        // As with Tendermint, validators and consensus parameters returned by
+        // the application take precedence over those in the genesis.
+        let consensus_params = response
+            .consensus_params
+            .unwrap_or_else(|| genesis.consensus_params.clone());
+        let updates = if response.validators.is_empty() {
+            genesis_validators
+        } else {
+            response.validators
+        };
+        let validators = apply_validator_updates(
+            &validator::Set::without_proposer(Vec::new()),
+            &updates,
+            &consensus_params,
+        )
+        .map_err(|e| Error::invalid_genesis(e.to_string()))?;
+        let app_hash = if response.app_hash.is_empty() {
+            genesis.app_hash.clone()
+        } else {
+            response.app_hash.to_vec()
+        };
+
+        info!(
+            "Initialized chain {} at height {}",
+            genesis.chain_id, initial_height
+        );
+        Ok(Self {
+            app,
+            state: Arc::new(Mutex::new(State {
+                chain_id: genesis.chain_id.clone(),
+                height: initial_height,
+                last_block_id: None,
+                last_block_time: None,
+                genesis_time: genesis.genesis_time,
+                app_hash: app_hash.try_into().unwrap(),
+                last_results_hash: None,
+                last_validators: None,
+                validators: validators.clone(),
+                next_validators: validators,
+                consensus_params,
+                mempool: VecDeque::new(),
+                blocks: Vec::new(),
+                halted: false,
+            })),
+        })
+    }
+
+    /// Check the given transaction, adding it to the mempool if the
+    /// application accepts it.
+    pub fn check_tx<T: Into<Bytes>>(&self, tx: T) -> response::CheckTx {
+        let tx = tx.into();
+        let mut state = self.lock();
+        let response = self.app.check_tx(request::CheckTx {
+            tx: tx.clone(),
+            kind: CheckTxKind::New,
+        });
+        if response.code == 0 {
+            state.mempool.push_back(MempoolTx {
+                tx,
+                gas_wanted: response.gas_wanted,
+            });
+        }
+        response
+    }
+
+    /// Execute and commit a block containing as many transactions from the
+    /// mempool as the current consensus parameters allow.
+    ///
+    /// Fails if the application responds with invalid validator updates, in
+    /// which case the block is not committed and the node halts.
+    pub fn produce_block(&self) -> Result<CommittedBlock, Error> {
+        let mut state = self.lock();
+        if state.halted {
+            return Err(Error::node_halted());
+        }
+        let txs = state.reap_mempool();
+        let header = state.next_header(&txs);
+        let height = header.height;
+        debug!("Executing block {} with {} txs", height, txs.len());
+
+        self.app.begin_block(request::BeginBlock {
+            hash: header.hash().as_bytes().to_vec().into(),
+            header: header.clone(),
+            last_commit_info: state.last_commit_info(),
+            byzantine_validators: Vec::new(),
+        });
+        let deliver_tx = txs
+            .iter()
+            .map(|tx| self.app.deliver_tx(request::DeliverTx { tx: tx.clone() }))
+            .collect::<Vec<_>>();
+        let end_block = self.app.end_block(request::EndBlock {
+            height: height.into(),
+        });
+
+        let consensus_params = match &end_block.consensus_param_updates {
+            Some(params) => update_consensus_params(&state.consensus_params, params),
+            None => state.consensus_params.clone(),
+        };
+        // Updates take effect at the height after the next one, since the
+        // next validator set has already been fixed by this block's header.
+        let next_validators = match apply_validator_updates(
+            &state.next_validators,
+            &end_block.validator_updates,
+            &consensus_params,
+        ) {
+            Ok(validators) => validators,
+            Err(e) => {
+                error!("Halting at height {}: {}", height, e);
+                state.halted = true;
+                return Err(e);
+            },
+        };
+        let commit = self.app.commit();
+
+        state.height = height.increment();
+        state.last_block_id = Some(block::Id {
+            hash: header.hash(),
+            part_set_header: Default::default(),
+        });
+        state.last_block_time = Some(header.time);
+        state.app_hash = commit.data.to_vec().try_into().unwrap();
+        state.last_results_hash = Some(results_hash(&deliver_tx));
+        let validators = std::mem::replace(&mut state.next_validators, next_validators);
+        state.last_validators = Some(std::mem::replace(&mut state.validators, validators));
+        state.consensus_params = consensus_params;
+        self.recheck_mempool(&mut state);
+
+        let block = CommittedBlock {
+            header,
+            txs,
+            deliver_tx,
+            end_block,
+            commit,
+        };
+        state.blocks.push(block.clone());
+        info!("Committed block {}", height);
+        Ok(block)
+    }
+
+    /// Produce a new block at the given interval on a background thread,
+    /// until the returned [`BlockProducer`] is stopped or dropped.
+    pub fn spawn_producer(&self, interval: Duration) -> BlockProducer {
+        let node = self.clone();
+        let (stop_tx, stop_rx) = mpsc::channel::<()>();
+        let handle = thread::spawn(move || loop {
+            match stop_rx.recv_timeout(interval) {
+                Err(RecvTimeoutError::Timeout) => {
+                    node.produce_block()?;
+                },
+                _ => return Ok(()),
+            }
+        });
+        BlockProducer {
+            stop: stop_tx,
+            handle,
+        }
+    }
+
+    /// The application driven by this node.
+    pub fn app(&self) -> &A {
+        &self.app
+    }
+
+    /// The height of the next block to be produced.
+    pub fn height(&self) -> block::Height {
+        self.lock().height
+    }
+
+    /// The application hash as of the most recently committed block.
+    pub fn app_hash(&self) -> AppHash {
+        self.lock().app_hash.clone()
+    }
+
+    /// The validator set for the next block to be produced.
+    pub fn validators(&self) -> validator::Set {
+        self.lock().validators.clone()
+    }
+
+    /// The consensus parameters for the next block to be produced.
+    pub fn consensus_params(&self) -> consensus::Params {
+        self.lock().consensus_params.clone()
+    }
+
+    /// The transactions in the mempool, in the order in which they will be
+    /// included in blocks.
+    pub fn mempool(&self) -> Vec<Bytes> {
+        self.lock().mempool.iter().map(|m| m.tx.clone()).collect()
+    }
+
+    /// All blocks committed so far.
+    pub fn blocks(&self) -> Vec<CommittedBlock> {
+        self.lock().blocks.clone()
+    }
+
+    /// The headers of all blocks committed so far.
+    pub fn headers(&self) -> Vec<block::Header> {
+        self.lock()
+            .blocks
+            .iter()
+            .map(|b| b.header.clone())
+            .collect()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    // Rechecks the transactions remaining in the mempool against the newly
+    // committed state, evicting those that are no longer valid.
+    fn recheck_mempool(&self, state: &mut State) {
+        let app = &self.app;
+        state.mempool.retain(|m| {
+            app.check_tx(request::CheckTx {
+                tx: m.tx.clone(),
+                kind: CheckTxKind::Recheck,
+            })
+            .code
+                == 0
+        });
+    }
+}
+
+impl State {
+    // Takes transactions from the front of the mempool for as long as they fit
+    // into a block.
+    fn reap_mempool(&mut self) -> Vec<Bytes> {
+        let max_bytes = self.consensus_params.block.max_bytes;
+        let max_gas = self.consensus_params.block.max_gas;
+        let (mut bytes, mut gas) = (0_u64, 0_i64);
+        let mut txs = Vec::new();
+        while let Some(m) = self.mempool.front() {
+            let tx_bytes = bytes + m.tx.len() as u64;
+            let tx_gas = gas.saturating_add(m.gas_wanted);
+            if tx_bytes > max_bytes || (max_gas >= 0 && tx_gas > max_gas) {
+                break;
+            }
+            bytes = tx_bytes;
+            gas = tx_gas;
+            txs.push(self.mempool.pop_front().unwrap().tx);
+        }
+        txs
+    }
+
+    fn next_header(&self, txs: &[Bytes]) -> block::Header {
+        // Block times must increase strictly monotonically.
+        let time = match self.last_block_time {
+            None => self.genesis_time,
+            Some(last) => {
+                let min = last.checked_add(Duration::from_millis(1)).unwrap();
+                Time::now().max(min)
+            },
+        };
+        let tx_hashes = txs.iter().map(|tx| Sha256::digest(tx).to_vec()).collect();
+        block::Header {
+            version: Version {
+                block: BLOCK_PROTOCOL,
+                app: self
+                    .consensus_params
+                    .version
+                    .as_ref()
+                    .map(|v| v.app_version)
+                    .unwrap_or_default(),
+            },
+            chain_id: self.chain_id.clone(),
+            height: self.height,
+            time,
+            last_block_id: self.last_block_id,
+            last_commit_hash: None,
+            data_hash: Some(merkle_hash(tx_hashes)),
+            validators_hash: self.validators.hash(),
+            next_validators_hash: self.next_validators.hash(),
+            consensus_hash: consensus_hash(&self.consensus_params),
+            app_hash: self.app_hash.clone(),
+            last_results_hash: self.last_results_hash,
+            evidence_hash: Some(merkle_hash(Vec::new())),
+            proposer_address: self.validators.validators()[0].address,
+        }
+    }
+
+    // All of the previous block's validators are reported to have signed it.
+    fn last_commit_info(&self) -> LastCommitInfo {
+        let votes = self
+            .last_validators
+            .iter()
+            .flat_map(|set| set.validators())
+            .map(|v| VoteInfo {
+                validator: Validator {
+                    address: v.address.as_bytes().try_into().unwrap(),
+                    power: v.power,
+                },
+                signed_last_block: true,
+            })
+            .collect();
+        LastCommitInfo {
+            round: Default::default(),
+            votes,
+        }
+    }
+}
+
+/// Produces blocks on a background thread. See [`Node::spawn_producer`].
+pub struct BlockProducer {
+    stop: mpsc::Sender<()>,
+    handle: JoinHandle<Result<(), Error>>,
+}
+
+impl BlockProducer {
+    /// Stop producing blocks, waiting for any block currently being produced
+    /// to be committed.
+    ///
+    /// Returns the error that stopped the producer early, if any.
+    pub fn stop(self) -> Result<(), Error> {
+        // The producer may already have stopped due to an error.
+        let _ = self.stop.send(());
+        self.handle
+            .join()
+            .unwrap_or_else(|_| Err(Error::node_halted()))
+    }
+}
+
+// Applies the given validator updates to the given validator set, as per
+// Tendermint's `validateValidatorUpdates` and `updateWithChangeSet`.
+fn apply_validator_updates(
+    validators: &validator::Set,
+    updates: &[ValidatorUpdate],
+    params: &consensus::Params,
+) -> Result<validator::Set, Error> {
+    for update in updates {
+        let algorithm = if update.pub_key.ed25519().is_some() {
+            public_key::Algorithm::Ed25519
+        } else {
+            public_key::Algorithm::Secp256k1
+        };
+        if !params.validator.pub_key_types.contains(&algorithm) {
+            return Err(Error::invalid_validator_updates(format!(
+                "validator {} uses unsupported public key type {}",
+                account::Id::from(update.pub_key),
+                algorithm.as_str()
+            )));
+        }
+    }
+    let mut result = validators.clone();
+    result
+        .update_with_change_set(updates.to_vec())
+        .map_err(|e| Error::invalid_validator_updates(e.to_string()))?;
+    if result.validators().is_empty() {
+        return Err(Error::invalid_validator_updates(
+            "validator set would be empty".to_string(),
+        ));
+    }
+    Ok(result)
+}
+
+// Applies the given consensus parameter updates, retaining the current version
+// and ABCI parameters unless they are updated as well.
+fn update_consensus_params(
+    params: &consensus::Params,
+    updates: &consensus::Params,
+) -> consensus::Params {
+    let mut result = updates.clone();
+    if result.version.is_none() {
+        result.version = params.version.clone();
+    }
+    if result.abci.is_none() {
+        result.abci = params.abci.clone();
+    }
+    result
+}
+
+fn merkle_hash(leaves: Vec<Vec<u8>>) -> Hash {
+    Hash::from_bytes(Algorithm::Sha256, &simple_hash_from_byte_vectors(leaves)).unwrap()
+}
+
+// As per Tendermint's `HashConsensusParams`, only the block size parameters
+// are included.
+fn consensus_hash(params: &consensus::Params) -> Hash {
+    let hashed = HashedParams {
+        block_max_bytes: params.block.max_bytes as i64,
+        block_max_gas: params.block.max_gas,
+    };
+    Hash::from_bytes(Algorithm::Sha256, &Sha256::digest(hashed.encode_to_vec())).unwrap()
+}
+
+// As per Tendermint's `ABCIResults`, only the deterministic fields of each
+// result are included.
+fn results_hash(results: &[response::DeliverTx]) -> Hash {
+    let leaves = results
+        .iter()
+        .map(|r| {
+            pb::ResponseDeliverTx {
+                code: r.code,
+                data: r.data.clone(),
+                gas_wanted: r.gas_wanted,
+                gas_used: r.gas_used,
+                ..Default::default()
+            }
+            .encode_to_vec()
+        })
+        .collect();
+    merkle_hash(leaves)
+}
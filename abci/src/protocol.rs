@@ -0,0 +1,346 @@
+//! Versions of the ABCI wire protocol.
+//!
+//! Tendermint v0.34 and v0.35 encode ABCI requests and responses differently,
+//! even though they call the same methods (other than the deprecated
+//! `SetOption`, which is only part of the v0.34 protocol). CometBFT v0.37 adds
+//! the ABCI++ `PrepareProposal` and `ProcessProposal` methods to the v0.34
+//! protocol, and CometBFT v0.38 additionally replaces `BeginBlock`, `DeliverTx`
+//! and `EndBlock` with `FinalizeBlock`, and adds vote extensions. Servers
+//! decode requests using the protocol version they are configured with, so
+//! that the same [`Application`] can serve chains running any of these
+//! versions.
+//!
+//! [`Application`]: crate::Application
+
+use std::{convert::TryFrom, fmt, str::FromStr};
+
+use prost::Message;
+use tendermint::abci::{response, Request, Response};
+use tendermint_proto::{v0_34, v0_35, v0_37, v0_38};
+
+use crate::Error;
+
+/// The version of the ABCI wire protocol spoken by a server, named after the
+/// Tendermint or CometBFT version that introduced it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProtocolVersion {
+    /// The protocol spoken by Tendermint v0.34.
+    V0_34,
+    /// The protocol spoken by Tendermint v0.35.
+    V0_35,
+    /// The protocol spoken by CometBFT v0.37, which adds `PrepareProposal`
+    /// and `ProcessProposal` to that of v0.34.
+    V0_37,
+    /// The protocol spoken by CometBFT v0.38, in which `FinalizeBlock`
+    /// replaces `BeginBlock`, `DeliverTx` and `EndBlock`, and which adds
+    /// `ExtendVote` and `VerifyVoteExtension`.
+    V0_38,
+}
+
+impl Default for ProtocolVersion {
+    fn default() -> Self {
+        Self::V0_35
+    }
+}
+
+impl fmt::Display for ProtocolVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::V0_34 => write!(f, "0.34"),
+            Self::V0_35 => write!(f, "0.35"),
+            Self::V0_37 => write!(f, "0.37"),
+            Self::V0_38 => write!(f, "0.38"),
+        }
+    }
+}
+
+impl FromStr for ProtocolVersion {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "0.34" => Ok(Self::V0_34),
+            "0.35" => Ok(Self::V0_35),
+            "0.37" => Ok(Self::V0_37),
+            "0.38" => Ok(Self::V0_38),
+            _ => Err(Error::unsupported_protocol_version(s.to_string())),
+        }
+    }
+}
+
+/// The messages of a version of the ABCI wire protocol, along with their
+/// conversions to and from the domain types.
+pub(crate) trait Protocol {
+    /// The raw request type of this protocol version.
+    type Request: Message + Default + Send + 'static;
+    /// The raw response type of this protocol version.
+    type Response: Message + Default + Send + 'static;
+
+    /// Convert a raw incoming request into its domain representation. Requests
+    /// that cannot be converted are answered without involving the
+    /// application, with the returned response.
+    fn parse_request(request: Self::Request) -> Result<Request, Self::Response>;
+
+    /// Convert the response to a request into its raw representation.
+    fn encode_response(response: Response) -> Self::Response;
+
+    /// Convert a request made through a client into the raw representation
+    /// of this protocol, failing for requests that are not part of it.
+    #[cfg(feature = "client")]
+    fn encode_request(request: ClientRequest) -> Result<Self::Request, Error>;
+}
+
+/// Defines a [`Protocol`] for the given version of the `tendermint_proto`
+/// ABCI messages. Requests may optionally be intercepted by a function that
+/// answers them without involving the application.
+macro_rules! protocol {
+    ($(#[$attr:meta])* $name:ident => $version:ident $(, intercept: $intercept:path)?) => {
+        $(#[$attr])*
+        pub(crate) struct $name;
+
+        impl Protocol for $name {
+            type Request = $version::abci::Request;
+            type Response = $version::abci::Response;
+
+            fn parse_request(request: Self::Request) -> Result<Request, Self::Response> {
+                $(
+                    if let Some(response) = $intercept(&request) {
+                        return Err(response);
+                    }
+                )?
+                match request.value {
+                    None => Err(Self::encode_response(invalid_request("request is empty"))),
+                    Some(_) => Request::try_from(request)
+                        .map_err(|e| Self::encode_response(invalid_request(e))),
+                }
+            }
+
+            fn encode_response(response: Response) -> Self::Response {
+                // Only ever fails for responses to requests that are not part
+                // of this protocol, which cannot be received over it in the
+                // first place.
+                Self::Response::try_from(response).unwrap_or_else(|e| {
+                    Self::encode_response(Response::Exception(response::Exception {
+                        error: e.to_string(),
+                    }))
+                })
+            }
+
+            #[cfg(feature = "client")]
+            fn encode_request(request: ClientRequest) -> Result<Self::Request, Error> {
+                match request {
+                    ClientRequest::V0_35(request) => request.translate(),
+                    ClientRequest::V0_38(request) => request.translate(),
+                }
+            }
+        }
+    };
+}
+
+protocol!(
+    /// The Tendermint v0.34 protocol.
+    ProtocolV0_34 => v0_34, intercept: acknowledge_set_option
+);
+protocol!(
+    /// The Tendermint v0.35 protocol.
+    ProtocolV0_35 => v0_35
+);
+protocol!(
+    /// The CometBFT v0.37 protocol.
+    ProtocolV0_37 => v0_37
+);
+protocol!(
+    /// The CometBFT v0.38 protocol.
+    ProtocolV0_38 => v0_38
+);
+
+/// `SetOption` has never been used by Tendermint itself, so we acknowledge it
+/// without involving the application.
+fn acknowledge_set_option(request: &v0_34::abci::Request) -> Option<v0_34::abci::Response> {
+    use v0_34::abci::{request::Value, response::Value as ResponseValue};
+
+    match request.value {
+        Some(Value::SetOption(_)) => Some(v0_34::abci::Response {
+            value: Some(ResponseValue::SetOption(Default::default())),
+        }),
+        _ => None,
+    }
+}
+
+/// A raw request made through a client.
+///
+/// The methods of the clients take the messages of the v0.35 protocol, other
+/// than the ABCI++ methods, which take those of the v0.38 protocol.
+#[cfg(feature = "client")]
+#[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
+pub(crate) enum ClientRequest {
+    V0_35(v0_35::abci::Request),
+    V0_38(v0_38::abci::Request),
+}
+
+#[cfg(feature = "client")]
+impl From<v0_35::abci::Request> for ClientRequest {
+    fn from(request: v0_35::abci::Request) -> Self {
+        Self::V0_35(request)
+    }
+}
+
+#[cfg(feature = "client")]
+impl From<v0_38::abci::Request> for ClientRequest {
+    fn from(request: v0_38::abci::Request) -> Self {
+        Self::V0_38(request)
+    }
+}
+
+/// A raw response received by a client, in the wire format of the protocol
+/// version spoken by the server.
+#[cfg(feature = "client")]
+#[derive(Debug)]
+pub(crate) enum ServerResponse {
+    V0_34(v0_34::abci::Response),
+    V0_35(v0_35::abci::Response),
+    V0_37(v0_37::abci::Response),
+    V0_38(v0_38::abci::Response),
+}
+
+macro_rules! server_response {
+    ($($variant:ident => $version:ident),+) => {
+        $(
+            #[cfg(feature = "client")]
+            impl From<$version::abci::Response> for ServerResponse {
+                fn from(response: $version::abci::Response) -> Self {
+                    Self::$variant(response)
+                }
+            }
+        )+
+    };
+}
+
+server_response!(V0_34 => v0_34, V0_35 => v0_35, V0_37 => v0_37, V0_38 => v0_38);
+
+/// Conversion of the raw messages of one protocol version into those of
+/// another.
+///
+/// Messages are passed on as they are to a protocol of the same version, so
+/// that clients never alter them, and are otherwise translated through their
+/// domain representation.
+#[cfg(feature = "client")]
+pub(crate) trait Translate<T> {
+    fn translate(self) -> Result<T, Error>;
+}
+
+#[cfg(feature = "client")]
+impl<T> Translate<T> for T {
+    fn translate(self) -> Result<T, Error> {
+        Ok(self)
+    }
+}
+
+/// Implements [`Translate`] for the requests of the given protocol version
+/// into those of the other versions, and for their responses back.
+macro_rules! translate {
+    ($from:ident => $($to:ident),+) => {
+        $(
+            #[cfg(feature = "client")]
+            impl Translate<$to::abci::Request> for $from::abci::Request {
+                fn translate(self) -> Result<$to::abci::Request, Error> {
+                    let request = Request::try_from(self).map_err(Error::invalid_request)?;
+                    $to::abci::Request::try_from(request).map_err(Error::invalid_request)
+                }
+            }
+
+            #[cfg(feature = "client")]
+            impl Translate<$from::abci::Response> for $to::abci::Response {
+                fn translate(self) -> Result<$from::abci::Response, Error> {
+                    if self.value.is_none() {
+                        return Err(Error::malformed_server_response());
+                    }
+                    let response =
+                        Response::try_from(self).map_err(Error::invalid_server_response)?;
+                    $from::abci::Response::try_from(response)
+                        .map_err(Error::invalid_server_response)
+                }
+            }
+        )+
+    };
+}
+
+translate!(v0_35 => v0_34, v0_37, v0_38);
+translate!(v0_38 => v0_34, v0_35, v0_37);
+
+/// Implements [`Translate`] for the responses received by clients into those
+/// of the given protocol versions.
+macro_rules! translate_server_response {
+    ($($to:ident),+) => {
+        $(
+            #[cfg(feature = "client")]
+            impl Translate<$to::abci::Response> for ServerResponse {
+                fn translate(self) -> Result<$to::abci::Response, Error> {
+                    match self {
+                        ServerResponse::V0_34(response) => response.translate(),
+                        ServerResponse::V0_35(response) => response.translate(),
+                        ServerResponse::V0_37(response) => response.translate(),
+                        ServerResponse::V0_38(response) => response.translate(),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+translate_server_response!(v0_35, v0_38);
+
+fn invalid_request(e: impl std::fmt::Display) -> Response {
+    tracing::error!("Failed to parse incoming request: {}", e);
+    Response::Exception(response::Exception {
+        error: format!("invalid request: {}", e),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn abci_plus_plus_responses_are_rejected_over_v0_35() {
+        use v0_35::abci::response::Value;
+
+        let res = ProtocolV0_35::encode_response(Response::PrepareProposal(Default::default()));
+        assert!(matches!(
+            res.value,
+            Some(Value::Exception(e)) if e.error.contains("PrepareProposal is not part of the v0.35")
+        ));
+        let res = ProtocolV0_35::encode_response(Response::FinalizeBlock(Default::default()));
+        assert!(matches!(
+            res.value,
+            Some(Value::Exception(e)) if e.error.contains("FinalizeBlock is not part of the v0.35")
+        ));
+    }
+
+    #[test]
+    fn only_proposal_responses_are_accepted_over_v0_37() {
+        use v0_37::abci::response::Value;
+
+        let res = ProtocolV0_37::encode_response(Response::PrepareProposal(Default::default()));
+        assert!(matches!(res.value, Some(Value::PrepareProposal(_))));
+        let res = ProtocolV0_37::encode_response(Response::FinalizeBlock(Default::default()));
+        assert!(matches!(
+            res.value,
+            Some(Value::Exception(e)) if e.error.contains("FinalizeBlock is not part of the v0.37")
+        ));
+    }
+
+    #[test]
+    fn finalize_block_replaces_block_execution_over_v0_38() {
+        use v0_38::abci::response::Value;
+
+        let res = ProtocolV0_38::encode_response(Response::FinalizeBlock(Default::default()));
+        assert!(matches!(res.value, Some(Value::FinalizeBlock(_))));
+        let res = ProtocolV0_38::encode_response(Response::DeliverTx(Default::default()));
+        assert!(matches!(
+            res.value,
+            Some(Value::Exception(e)) if e.error.contains("DeliverTx is not part of the v0.38")
+        ));
+    }
+}
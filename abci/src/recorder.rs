@@ -0,0 +1,410 @@
+//! Recording and replaying of ABCI sessions.
+//!
+//! A [`RecordingApp`] wraps any [`Application`] and records every request it
+//! handles, along with the application's response. Recordings start with a
+//! header line naming the [`ProtocolVersion`] they were made with, followed by
+//! alternating length-delimited `Request` and `Response` messages, encoded in
+//! the same way as on the wire by that version. They can subsequently be
+//! [`replay`]ed against an application in order to reproduce and diagnose its
+//! behaviour.
+
+use std::{
+    convert::TryFrom,
+    fs::File,
+    io::{BufWriter, Read, Write},
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    str,
+    sync::{Arc, Mutex},
+};
+
+use bytes::BytesMut;
+use tendermint::abci::{request, response, Request, Response};
+use tracing::error;
+
+use crate::{
+    codec::{decode_length_delimited, encode_length_delimited},
+    protocol::{Protocol, ProtocolV0_34, ProtocolV0_35, ProtocolV0_37, ProtocolV0_38},
+    server::{handle_catching_panics, panic_response},
+    Application, Consensus, Error, Info, Mempool, ProtocolVersion, Snapshot,
+};
+
+/// The start of the header line of a recording, which is followed by the
+/// protocol version of the recording.
+const HEADER_PREFIX: &str = "ABCI-RECORDING ";
+
+/// Wraps an [`Application`], recording every request and response passing
+/// through it.
+///
+/// All clones of a `RecordingApp` share the same recording. Requests from all
+/// connections are therefore recorded in the order in which the application
+/// finished handling them.
+///
+/// A request that makes the application panic is recorded along with an
+/// exception response, as reported to the client by the server, before the
+/// panic is propagated.
+///
+/// The recording must be made with the protocol version spoken by the
+/// server. Requests that are not part of that version cannot be recorded, and
+/// cause a panic rather than being left out of the recording.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use tendermint_abci::{
+///     Consensus, Info, Mempool, ProtocolVersion, RecordingApp, ServerBuilder, Snapshot,
+/// };
+///
+/// #[derive(Clone)]
+/// struct MyApp;
+///
+/// impl Consensus for MyApp {}
+/// impl Mempool for MyApp {}
+/// impl Info for MyApp {}
+/// impl Snapshot for MyApp {}
+///
+/// let version = ProtocolVersion::V0_38;
+/// let app = RecordingApp::create(MyApp, "session.abci", version).unwrap();
+/// let server = ServerBuilder::default()
+///     .protocol_version(version)
+///     .bind("127.0.0.1:26658", app)
+///     .unwrap();
+/// server.listen().unwrap();
+/// ```
+#[derive(Clone)]
+pub struct RecordingApp<A> {
+    inner: A,
+    protocol_version: ProtocolVersion,
+    writer: Arc<Mutex<Box<dyn Write + Send>>>,
+}
+
+impl<A: Application> RecordingApp<A> {
+    /// Record the given application's requests and responses to the given
+    /// writer, encoded with the given protocol version.
+    pub fn new<W: Write + Send + 'static>(
+        inner: A,
+        mut writer: W,
+        protocol_version: ProtocolVersion,
+    ) -> Result<Self, Error> {
+        writeln!(writer, "{}{}", HEADER_PREFIX, protocol_version).map_err(Error::io)?;
+        writer.flush().map_err(Error::io)?;
+        Ok(Self {
+            inner,
+            protocol_version,
+            writer: Arc::new(Mutex::new(Box::new(writer))),
+        })
+    }
+
+    /// Record the given application's requests and responses to a newly
+    /// created file at the given path, truncating it if it already exists.
+    pub fn create<P: AsRef<Path>>(
+        inner: A,
+        path: P,
+        protocol_version: ProtocolVersion,
+    ) -> Result<Self, Error> {
+        let file = File::create(path).map_err(Error::io)?;
+        Self::new(inner, BufWriter::new(file), protocol_version)
+    }
+
+    fn record(&self, request: Request, response: Response) {
+        let buf = match self.protocol_version {
+            ProtocolVersion::V0_34 => encode_record::<ProtocolV0_34>(request, response),
+            ProtocolVersion::V0_35 => encode_record::<ProtocolV0_35>(request, response),
+            ProtocolVersion::V0_37 => encode_record::<ProtocolV0_37>(request, response),
+            ProtocolVersion::V0_38 => encode_record::<ProtocolV0_38>(request, response),
+        }
+        // Only fails for requests that are not part of the protocol, which
+        // the server cannot have received if it speaks the same version.
+        .unwrap_or_else(|e| {
+            panic!(
+                "failed to record ABCI request/response pair over the v{} protocol: {}",
+                self.protocol_version, e
+            )
+        });
+        // Flush after every record so that the session survives a crash of the
+        // application, up to and including the request that caused it.
+        let mut writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        let result = writer
+            .write_all(&buf)
+            .and_then(|_| writer.flush())
+            .map_err(Error::io);
+        if let Err(e) = result {
+            error!("Failed to record ABCI request/response pair: {}", e);
+        }
+    }
+
+    /// Call the wrapped application, recording the given request along with
+    /// the application's response, or along with an exception if it panics.
+    fn record_call<R: Clone>(
+        &self,
+        request: Request,
+        call: impl FnOnce(&A) -> R,
+        into_response: fn(R) -> Response,
+    ) -> R {
+        match panic::catch_unwind(AssertUnwindSafe(|| call(&self.inner))) {
+            Ok(response) => {
+                self.record(request, into_response(response.clone()));
+                response
+            },
+            Err(payload) => {
+                self.record(request, panic_response(payload.as_ref()));
+                panic::resume_unwind(payload)
+            },
+        }
+    }
+}
+
+/// Encode a request and its response with the protocol `P`.
+fn encode_record<P>(request: Request, response: Response) -> Result<BytesMut, Error>
+where
+    P: Protocol,
+    P::Request: TryFrom<Request, Error = tendermint::Error>,
+    P::Response: TryFrom<Response, Error = tendermint::Error>,
+{
+    let request = P::Request::try_from(request).map_err(Error::invalid_request)?;
+    let response = P::Response::try_from(response).map_err(Error::invalid_request)?;
+    let mut buf = BytesMut::new();
+    encode_length_delimited(request, &mut buf)?;
+    encode_length_delimited(response, &mut buf)?;
+    Ok(buf)
+}
+
+macro_rules! record {
+    ($self:expr, $method:ident, $type:ident, $req:expr) => {{
+        let request = $req;
+        $self.record_call(
+            Request::$type(request.clone()),
+            |app| app.$method(request),
+            Response::$type,
+        )
+    }};
+    ($self:expr, $method:ident, $type:ident) => {{
+        $self.record_call(Request::$type, |app| app.$method(), Response::$type)
+    }};
+}
+
+impl<A: Application> Consensus for RecordingApp<A> {
+    fn init_chain(&self, request: request::InitChain) -> response::InitChain {
+        record!(self, init_chain, InitChain, request)
+    }
+
+    fn begin_block(&self, request: request::BeginBlock) -> response::BeginBlock {
+        record!(self, begin_block, BeginBlock, request)
+    }
+
+    fn deliver_tx(&self, request: request::DeliverTx) -> response::DeliverTx {
+        record!(self, deliver_tx, DeliverTx, request)
+    }
+
+    fn end_block(&self, request: request::EndBlock) -> response::EndBlock {
+        record!(self, end_block, EndBlock, request)
+    }
+
+    fn commit(&self) -> response::Commit {
+        record!(self, commit, Commit)
+    }
+
+    fn prepare_proposal(&self, request: request::PrepareProposal) -> response::PrepareProposal {
+        record!(self, prepare_proposal, PrepareProposal, request)
+    }
+
+    fn process_proposal(&self, request: request::ProcessProposal) -> response::ProcessProposal {
+        record!(self, process_proposal, ProcessProposal, request)
+    }
+
+    fn extend_vote(&self, request: request::ExtendVote) -> response::ExtendVote {
+        record!(self, extend_vote, ExtendVote, request)
+    }
+
+    fn verify_vote_extension(
+        &self,
+        request: request::VerifyVoteExtension,
+    ) -> response::VerifyVoteExtension {
+        record!(self, verify_vote_extension, VerifyVoteExtension, request)
+    }
+
+    fn finalize_block(&self, request: request::FinalizeBlock) -> response::FinalizeBlock {
+        record!(self, finalize_block, FinalizeBlock, request)
+    }
+}
+
+impl<A: Application> Mempool for RecordingApp<A> {
+    fn check_tx(&self, request: request::CheckTx) -> response::CheckTx {
+        record!(self, check_tx, CheckTx, request)
+    }
+}
+
+impl<A: Application> Info for RecordingApp<A> {
+    fn echo(&self, request: request::Echo) -> response::Echo {
+        record!(self, echo, Echo, request)
+    }
+
+    fn info(&self, request: request::Info) -> response::Info {
+        record!(self, info, Info, request)
+    }
+
+    fn query(&self, request: request::Query) -> response::Query {
+        record!(self, query, Query, request)
+    }
+}
+
+impl<A: Application> Snapshot for RecordingApp<A> {
+    fn list_snapshots(&self) -> response::ListSnapshots {
+        record!(self, list_snapshots, ListSnapshots)
+    }
+
+    fn offer_snapshot(&self, request: request::OfferSnapshot) -> response::OfferSnapshot {
+        record!(self, offer_snapshot, OfferSnapshot, request)
+    }
+
+    fn load_snapshot_chunk(
+        &self,
+        request: request::LoadSnapshotChunk,
+    ) -> response::LoadSnapshotChunk {
+        record!(self, load_snapshot_chunk, LoadSnapshotChunk, request)
+    }
+
+    fn apply_snapshot_chunk(
+        &self,
+        request: request::ApplySnapshotChunk,
+    ) -> response::ApplySnapshotChunk {
+        record!(self, apply_snapshot_chunk, ApplySnapshotChunk, request)
+    }
+}
+
+/// A single request recorded by a [`RecordingApp`], along with the response
+/// produced by the application at the time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    /// The recorded request.
+    pub request: Request,
+    /// The recorded response.
+    pub response: Response,
+}
+
+/// The contents of a recording made by a [`RecordingApp`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recording {
+    /// The protocol version the recording was made with.
+    pub protocol_version: ProtocolVersion,
+    /// The recorded requests and responses, in order.
+    pub records: Vec<Record>,
+}
+
+/// A recorded request for which an application produced a different response
+/// during replay.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mismatch {
+    /// The index of the request within the recording.
+    pub index: usize,
+    /// The recorded request.
+    pub request: Request,
+    /// The recorded response.
+    pub expected: Response,
+    /// The response produced during replay.
+    pub actual: Response,
+}
+
+/// Read the given recording, decoding its records with the protocol version
+/// named in its header.
+pub fn read_recording<R: Read>(mut reader: R) -> Result<Recording, Error> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data).map_err(Error::io)?;
+    let header_len = data
+        .iter()
+        .position(|&b| b == b'\n')
+        .ok_or_else(|| Error::invalid_recording("missing header".to_string()))?;
+    let protocol_version = str::from_utf8(&data[..header_len])
+        .ok()
+        .and_then(|header| header.strip_prefix(HEADER_PREFIX))
+        .ok_or_else(|| Error::invalid_recording("malformed header".to_string()))?
+        .parse()?;
+    let mut buf = BytesMut::from(&data[header_len + 1..]);
+    let records = match protocol_version {
+        ProtocolVersion::V0_34 => decode_records::<ProtocolV0_34>(&mut buf),
+        ProtocolVersion::V0_35 => decode_records::<ProtocolV0_35>(&mut buf),
+        ProtocolVersion::V0_37 => decode_records::<ProtocolV0_37>(&mut buf),
+        ProtocolVersion::V0_38 => decode_records::<ProtocolV0_38>(&mut buf),
+    }?;
+    Ok(Recording {
+        protocol_version,
+        records,
+    })
+}
+
+/// Decode the records of a recording made with the protocol `P`.
+fn decode_records<P>(buf: &mut BytesMut) -> Result<Vec<Record>, Error>
+where
+    P: Protocol,
+    Request: TryFrom<P::Request, Error = tendermint::Error>,
+    Response: TryFrom<P::Response, Error = tendermint::Error>,
+{
+    let mut records = Vec::new();
+    while !buf.is_empty() {
+        // The entire recording has already been read into memory, so there is
+        // no point in limiting the size of individual messages.
+        let request: Option<P::Request> = decode_length_delimited(buf, usize::MAX)?;
+        let response: Option<P::Response> = decode_length_delimited(buf, usize::MAX)?;
+        let (request, response) = match (request, response) {
+            (Some(request), Some(response)) => (request, response),
+            _ => return Err(Error::truncated_recording(records.len())),
+        };
+        let invalid = |e: tendermint::Error| {
+            Error::invalid_recording(format!("record #{}: {}", records.len(), e))
+        };
+        records.push(Record {
+            request: Request::try_from(request).map_err(invalid)?,
+            response: Response::try_from(response).map_err(invalid)?,
+        });
+    }
+    Ok(records)
+}
+
+/// Replay the given recording against the given application, returning the
+/// requests for which the application's responses differ from the recorded
+/// ones.
+///
+/// Requests are handled by the application in the order in which they were
+/// recorded. Responses are compared as encoded by the protocol version the
+/// recording was made with, which may leave out some of their fields. Panics
+/// of the application are reported as exceptions, as they are by the server,
+/// so that recorded panics can be reproduced.
+pub fn replay<A, R>(app: &A, reader: R) -> Result<Vec<Mismatch>, Error>
+where
+    A: Application,
+    R: Read,
+{
+    let recording = read_recording(reader)?;
+    let records = recording.records;
+    Ok(match recording.protocol_version {
+        ProtocolVersion::V0_34 => replay_records::<_, ProtocolV0_34>(app, records),
+        ProtocolVersion::V0_35 => replay_records::<_, ProtocolV0_35>(app, records),
+        ProtocolVersion::V0_37 => replay_records::<_, ProtocolV0_37>(app, records),
+        ProtocolVersion::V0_38 => replay_records::<_, ProtocolV0_38>(app, records),
+    })
+}
+
+/// Replay the records of a recording made with the protocol `P`.
+fn replay_records<A, P>(app: &A, records: Vec<Record>) -> Vec<Mismatch>
+where
+    A: Application,
+    P: Protocol,
+    P::Response: TryFrom<Response> + PartialEq,
+{
+    let mut mismatches = Vec::new();
+    for (index, record) in records.into_iter().enumerate() {
+        let actual = handle_catching_panics(app, record.request.clone());
+        let matches = P::Response::try_from(record.response.clone())
+            .map(|expected| expected == P::encode_response(actual.clone()))
+            .unwrap_or(false);
+        if !matches {
+            mismatches.push(Mismatch {
+                index,
+                request: record.request,
+                expected: record.response,
+                actual,
+            });
+        }
+    }
+    mismatches
+}
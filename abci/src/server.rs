@@ -1,21 +1,51 @@
 //! ABCI application server interface.
 
 use std::{
-    net::{TcpListener, TcpStream, ToSocketAddrs},
-    thread,
+    any::Any,
+    collections::HashMap,
+    net::{TcpListener, ToSocketAddrs},
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
 };
 
-use tracing::{error, info};
+use tendermint::abci::{response, Request, Response};
+use tendermint_config::net::Address;
+use tracing::{error, info, warn};
 
-use crate::{application::RequestDispatcher, codec::ServerCodec, error::Error, Application};
+use crate::{
+    application::RequestDispatcher,
+    codec::{ServerCodec, DEFAULT_MAX_MESSAGE_SIZE},
+    connection::ConnectionGuard,
+    error::Error,
+    net::{is_transient_accept_error, Listener, Stream, WakeAddr},
+    protocol::{
+        Protocol, ProtocolV0_34, ProtocolV0_35, ProtocolV0_37, ProtocolV0_38, ProtocolVersion,
+    },
+    Application,
+};
 
 /// The size of the read buffer for each incoming connection to the ABCI
 /// server (1MB).
 pub const DEFAULT_SERVER_READ_BUF_SIZE: usize = 1024 * 1024;
 
+/// How long to wait before accepting connections again after a transient
+/// failure, such as running out of file descriptors, which would otherwise
+/// keep recurring immediately.
+pub(crate) const ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(100);
+
 /// Allows us to configure and construct an ABCI server.
 pub struct ServerBuilder {
     read_buf_size: usize,
+    max_message_size: usize,
+    enforce_connection_roles: bool,
+    max_connections: Option<usize>,
+    protocol_version: ProtocolVersion,
 }
 
 impl ServerBuilder {
@@ -25,7 +55,63 @@ impl ServerBuilder {
     /// incoming data from the client. This needs to be tuned for your
     /// application.
     pub fn new(read_buf_size: usize) -> Self {
-        Self { read_buf_size }
+        Self {
+            read_buf_size,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            enforce_connection_roles: true,
+            max_connections: None,
+            protocol_version: ProtocolVersion::default(),
+        }
+    }
+
+    /// Limit the size of incoming messages (100MB by default).
+    ///
+    /// Connections on which a larger message is received are closed.
+    pub fn max_message_size(mut self, max: usize) -> Self {
+        self.max_message_size = max;
+        self
+    }
+
+    /// Limit the number of concurrently open connections (unlimited by
+    /// default).
+    ///
+    /// Incoming connections beyond this limit are closed immediately.
+    pub fn max_connections(mut self, max: usize) -> Self {
+        self.max_connections = Some(max);
+        self
+    }
+
+    /// Whether or not to enforce the role of each incoming connection
+    /// (enabled by default).
+    ///
+    /// When enabled, the role of each connection (consensus, mempool, info or
+    /// snapshot) is determined by the first request received on it, and
+    /// requests belonging to any other role are answered with an exception.
+    /// `Echo` and `Flush` requests are allowed on all connections. On the
+    /// consensus connection, the server also enforces the order of
+    /// `InitChain`, and `BeginBlock`, `DeliverTx`, `EndBlock` and `Commit` for
+    /// each block.
+    ///
+    /// Disabling this allows a single client connection to issue requests of
+    /// any kind, e.g. for testing.
+    pub fn enforce_connection_roles(mut self, enforce: bool) -> Self {
+        self.enforce_connection_roles = enforce;
+        self
+    }
+
+    /// The version of the ABCI wire protocol spoken by the server
+    /// ([`ProtocolVersion::V0_35`] by default).
+    ///
+    /// This must match the version of the Tendermint or CometBFT node
+    /// connecting to the server. Applications are unaffected by this setting,
+    /// other than only receiving the requests that are part of the protocol:
+    /// ABCI++ requests are only received over the v0.37 and v0.38 protocols
+    /// (the former only having `PrepareProposal` and `ProcessProposal`), and
+    /// `BeginBlock`, `DeliverTx` and `EndBlock` are replaced by
+    /// `FinalizeBlock` over the v0.38 protocol.
+    pub fn protocol_version(mut self, version: ProtocolVersion) -> Self {
+        self.protocol_version = version;
+        self
     }
 
     /// Constructor for an ABCI server.
@@ -39,62 +125,226 @@ impl ServerBuilder {
         App: Application,
     {
         let listener = TcpListener::bind(addr).map_err(Error::io)?;
-        let local_addr = listener.local_addr().map_err(Error::io)?.to_string();
+        self.serve(Listener::Tcp(listener), app)
+    }
+
+    /// Constructor for an ABCI server listening on a Unix domain socket.
+    ///
+    /// Binds the server to a socket at the given path, which must not already
+    /// exist, and which is removed once the server is dropped. Only supported
+    /// on Unix platforms.
+    pub fn bind_unix<P, App>(self, path: P, app: App) -> Result<Server<App>, Error>
+    where
+        P: AsRef<Path>,
+        App: Application,
+    {
+        let listener = Listener::bind_unix(path.as_ref())?;
+        self.serve(listener, app)
+    }
+
+    /// Constructor for an ABCI server bound to the given `tcp://` or `unix://`
+    /// address, such as the `proxy_app` address from Tendermint's
+    /// configuration.
+    pub fn bind_address<App>(self, addr: &Address, app: App) -> Result<Server<App>, Error>
+    where
+        App: Application,
+    {
+        let listener = Listener::bind(addr)?;
+        self.serve(listener, app)
+    }
+
+    fn serve<App: Application>(self, listener: Listener, app: App) -> Result<Server<App>, Error> {
+        let local_addr = listener.local_addr()?;
+        let shared = Arc::new(Shared {
+            shutdown: AtomicBool::new(false),
+            next_id: AtomicU64::new(0),
+            connections: Mutex::new(HashMap::new()),
+            wake_addr: listener.wake_addr()?,
+        });
         info!("ABCI server running at {}", local_addr);
         Ok(Server {
             app,
             listener,
             local_addr,
             read_buf_size: self.read_buf_size,
+            max_message_size: self.max_message_size,
+            enforce_connection_roles: self.enforce_connection_roles,
+            max_connections: self.max_connections,
+            protocol_version: self.protocol_version,
+            shared,
         })
     }
 }
 
 impl Default for ServerBuilder {
     fn default() -> Self {
-        Self {
-            read_buf_size: DEFAULT_SERVER_READ_BUF_SIZE,
-        }
+        Self::new(DEFAULT_SERVER_READ_BUF_SIZE)
     }
 }
 
-/// A TCP-based server for serving a specific ABCI application.
+/// A TCP- or Unix domain socket-based server for serving a specific ABCI
+/// application.
 ///
 /// Each incoming connection is handled in a separate thread. The ABCI
 /// application is cloned for access in each thread. It is up to the
 /// application developer to manage shared state across these different
 /// threads.
+///
+/// A panic in any of the application's methods is caught and reported to the
+/// client as an exception, leaving the connection (and the rest of the server)
+/// running.
 pub struct Server<App> {
     app: App,
-    listener: TcpListener,
+    listener: Listener,
     local_addr: String,
     read_buf_size: usize,
+    max_message_size: usize,
+    enforce_connection_roles: bool,
+    max_connections: Option<usize>,
+    protocol_version: ProtocolVersion,
+    shared: Arc<Shared>,
 }
 
 impl<App: Application> Server<App> {
     /// Initiate a blocking listener for incoming connections.
+    ///
+    /// Returns once shutdown has been requested via a [`ShutdownHandle`] and
+    /// all open connections have finished processing their in-flight
+    /// requests.
+    ///
+    /// Failures to accept individual connections are logged and otherwise
+    /// ignored. If the listener itself fails, the server shuts down as if
+    /// requested to, and the error is returned once all open connections have
+    /// been closed.
     pub fn listen(self) -> Result<(), Error> {
-        loop {
-            let (stream, addr) = self.listener.accept().map_err(Error::io)?;
-            let addr = addr.to_string();
+        let mut handlers = Vec::new();
+        let result = loop {
+            let (stream, addr) = match self.listener.accept() {
+                Ok(accepted) => accepted,
+                Err(e) if is_transient_accept_error(&e) => {
+                    warn!("Failed to accept incoming connection: {}", e);
+                    thread::sleep(ACCEPT_RETRY_DELAY);
+                    continue;
+                },
+                Err(e) => {
+                    error!("Failed to accept incoming connections: {}", e);
+                    self.shared.close_connections();
+                    break Err(Error::io(e));
+                },
+            };
+            // Shutdown requests wake us up by connecting to the listener, so
+            // this connection is not served.
+            if self.shared.is_shutdown() {
+                break Ok(());
+            }
             info!("Incoming connection from: {}", addr);
-            self.spawn_client_handler(stream, addr);
+            handlers.retain(|h: &JoinHandle<()>| !h.is_finished());
+            match self.spawn_client_handler(stream, addr) {
+                Ok(Some(handler)) => handlers.push(handler),
+                Ok(None) => {},
+                Err(e) => warn!("Failed to handle incoming connection: {}", e),
+            }
+        };
+        info!("ABCI server shutting down, waiting for open connections to close");
+        for handler in handlers {
+            let _ = handler.join();
         }
+        result
     }
 
     /// Getter for this server's local address.
+    ///
+    /// For TCP servers this is of the form `host:port`, and for Unix domain
+    /// socket servers it is the socket path prefixed with `unix://`.
     pub fn local_addr(&self) -> String {
         self.local_addr.clone()
     }
 
-    fn spawn_client_handler(&self, stream: TcpStream, addr: String) {
+    /// Obtain a handle through which this server can be shut down.
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        ShutdownHandle {
+            shared: self.shared.clone(),
+        }
+    }
+
+    fn spawn_client_handler(
+        &self,
+        stream: Stream,
+        addr: String,
+    ) -> Result<Option<JoinHandle<()>>, Error> {
+        let id = {
+            let mut connections = self.shared.connections.lock().unwrap();
+            // Checked while holding the lock to avoid racing with a shutdown
+            // request, which needs to see all open connections.
+            if self.shared.is_shutdown() {
+                return Ok(None);
+            }
+            if let Some(max) = self.max_connections {
+                if connections.len() >= max {
+                    warn!(
+                        "Rejecting connection from {}: maximum of {} connections reached",
+                        addr, max
+                    );
+                    return Ok(None);
+                }
+            }
+            let id = self.shared.next_id.fetch_add(1, Ordering::SeqCst);
+            connections.insert(id, stream.try_clone()?);
+            id
+        };
         let app = self.app.clone();
-        let read_buf_size = self.read_buf_size;
-        let _ = thread::spawn(move || Self::handle_client(stream, addr, app, read_buf_size));
+        let (read_buf_size, max_message_size) = (self.read_buf_size, self.max_message_size);
+        let guard = ConnectionGuard::new(self.enforce_connection_roles);
+        let shared = self.shared.clone();
+        let protocol_version = self.protocol_version;
+        Ok(Some(thread::spawn(move || {
+            match protocol_version {
+                ProtocolVersion::V0_34 => Self::handle_client::<ProtocolV0_34>(
+                    stream,
+                    addr,
+                    app,
+                    read_buf_size,
+                    max_message_size,
+                    guard,
+                ),
+                ProtocolVersion::V0_35 => Self::handle_client::<ProtocolV0_35>(
+                    stream,
+                    addr,
+                    app,
+                    read_buf_size,
+                    max_message_size,
+                    guard,
+                ),
+                ProtocolVersion::V0_37 => Self::handle_client::<ProtocolV0_37>(
+                    stream,
+                    addr,
+                    app,
+                    read_buf_size,
+                    max_message_size,
+                    guard,
+                ),
+                ProtocolVersion::V0_38 => Self::handle_client::<ProtocolV0_38>(
+                    stream,
+                    addr,
+                    app,
+                    read_buf_size,
+                    max_message_size,
+                    guard,
+                ),
+            }
+            shared.connections.lock().unwrap().remove(&id);
+        })))
     }
 
-    fn handle_client(stream: TcpStream, addr: String, app: App, read_buf_size: usize) {
-        let mut codec = ServerCodec::new(stream, read_buf_size);
+    fn handle_client<P: Protocol>(
+        stream: Stream,
+        addr: String,
+        app: App,
+        read_buf_size: usize,
+        max_message_size: usize,
+        mut guard: ConnectionGuard,
+    ) {
+        let mut codec = ServerCodec::<_, P>::new(stream, read_buf_size, max_message_size);
         info!("Listening for incoming requests from {}", addr);
         loop {
             let request = match codec.next() {
@@ -113,7 +363,13 @@ impl<App: Application> Server<App> {
                     return;
                 },
             };
-            let response = app.handle(request);
+            let response = match P::parse_request(request) {
+                Ok(request) => P::encode_response(match guard.check(request) {
+                    Ok(request) => handle_catching_panics(&app, request),
+                    Err(exception) => exception,
+                }),
+                Err(response) => response,
+            };
             if let Err(e) = codec.send(response) {
                 error!("Failed sending response to client {}: {:?}", addr, e);
                 return;
@@ -121,3 +377,79 @@ impl<App: Application> Server<App> {
         }
     }
 }
+
+/// A handle that allows for shutting down a [`Server`] from another thread.
+#[derive(Clone)]
+pub struct ShutdownHandle {
+    shared: Arc<Shared>,
+}
+
+impl ShutdownHandle {
+    /// Request that the server shut down.
+    ///
+    /// The server stops accepting new connections, and stops reading new
+    /// requests from open connections. Requests that are already being
+    /// processed are allowed to complete and their responses are sent, after
+    /// which [`Server::listen`] returns.
+    pub fn shutdown(&self) {
+        if !self.shared.close_connections() {
+            return;
+        }
+        // Unblock the server if it's waiting for incoming connections
+        if let Err(e) = self.shared.wake_addr.wake() {
+            warn!("Failed to wake up the ABCI server listener: {:?}", e);
+        }
+    }
+}
+
+/// State shared between a server, its connection handlers and its shutdown
+/// handles.
+struct Shared {
+    shutdown: AtomicBool,
+    next_id: AtomicU64,
+    connections: Mutex<HashMap<u64, Stream>>,
+    wake_addr: WakeAddr,
+}
+
+impl Shared {
+    fn is_shutdown(&self) -> bool {
+        self.shutdown.load(Ordering::SeqCst)
+    }
+
+    /// Mark the server as shut down, and stop reading requests from all open
+    /// connections. Returns false if the server was already shut down.
+    fn close_connections(&self) -> bool {
+        let connections = self.connections.lock().unwrap();
+        if self.shutdown.swap(true, Ordering::SeqCst) {
+            return false;
+        }
+        for stream in connections.values() {
+            if let Err(e) = stream.shutdown_read() {
+                warn!("Failed to shut down connection: {:?}", e);
+            }
+        }
+        true
+    }
+}
+
+/// Execute the given request against the application, converting any panic
+/// into an exception response.
+pub(crate) fn handle_catching_panics<App: Application>(app: &App, request: Request) -> Response {
+    match panic::catch_unwind(AssertUnwindSafe(|| app.handle(request))) {
+        Ok(response) => response,
+        Err(payload) => panic_response(payload.as_ref()),
+    }
+}
+
+/// Build the exception response reporting the panic with the given payload.
+pub(crate) fn panic_response(payload: &(dyn Any + Send)) -> Response {
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string());
+    error!("Application panicked while handling request: {}", message);
+    Response::Exception(response::Exception {
+        error: format!("application panicked: {}", message),
+    })
+}
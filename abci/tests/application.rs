@@ -0,0 +1,35 @@
+//! Tests of the default implementations of application methods.
+
+mod application_defaults {
+    use tendermint::{
+        abci::{request, types::LastCommitInfo},
+        account, Hash, Time,
+    };
+    use tendermint_abci::Consensus;
+
+    /// Relies on the defaults of all of its methods.
+    struct DefaultApp;
+
+    impl Consensus for DefaultApp {}
+
+    #[test]
+    fn finalize_block_returns_a_default_result_per_tx() {
+        let request = request::FinalizeBlock {
+            txs: vec!["a=1".into(), "b=2".into()],
+            decided_last_commit: LastCommitInfo {
+                round: Default::default(),
+                votes: vec![],
+            },
+            misbehavior: vec![],
+            hash: Hash::None,
+            height: 3u32.into(),
+            time: Time::from_unix_timestamp(1_600_000_000, 0).unwrap(),
+            next_validators_hash: Hash::None,
+            proposer_address: account::Id::new([0xab; 20]),
+        };
+
+        let response = DefaultApp.finalize_block(request);
+        assert_eq!(response.tx_results, vec![Default::default(); 2]);
+        assert!(response.app_hash.is_empty());
+    }
+}
@@ -0,0 +1,185 @@
+//! Integration tests for the pipelined asynchronous ABCI client.
+
+#[cfg(all(feature = "client", feature = "async"))]
+mod common;
+
+#[cfg(all(feature = "client", feature = "async"))]
+mod async_client_integration {
+    use async_trait::async_trait;
+    use bytes::Bytes;
+    use tendermint::abci::{request, response};
+    use tendermint_abci::{
+        error::ErrorDetail, AsyncApplication, AsyncClientBuilder, AsyncServerBuilder, Error,
+        ProtocolVersion,
+    };
+    use tendermint_proto::{
+        abci::{RequestEcho, RequestInfo},
+        v0_38::abci::{
+            response_process_proposal::ProposalStatus, response_verify_vote_extension::VerifyStatus,
+        },
+    };
+
+    use crate::common::{
+        extend_vote_request, finalize_block_request, prepare_proposal_request,
+        process_proposal_request, txs, verify_vote_extension_request,
+    };
+
+    /// Relies entirely on the default method implementations.
+    struct EchoApp;
+
+    #[async_trait]
+    impl AsyncApplication for EchoApp {}
+
+    /// Finalizes blocks by reporting their height as the application hash.
+    struct FinalizingApp;
+
+    #[async_trait]
+    impl AsyncApplication for FinalizingApp {
+        async fn finalize_block(&self, request: request::FinalizeBlock) -> response::FinalizeBlock {
+            response::FinalizeBlock {
+                app_hash: request.height.to_string().into(),
+                ..Default::default()
+            }
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn responses_are_matched_to_pipelined_requests() {
+        let server = AsyncServerBuilder::default()
+            .bind("127.0.0.1:0", EchoApp)
+            .await
+            .unwrap();
+        let server_addr = server.local_addr();
+        tokio::spawn(server.listen());
+
+        let mut client = AsyncClientBuilder::default()
+            .connect(&server_addr)
+            .await
+            .unwrap();
+        let mut echoes = Vec::new();
+        for i in 0..100 {
+            echoes.push(
+                client
+                    .echo(RequestEcho {
+                        message: format!("message {}", i),
+                    })
+                    .await
+                    .unwrap(),
+            );
+        }
+        let info = client.info(RequestInfo::default()).await.unwrap();
+        client.flush().await.unwrap();
+
+        for (i, echo) in echoes.into_iter().enumerate() {
+            assert_eq!(echo.await.unwrap().message, format!("message {}", i));
+        }
+        assert_eq!(info.await.unwrap().last_block_height, 0);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn pending_responses_fail_when_connection_terminates() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = listener.local_addr().unwrap();
+        let (close_tx, close_rx) = tokio::sync::oneshot::channel::<()>();
+        tokio::spawn(async move {
+            // Accept a single connection and close it without responding.
+            let (stream, _) = listener.accept().await.unwrap();
+            let _ = close_rx.await;
+            drop(stream);
+        });
+
+        let mut client = AsyncClientBuilder::default()
+            .connect(server_addr)
+            .await
+            .unwrap();
+        let echo = client
+            .echo(RequestEcho {
+                message: "Hello ABCI!".to_string(),
+            })
+            .await
+            .unwrap();
+        close_tx.send(()).unwrap();
+        assert!(client.flush().await.is_err());
+        let err: Error = echo.await.unwrap_err();
+        assert!(err.to_string().contains("server connection terminated"));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn abci_plus_plus_requests_round_trip_over_v0_38() {
+        let server = AsyncServerBuilder::default()
+            .protocol_version(ProtocolVersion::V0_38)
+            .bind("127.0.0.1:0", FinalizingApp)
+            .await
+            .unwrap();
+        let server_addr = server.local_addr();
+        tokio::spawn(server.listen());
+
+        let mut client = AsyncClientBuilder::default()
+            .protocol_version(ProtocolVersion::V0_38)
+            .connect(&server_addr)
+            .await
+            .unwrap();
+        let prepare_proposal = client
+            .prepare_proposal(prepare_proposal_request())
+            .await
+            .unwrap();
+        let process_proposal = client
+            .process_proposal(process_proposal_request(txs()))
+            .await
+            .unwrap();
+        let extend_vote = client.extend_vote(extend_vote_request(1)).await.unwrap();
+        let verify_vote_extension = client
+            .verify_vote_extension(verify_vote_extension_request(1, Bytes::new()))
+            .await
+            .unwrap();
+        let finalize_block = client
+            .finalize_block(finalize_block_request(1))
+            .await
+            .unwrap();
+        client.flush().await.unwrap();
+
+        assert_eq!(prepare_proposal.await.unwrap().txs.len(), 2);
+        assert_eq!(
+            process_proposal.await.unwrap().status,
+            ProposalStatus::Accept as i32
+        );
+        assert!(extend_vote.await.unwrap().vote_extension.is_empty());
+        assert_eq!(
+            verify_vote_extension.await.unwrap().status,
+            VerifyStatus::Accept as i32
+        );
+        assert_eq!(finalize_block.await.unwrap().app_hash, Bytes::from("1"));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn requests_outside_the_protocol_are_not_sent() {
+        let server = AsyncServerBuilder::default()
+            .protocol_version(ProtocolVersion::V0_37)
+            .bind("127.0.0.1:0", EchoApp)
+            .await
+            .unwrap();
+        let server_addr = server.local_addr();
+        tokio::spawn(server.listen());
+
+        let mut client = AsyncClientBuilder::default()
+            .protocol_version(ProtocolVersion::V0_37)
+            .connect(&server_addr)
+            .await
+            .unwrap();
+        let err = match client.extend_vote(extend_vote_request(1)).await {
+            Ok(_) => panic!("extend_vote is not part of the v0.37 protocol"),
+            Err(e) => e,
+        };
+        assert!(matches!(err.detail(), ErrorDetail::InvalidRequest(_)));
+
+        // The connection is still usable
+        let echo = client
+            .echo(RequestEcho {
+                message: "Hello ABCI!".to_string(),
+            })
+            .await
+            .unwrap();
+        client.flush().await.unwrap();
+        assert_eq!(echo.await.unwrap().message, "Hello ABCI!");
+    }
+}
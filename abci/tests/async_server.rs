@@ -0,0 +1,123 @@
+//! Integration tests for the asynchronous ABCI server.
+
+#[cfg(all(feature = "client", feature = "async"))]
+mod async_server_integration {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use async_trait::async_trait;
+    use tendermint::abci::{request, response};
+    use tendermint_abci::{AsyncApplication, AsyncServerBuilder, ClientBuilder};
+    use tendermint_proto::abci::{RequestDeliverTx, RequestEcho, RequestInfo, RequestQuery};
+
+    /// Counts delivered transactions across all connections.
+    #[derive(Default)]
+    struct CounterApp {
+        count: AtomicU64,
+    }
+
+    #[async_trait]
+    impl AsyncApplication for CounterApp {
+        async fn info(&self, _request: request::Info) -> response::Info {
+            response::Info {
+                data: Default::default(),
+                version: Default::default(),
+                app_version: 0,
+                last_block_height: (self.count.load(Ordering::SeqCst) as u32).into(),
+                last_block_app_hash: Default::default(),
+            }
+        }
+
+        async fn deliver_tx(&self, _request: request::DeliverTx) -> response::DeliverTx {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            Default::default()
+        }
+
+        async fn query(&self, _request: request::Query) -> response::Query {
+            panic!("query not supported");
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn state_is_shared_between_connections() {
+        // A single connection delivers transactions outside of blocks
+        let server = AsyncServerBuilder::default()
+            .enforce_connection_roles(false)
+            .bind("127.0.0.1:0", CounterApp::default())
+            .await
+            .unwrap();
+        let server_addr = server.local_addr();
+        tokio::spawn(server.listen());
+
+        let info = tokio::task::spawn_blocking(move || {
+            let mut consensus = ClientBuilder::default().connect(&server_addr).unwrap();
+            let mut info = ClientBuilder::default().connect(&server_addr).unwrap();
+
+            let res = info
+                .echo(RequestEcho {
+                    message: "Hello ABCI!".to_string(),
+                })
+                .unwrap();
+            assert_eq!(res.message, "Hello ABCI!");
+
+            for _ in 0..3 {
+                consensus
+                    .deliver_tx(RequestDeliverTx {
+                        tx: "test-tx".into(),
+                    })
+                    .unwrap();
+            }
+            info.info(RequestInfo::default()).unwrap()
+        })
+        .await
+        .unwrap();
+        assert_eq!(info.last_block_height, 3);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn panics_are_reported_as_exceptions() {
+        let server = AsyncServerBuilder::default()
+            .bind("127.0.0.1:0", CounterApp::default())
+            .await
+            .unwrap();
+        let server_addr = server.local_addr();
+        tokio::spawn(server.listen());
+
+        tokio::task::spawn_blocking(move || {
+            let mut client = ClientBuilder::default().connect(&server_addr).unwrap();
+            let err = client.query(RequestQuery::default()).unwrap_err();
+            assert!(err.to_string().contains("query not supported"), "{}", err);
+            let res = client
+                .echo(RequestEcho {
+                    message: "Hello ABCI!".to_string(),
+                })
+                .unwrap();
+            assert_eq!(res.message, "Hello ABCI!");
+        })
+        .await
+        .unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn dropping_the_server_removes_the_socket_file() {
+        let path =
+            std::env::temp_dir().join(format!("abci-async-server-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let server = AsyncServerBuilder::default()
+            .bind_unix(&path, CounterApp::default())
+            .await
+            .unwrap();
+        assert!(path.exists());
+        let listener = tokio::spawn(server.listen());
+        listener.abort();
+        assert!(listener.await.unwrap_err().is_cancelled());
+        assert!(!path.exists());
+
+        // The path can be bound again
+        AsyncServerBuilder::default()
+            .bind_unix(&path, CounterApp::default())
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,168 @@
+//! Integration tests for the blocking ABCI client over the ABCI++ protocols.
+
+#[cfg(feature = "client")]
+mod common;
+
+#[cfg(feature = "client")]
+mod client_integration {
+    use std::thread;
+
+    use bytes::Bytes;
+    use tendermint::abci::{request, response, types::ExecTxResult};
+    use tendermint_abci::{
+        error::ErrorDetail, Client, ClientBuilder, Consensus, Info, Mempool, ProtocolVersion,
+        ServerBuilder, Snapshot,
+    };
+    use tendermint_proto::{
+        abci::RequestDeliverTx,
+        v0_38::abci::{
+            response_process_proposal::ProposalStatus, response_verify_vote_extension::VerifyStatus,
+        },
+    };
+
+    use crate::common::{
+        extend_vote_request, finalize_block_request, prepare_proposal_request,
+        process_proposal_request, txs, verify_vote_extension_request,
+    };
+
+    /// Answers the ABCI++ requests deterministically from their contents.
+    #[derive(Clone)]
+    struct ProposalApp;
+
+    impl Consensus for ProposalApp {
+        fn prepare_proposal(&self, request: request::PrepareProposal) -> response::PrepareProposal {
+            response::PrepareProposal {
+                txs: request.txs.into_iter().rev().collect(),
+            }
+        }
+
+        fn process_proposal(&self, request: request::ProcessProposal) -> response::ProcessProposal {
+            if request.txs.is_empty() {
+                response::ProcessProposal::Reject
+            } else {
+                response::ProcessProposal::Accept
+            }
+        }
+
+        fn extend_vote(&self, request: request::ExtendVote) -> response::ExtendVote {
+            response::ExtendVote {
+                vote_extension: request.height.to_string().into(),
+            }
+        }
+
+        fn verify_vote_extension(
+            &self,
+            request: request::VerifyVoteExtension,
+        ) -> response::VerifyVoteExtension {
+            if request.vote_extension == request.height.to_string().as_bytes() {
+                response::VerifyVoteExtension::Accept
+            } else {
+                response::VerifyVoteExtension::Reject
+            }
+        }
+
+        fn finalize_block(&self, request: request::FinalizeBlock) -> response::FinalizeBlock {
+            response::FinalizeBlock {
+                tx_results: request
+                    .txs
+                    .into_iter()
+                    .map(|tx| ExecTxResult {
+                        data: tx,
+                        ..Default::default()
+                    })
+                    .collect(),
+                app_hash: request.height.to_string().into(),
+                ..Default::default()
+            }
+        }
+    }
+
+    impl Mempool for ProposalApp {}
+
+    impl Info for ProposalApp {}
+
+    impl Snapshot for ProposalApp {}
+
+    fn connect(version: ProtocolVersion) -> Client {
+        let server = ServerBuilder::default()
+            .protocol_version(version)
+            .bind("127.0.0.1:0", ProposalApp)
+            .unwrap();
+        let server_addr = server.local_addr();
+        thread::spawn(move || server.listen());
+
+        ClientBuilder::default()
+            .protocol_version(version)
+            .connect(server_addr)
+            .unwrap()
+    }
+
+    #[test]
+    fn prepare_proposal_round_trips() {
+        for version in [ProtocolVersion::V0_37, ProtocolVersion::V0_38] {
+            let mut client = connect(version);
+            let res = client.prepare_proposal(prepare_proposal_request()).unwrap();
+            assert_eq!(res.txs, vec![Bytes::from("b=2"), Bytes::from("a=1")]);
+        }
+    }
+
+    #[test]
+    fn process_proposal_round_trips() {
+        for version in [ProtocolVersion::V0_37, ProtocolVersion::V0_38] {
+            let mut client = connect(version);
+            let res = client
+                .process_proposal(process_proposal_request(txs()))
+                .unwrap();
+            assert_eq!(res.status, ProposalStatus::Accept as i32);
+            let res = client
+                .process_proposal(process_proposal_request(vec![]))
+                .unwrap();
+            assert_eq!(res.status, ProposalStatus::Reject as i32);
+        }
+    }
+
+    #[test]
+    fn extend_vote_round_trips() {
+        let mut client = connect(ProtocolVersion::V0_38);
+        let res = client.extend_vote(extend_vote_request(5)).unwrap();
+        assert_eq!(res.vote_extension, Bytes::from("5"));
+    }
+
+    #[test]
+    fn verify_vote_extension_round_trips() {
+        let mut client = connect(ProtocolVersion::V0_38);
+        let res = client
+            .verify_vote_extension(verify_vote_extension_request(5, "5".into()))
+            .unwrap();
+        assert_eq!(res.status, VerifyStatus::Accept as i32);
+        let res = client
+            .verify_vote_extension(verify_vote_extension_request(5, "6".into()))
+            .unwrap();
+        assert_eq!(res.status, VerifyStatus::Reject as i32);
+    }
+
+    #[test]
+    fn finalize_block_round_trips() {
+        let mut client = connect(ProtocolVersion::V0_38);
+        let res = client.finalize_block(finalize_block_request(7)).unwrap();
+        let data: Vec<_> = res.tx_results.into_iter().map(|r| r.data).collect();
+        assert_eq!(data, txs());
+        assert_eq!(res.app_hash, Bytes::from("7"));
+    }
+
+    #[test]
+    fn requests_outside_the_protocol_are_not_sent() {
+        let mut client = connect(ProtocolVersion::V0_37);
+        let err = client.extend_vote(extend_vote_request(5)).unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::InvalidRequest(_)));
+        // The connection is still usable
+        client.prepare_proposal(prepare_proposal_request()).unwrap();
+
+        let mut client = connect(ProtocolVersion::V0_38);
+        let err = client
+            .deliver_tx(RequestDeliverTx { tx: "a=1".into() })
+            .unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::InvalidRequest(_)));
+        client.extend_vote(extend_vote_request(5)).unwrap();
+    }
+}
@@ -0,0 +1,84 @@
+//! Requests shared between the integration tests of the ABCI++ methods.
+
+// Not every test uses every request.
+#![allow(dead_code)]
+
+use bytes::Bytes;
+use tendermint_proto::{
+    google::protobuf::Timestamp,
+    v0_38::abci::{
+        RequestExtendVote, RequestFinalizeBlock, RequestPrepareProposal, RequestProcessProposal,
+        RequestVerifyVoteExtension,
+    },
+};
+
+pub fn time() -> Option<Timestamp> {
+    Some(Timestamp {
+        seconds: 1_600_000_000,
+        nanos: 0,
+    })
+}
+
+pub fn proposer_address() -> Bytes {
+    vec![0xab; 20].into()
+}
+
+pub fn txs() -> Vec<Bytes> {
+    vec!["a=1".into(), "b=2".into()]
+}
+
+pub fn prepare_proposal_request() -> RequestPrepareProposal {
+    RequestPrepareProposal {
+        max_tx_bytes: 1024,
+        txs: txs(),
+        local_last_commit: Some(Default::default()),
+        height: 1,
+        time: time(),
+        proposer_address: proposer_address(),
+        ..Default::default()
+    }
+}
+
+pub fn process_proposal_request(txs: Vec<Bytes>) -> RequestProcessProposal {
+    RequestProcessProposal {
+        txs,
+        proposed_last_commit: Some(Default::default()),
+        height: 1,
+        time: time(),
+        proposer_address: proposer_address(),
+        ..Default::default()
+    }
+}
+
+pub fn extend_vote_request(height: i64) -> RequestExtendVote {
+    RequestExtendVote {
+        height,
+        time: time(),
+        proposed_last_commit: Some(Default::default()),
+        proposer_address: proposer_address(),
+        ..Default::default()
+    }
+}
+
+pub fn verify_vote_extension_request(
+    height: i64,
+    vote_extension: Bytes,
+) -> RequestVerifyVoteExtension {
+    RequestVerifyVoteExtension {
+        validator_address: proposer_address(),
+        height,
+        vote_extension,
+        ..Default::default()
+    }
+}
+
+pub fn finalize_block_request(height: i64) -> RequestFinalizeBlock {
+    RequestFinalizeBlock {
+        txs: txs(),
+        decided_last_commit: Some(Default::default()),
+        height,
+        time: time(),
+        proposer_address: proposer_address(),
+        ..Default::default()
+    }
+}
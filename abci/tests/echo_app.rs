@@ -3,7 +3,9 @@
 #[cfg(all(feature = "client", feature = "echo-app"))]
 mod echo_app_integration {
     use tendermint_abci::{ClientBuilder, EchoApp, ServerBuilder};
-    use tendermint_proto::abci::RequestEcho;
+    use tendermint_proto::abci::{
+        RequestCheckTx, RequestDeliverTx, RequestEcho, RequestInfo, RequestQuery,
+    };
 
     #[test]
     fn echo() {
@@ -21,4 +23,108 @@ mod echo_app_integration {
             .unwrap();
         assert_eq!(response.message, "Hello ABCI!");
     }
+
+    #[test]
+    fn invalid_request_produces_exception() {
+        let server = ServerBuilder::default()
+            .bind("127.0.0.1:0", EchoApp)
+            .unwrap();
+        let server_addr = server.local_addr();
+        let _ = std::thread::spawn(move || server.listen());
+        let mut client = ClientBuilder::default().connect(server_addr).unwrap();
+
+        // Negative heights cannot be converted into a `block::Height`
+        let err = client
+            .query(RequestQuery {
+                height: -1,
+                ..Default::default()
+            })
+            .unwrap_err();
+        assert!(err.to_string().contains("Exception"), "{}", err);
+
+        // The connection remains usable afterwards
+        let response = client
+            .echo(RequestEcho {
+                message: "Hello ABCI!".to_string(),
+            })
+            .unwrap();
+        assert_eq!(response.message, "Hello ABCI!");
+    }
+
+    #[test]
+    fn connection_roles_are_enforced() {
+        let server = ServerBuilder::default()
+            .bind("127.0.0.1:0", EchoApp)
+            .unwrap();
+        let server_addr = server.local_addr();
+        let _ = std::thread::spawn(move || server.listen());
+
+        let mut info = ClientBuilder::default().connect(&server_addr).unwrap();
+        info.echo(RequestEcho::default()).unwrap();
+        info.info(RequestInfo::default()).unwrap();
+        let err = info.check_tx(RequestCheckTx::default()).unwrap_err();
+        assert!(err.to_string().contains("not allowed on Info"), "{}", err);
+        info.query(RequestQuery::default()).unwrap();
+
+        let mut mempool = ClientBuilder::default().connect(&server_addr).unwrap();
+        mempool.flush().unwrap();
+        mempool.check_tx(RequestCheckTx::default()).unwrap();
+        let err = mempool.query(RequestQuery::default()).unwrap_err();
+        assert!(
+            err.to_string().contains("not allowed on Mempool"),
+            "{}",
+            err
+        );
+
+        let mut consensus = ClientBuilder::default().connect(&server_addr).unwrap();
+        let err = consensus
+            .deliver_tx(RequestDeliverTx::default())
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("out of order DeliverTx"),
+            "{}",
+            err
+        );
+        let err = consensus.commit().unwrap_err();
+        assert!(err.to_string().contains("out of order Commit"), "{}", err);
+    }
+
+    #[test]
+    fn connection_roles_can_be_ignored() {
+        let server = ServerBuilder::default()
+            .enforce_connection_roles(false)
+            .bind("127.0.0.1:0", EchoApp)
+            .unwrap();
+        let server_addr = server.local_addr();
+        let _ = std::thread::spawn(move || server.listen());
+
+        let mut client = ClientBuilder::default().connect(&server_addr).unwrap();
+        client.info(RequestInfo::default()).unwrap();
+        client.check_tx(RequestCheckTx::default()).unwrap();
+        client.deliver_tx(RequestDeliverTx::default()).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn echo_unix() {
+        let path = std::env::temp_dir().join(format!("abci-echo-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let addr: tendermint_config::net::Address =
+            format!("unix://{}", path.display()).parse().unwrap();
+
+        let server = ServerBuilder::default()
+            .bind_address(&addr, EchoApp)
+            .unwrap();
+        assert_eq!(server.local_addr(), addr.to_string());
+        let _ = std::thread::spawn(move || server.listen());
+        let mut client = ClientBuilder::default().connect_address(&addr).unwrap();
+
+        let response = client
+            .echo(RequestEcho {
+                message: "Hello ABCI!".to_string(),
+            })
+            .unwrap();
+        assert_eq!(response.message, "Hello ABCI!");
+        std::fs::remove_file(&path).unwrap();
+    }
 }
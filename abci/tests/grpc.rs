@@ -0,0 +1,207 @@
+//! Integration tests for the gRPC ABCI transport.
+
+#[cfg(all(feature = "grpc", feature = "kvstore-app"))]
+mod common;
+
+#[cfg(all(feature = "grpc", feature = "kvstore-app"))]
+mod grpc_integration {
+    use std::thread;
+
+    use tendermint_abci::{
+        error::ErrorDetail, GrpcClient, GrpcClientBuilder, GrpcServerBuilder, KeyValueStoreApp,
+        ProtocolVersion,
+    };
+    use tendermint_proto::{
+        abci::{RequestDeliverTx, RequestEcho, RequestInfo, RequestQuery},
+        v0_38::abci::{
+            response_process_proposal::ProposalStatus, response_verify_vote_extension::VerifyStatus,
+        },
+    };
+
+    use crate::common::{
+        extend_vote_request, finalize_block_request, prepare_proposal_request,
+        process_proposal_request, txs, verify_vote_extension_request,
+    };
+
+    /// Serves a fresh key/value store over the given protocol version, and
+    /// connects a client to it.
+    async fn connect(version: ProtocolVersion) -> GrpcClient {
+        let (app, driver) = KeyValueStoreApp::new();
+        let server = GrpcServerBuilder::default()
+            .protocol_version(version)
+            .bind("127.0.0.1:0", app)
+            .await
+            .unwrap();
+        let server_addr = server.local_addr();
+        thread::spawn(move || driver.run());
+        tokio::spawn(server.listen());
+
+        GrpcClientBuilder::default()
+            .protocol_version(version)
+            .connect(server_addr)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn happy_path() {
+        let (app, driver) = KeyValueStoreApp::new();
+        let server = GrpcServerBuilder::default()
+            .bind("127.0.0.1:0", app)
+            .await
+            .unwrap();
+        let server_addr = server.local_addr();
+        thread::spawn(move || driver.run());
+        tokio::spawn(server.listen());
+
+        let mut client = GrpcClientBuilder::default()
+            .connect(server_addr)
+            .await
+            .unwrap();
+        let res = client
+            .echo(RequestEcho {
+                message: "Hello ABCI!".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(res.message, "Hello ABCI!");
+
+        client
+            .deliver_tx(RequestDeliverTx {
+                tx: "test-key=test-value".into(),
+            })
+            .await
+            .unwrap();
+        client.commit().await.unwrap();
+
+        let res = client
+            .query(RequestQuery {
+                data: "test-key".into(),
+                path: "".to_string(),
+                height: 0,
+                prove: false,
+            })
+            .await
+            .unwrap();
+        assert_eq!(res.value, "test-value".as_bytes());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn invalid_request_produces_error_status() {
+        let (app, driver) = KeyValueStoreApp::new();
+        let server = GrpcServerBuilder::default()
+            .bind("127.0.0.1:0", app)
+            .await
+            .unwrap();
+        let server_addr = server.local_addr();
+        thread::spawn(move || driver.run());
+        tokio::spawn(server.listen());
+
+        let mut client = GrpcClientBuilder::default()
+            .connect(server_addr)
+            .await
+            .unwrap();
+        // Negative heights cannot be converted into a `block::Height`
+        let err = client
+            .query(RequestQuery {
+                height: -1,
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("InvalidArgument"), "{}", err);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn echo_unix() {
+        let path = std::env::temp_dir().join(format!("abci-grpc-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let addr: tendermint_config::net::Address =
+            format!("unix://{}", path.display()).parse().unwrap();
+
+        let (app, driver) = KeyValueStoreApp::new();
+        let server = GrpcServerBuilder::default()
+            .bind_address(&addr, app)
+            .await
+            .unwrap();
+        assert_eq!(server.local_addr(), addr.to_string());
+        thread::spawn(move || driver.run());
+        tokio::spawn(server.listen());
+
+        let mut client = GrpcClientBuilder::default()
+            .connect_address(&addr)
+            .await
+            .unwrap();
+        let res = client
+            .echo(RequestEcho {
+                message: "Hello ABCI!".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(res.message, "Hello ABCI!");
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn proposals_round_trip_over_v0_37_and_v0_38() {
+        for version in [ProtocolVersion::V0_37, ProtocolVersion::V0_38] {
+            let mut client = connect(version).await;
+            let res = client
+                .prepare_proposal(prepare_proposal_request())
+                .await
+                .unwrap();
+            assert_eq!(res.txs, txs());
+            let res = client
+                .process_proposal(process_proposal_request(txs()))
+                .await
+                .unwrap();
+            assert_eq!(res.status, ProposalStatus::Accept as i32);
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn vote_extensions_round_trip_over_v0_38() {
+        let mut client = connect(ProtocolVersion::V0_38).await;
+        let res = client.extend_vote(extend_vote_request(1)).await.unwrap();
+        let res = client
+            .verify_vote_extension(verify_vote_extension_request(1, res.vote_extension))
+            .await
+            .unwrap();
+        assert_eq!(res.status, VerifyStatus::Accept as i32);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn finalize_block_round_trips_over_v0_38() {
+        let mut client = connect(ProtocolVersion::V0_38).await;
+        let res = client
+            .finalize_block(finalize_block_request(1))
+            .await
+            .unwrap();
+        assert_eq!(res.tx_results.len(), 2);
+        assert!(res.tx_results.iter().all(|r| r.code == 0));
+        client.commit().await.unwrap();
+
+        let info = client.info(RequestInfo::default()).await.unwrap();
+        assert_eq!(info.last_block_height, 1);
+        assert_eq!(info.last_block_app_hash, res.app_hash);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn requests_outside_the_protocol_are_not_sent() {
+        let mut client = connect(ProtocolVersion::V0_37).await;
+        let err = client
+            .extend_vote(extend_vote_request(1))
+            .await
+            .unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::InvalidRequest(_)));
+
+        let mut client = connect(ProtocolVersion::V0_38).await;
+        let err = client
+            .deliver_tx(RequestDeliverTx { tx: "a=1".into() })
+            .await
+            .unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::InvalidRequest(_)));
+        assert!(err.to_string().contains("not part of the v0.38"), "{}", err);
+    }
+}
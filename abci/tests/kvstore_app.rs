@@ -2,15 +2,49 @@
 
 #[cfg(all(feature = "client", feature = "kvstore-app"))]
 mod kvstore_app_integration {
-    use std::thread;
+    use std::{
+        convert::TryFrom,
+        io::{Read, Write},
+        net::TcpStream,
+        thread,
+    };
 
-    use tendermint_abci::{ClientBuilder, KeyValueStoreApp, ServerBuilder};
-    use tendermint_proto::abci::{RequestDeliverTx, RequestEcho, RequestQuery};
+    use bytes::Bytes;
+    use prost::Message;
+    use sha2::{Digest, Sha256};
+    use tendermint::merkle::{proof::ProofOps, ProofRuntime};
+    use tendermint_abci::{
+        Client, ClientBuilder, KeyValueStoreApp, ProtocolVersion, ServerBuilder, SnapshotConfig,
+        PROOF_OP_VALUE,
+    };
+    use tendermint_proto::abci::{
+        response_apply_snapshot_chunk::Result as ApplyResult,
+        response_offer_snapshot::Result as OfferResult, RequestApplySnapshotChunk,
+        RequestDeliverTx, RequestEcho, RequestInfo, RequestLoadSnapshotChunk, RequestOfferSnapshot,
+        RequestQuery,
+    };
+    use tendermint_proto::crypto::ValueOp;
+    use tendermint_proto::google::protobuf::Timestamp;
+    use tendermint_proto::v0_38::abci::{
+        response_process_proposal::ProposalStatus, RequestFinalizeBlock, RequestPrepareProposal,
+        RequestProcessProposal,
+    };
+
+    mod v0_34 {
+        pub use tendermint_proto::v0_34::abci::{
+            request::Value, response::Value as ResponseValue, *,
+        };
+    }
+
+    /// Most of these tests issue requests of all kinds on a single connection.
+    fn server_builder() -> ServerBuilder {
+        ServerBuilder::default().enforce_connection_roles(false)
+    }
 
     #[test]
     fn happy_path() {
         let (app, driver) = KeyValueStoreApp::new();
-        let server = ServerBuilder::default().bind("127.0.0.1:0", app).unwrap();
+        let server = server_builder().bind("127.0.0.1:0", app).unwrap();
         let server_addr = server.local_addr();
         thread::spawn(move || driver.run());
         thread::spawn(move || server.listen());
@@ -40,4 +74,450 @@ mod kvstore_app_integration {
             .unwrap();
         assert_eq!(res.value, "test-value".as_bytes());
     }
+
+    /// Sends a raw request to the server and reads back its raw response, in
+    /// order to observe the details of the v0.34 wire format, which the client
+    /// translates into the v0.35 messages.
+    fn round_trip<Req: Message, Res: Message + Default>(
+        stream: &mut TcpStream,
+        request: Req,
+    ) -> Res {
+        stream
+            .write_all(&request.encode_length_delimited_to_vec())
+            .unwrap();
+        let mut buf = Vec::new();
+        let len = loop {
+            let mut byte = [0_u8];
+            stream.read_exact(&mut byte).unwrap();
+            buf.push(byte[0]);
+            if let Ok(len) = prost::decode_length_delimiter(buf.as_slice()) {
+                break len;
+            }
+        };
+        let mut buf = vec![0_u8; len];
+        stream.read_exact(&mut buf).unwrap();
+        Res::decode(buf.as_slice()).unwrap()
+    }
+
+    fn round_trip_v0_34(stream: &mut TcpStream, request: v0_34::Value) -> v0_34::ResponseValue {
+        let request = v0_34::Request {
+            value: Some(request),
+        };
+        round_trip::<_, v0_34::Response>(stream, request)
+            .value
+            .unwrap()
+    }
+
+    #[test]
+    fn happy_path_v0_34() {
+        let (app, driver) = KeyValueStoreApp::new();
+        let server = server_builder()
+            .protocol_version(ProtocolVersion::V0_34)
+            .bind("127.0.0.1:0", app)
+            .unwrap();
+        let server_addr = server.local_addr();
+        thread::spawn(move || driver.run());
+        thread::spawn(move || server.listen());
+
+        let mut stream = TcpStream::connect(server_addr).unwrap();
+        let res = round_trip_v0_34(
+            &mut stream,
+            v0_34::Value::Echo(v0_34::RequestEcho {
+                message: "Hello ABCI!".to_string(),
+            }),
+        );
+        assert!(matches!(res, v0_34::ResponseValue::Echo(r) if r.message == "Hello ABCI!"));
+
+        // Acknowledged without reaching the application
+        let res = round_trip_v0_34(
+            &mut stream,
+            v0_34::Value::SetOption(v0_34::RequestSetOption {
+                key: "key".to_string(),
+                value: "value".to_string(),
+            }),
+        );
+        assert!(matches!(res, v0_34::ResponseValue::SetOption(r) if r.code == 0));
+
+        let res = round_trip_v0_34(
+            &mut stream,
+            v0_34::Value::DeliverTx(v0_34::RequestDeliverTx {
+                tx: "test-key=test-value".into(),
+            }),
+        );
+        let res = match res {
+            v0_34::ResponseValue::DeliverTx(res) => res,
+            res => panic!("unexpected response: {:?}", res),
+        };
+        assert_eq!(res.code, 0);
+        // Event attributes are encoded as bytes in the v0.34 protocol
+        let attribute = &res.events[0].attributes[0];
+        assert_eq!(attribute.key, "key".as_bytes());
+        assert_eq!(attribute.value, "test-key".as_bytes());
+
+        let res = round_trip_v0_34(&mut stream, v0_34::Value::Commit(Default::default()));
+        assert!(matches!(res, v0_34::ResponseValue::Commit(_)));
+
+        let res = round_trip_v0_34(
+            &mut stream,
+            v0_34::Value::Query(v0_34::RequestQuery {
+                data: "test-key".into(),
+                ..Default::default()
+            }),
+        );
+        assert!(
+            matches!(res, v0_34::ResponseValue::Query(r) if r.value == "test-value".as_bytes())
+        );
+
+        let res = round_trip_v0_34(&mut stream, v0_34::Value::Info(Default::default()));
+        assert!(matches!(res, v0_34::ResponseValue::Info(r) if r.last_block_height == 1));
+    }
+
+    #[test]
+    fn finalize_block_v0_38() {
+        let (app, driver) = KeyValueStoreApp::new();
+        let server = ServerBuilder::default()
+            .protocol_version(ProtocolVersion::V0_38)
+            .bind("127.0.0.1:0", app)
+            .unwrap();
+        let server_addr = server.local_addr();
+        thread::spawn(move || driver.run());
+        thread::spawn(move || server.listen());
+
+        let v0_38_client = || {
+            ClientBuilder::default()
+                .protocol_version(ProtocolVersion::V0_38)
+                .connect(&server_addr)
+                .unwrap()
+        };
+        let mut consensus = v0_38_client();
+        let time = Timestamp {
+            seconds: 1_600_000_000,
+            nanos: 0,
+        };
+        let proposer_address: Bytes = vec![0xab; 20].into();
+        let txs: Vec<Bytes> = vec!["a=1".into(), "b=2".into(), "c=3".into()];
+        let finalize_block = RequestFinalizeBlock {
+            txs: txs[..2].to_vec(),
+            decided_last_commit: Some(Default::default()),
+            height: 1,
+            time: Some(time.clone()),
+            proposer_address: proposer_address.clone(),
+            ..Default::default()
+        };
+
+        // Transactions beyond `max_tx_bytes` are left out of the proposal
+        let res = consensus
+            .prepare_proposal(RequestPrepareProposal {
+                max_tx_bytes: 6,
+                txs: txs.clone(),
+                height: 1,
+                time: Some(time.clone()),
+                proposer_address: proposer_address.clone(),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(res.txs, txs[..2].to_vec());
+
+        let res = consensus
+            .process_proposal(RequestProcessProposal {
+                txs: res.txs,
+                proposed_last_commit: Some(Default::default()),
+                height: 1,
+                time: Some(time),
+                proposer_address,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(res.status, ProposalStatus::Accept as i32);
+
+        let res = consensus.finalize_block(finalize_block.clone()).unwrap();
+        assert_eq!(res.tx_results.len(), 2);
+        assert!(res.tx_results.iter().all(|r| r.code == 0));
+        let app_hash = res.app_hash;
+        assert!(!app_hash.is_empty());
+
+        // A finalized block must be committed before the next one
+        let err = consensus.finalize_block(finalize_block).unwrap_err();
+        assert!(
+            err.to_string().contains("out of order FinalizeBlock"),
+            "{}",
+            err
+        );
+        consensus.commit().unwrap();
+
+        // The application hash reported on finalizing the block is the one of
+        // the committed state
+        let mut info = v0_38_client();
+        let res = info.info(RequestInfo::default()).unwrap();
+        assert_eq!(res.last_block_height, 1);
+        assert_eq!(res.last_block_app_hash, app_hash);
+
+        let res = info
+            .query(RequestQuery {
+                data: "b".into(),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(res.value, "2".as_bytes());
+        assert_eq!(res.height, 1);
+    }
+
+    #[test]
+    fn state_sync() {
+        let (app, driver) = KeyValueStoreApp::with_snapshot_config(SnapshotConfig {
+            interval: 2,
+            chunk_size: 16,
+            keep_recent: 1,
+        });
+        let server = server_builder().bind("127.0.0.1:0", app).unwrap();
+        let source_addr = server.local_addr();
+        thread::spawn(move || driver.run());
+        thread::spawn(move || server.listen());
+
+        let (app, driver) = KeyValueStoreApp::new();
+        let server = server_builder().bind("127.0.0.1:0", app).unwrap();
+        let target_addr = server.local_addr();
+        thread::spawn(move || driver.run());
+        thread::spawn(move || server.listen());
+
+        let mut source = ClientBuilder::default().connect(source_addr).unwrap();
+        let mut target = ClientBuilder::default().connect(target_addr).unwrap();
+
+        for height in 1..=4 {
+            source
+                .deliver_tx(RequestDeliverTx {
+                    tx: format!("key-{}=value-{}", height, height).into(),
+                })
+                .unwrap();
+            let res = source.commit().unwrap();
+            // Blocks since the oldest snapshot are retained for nodes
+            // restoring it, and all blocks until there is a snapshot
+            let retain_height = if height < 2 { 0 } else { height / 2 * 2 };
+            assert_eq!(res.retain_height, retain_height);
+        }
+        let snapshots = source.list_snapshots().unwrap().snapshots;
+        // Only the most recent snapshot is retained
+        assert_eq!(snapshots.len(), 1);
+        let snapshot = snapshots[0].clone();
+        assert_eq!(snapshot.height, 4);
+        assert!(snapshot.chunks > 1);
+        let app_hash = source
+            .info(RequestInfo::default())
+            .unwrap()
+            .last_block_app_hash;
+
+        let res = target
+            .offer_snapshot(RequestOfferSnapshot {
+                snapshot: Some(snapshot.clone()),
+                app_hash: app_hash.clone(),
+            })
+            .unwrap();
+        assert_eq!(res.result, OfferResult::Accept as i32);
+
+        for index in 0..snapshot.chunks {
+            let chunk = source
+                .load_snapshot_chunk(RequestLoadSnapshotChunk {
+                    height: snapshot.height,
+                    format: snapshot.format,
+                    chunk: index,
+                })
+                .unwrap()
+                .chunk;
+
+            // A corrupted chunk must be refetched
+            let res = target
+                .apply_snapshot_chunk(RequestApplySnapshotChunk {
+                    index,
+                    chunk: "garbage".into(),
+                    sender: "bad-peer".to_string(),
+                })
+                .unwrap();
+            assert_eq!(res.result, ApplyResult::Retry as i32);
+            assert_eq!(res.refetch_chunks, vec![index]);
+            assert_eq!(res.reject_senders, vec!["bad-peer".to_string()]);
+
+            let res = target
+                .apply_snapshot_chunk(RequestApplySnapshotChunk {
+                    index,
+                    chunk,
+                    sender: "good-peer".to_string(),
+                })
+                .unwrap();
+            assert_eq!(res.result, ApplyResult::Accept as i32);
+        }
+
+        let info = target.info(RequestInfo::default()).unwrap();
+        assert_eq!(info.last_block_height, 4);
+        assert_eq!(info.last_block_app_hash, app_hash);
+        let res = target
+            .query(RequestQuery {
+                data: "key-3".into(),
+                path: "".to_string(),
+                height: 0,
+                prove: false,
+            })
+            .unwrap();
+        assert_eq!(res.value, "value-3".as_bytes());
+    }
+
+    #[test]
+    fn query_proofs() {
+        let (app, driver) = KeyValueStoreApp::new();
+        let server = server_builder().bind("127.0.0.1:0", app).unwrap();
+        let server_addr = server.local_addr();
+        thread::spawn(move || driver.run());
+        thread::spawn(move || server.listen());
+
+        let mut client = ClientBuilder::default().connect(server_addr).unwrap();
+        for i in 1..=5 {
+            client
+                .deliver_tx(RequestDeliverTx {
+                    tx: format!("key-{}=value-{}", i, i).into(),
+                })
+                .unwrap();
+        }
+        client.commit().unwrap();
+        // Uncommitted changes must not be reflected in proven queries
+        client
+            .deliver_tx(RequestDeliverTx {
+                tx: "key-3=changed".into(),
+            })
+            .unwrap();
+        let app_hash = client
+            .info(RequestInfo::default())
+            .unwrap()
+            .last_block_app_hash;
+
+        let res = client
+            .query(RequestQuery {
+                data: "key-3".into(),
+                path: "".to_string(),
+                height: 0,
+                prove: true,
+            })
+            .unwrap();
+        assert_eq!(res.value, "value-3".as_bytes());
+        assert_eq!(res.height, 1);
+        let proof_ops = res.proof_ops.unwrap();
+        let ops = &proof_ops.ops;
+        assert_eq!(ops.len(), 1);
+        assert_eq!(ops[0].r#type, PROOF_OP_VALUE);
+        assert_eq!(ops[0].key, b"key-3");
+
+        let op = ValueOp::decode(ops[0].data.as_slice()).unwrap();
+        let proof = op.proof.unwrap();
+        assert_eq!((proof.index, proof.total), (2, 5));
+        let mut leaf = vec![0_u8];
+        leaf.extend(length_prefixed(b"key-3"));
+        leaf.extend(length_prefixed(&Sha256::digest(b"value-3")));
+        assert_eq!(proof.leaf_hash, sha256(&leaf));
+
+        let proof_ops = ProofOps::try_from(proof_ops).unwrap();
+        let runtime = ProofRuntime::default();
+        runtime
+            .verify_value(&proof_ops, &app_hash, "/key-3", b"value-3")
+            .unwrap();
+        assert!(runtime
+            .verify_value(&proof_ops, &app_hash, "/key-3", b"changed")
+            .is_err());
+
+        // No proofs are produced for absent keys
+        let res = client
+            .query(RequestQuery {
+                data: "missing".into(),
+                path: "".to_string(),
+                height: 0,
+                prove: true,
+            })
+            .unwrap();
+        assert!(res.proof_ops.is_none());
+    }
+
+    #[test]
+    fn state_survives_restarts() {
+        let path = std::env::temp_dir().join(format!("abci-kvstore-{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let connect = || {
+            let (app, driver) = KeyValueStoreApp::new();
+            let driver = driver.persist_to(&path).unwrap();
+            let server = server_builder().bind("127.0.0.1:0", app).unwrap();
+            let server_addr = server.local_addr();
+            thread::spawn(move || driver.run());
+            thread::spawn(move || server.listen());
+            ClientBuilder::default().connect(server_addr).unwrap()
+        };
+
+        let mut client = connect();
+        for height in 1..=3 {
+            client
+                .deliver_tx(RequestDeliverTx {
+                    tx: format!("key-{}=value-{}", height, height).into(),
+                })
+                .unwrap();
+            client.commit().unwrap();
+        }
+        // Uncommitted changes are not persisted
+        client
+            .deliver_tx(RequestDeliverTx {
+                tx: "key-4=value-4".into(),
+            })
+            .unwrap();
+        let info = client.info(RequestInfo::default()).unwrap();
+
+        let mut client = connect();
+        let restarted = client.info(RequestInfo::default()).unwrap();
+        assert_eq!(restarted.last_block_height, 3);
+        assert_eq!(restarted.last_block_app_hash, info.last_block_app_hash);
+        let query = |client: &mut Client, key: &str| {
+            client
+                .query(RequestQuery {
+                    data: key.to_string().into(),
+                    path: "".to_string(),
+                    height: 0,
+                    prove: false,
+                })
+                .unwrap()
+                .value
+        };
+        assert_eq!(query(&mut client, "key-2"), "value-2".as_bytes());
+        assert!(query(&mut client, "key-4").is_empty());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn failing_to_persist_state_is_not_fatal() {
+        let dir = std::env::temp_dir().join(format!("abci-kvstore-dir-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (app, driver) = KeyValueStoreApp::new();
+        let driver = driver.persist_to(dir.join("state")).unwrap();
+        let server = server_builder().bind("127.0.0.1:0", app).unwrap();
+        let server_addr = server.local_addr();
+        thread::spawn(move || driver.run());
+        thread::spawn(move || server.listen());
+        let mut client = ClientBuilder::default().connect(server_addr).unwrap();
+        client.commit().unwrap();
+
+        // The state can no longer be written
+        std::fs::remove_dir_all(&dir).unwrap();
+        client
+            .deliver_tx(RequestDeliverTx {
+                tx: "key=value".into(),
+            })
+            .unwrap();
+        client.commit().unwrap();
+        let info = client.info(RequestInfo::default()).unwrap();
+        assert_eq!(info.last_block_height, 2);
+    }
+
+    fn sha256(data: &[u8]) -> Vec<u8> {
+        Sha256::digest(data).to_vec()
+    }
+
+    fn length_prefixed(data: &[u8]) -> Vec<u8> {
+        let mut buf = Vec::new();
+        prost::encoding::encode_varint(data.len() as u64, &mut buf);
+        buf.extend_from_slice(data);
+        buf
+    }
 }
@@ -0,0 +1,136 @@
+//! Application middleware integration tests.
+
+mod middleware_integration {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    };
+
+    use tendermint::abci::{
+        request::{self, CheckTxKind},
+        response, MethodKind,
+    };
+    use tendermint_abci::{
+        middleware::{Logging, Metrics, QueryCache, RateLimit, RATE_LIMITED_CODE},
+        ApplicationExt, Consensus, Info, Mempool, Snapshot,
+    };
+
+    /// Counts the queries it answers, and answers them with the number of
+    /// transactions delivered so far.
+    #[derive(Clone, Default)]
+    struct CounterApp {
+        txs: Arc<Mutex<u64>>,
+        queries: Arc<AtomicUsize>,
+    }
+
+    impl Consensus for CounterApp {
+        fn deliver_tx(&self, _request: request::DeliverTx) -> response::DeliverTx {
+            *self.txs.lock().unwrap() += 1;
+            Default::default()
+        }
+    }
+
+    impl Mempool for CounterApp {}
+
+    impl Info for CounterApp {
+        fn query(&self, _request: request::Query) -> response::Query {
+            self.queries.fetch_add(1, Ordering::SeqCst);
+            response::Query {
+                value: self.txs.lock().unwrap().to_string().into(),
+                ..Default::default()
+            }
+        }
+    }
+
+    impl Snapshot for CounterApp {}
+
+    fn query(data: &str) -> request::Query {
+        request::Query {
+            data: data.to_string().into(),
+            path: "".to_string(),
+            height: Default::default(),
+            prove: false,
+        }
+    }
+
+    fn check_tx(kind: CheckTxKind) -> request::CheckTx {
+        request::CheckTx {
+            tx: "tx".into(),
+            kind,
+        }
+    }
+
+    #[test]
+    fn query_cache_is_invalidated_by_state_changes() {
+        let inner = CounterApp::default();
+        let cache = QueryCache::new(2);
+        let app = inner.clone().layer(cache.clone());
+
+        assert_eq!(app.query(query("a")).value, "0");
+        assert_eq!(app.query(query("a")).value, "0");
+        assert_eq!(inner.queries.load(Ordering::SeqCst), 1);
+
+        app.deliver_tx(request::DeliverTx { tx: "tx".into() });
+        assert!(cache.is_empty());
+        assert_eq!(app.query(query("a")).value, "1");
+        assert_eq!(inner.queries.load(Ordering::SeqCst), 2);
+
+        // Once the cache is full, the least recently used response is evicted
+        app.query(query("b"));
+        app.query(query("a"));
+        app.query(query("c"));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(inner.queries.load(Ordering::SeqCst), 4);
+        app.query(query("a"));
+        app.query(query("c"));
+        assert_eq!(inner.queries.load(Ordering::SeqCst), 4);
+        app.query(query("b"));
+        assert_eq!(inner.queries.load(Ordering::SeqCst), 5);
+    }
+
+    #[test]
+    fn rate_limit_rejects_new_transactions_only() {
+        let app = CounterApp::default().layer(RateLimit::new(0, 2));
+
+        assert_eq!(app.check_tx(check_tx(CheckTxKind::New)).code, 0);
+        assert_eq!(app.check_tx(check_tx(CheckTxKind::New)).code, 0);
+        assert_eq!(
+            app.check_tx(check_tx(CheckTxKind::New)).code,
+            RATE_LIMITED_CODE
+        );
+        assert_eq!(app.check_tx(check_tx(CheckTxKind::Recheck)).code, 0);
+    }
+
+    #[test]
+    fn layers_compose() {
+        let inner = CounterApp::default();
+        let metrics = Metrics::new();
+        let app = inner
+            .clone()
+            .layer(QueryCache::new(16))
+            .layer(RateLimit::new(0, 1))
+            .layer(metrics.clone())
+            .layer(Logging::new());
+        // Each connection works with its own clone of the stack
+        let mempool = app.clone();
+
+        app.deliver_tx(request::DeliverTx { tx: "tx".into() });
+        app.commit();
+        assert_eq!(app.query(query("a")).value, "1");
+        assert_eq!(app.query(query("a")).value, "1");
+        assert_eq!(mempool.check_tx(check_tx(CheckTxKind::New)).code, 0);
+        assert_eq!(
+            mempool.check_tx(check_tx(CheckTxKind::New)).code,
+            RATE_LIMITED_CODE
+        );
+
+        // Metrics are recorded outside of the query cache and rate limiter
+        assert_eq!(inner.queries.load(Ordering::SeqCst), 1);
+        assert_eq!(metrics.latency(MethodKind::Consensus).count, 2);
+        assert_eq!(metrics.latency(MethodKind::Info).count, 2);
+        assert_eq!(metrics.latency(MethodKind::Mempool).count, 2);
+        assert_eq!(metrics.latency(MethodKind::Snapshot).count, 0);
+        assert!(metrics.latency(MethodKind::Snapshot).mean().is_none());
+        assert_eq!(metrics.snapshot().len(), 3);
+    }
+}
@@ -0,0 +1,311 @@
+//! In-process consensus driver integration tests.
+
+#[cfg(all(feature = "node", feature = "kvstore-app"))]
+mod node_integration {
+    use std::{
+        convert::TryInto,
+        sync::{Arc, Mutex},
+        thread,
+        time::Duration,
+    };
+
+    use subtle_encoding::base64;
+    use tendermint::{
+        abci::{request, response, types::ValidatorUpdate},
+        block, consensus, evidence, public_key, validator, Genesis, PublicKey, Time,
+    };
+    use tendermint_abci::{
+        error::ErrorDetail, node::Node, Consensus, Info, KeyValueStoreApp, Mempool, Snapshot,
+        CODE_TYPE_ENCODING_ERROR, CODE_TYPE_UNAUTHORIZED, VALIDATOR_TX_PREFIX,
+    };
+
+    // Public keys from the RFC 8032 test vectors
+    const KEYS: [&str; 2] = [
+        "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+        "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+    ];
+
+    fn key(index: usize) -> PublicKey {
+        let bytes = (0..32)
+            .map(|i| u8::from_str_radix(&KEYS[index][2 * i..2 * i + 2], 16).unwrap())
+            .collect::<Vec<_>>();
+        PublicKey::from_raw_ed25519(&bytes).unwrap()
+    }
+
+    fn genesis() -> Genesis {
+        Genesis {
+            genesis_time: Time::from_unix_timestamp(1_600_000_000, 0).unwrap(),
+            chain_id: "test-chain".parse().unwrap(),
+            initial_height: 1,
+            consensus_params: consensus::Params {
+                block: block::Size {
+                    max_bytes: 22020096,
+                    max_gas: -1,
+                    time_iota_ms: 1000,
+                },
+                evidence: evidence::Params {
+                    max_age_num_blocks: 100000,
+                    max_age_duration: evidence::Duration(Duration::from_secs(172800)),
+                    max_bytes: 1048576,
+                },
+                validator: consensus::params::ValidatorParams {
+                    pub_key_types: vec![public_key::Algorithm::Ed25519],
+                },
+                version: None,
+                abci: None,
+            },
+            validators: vec![validator::Info::new(key(0), 10_u32.into())],
+            app_hash: Vec::new(),
+            app_state: Default::default(),
+        }
+    }
+
+    #[test]
+    fn kvstore_lifecycle() {
+        let (app, driver) = KeyValueStoreApp::new();
+        thread::spawn(move || driver.run());
+        let node = Node::new(app, &genesis()).unwrap();
+
+        assert_eq!(node.check_tx("a=1").code, 0);
+        assert_eq!(node.check_tx("b=2").code, 0);
+        let first = node.produce_block().unwrap();
+        assert_eq!(first.header.height.value(), 1);
+        assert_eq!(first.header.time, genesis().genesis_time);
+        assert_eq!(first.txs.len(), 2);
+        assert!(first.header.app_hash.value().is_empty());
+        assert!(first.header.last_block_id.is_none());
+        assert!(node.mempool().is_empty());
+
+        node.check_tx("c=3");
+        let second = node.produce_block().unwrap();
+        assert_eq!(second.header.height.value(), 2);
+        assert!(second.header.time > first.header.time);
+        assert_eq!(
+            second.header.last_block_id.unwrap().hash,
+            first.header.hash()
+        );
+        assert_eq!(second.header.app_hash.value(), first.commit.data.to_vec());
+        assert!(second.header.last_results_hash.is_some());
+        assert_eq!(
+            second.header.proposer_address,
+            node.validators().validators()[0].address
+        );
+
+        // Each header's results hash covers the previous block's results
+        let third = node.produce_block().unwrap();
+        let fourth = node.produce_block().unwrap();
+        let fifth = node.produce_block().unwrap();
+        assert!(third.txs.is_empty() && fourth.txs.is_empty());
+        assert_ne!(
+            third.header.last_results_hash,
+            second.header.last_results_hash
+        );
+        assert_ne!(
+            fourth.header.last_results_hash,
+            third.header.last_results_hash
+        );
+        assert_eq!(
+            fifth.header.last_results_hash,
+            fourth.header.last_results_hash
+        );
+
+        assert_eq!(node.headers().len(), 5);
+        assert_eq!(node.height().value(), 6);
+        let info = node.app().info(request::Info {
+            version: "".to_string(),
+            block_version: 0,
+            p2p_version: 0,
+            abci_version: "".to_string(),
+        });
+        assert_eq!(info.last_block_height.value(), 5);
+        assert_eq!(info.last_block_app_hash.to_vec(), node.app_hash().value());
+        assert_eq!(node.app().get("c").unwrap(), (5, Some("3".to_string())));
+    }
+
+    // The key under which the kvstore keeps the validator's power
+    fn validator_key(index: usize) -> String {
+        let pub_key = base64::encode(key(index).to_bytes());
+        format!(
+            "{}{}",
+            VALIDATOR_TX_PREFIX,
+            String::from_utf8(pub_key).unwrap()
+        )
+    }
+
+    fn validator_tx(index: usize, power: u64) -> String {
+        format!("{}!{}", validator_key(index), power)
+    }
+
+    #[test]
+    fn kvstore_validator_rotation() {
+        let (app, driver) = KeyValueStoreApp::new();
+        thread::spawn(move || driver.run());
+        let node = Node::new(app, &genesis()).unwrap();
+
+        assert_eq!(
+            node.check_tx("val:not-a-key!10").code,
+            CODE_TYPE_ENCODING_ERROR
+        );
+        assert_eq!(node.check_tx(validator_tx(1, 20)).code, 0);
+        let block = node.produce_block().unwrap();
+        assert_eq!(
+            block.end_block.validator_updates,
+            vec![ValidatorUpdate {
+                pub_key: key(1),
+                power: 20_u32.into(),
+            }]
+        );
+        node.produce_block().unwrap();
+        assert_eq!(node.validators().validators().len(), 2);
+
+        // Rotate out the genesis validator
+        node.check_tx(validator_tx(0, 0));
+        // The mempool does not know which validators exist
+        node.check_tx(validator_tx(0, 0));
+        let block = node.produce_block().unwrap();
+        assert_eq!(block.deliver_tx[0].code, 0);
+        assert_eq!(block.deliver_tx[1].code, CODE_TYPE_UNAUTHORIZED);
+        assert_eq!(block.end_block.validator_updates.len(), 1);
+        node.produce_block().unwrap();
+        let validators = node.validators();
+        assert_eq!(validators.validators().len(), 1);
+        assert_eq!(validators.validators()[0].pub_key, key(1));
+
+        // The validators are part of the application's state
+        let (_, power) = node.app().get(validator_key(1)).unwrap();
+        assert_eq!(power, Some("20".to_string()));
+        let (_, removed) = node.app().get(validator_key(0)).unwrap();
+        assert!(removed.is_none());
+    }
+
+    /// Applies the validator updates and consensus parameters it is given at
+    /// the given heights.
+    #[derive(Clone, Default)]
+    struct UpdatingApp {
+        validator_updates: Arc<Mutex<Vec<(i64, ValidatorUpdate)>>>,
+        param_updates: Arc<Mutex<Vec<(i64, consensus::Params)>>>,
+    }
+
+    impl Consensus for UpdatingApp {
+        fn end_block(&self, request: request::EndBlock) -> response::EndBlock {
+            let height = request.height;
+            response::EndBlock {
+                validator_updates: self
+                    .validator_updates
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .filter(|(h, _)| *h == height)
+                    .map(|(_, u)| u.clone())
+                    .collect(),
+                consensus_param_updates: self
+                    .param_updates
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .find(|(h, _)| *h == height)
+                    .map(|(_, p)| p.clone()),
+                events: Vec::new(),
+            }
+        }
+    }
+
+    impl Mempool for UpdatingApp {}
+    impl Info for UpdatingApp {}
+    impl Snapshot for UpdatingApp {}
+
+    #[test]
+    fn validator_and_consensus_param_updates() {
+        let app = UpdatingApp::default();
+        let mut params = genesis().consensus_params;
+        params.block.max_gas = 1000;
+        app.validator_updates.lock().unwrap().push((
+            1,
+            ValidatorUpdate {
+                pub_key: key(1),
+                power: 20_u32.into(),
+            },
+        ));
+        app.param_updates.lock().unwrap().push((1, params.clone()));
+        let node = Node::new(app, &genesis()).unwrap();
+        let initial_validators = node.validators();
+
+        let first = node.produce_block().unwrap().header;
+        let second = node.produce_block().unwrap().header;
+        let third = node.produce_block().unwrap().header;
+
+        // Validator updates take effect two blocks later
+        assert_eq!(first.validators_hash, initial_validators.hash());
+        assert_eq!(first.next_validators_hash, initial_validators.hash());
+        assert_eq!(second.validators_hash, initial_validators.hash());
+        assert_ne!(second.next_validators_hash, initial_validators.hash());
+        assert_eq!(third.validators_hash, second.next_validators_hash);
+        let validators = node.validators();
+        assert_eq!(validators.validators().len(), 2);
+        assert_eq!(
+            third.proposer_address,
+            validator::Info::new(key(1), 20_u32.into()).address
+        );
+
+        // Consensus parameter updates take effect in the next block
+        assert_ne!(second.consensus_hash, first.consensus_hash);
+        assert_eq!(third.consensus_hash, second.consensus_hash);
+        assert_eq!(node.consensus_params(), params);
+    }
+
+    #[test]
+    fn invalid_validator_updates_halt_the_node() {
+        let app = UpdatingApp::default();
+        // Removes a validator that is not part of the set
+        app.validator_updates.lock().unwrap().push((
+            2,
+            ValidatorUpdate {
+                pub_key: key(1),
+                power: 0_u32.into(),
+            },
+        ));
+        let node = Node::new(app, &genesis()).unwrap();
+
+        node.produce_block().unwrap();
+        assert!(node.produce_block().is_err());
+        assert_eq!(node.headers().len(), 1);
+        assert!(matches!(
+            node.produce_block().unwrap_err().detail(),
+            ErrorDetail::NodeHalted(_)
+        ));
+    }
+
+    #[test]
+    fn duplicate_or_overpowered_validator_updates_halt_the_node() {
+        let update = |power: u64| ValidatorUpdate {
+            pub_key: key(1),
+            power: power.try_into().unwrap(),
+        };
+        let max_power = validator::MAX_TOTAL_VOTING_POWER as u64;
+        for updates in &[vec![update(20), update(30)], vec![update(max_power)]] {
+            let app = UpdatingApp::default();
+            app.validator_updates
+                .lock()
+                .unwrap()
+                .extend(updates.iter().map(|u| (1, u.clone())));
+            let node = Node::new(app, &genesis()).unwrap();
+
+            assert!(node.produce_block().is_err());
+            assert!(node.headers().is_empty());
+            assert_eq!(node.validators().validators().len(), 1);
+        }
+    }
+
+    #[test]
+    fn blocks_are_produced_on_a_timer() {
+        let node = Node::new(UpdatingApp::default(), &genesis()).unwrap();
+        let producer = node.spawn_producer(Duration::from_millis(10));
+        while node.headers().len() < 3 {
+            thread::sleep(Duration::from_millis(10));
+        }
+        producer.stop().unwrap();
+        let height = node.height();
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(node.height(), height);
+    }
+}
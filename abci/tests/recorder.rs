@@ -0,0 +1,207 @@
+//! Integration tests for recording and replaying ABCI sessions.
+
+#[cfg(all(feature = "client", feature = "kvstore-app"))]
+mod recorder_integration {
+    use std::{fs::File, panic, thread};
+
+    use bytes::Bytes;
+    use tendermint::abci::{request, response, Request, Response};
+    use tendermint_abci::{
+        recorder::{read_recording, replay, Record},
+        ClientBuilder, Consensus, Error, Info, KeyValueStoreApp, Mempool, ProtocolVersion,
+        RecordingApp, ServerBuilder, Snapshot,
+    };
+    use tendermint_proto::{
+        abci::{RequestDeliverTx, RequestInfo, RequestQuery},
+        google::protobuf::Timestamp,
+        v0_38::abci::RequestFinalizeBlock,
+    };
+
+    fn recording_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("abci-recording-{}-{}", name, std::process::id()))
+    }
+
+    #[test]
+    fn recorded_session_can_be_replayed() {
+        let path = recording_path("v0_35");
+        let (app, driver) = KeyValueStoreApp::new();
+        let app = RecordingApp::create(app, &path, ProtocolVersion::V0_35).unwrap();
+        // Requests of all kinds are issued on a single connection
+        let server = ServerBuilder::default()
+            .enforce_connection_roles(false)
+            .bind("127.0.0.1:0", app)
+            .unwrap();
+        let server_addr = server.local_addr();
+        thread::spawn(move || driver.run());
+        thread::spawn(move || server.listen());
+
+        let mut client = ClientBuilder::default().connect(server_addr).unwrap();
+        client.info(RequestInfo::default()).unwrap();
+        client
+            .deliver_tx(RequestDeliverTx {
+                tx: "test-key=test-value".into(),
+            })
+            .unwrap();
+        client.commit().unwrap();
+        client
+            .query(RequestQuery {
+                data: "test-key".into(),
+                path: "".to_string(),
+                height: 0,
+                prove: true,
+            })
+            .unwrap();
+
+        let recording = read_recording(File::open(&path).unwrap()).unwrap();
+        assert_eq!(recording.protocol_version, ProtocolVersion::V0_35);
+        assert_eq!(recording.records.len(), 4);
+
+        // A fresh instance of the application behaves identically
+        let (app, driver) = KeyValueStoreApp::new();
+        thread::spawn(move || driver.run());
+        let mismatches = replay(&app, File::open(&path).unwrap()).unwrap();
+        assert!(mismatches.is_empty(), "{:?}", mismatches);
+
+        // An instance with different state does not
+        let (app, driver) = KeyValueStoreApp::new();
+        thread::spawn(move || driver.run());
+        app.deliver_tx(request::DeliverTx {
+            tx: "other-key=other-value".into(),
+        });
+        let mismatches = replay(&app, File::open(&path).unwrap()).unwrap();
+        // The commit (app hash) and query (proof) responses differ
+        let indices = mismatches.iter().map(|m| m.index).collect::<Vec<_>>();
+        assert_eq!(indices, vec![2, 3]);
+
+        // Truncated recordings are detected
+        let data = std::fs::read(&path).unwrap();
+        let err: Error = read_recording(&data[..data.len() - 1]).unwrap_err();
+        assert!(err.to_string().contains("truncated after 3"), "{}", err);
+
+        // As are recordings without a header
+        let header_len = data.iter().position(|&b| b == b'\n').unwrap();
+        let err: Error = read_recording(&data[header_len + 1..]).unwrap_err();
+        assert!(err.to_string().contains("invalid recording"), "{}", err);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn abci_plus_plus_session_is_recorded_over_v0_38() {
+        let path = recording_path("v0_38");
+        let (app, driver) = KeyValueStoreApp::new();
+        let app = RecordingApp::create(app, &path, ProtocolVersion::V0_38).unwrap();
+        let server = ServerBuilder::default()
+            .enforce_connection_roles(false)
+            .protocol_version(ProtocolVersion::V0_38)
+            .bind("127.0.0.1:0", app)
+            .unwrap();
+        let server_addr = server.local_addr();
+        thread::spawn(move || driver.run());
+        thread::spawn(move || server.listen());
+
+        let mut client = ClientBuilder::default()
+            .protocol_version(ProtocolVersion::V0_38)
+            .connect(server_addr)
+            .unwrap();
+        client
+            .finalize_block(RequestFinalizeBlock {
+                txs: vec!["test-key=test-value".into()],
+                decided_last_commit: Some(Default::default()),
+                height: 1,
+                time: Some(Timestamp {
+                    seconds: 1_600_000_000,
+                    nanos: 0,
+                }),
+                proposer_address: Bytes::from(vec![0xab; 20]),
+                ..Default::default()
+            })
+            .unwrap();
+        client.commit().unwrap();
+
+        // The requests are read back with the protocol they were recorded with
+        let recording = read_recording(File::open(&path).unwrap()).unwrap();
+        assert_eq!(recording.protocol_version, ProtocolVersion::V0_38);
+        assert!(matches!(
+            recording.records[0],
+            Record {
+                request: Request::FinalizeBlock(_),
+                response: Response::FinalizeBlock(_),
+            }
+        ));
+        assert!(matches!(recording.records[1].request, Request::Commit));
+
+        let (app, driver) = KeyValueStoreApp::new();
+        thread::spawn(move || driver.run());
+        let mismatches = replay(&app, File::open(&path).unwrap()).unwrap();
+        assert!(mismatches.is_empty(), "{:?}", mismatches);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn requests_outside_the_protocol_are_not_skipped() {
+        let (app, driver) = KeyValueStoreApp::new();
+        thread::spawn(move || driver.run());
+        let app = RecordingApp::new(app, Vec::new(), ProtocolVersion::V0_38).unwrap();
+
+        // `DeliverTx` cannot be recorded over the v0.38 protocol, so the
+        // recording fails loudly instead of silently leaving it out
+        let err = panic::catch_unwind(|| {
+            app.deliver_tx(request::DeliverTx {
+                tx: "test-key=test-value".into(),
+            })
+        })
+        .unwrap_err();
+        let message = err.downcast_ref::<String>().unwrap();
+        assert!(message.contains("DeliverTx is not part of"), "{}", message);
+    }
+
+    /// An application that panics when delivering the transaction `crash`.
+    #[derive(Clone)]
+    struct CrashingApp;
+
+    impl Consensus for CrashingApp {
+        fn deliver_tx(&self, request: request::DeliverTx) -> response::DeliverTx {
+            if request.tx == "crash" {
+                panic!("cannot deliver crash");
+            }
+            Default::default()
+        }
+    }
+    impl Mempool for CrashingApp {}
+    impl Info for CrashingApp {}
+    impl Snapshot for CrashingApp {}
+
+    #[test]
+    fn request_that_panics_is_recorded() {
+        let path = recording_path("panic");
+        let app = RecordingApp::create(CrashingApp, &path, ProtocolVersion::V0_35).unwrap();
+
+        app.deliver_tx(request::DeliverTx { tx: "ok".into() });
+        let err = panic::catch_unwind(|| app.deliver_tx(request::DeliverTx { tx: "crash".into() }))
+            .unwrap_err();
+        // The panic is propagated to the caller
+        assert_eq!(*err.downcast_ref::<&str>().unwrap(), "cannot deliver crash");
+
+        let recording = read_recording(File::open(&path).unwrap()).unwrap();
+        assert_eq!(recording.records.len(), 2);
+        let last = recording.records.last().unwrap();
+        assert_eq!(
+            last.request,
+            Request::DeliverTx(request::DeliverTx { tx: "crash".into() })
+        );
+        assert_eq!(
+            last.response,
+            Response::Exception(response::Exception {
+                error: "application panicked: cannot deliver crash".to_string(),
+            })
+        );
+
+        // Replaying the recording reproduces the panic as an exception
+        let mismatches = replay(&CrashingApp, File::open(&path).unwrap()).unwrap();
+        assert!(mismatches.is_empty(), "{:?}", mismatches);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,145 @@
+//! Integration tests for the blocking ABCI server's connection management.
+
+#[cfg(feature = "client")]
+mod server_integration {
+    use std::thread;
+
+    use tendermint::abci::{request, response};
+    use tendermint_abci::{
+        error::ErrorDetail, ClientBuilder, Consensus, Info, Mempool, ServerBuilder, Snapshot,
+    };
+    use tendermint_proto::abci::{RequestEcho, RequestQuery};
+
+    /// Panics whenever it is queried.
+    #[derive(Clone)]
+    struct PanickingApp;
+
+    impl Consensus for PanickingApp {}
+
+    impl Mempool for PanickingApp {}
+
+    impl Info for PanickingApp {
+        fn query(&self, _request: request::Query) -> response::Query {
+            panic!("query not supported");
+        }
+    }
+
+    impl Snapshot for PanickingApp {}
+
+    fn echo_request() -> RequestEcho {
+        RequestEcho {
+            message: "Hello ABCI!".to_string(),
+        }
+    }
+
+    #[test]
+    fn panics_are_reported_as_exceptions() {
+        let server = ServerBuilder::default()
+            .bind("127.0.0.1:0", PanickingApp)
+            .unwrap();
+        let server_addr = server.local_addr();
+        thread::spawn(move || server.listen());
+
+        let mut client = ClientBuilder::default().connect(server_addr).unwrap();
+        let err = client.query(RequestQuery::default()).unwrap_err();
+        assert!(err.to_string().contains("query not supported"), "{}", err);
+        assert_eq!(client.echo(echo_request()).unwrap().message, "Hello ABCI!");
+    }
+
+    #[test]
+    fn connections_beyond_limit_are_rejected() {
+        let server = ServerBuilder::default()
+            .max_connections(1)
+            .bind("127.0.0.1:0", PanickingApp)
+            .unwrap();
+        let server_addr = server.local_addr();
+        thread::spawn(move || server.listen());
+
+        let mut first = ClientBuilder::default().connect(&server_addr).unwrap();
+        first.echo(echo_request()).unwrap();
+        let mut second = ClientBuilder::default().connect(&server_addr).unwrap();
+        assert!(second.echo(echo_request()).is_err());
+        first.echo(echo_request()).unwrap();
+    }
+
+    #[test]
+    fn shutdown_closes_open_connections() {
+        let server = ServerBuilder::default()
+            .bind("127.0.0.1:0", PanickingApp)
+            .unwrap();
+        let server_addr = server.local_addr();
+        let shutdown = server.shutdown_handle();
+        let listener = thread::spawn(move || server.listen());
+
+        let mut client = ClientBuilder::default().connect(&server_addr).unwrap();
+        client.echo(echo_request()).unwrap();
+
+        shutdown.shutdown();
+        listener.join().unwrap().unwrap();
+        assert!(client.echo(echo_request()).is_err());
+    }
+
+    #[test]
+    fn shutdown_wakes_an_idle_listener() {
+        let server = ServerBuilder::default()
+            .bind("0.0.0.0:0", PanickingApp)
+            .unwrap();
+        let shutdown = server.shutdown_handle();
+        let listener = thread::spawn(move || server.listen());
+
+        shutdown.shutdown();
+        // Repeated requests are harmless
+        shutdown.shutdown();
+        listener.join().unwrap().unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn shutdown_removes_the_socket_file() {
+        let path = std::env::temp_dir().join(format!("abci-server-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        for _ in 0..2 {
+            // The path can be bound again once the previous server is gone
+            let server = ServerBuilder::default()
+                .bind_unix(&path, PanickingApp)
+                .unwrap();
+            let shutdown = server.shutdown_handle();
+            let listener = thread::spawn(move || server.listen());
+            assert!(path.exists());
+
+            shutdown.shutdown();
+            listener.join().unwrap().unwrap();
+            assert!(!path.exists());
+        }
+    }
+
+    #[test]
+    fn oversized_messages_close_the_connection() {
+        let server = ServerBuilder::default()
+            .max_message_size(64)
+            .bind("127.0.0.1:0", PanickingApp)
+            .unwrap();
+        let server_addr = server.local_addr();
+        thread::spawn(move || server.listen());
+
+        let mut client = ClientBuilder::default().connect(&server_addr).unwrap();
+        let err = client
+            .echo(RequestEcho {
+                message: "a".repeat(1024),
+            })
+            .unwrap_err();
+        assert!(matches!(
+            err.detail(),
+            ErrorDetail::ServerConnectionTerminated(_) | ErrorDetail::Io(_)
+        ));
+
+        // Responses beyond the client's own limit are rejected too
+        let mut client = ClientBuilder::default()
+            .max_message_size(8)
+            .connect(&server_addr)
+            .unwrap();
+        let err = client.echo(echo_request()).unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::MessageTooLarge(_)));
+    }
+}
@@ -3,7 +3,7 @@
 use async_recursion::async_recursion;
 use async_trait::async_trait;
 use flume;
-use tendermint::evidence::{ConflictingHeadersEvidence, Evidence};
+use tendermint::evidence::{ConflictingBlock, Evidence, LightClientAttackEvidence};
 
 use crate::{
     errors::Error,
@@ -235,7 +235,7 @@ impl Supervisor {
                 match outcome {
                     // There was a fork or a faulty peer
                     ForkDetection::Detected(forks) => {
-                        let forked = self.process_forks(forks).await?;
+                        let forked = self.process_forks(forks, &trusted_block).await?;
                         if !forked.is_empty() {
                             // Fork detected, exiting
                             return Err(Error::fork_detected(forked));
@@ -266,7 +266,11 @@ impl Supervisor {
         }
     }
 
-    async fn process_forks(&mut self, forks: Vec<Fork>) -> Result<Vec<PeerId>, Error> {
+    async fn process_forks(
+        &mut self,
+        forks: Vec<Fork>,
+        trusted_block: &LightBlock,
+    ) -> Result<Vec<PeerId>, Error> {
         let mut forked = Vec::with_capacity(forks.len());
 
         for fork in forks {
@@ -275,7 +279,8 @@ impl Supervisor {
                 // TODO: also report to primary
                 Fork::Forked { primary, witness } => {
                     let provider = witness.provider;
-                    self.report_evidence(provider, &primary, &witness).await?;
+                    self.report_evidence(provider, &primary, &witness, trusted_block)
+                        .await?;
 
                     forked.push(provider);
                 },
@@ -295,20 +300,29 @@ impl Supervisor {
         Ok(forked)
     }
 
-    /// Report the given evidence of a fork.
+    /// Report to the given provider the evidence of an attack by the primary,
+    /// whose block conflicts with the witness's one, both having been verified
+    /// from the given trusted block.
     async fn report_evidence(
         &mut self,
         provider: PeerId,
         primary: &LightBlock,
         witness: &LightBlock,
+        trusted_block: &LightBlock,
     ) -> Result<(), Error> {
-        let evidence = ConflictingHeadersEvidence::new(
-            primary.signed_header.clone(),
-            witness.signed_header.clone(),
+        let conflicting_block = ConflictingBlock {
+            signed_header: primary.signed_header.clone(),
+            validator_set: primary.validators.clone(),
+        };
+        let evidence = LightClientAttackEvidence::new(
+            conflicting_block,
+            &witness.signed_header,
+            &trusted_block.signed_header,
+            &trusted_block.validators,
         );
 
         self.evidence_reporter
-            .report(Evidence::ConflictingHeaders(Box::new(evidence)), provider)
+            .report(Evidence::LightClientAttack(Box::new(evidence)), provider)
             .await
             .map_err(Error::io)?;
 
@@ -768,6 +782,78 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_light_client_attack_evidence() {
+        let mut chain = LightChain::default_with_length(5);
+        let primary = chain
+            .light_blocks
+            .clone()
+            .into_iter()
+            .map(|lb| lb.generate().unwrap().into_light_block())
+            .collect::<Vec<LightBlock>>();
+
+        let mut header = chain.light_blocks[4].header.clone().unwrap();
+        let time = (header.time.unwrap() + Duration::from_secs(3)).unwrap();
+        header.time = Some(time);
+        chain.light_blocks[4].commit = Some(Commit::new(header.clone(), 1));
+        chain.light_blocks[4].header = Some(header);
+        let witness = chain.light_blocks[4].generate().unwrap().into_light_block();
+
+        // Equivocation: both validators signed both blocks
+        let conflicting_block = ConflictingBlock {
+            signed_header: primary[4].signed_header.clone(),
+            validator_set: primary[4].validators.clone(),
+        };
+        let evidence = LightClientAttackEvidence::new(
+            conflicting_block,
+            &witness.signed_header,
+            &primary[0].signed_header,
+            &primary[0].validators,
+        );
+        assert_eq!(evidence.common_height, witness.height());
+        assert_eq!(evidence.timestamp, witness.signed_header.header.time);
+        assert_eq!(evidence.total_voting_power.value(), 100);
+        assert_eq!(
+            evidence.byzantine_validators,
+            *primary[4].validators.validators()
+        );
+
+        // Through the raw protobuf types, as when broadcasting evidence
+        let evidence = Evidence::LightClientAttack(Box::new(evidence));
+        let json = serde_json::to_string(&evidence).unwrap();
+        assert!(json.contains(r#""TotalVotingPower":"100""#));
+        assert_eq!(serde_json::from_str::<Evidence>(&json).unwrap(), evidence);
+
+        // Lunatic: only one of the common validators signed the conflicting block
+        let lunatic = make_conflicting_witness(1, Some(vec!["1", "3"]), Some("test-chain"), None);
+        let conflicting_block = ConflictingBlock {
+            signed_header: lunatic[0].signed_header.clone(),
+            validator_set: lunatic[0].validators.clone(),
+        };
+        let evidence = LightClientAttackEvidence::new(
+            conflicting_block,
+            &primary[0].signed_header,
+            &primary[0].signed_header,
+            &primary[0].validators,
+        );
+        assert_eq!(evidence.common_height, primary[0].height());
+        assert_eq!(evidence.total_voting_power.value(), 100);
+        let byzantine = evidence
+            .byzantine_validators
+            .iter()
+            .map(|v| v.address)
+            .collect::<Vec<_>>();
+        let expected = lunatic[0]
+            .validators
+            .validators()
+            .iter()
+            .map(|v| v.address)
+            .filter(|a| primary[0].validators.validator(*a).is_some())
+            .collect::<Vec<_>>();
+        assert_eq!(byzantine.len(), 1);
+        assert_eq!(byzantine, expected);
+    }
+
     #[tokio::test]
     async fn test_bisection_no_initial_trusted_state() {
         let chain = LightChain::default_with_length(10);
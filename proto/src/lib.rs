@@ -24,6 +24,90 @@ mod error;
 #[allow(warnings)]
 mod tendermint;
 
+/// Protobuf definitions of the ABCI wire protocol spoken by Tendermint v0.34.
+///
+/// Only the `abci` package differs between Tendermint v0.34 and v0.35 in ways
+/// that affect the ABCI wire protocol, so the messages of all other packages
+/// are shared with the default definitions.
+#[allow(warnings)]
+pub mod v0_34 {
+    pub use crate::{crypto, types, version};
+
+    pub mod abci {
+        include!("prost/v0_34/tendermint.abci.rs");
+    }
+
+    pub mod meta {
+        pub const REPOSITORY: &str = "https://github.com/tendermint/tendermint";
+        pub const COMMITISH: &str = "v0.34.20";
+    }
+}
+
+/// Protobuf definitions of the ABCI wire protocol spoken by CometBFT v0.37,
+/// which adds `PrepareProposal` and `ProcessProposal` to that of v0.34.
+///
+/// As for [`v0_34`], the messages of all packages other than `abci` are
+/// shared with the default definitions.
+#[allow(warnings)]
+pub mod v0_37 {
+    pub use crate::{crypto, types, version};
+
+    pub mod abci {
+        include!("prost/v0_37/tendermint.abci.rs");
+    }
+
+    pub mod meta {
+        pub const REPOSITORY: &str = "https://github.com/cometbft/cometbft";
+        pub const COMMITISH: &str = "v0.37.2";
+    }
+}
+
+/// Protobuf definitions of the ABCI wire protocol spoken by CometBFT v0.38,
+/// which replaces `BeginBlock`, `DeliverTx` and `EndBlock` with
+/// `FinalizeBlock`, and adds vote extensions.
+///
+/// The consensus parameters of CometBFT v0.38 also configure vote
+/// extensions, so the `types` messages used by the `abci` package are
+/// generated as well. The messages of all other packages are shared with the
+/// default definitions.
+#[allow(warnings)]
+pub mod v0_38 {
+    pub use crate::{crypto, version};
+
+    pub mod abci {
+        include!("prost/v0_38/tendermint.abci.rs");
+    }
+
+    pub mod types {
+        include!("prost/v0_38/tendermint.types.rs");
+    }
+
+    pub mod meta {
+        pub const REPOSITORY: &str = "https://github.com/cometbft/cometbft";
+        pub const COMMITISH: &str = "v0.38.0";
+    }
+}
+
+/// Protobuf definitions of [ICS23] commitment proofs, which Cosmos SDK-based
+/// applications return in the `ProofOps` of their query responses.
+///
+/// [ICS23]: https://github.com/cosmos/ibc/tree/main/spec/core/ics-023-vector-commitments
+#[allow(warnings)]
+pub mod ics23 {
+    include!("prost/ics23.rs");
+
+    pub mod meta {
+        pub const REPOSITORY: &str = "https://github.com/confio/ics23";
+        pub const COMMITISH: &str = "v0.9.0";
+    }
+}
+
+/// Protobuf definitions of Tendermint v0.35, which are also exported at the
+/// root of this crate.
+pub mod v0_35 {
+    pub use crate::tendermint::*;
+}
+
 use core::{
     convert::{TryFrom, TryInto},
     fmt::Display,
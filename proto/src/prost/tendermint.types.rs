@@ -6,6 +6,7 @@ pub struct ValidatorSet {
     #[prost(message, optional, tag="2")]
     pub proposer: ::core::option::Option<Validator>,
     #[prost(int64, tag="3")]
+    #[serde(with = "crate::serializers::from_str", default)]
     pub total_voting_power: i64,
 }
 #[derive(::serde::Deserialize, ::serde::Serialize)]
@@ -444,10 +445,16 @@ pub struct DuplicateVoteEvidence {
     #[prost(message, optional, tag="2")]
     pub vote_b: ::core::option::Option<Vote>,
     #[prost(int64, tag="3")]
+    #[serde(rename = "TotalVotingPower")]
+    #[serde(with = "crate::serializers::from_str")]
     pub total_voting_power: i64,
     #[prost(int64, tag="4")]
+    #[serde(rename = "ValidatorPower")]
+    #[serde(with = "crate::serializers::from_str")]
     pub validator_power: i64,
     #[prost(message, optional, tag="5")]
+    #[serde(rename = "Timestamp")]
+    #[serde(with = "crate::serializers::optional")]
     pub timestamp: ::core::option::Option<super::super::google::protobuf::Timestamp>,
 }
 /// LightClientAttackEvidence contains evidence of a set of validators attempting to mislead a light client.
@@ -455,14 +462,23 @@ pub struct DuplicateVoteEvidence {
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct LightClientAttackEvidence {
     #[prost(message, optional, tag="1")]
+    #[serde(rename = "ConflictingBlock")]
     pub conflicting_block: ::core::option::Option<LightBlock>,
     #[prost(int64, tag="2")]
+    #[serde(rename = "CommonHeight")]
+    #[serde(with = "crate::serializers::from_str")]
     pub common_height: i64,
     #[prost(message, repeated, tag="3")]
+    #[serde(rename = "ByzantineValidators")]
+    #[serde(with = "crate::serializers::nullable")]
     pub byzantine_validators: ::prost::alloc::vec::Vec<Validator>,
     #[prost(int64, tag="4")]
+    #[serde(rename = "TotalVotingPower")]
+    #[serde(with = "crate::serializers::from_str")]
     pub total_voting_power: i64,
     #[prost(message, optional, tag="5")]
+    #[serde(rename = "Timestamp")]
+    #[serde(with = "crate::serializers::optional")]
     pub timestamp: ::core::option::Option<super::super::google::protobuf::Timestamp>,
 }
 #[derive(::serde::Deserialize, ::serde::Serialize)]
@@ -1,7 +1,7 @@
 //! `/abci_query` endpoint JSON-RPC wrapper
 
 use serde::{Deserialize, Serialize};
-use tendermint::{block, merkle::proof::Proof, serializers};
+use tendermint::{block, merkle::proof::ProofOps, serializers};
 
 use crate::{
     abci::{Code, Log, Path},
@@ -89,8 +89,12 @@ pub struct AbciQuery {
     pub value: Vec<u8>,
 
     /// Proof (might be explicit null)
+    ///
+    /// This is a list of proof operators, and used to be the type named
+    /// `merkle::proof::Proof`, which now names a simple Merkle tree inclusion
+    /// proof instead.
     #[serde(alias = "proofOps")]
-    pub proof: Option<Proof>,
+    pub proof: Option<ProofOps>,
 
     /// Block height
     pub height: block::Height,
@@ -1,8 +1,7 @@
 //! `/tx` endpoint JSON-RPC wrapper
 
 use serde::{Deserialize, Serialize};
-use tendermint::block;
-use tendermint_proto::types::TxProof;
+use tendermint::{block, tx::Proof};
 
 use crate::{abci, Method};
 
@@ -49,7 +48,7 @@ pub struct Response {
     pub tx_result: abci::DeliverTx,
     pub tx: abci::Transaction,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub proof: Option<TxProof>,
+    pub proof: Option<Proof>,
 }
 
 impl crate::Response for Response {}
@@ -388,6 +388,15 @@ fn incoming_fixtures() {
             },
             "block_at_height_1" => {
                 let result = endpoint::block::Response::from_string(content).unwrap();
+                result.block.validate_basic().unwrap();
+                assert_eq!(
+                    result
+                        .block
+                        .make_part_set(tendermint::block::parts::BLOCK_PART_SIZE_BYTES)
+                        .unwrap()
+                        .header(),
+                    result.block_id.part_set_header
+                );
                 assert!(result.block.data.get(0).is_none());
                 assert!(result.block.evidence.iter().next().is_none());
                 assert!(result.block.header.app_hash.value().is_empty());
@@ -429,6 +438,15 @@ fn incoming_fixtures() {
             },
             "block_at_height_10" => {
                 let result = endpoint::block::Response::from_string(content).unwrap();
+                result.block.validate_basic().unwrap();
+                assert_eq!(
+                    result
+                        .block
+                        .make_part_set(tendermint::block::parts::BLOCK_PART_SIZE_BYTES)
+                        .unwrap()
+                        .header(),
+                    result.block_id.part_set_header
+                );
                 assert!(result.block.data.get(0).is_none());
                 assert!(result.block.evidence.iter().next().is_none());
                 assert_eq!(result.block.header.app_hash.value(), [0u8; 8]);
@@ -485,6 +503,7 @@ fn incoming_fixtures() {
             },
             "block_by_hash" => {
                 let result = endpoint::block::Response::from_string(content).unwrap();
+                result.block.validate_basic().unwrap();
                 assert_eq!(
                     result.block_id.hash.to_string(),
                     "BCF3DB412E80A396D10BF5B5E6D3E63D3B06DEB25AA958BCB8CE18D023838042"
@@ -861,6 +880,7 @@ fn incoming_fixtures() {
                 } = result.data
                 {
                     let b = block.unwrap();
+                    b.validate_basic().unwrap();
                     assert!(b.data.get(0).is_none());
                     assert!(b.evidence.iter().next().is_none());
                     assert!(!b.header.app_hash.value().is_empty());
@@ -916,6 +936,7 @@ fn incoming_fixtures() {
                 } = result.data
                 {
                     let b = block.unwrap();
+                    b.validate_basic().unwrap();
                     assert!(b.data.get(0).is_none());
                     assert!(b.evidence.iter().next().is_none());
                     assert!(!b.header.app_hash.value().is_empty());
@@ -971,6 +992,7 @@ fn incoming_fixtures() {
                 } = result.data
                 {
                     let b = block.unwrap();
+                    b.validate_basic().unwrap();
                     assert!(b.data.get(0).is_none());
                     assert!(b.evidence.iter().next().is_none());
                     assert!(!b.header.app_hash.value().is_empty());
@@ -1026,6 +1048,7 @@ fn incoming_fixtures() {
                 } = result.data
                 {
                     let b = block.unwrap();
+                    b.validate_basic().unwrap();
                     assert!(b.data.get(0).is_none());
                     assert!(b.evidence.iter().next().is_none());
                     assert!(!b.header.app_hash.value().is_empty());
@@ -1081,6 +1104,7 @@ fn incoming_fixtures() {
                 } = result.data
                 {
                     let b = block.unwrap();
+                    b.validate_basic().unwrap();
                     assert!(b.data.get(0).is_none());
                     assert!(b.evidence.iter().next().is_none());
                     assert!(!b.header.app_hash.value().is_empty());
@@ -1364,13 +1388,15 @@ fn incoming_fixtures() {
                 assert!(result.proof.is_some());
                 let proof = result.proof.unwrap();
                 assert_eq!(
-                    proof.root_hash,
-                    vec![
+                    proof.root_hash.as_bytes(),
+                    [
                         199, 124, 183, 99, 203, 39, 4, 138, 141, 159, 9, 218, 112, 123, 122, 25,
                         236, 244, 180, 12, 150, 122, 87, 207, 22, 206, 222, 225, 165, 19, 10, 143
                     ]
                 );
-                assert_eq!(proof.proof.unwrap().total, 2);
+                assert_eq!(proof.proof.total, 2);
+                assert_eq!(proof.data, result.tx.as_bytes());
+                proof.validate(&proof.root_hash).unwrap();
             },
             "tx_no_prove" => {
                 let result = endpoint::tx::Response::from_string(content).unwrap();
@@ -1415,8 +1441,7 @@ fn incoming_fixtures() {
                     assert!(tx.tx_result.log.as_str().is_empty());
                     let proof = tx.proof.unwrap();
                     assert_eq!(proof.data, tx.tx.as_bytes());
-                    assert!(proof.proof.is_some());
-                    assert_ne!(proof.root_hash, [0; 32]);
+                    proof.validate(&proof.root_hash).unwrap();
                 }
             },
             _ => {
@@ -35,6 +35,36 @@
 //! [ABCI]: https://docs.tendermint.com/master/spec/abci/
 //! [mat]: https://docs.tendermint.com/master/spec/abci/abci.html
 //! [tmabci]: https://github.com/informalsystems/tendermint-rs/tree/master/abci
+//!
+//! Tendermint v0.34 and v0.35 and CometBFT v0.37 and v0.38 speak different
+//! versions of the ABCI wire protocol, so the domain types convert to and from
+//! the Protobuf types of each of the [`tendermint_proto::v0_34`],
+//! [`tendermint_proto::v0_35`], [`tendermint_proto::v0_37`] and
+//! [`tendermint_proto::v0_38`] modules, wherever the corresponding messages
+//! exist.
+
+/// Expands the given Protobuf conversions once for each of the listed
+/// versions of the ABCI wire protocol, or for all of them if none are listed,
+/// with `pb` referring to that version's `abci` module.
+///
+/// Each expansion is scoped to an anonymous constant rather than a module, so
+/// that conversions specific to some versions can be expanded separately in
+/// the same file.
+macro_rules! abci_pb_modules {
+    (@module $version:ident { $($contents:item)* }) => {
+        const _: () = {
+            use tendermint_proto::$version::abci as pb;
+
+            $($contents)*
+        };
+    };
+    ($($version:ident),+ => $contents:tt) => {
+        $(abci_pb_modules!(@module $version $contents);)+
+    };
+    ($($contents:item)*) => {
+        abci_pb_modules!(v0_34, v0_35, v0_37, v0_38 => { $($contents)* });
+    };
+}
 
 mod event;
 mod kind;
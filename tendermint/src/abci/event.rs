@@ -129,54 +129,116 @@ impl<K: Into<String>, V: Into<String>> From<(K, V)> for EventAttribute {
 
 use core::convert::{TryFrom, TryInto};
 
-use tendermint_proto::{abci as pb, Protobuf};
+use tendermint_proto::Protobuf;
+
+mod v0_34 {
+    use super::*;
+    use tendermint_proto::v0_34::abci as pb;
+
+    // Event attributes are byte strings in the v0.34 protocol, but are always
+    // valid UTF-8 in practice.
+    impl From<EventAttribute> for pb::EventAttribute {
+        fn from(event: EventAttribute) -> Self {
+            Self {
+                key: event.key.into_bytes().into(),
+                value: event.value.into_bytes().into(),
+                index: event.index,
+            }
+        }
+    }
 
-impl From<EventAttribute> for pb::EventAttribute {
-    fn from(event: EventAttribute) -> Self {
-        Self {
-            key: event.key,
-            value: event.value,
-            index: event.index,
+    impl TryFrom<pb::EventAttribute> for EventAttribute {
+        type Error = crate::Error;
+
+        fn try_from(event: pb::EventAttribute) -> Result<Self, Self::Error> {
+            let to_string = |bytes: bytes::Bytes| {
+                String::from_utf8(bytes.to_vec()).map_err(|e| crate::Error::parse(e.to_string()))
+            };
+            Ok(Self {
+                key: to_string(event.key)?,
+                value: to_string(event.value)?,
+                index: event.index,
+            })
         }
     }
-}
 
-impl TryFrom<pb::EventAttribute> for EventAttribute {
-    type Error = crate::Error;
+    impl Protobuf<pb::EventAttribute> for EventAttribute {}
+
+    impl From<Event> for pb::Event {
+        fn from(event: Event) -> Self {
+            Self {
+                r#type: event.kind,
+                attributes: event.attributes.into_iter().map(Into::into).collect(),
+            }
+        }
+    }
 
-    fn try_from(event: pb::EventAttribute) -> Result<Self, Self::Error> {
-        Ok(Self {
-            key: event.key,
-            value: event.value,
-            index: event.index,
-        })
+    impl TryFrom<pb::Event> for Event {
+        type Error = crate::Error;
+
+        fn try_from(event: pb::Event) -> Result<Self, Self::Error> {
+            Ok(Self {
+                kind: event.r#type,
+                attributes: event
+                    .attributes
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+            })
+        }
     }
+
+    impl Protobuf<pb::Event> for Event {}
 }
 
-impl Protobuf<pb::EventAttribute> for EventAttribute {}
+abci_pb_modules!(v0_35, v0_37, v0_38 => {
+    impl From<EventAttribute> for pb::EventAttribute {
+        fn from(event: EventAttribute) -> Self {
+            Self {
+                key: event.key,
+                value: event.value,
+                index: event.index,
+            }
+        }
+    }
+
+    impl TryFrom<pb::EventAttribute> for EventAttribute {
+        type Error = crate::Error;
 
-impl From<Event> for pb::Event {
-    fn from(event: Event) -> Self {
-        Self {
-            r#type: event.kind,
-            attributes: event.attributes.into_iter().map(Into::into).collect(),
+        fn try_from(event: pb::EventAttribute) -> Result<Self, Self::Error> {
+            Ok(Self {
+                key: event.key,
+                value: event.value,
+                index: event.index,
+            })
         }
     }
-}
 
-impl TryFrom<pb::Event> for Event {
-    type Error = crate::Error;
-
-    fn try_from(event: pb::Event) -> Result<Self, Self::Error> {
-        Ok(Self {
-            kind: event.r#type,
-            attributes: event
-                .attributes
-                .into_iter()
-                .map(TryInto::try_into)
-                .collect::<Result<_, _>>()?,
-        })
+    impl Protobuf<pb::EventAttribute> for EventAttribute {}
+
+    impl From<Event> for pb::Event {
+        fn from(event: Event) -> Self {
+            Self {
+                r#type: event.kind,
+                attributes: event.attributes.into_iter().map(Into::into).collect(),
+            }
+        }
+    }
+
+    impl TryFrom<pb::Event> for Event {
+        type Error = crate::Error;
+
+        fn try_from(event: pb::Event) -> Result<Self, Self::Error> {
+            Ok(Self {
+                kind: event.r#type,
+                attributes: event
+                    .attributes
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+            })
+        }
     }
-}
 
-impl Protobuf<pb::Event> for Event {}
+    impl Protobuf<pb::Event> for Event {}
+});
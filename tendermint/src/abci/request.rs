@@ -32,11 +32,16 @@ mod check_tx;
 mod deliver_tx;
 mod echo;
 mod end_block;
+mod extend_vote;
+mod finalize_block;
 mod info;
 mod init_chain;
 mod load_snapshot_chunk;
 mod offer_snapshot;
+mod prepare_proposal;
+mod process_proposal;
 mod query;
+mod verify_vote_extension;
 
 pub use apply_snapshot_chunk::ApplySnapshotChunk;
 pub use begin_block::BeginBlock;
@@ -44,11 +49,16 @@ pub use check_tx::{CheckTx, CheckTxKind};
 pub use deliver_tx::DeliverTx;
 pub use echo::Echo;
 pub use end_block::EndBlock;
+pub use extend_vote::ExtendVote;
+pub use finalize_block::FinalizeBlock;
 pub use info::Info;
 pub use init_chain::InitChain;
 pub use load_snapshot_chunk::LoadSnapshotChunk;
 pub use offer_snapshot::OfferSnapshot;
+pub use prepare_proposal::PrepareProposal;
+pub use process_proposal::ProcessProposal;
 pub use query::Query;
+pub use verify_vote_extension::VerifyVoteExtension;
 
 /// All possible ABCI requests.
 #[allow(clippy::large_enum_variant)]
@@ -82,6 +92,16 @@ pub enum Request {
     LoadSnapshotChunk(LoadSnapshotChunk),
     #[doc = include_str!("doc/request-applysnapshotchunk.md")]
     ApplySnapshotChunk(ApplySnapshotChunk),
+    #[doc = include_str!("doc/request-prepareproposal.md")]
+    PrepareProposal(PrepareProposal),
+    #[doc = include_str!("doc/request-processproposal.md")]
+    ProcessProposal(ProcessProposal),
+    #[doc = include_str!("doc/request-extendvote.md")]
+    ExtendVote(ExtendVote),
+    #[doc = include_str!("doc/request-verifyvoteextension.md")]
+    VerifyVoteExtension(VerifyVoteExtension),
+    #[doc = include_str!("doc/request-finalizeblock.md")]
+    FinalizeBlock(FinalizeBlock),
 }
 
 impl Request {
@@ -95,6 +115,11 @@ impl Request {
             DeliverTx(_) => MethodKind::Consensus,
             EndBlock(_) => MethodKind::Consensus,
             Commit => MethodKind::Consensus,
+            PrepareProposal(_) => MethodKind::Consensus,
+            ProcessProposal(_) => MethodKind::Consensus,
+            ExtendVote(_) => MethodKind::Consensus,
+            VerifyVoteExtension(_) => MethodKind::Consensus,
+            FinalizeBlock(_) => MethodKind::Consensus,
             CheckTx(_) => MethodKind::Mempool,
             ListSnapshots => MethodKind::Snapshot,
             OfferSnapshot(_) => MethodKind::Snapshot,
@@ -121,6 +146,16 @@ pub enum ConsensusRequest {
     EndBlock(EndBlock),
     #[doc = include_str!("doc/request-commit.md")]
     Commit,
+    #[doc = include_str!("doc/request-prepareproposal.md")]
+    PrepareProposal(PrepareProposal),
+    #[doc = include_str!("doc/request-processproposal.md")]
+    ProcessProposal(ProcessProposal),
+    #[doc = include_str!("doc/request-extendvote.md")]
+    ExtendVote(ExtendVote),
+    #[doc = include_str!("doc/request-verifyvoteextension.md")]
+    VerifyVoteExtension(VerifyVoteExtension),
+    #[doc = include_str!("doc/request-finalizeblock.md")]
+    FinalizeBlock(FinalizeBlock),
 }
 
 impl From<ConsensusRequest> for Request {
@@ -131,6 +166,11 @@ impl From<ConsensusRequest> for Request {
             ConsensusRequest::DeliverTx(x) => Self::DeliverTx(x),
             ConsensusRequest::EndBlock(x) => Self::EndBlock(x),
             ConsensusRequest::Commit => Self::Commit,
+            ConsensusRequest::PrepareProposal(x) => Self::PrepareProposal(x),
+            ConsensusRequest::ProcessProposal(x) => Self::ProcessProposal(x),
+            ConsensusRequest::ExtendVote(x) => Self::ExtendVote(x),
+            ConsensusRequest::VerifyVoteExtension(x) => Self::VerifyVoteExtension(x),
+            ConsensusRequest::FinalizeBlock(x) => Self::FinalizeBlock(x),
         }
     }
 }
@@ -144,6 +184,11 @@ impl TryFrom<Request> for ConsensusRequest {
             Request::DeliverTx(x) => Ok(Self::DeliverTx(x)),
             Request::EndBlock(x) => Ok(Self::EndBlock(x)),
             Request::Commit => Ok(Self::Commit),
+            Request::PrepareProposal(x) => Ok(Self::PrepareProposal(x)),
+            Request::ProcessProposal(x) => Ok(Self::ProcessProposal(x)),
+            Request::ExtendVote(x) => Ok(Self::ExtendVote(x)),
+            Request::VerifyVoteExtension(x) => Ok(Self::VerifyVoteExtension(x)),
+            Request::FinalizeBlock(x) => Ok(Self::FinalizeBlock(x)),
             _ => Err(Error::invalid_abci_request_type()),
         }
     }
@@ -248,54 +293,288 @@ impl TryFrom<Request> for SnapshotRequest {
 // Protobuf conversions
 // =============================================================================
 
-use tendermint_proto::{abci as pb, Protobuf};
-
-impl From<Request> for pb::Request {
-    fn from(request: Request) -> pb::Request {
-        use pb::request::Value;
-        let value = match request {
-            Request::Echo(x) => Some(Value::Echo(x.into())),
-            Request::Flush => Some(Value::Flush(Default::default())),
-            Request::Info(x) => Some(Value::Info(x.into())),
-            Request::InitChain(x) => Some(Value::InitChain(x.into())),
-            Request::Query(x) => Some(Value::Query(x.into())),
-            Request::BeginBlock(x) => Some(Value::BeginBlock(x.into())),
-            Request::CheckTx(x) => Some(Value::CheckTx(x.into())),
-            Request::DeliverTx(x) => Some(Value::DeliverTx(x.into())),
-            Request::EndBlock(x) => Some(Value::EndBlock(x.into())),
-            Request::Commit => Some(Value::Commit(Default::default())),
-            Request::ListSnapshots => Some(Value::ListSnapshots(Default::default())),
-            Request::OfferSnapshot(x) => Some(Value::OfferSnapshot(x.into())),
-            Request::LoadSnapshotChunk(x) => Some(Value::LoadSnapshotChunk(x.into())),
-            Request::ApplySnapshotChunk(x) => Some(Value::ApplySnapshotChunk(x.into())),
-        };
-        pb::Request { value }
+// ABCI++ methods are not part of the v0.34 protocol, and `SetOption` is not
+// supported by the domain types.
+mod v0_34 {
+    use super::*;
+    use tendermint_proto::v0_34::abci as pb;
+
+    impl TryFrom<Request> for pb::Request {
+        type Error = Error;
+
+        fn try_from(request: Request) -> Result<Self, Self::Error> {
+            use pb::request::Value;
+            let value = match request {
+                Request::Echo(x) => Some(Value::Echo(x.into())),
+                Request::Flush => Some(Value::Flush(Default::default())),
+                Request::Info(x) => Some(Value::Info(x.into())),
+                Request::InitChain(x) => Some(Value::InitChain(x.into())),
+                Request::Query(x) => Some(Value::Query(x.into())),
+                Request::BeginBlock(x) => Some(Value::BeginBlock(x.into())),
+                Request::CheckTx(x) => Some(Value::CheckTx(x.into())),
+                Request::DeliverTx(x) => Some(Value::DeliverTx(x.into())),
+                Request::EndBlock(x) => Some(Value::EndBlock(x.into())),
+                Request::Commit => Some(Value::Commit(Default::default())),
+                Request::ListSnapshots => Some(Value::ListSnapshots(Default::default())),
+                Request::OfferSnapshot(x) => Some(Value::OfferSnapshot(x.into())),
+                Request::LoadSnapshotChunk(x) => Some(Value::LoadSnapshotChunk(x.into())),
+                Request::ApplySnapshotChunk(x) => Some(Value::ApplySnapshotChunk(x.into())),
+                Request::PrepareProposal(_) => return Err(unsupported("PrepareProposal")),
+                Request::ProcessProposal(_) => return Err(unsupported("ProcessProposal")),
+                Request::ExtendVote(_) => return Err(unsupported("ExtendVote")),
+                Request::VerifyVoteExtension(_) => return Err(unsupported("VerifyVoteExtension")),
+                Request::FinalizeBlock(_) => return Err(unsupported("FinalizeBlock")),
+            };
+            Ok(pb::Request { value })
+        }
+    }
+
+    impl TryFrom<pb::Request> for Request {
+        type Error = Error;
+
+        fn try_from(request: pb::Request) -> Result<Self, Self::Error> {
+            use pb::request::Value;
+            match request.value {
+                Some(Value::Echo(x)) => Ok(Request::Echo(x.try_into()?)),
+                Some(Value::Flush(pb::RequestFlush {})) => Ok(Request::Flush),
+                Some(Value::Info(x)) => Ok(Request::Info(x.try_into()?)),
+                Some(Value::InitChain(x)) => Ok(Request::InitChain(x.try_into()?)),
+                Some(Value::Query(x)) => Ok(Request::Query(x.try_into()?)),
+                Some(Value::BeginBlock(x)) => Ok(Request::BeginBlock(x.try_into()?)),
+                Some(Value::CheckTx(x)) => Ok(Request::CheckTx(x.try_into()?)),
+                Some(Value::DeliverTx(x)) => Ok(Request::DeliverTx(x.try_into()?)),
+                Some(Value::EndBlock(x)) => Ok(Request::EndBlock(x.try_into()?)),
+                Some(Value::Commit(pb::RequestCommit {})) => Ok(Request::Commit),
+                Some(Value::ListSnapshots(pb::RequestListSnapshots {})) => {
+                    Ok(Request::ListSnapshots)
+                },
+                Some(Value::OfferSnapshot(x)) => Ok(Request::OfferSnapshot(x.try_into()?)),
+                Some(Value::LoadSnapshotChunk(x)) => Ok(Request::LoadSnapshotChunk(x.try_into()?)),
+                Some(Value::ApplySnapshotChunk(x)) => {
+                    Ok(Request::ApplySnapshotChunk(x.try_into()?))
+                },
+                Some(Value::SetOption(_)) => Err(unsupported("SetOption")),
+                None => Err(Error::missing_data()),
+            }
+        }
+    }
+
+    fn unsupported(method: &str) -> Error {
+        Error::unsupported_abci_method(method.to_string(), "0.34".to_string())
     }
 }
 
-impl TryFrom<pb::Request> for Request {
-    type Error = Error;
+// ABCI++ methods are not part of the v0.35 protocol.
+mod v0_35 {
+    use super::*;
+    use tendermint_proto::v0_35::abci as pb;
+
+    impl TryFrom<Request> for pb::Request {
+        type Error = Error;
+
+        fn try_from(request: Request) -> Result<Self, Self::Error> {
+            use pb::request::Value;
+            let value = match request {
+                Request::Echo(x) => Some(Value::Echo(x.into())),
+                Request::Flush => Some(Value::Flush(Default::default())),
+                Request::Info(x) => Some(Value::Info(x.into())),
+                Request::InitChain(x) => Some(Value::InitChain(x.into())),
+                Request::Query(x) => Some(Value::Query(x.into())),
+                Request::BeginBlock(x) => Some(Value::BeginBlock(x.into())),
+                Request::CheckTx(x) => Some(Value::CheckTx(x.into())),
+                Request::DeliverTx(x) => Some(Value::DeliverTx(x.into())),
+                Request::EndBlock(x) => Some(Value::EndBlock(x.into())),
+                Request::Commit => Some(Value::Commit(Default::default())),
+                Request::ListSnapshots => Some(Value::ListSnapshots(Default::default())),
+                Request::OfferSnapshot(x) => Some(Value::OfferSnapshot(x.into())),
+                Request::LoadSnapshotChunk(x) => Some(Value::LoadSnapshotChunk(x.into())),
+                Request::ApplySnapshotChunk(x) => Some(Value::ApplySnapshotChunk(x.into())),
+                Request::PrepareProposal(_) => return Err(unsupported("PrepareProposal")),
+                Request::ProcessProposal(_) => return Err(unsupported("ProcessProposal")),
+                Request::ExtendVote(_) => return Err(unsupported("ExtendVote")),
+                Request::VerifyVoteExtension(_) => return Err(unsupported("VerifyVoteExtension")),
+                Request::FinalizeBlock(_) => return Err(unsupported("FinalizeBlock")),
+            };
+            Ok(pb::Request { value })
+        }
+    }
+
+    impl TryFrom<pb::Request> for Request {
+        type Error = Error;
+
+        fn try_from(request: pb::Request) -> Result<Self, Self::Error> {
+            use pb::request::Value;
+            match request.value {
+                Some(Value::Echo(x)) => Ok(Request::Echo(x.try_into()?)),
+                Some(Value::Flush(pb::RequestFlush {})) => Ok(Request::Flush),
+                Some(Value::Info(x)) => Ok(Request::Info(x.try_into()?)),
+                Some(Value::InitChain(x)) => Ok(Request::InitChain(x.try_into()?)),
+                Some(Value::Query(x)) => Ok(Request::Query(x.try_into()?)),
+                Some(Value::BeginBlock(x)) => Ok(Request::BeginBlock(x.try_into()?)),
+                Some(Value::CheckTx(x)) => Ok(Request::CheckTx(x.try_into()?)),
+                Some(Value::DeliverTx(x)) => Ok(Request::DeliverTx(x.try_into()?)),
+                Some(Value::EndBlock(x)) => Ok(Request::EndBlock(x.try_into()?)),
+                Some(Value::Commit(pb::RequestCommit {})) => Ok(Request::Commit),
+                Some(Value::ListSnapshots(pb::RequestListSnapshots {})) => {
+                    Ok(Request::ListSnapshots)
+                },
+                Some(Value::OfferSnapshot(x)) => Ok(Request::OfferSnapshot(x.try_into()?)),
+                Some(Value::LoadSnapshotChunk(x)) => Ok(Request::LoadSnapshotChunk(x.try_into()?)),
+                Some(Value::ApplySnapshotChunk(x)) => {
+                    Ok(Request::ApplySnapshotChunk(x.try_into()?))
+                },
+                None => Err(Error::missing_data()),
+            }
+        }
+    }
+
+    fn unsupported(method: &str) -> Error {
+        Error::unsupported_abci_method(method.to_string(), "0.35".to_string())
+    }
+}
+
+// Of the ABCI++ methods, the v0.37 protocol only has `PrepareProposal` and
+// `ProcessProposal`.
+mod v0_37 {
+    use super::*;
+    use tendermint_proto::v0_37::abci as pb;
+
+    impl TryFrom<Request> for pb::Request {
+        type Error = Error;
+
+        fn try_from(request: Request) -> Result<Self, Self::Error> {
+            use pb::request::Value;
+            let value = match request {
+                Request::Echo(x) => Some(Value::Echo(x.into())),
+                Request::Flush => Some(Value::Flush(Default::default())),
+                Request::Info(x) => Some(Value::Info(x.into())),
+                Request::InitChain(x) => Some(Value::InitChain(x.into())),
+                Request::Query(x) => Some(Value::Query(x.into())),
+                Request::BeginBlock(x) => Some(Value::BeginBlock(x.into())),
+                Request::CheckTx(x) => Some(Value::CheckTx(x.into())),
+                Request::DeliverTx(x) => Some(Value::DeliverTx(x.into())),
+                Request::EndBlock(x) => Some(Value::EndBlock(x.into())),
+                Request::Commit => Some(Value::Commit(Default::default())),
+                Request::ListSnapshots => Some(Value::ListSnapshots(Default::default())),
+                Request::OfferSnapshot(x) => Some(Value::OfferSnapshot(x.into())),
+                Request::LoadSnapshotChunk(x) => Some(Value::LoadSnapshotChunk(x.into())),
+                Request::ApplySnapshotChunk(x) => Some(Value::ApplySnapshotChunk(x.into())),
+                Request::PrepareProposal(x) => Some(Value::PrepareProposal(x.into())),
+                Request::ProcessProposal(x) => Some(Value::ProcessProposal(x.into())),
+                Request::ExtendVote(_) => return Err(unsupported("ExtendVote")),
+                Request::VerifyVoteExtension(_) => return Err(unsupported("VerifyVoteExtension")),
+                Request::FinalizeBlock(_) => return Err(unsupported("FinalizeBlock")),
+            };
+            Ok(pb::Request { value })
+        }
+    }
+
+    impl TryFrom<pb::Request> for Request {
+        type Error = Error;
 
-    fn try_from(request: pb::Request) -> Result<Self, Self::Error> {
-        use pb::request::Value;
-        match request.value {
-            Some(Value::Echo(x)) => Ok(Request::Echo(x.try_into()?)),
-            Some(Value::Flush(pb::RequestFlush {})) => Ok(Request::Flush),
-            Some(Value::Info(x)) => Ok(Request::Info(x.try_into()?)),
-            Some(Value::InitChain(x)) => Ok(Request::InitChain(x.try_into()?)),
-            Some(Value::Query(x)) => Ok(Request::Query(x.try_into()?)),
-            Some(Value::BeginBlock(x)) => Ok(Request::BeginBlock(x.try_into()?)),
-            Some(Value::CheckTx(x)) => Ok(Request::CheckTx(x.try_into()?)),
-            Some(Value::DeliverTx(x)) => Ok(Request::DeliverTx(x.try_into()?)),
-            Some(Value::EndBlock(x)) => Ok(Request::EndBlock(x.try_into()?)),
-            Some(Value::Commit(pb::RequestCommit {})) => Ok(Request::Commit),
-            Some(Value::ListSnapshots(pb::RequestListSnapshots {})) => Ok(Request::ListSnapshots),
-            Some(Value::OfferSnapshot(x)) => Ok(Request::OfferSnapshot(x.try_into()?)),
-            Some(Value::LoadSnapshotChunk(x)) => Ok(Request::LoadSnapshotChunk(x.try_into()?)),
-            Some(Value::ApplySnapshotChunk(x)) => Ok(Request::ApplySnapshotChunk(x.try_into()?)),
-            None => Err(crate::Error::missing_data()),
+        fn try_from(request: pb::Request) -> Result<Self, Self::Error> {
+            use pb::request::Value;
+            match request.value {
+                Some(Value::Echo(x)) => Ok(Request::Echo(x.try_into()?)),
+                Some(Value::Flush(pb::RequestFlush {})) => Ok(Request::Flush),
+                Some(Value::Info(x)) => Ok(Request::Info(x.try_into()?)),
+                Some(Value::InitChain(x)) => Ok(Request::InitChain(x.try_into()?)),
+                Some(Value::Query(x)) => Ok(Request::Query(x.try_into()?)),
+                Some(Value::BeginBlock(x)) => Ok(Request::BeginBlock(x.try_into()?)),
+                Some(Value::CheckTx(x)) => Ok(Request::CheckTx(x.try_into()?)),
+                Some(Value::DeliverTx(x)) => Ok(Request::DeliverTx(x.try_into()?)),
+                Some(Value::EndBlock(x)) => Ok(Request::EndBlock(x.try_into()?)),
+                Some(Value::Commit(pb::RequestCommit {})) => Ok(Request::Commit),
+                Some(Value::ListSnapshots(pb::RequestListSnapshots {})) => {
+                    Ok(Request::ListSnapshots)
+                },
+                Some(Value::OfferSnapshot(x)) => Ok(Request::OfferSnapshot(x.try_into()?)),
+                Some(Value::LoadSnapshotChunk(x)) => Ok(Request::LoadSnapshotChunk(x.try_into()?)),
+                Some(Value::ApplySnapshotChunk(x)) => {
+                    Ok(Request::ApplySnapshotChunk(x.try_into()?))
+                },
+                Some(Value::PrepareProposal(x)) => Ok(Request::PrepareProposal(x.try_into()?)),
+                Some(Value::ProcessProposal(x)) => Ok(Request::ProcessProposal(x.try_into()?)),
+                None => Err(Error::missing_data()),
+            }
         }
     }
+
+    fn unsupported(method: &str) -> Error {
+        Error::unsupported_abci_method(method.to_string(), "0.37".to_string())
+    }
 }
 
-impl Protobuf<pb::Request> for Request {}
+// The v0.38 protocol replaces `BeginBlock`, `DeliverTx` and `EndBlock` with
+// `FinalizeBlock`.
+mod v0_38 {
+    use super::*;
+    use tendermint_proto::v0_38::abci as pb;
+
+    impl TryFrom<Request> for pb::Request {
+        type Error = Error;
+
+        fn try_from(request: Request) -> Result<Self, Self::Error> {
+            use pb::request::Value;
+            let value = match request {
+                Request::Echo(x) => Some(Value::Echo(x.into())),
+                Request::Flush => Some(Value::Flush(Default::default())),
+                Request::Info(x) => Some(Value::Info(x.into())),
+                Request::InitChain(x) => Some(Value::InitChain(x.into())),
+                Request::Query(x) => Some(Value::Query(x.into())),
+                Request::BeginBlock(_) => return Err(unsupported("BeginBlock")),
+                Request::CheckTx(x) => Some(Value::CheckTx(x.into())),
+                Request::DeliverTx(_) => return Err(unsupported("DeliverTx")),
+                Request::EndBlock(_) => return Err(unsupported("EndBlock")),
+                Request::Commit => Some(Value::Commit(Default::default())),
+                Request::ListSnapshots => Some(Value::ListSnapshots(Default::default())),
+                Request::OfferSnapshot(x) => Some(Value::OfferSnapshot(x.into())),
+                Request::LoadSnapshotChunk(x) => Some(Value::LoadSnapshotChunk(x.into())),
+                Request::ApplySnapshotChunk(x) => Some(Value::ApplySnapshotChunk(x.into())),
+                Request::PrepareProposal(x) => Some(Value::PrepareProposal(x.into())),
+                Request::ProcessProposal(x) => Some(Value::ProcessProposal(x.into())),
+                Request::ExtendVote(x) => Some(Value::ExtendVote(x.into())),
+                Request::VerifyVoteExtension(x) => Some(Value::VerifyVoteExtension(x.into())),
+                Request::FinalizeBlock(x) => Some(Value::FinalizeBlock(x.into())),
+            };
+            Ok(pb::Request { value })
+        }
+    }
+
+    impl TryFrom<pb::Request> for Request {
+        type Error = Error;
+
+        fn try_from(request: pb::Request) -> Result<Self, Self::Error> {
+            use pb::request::Value;
+            match request.value {
+                Some(Value::Echo(x)) => Ok(Request::Echo(x.try_into()?)),
+                Some(Value::Flush(pb::RequestFlush {})) => Ok(Request::Flush),
+                Some(Value::Info(x)) => Ok(Request::Info(x.try_into()?)),
+                Some(Value::InitChain(x)) => Ok(Request::InitChain(x.try_into()?)),
+                Some(Value::Query(x)) => Ok(Request::Query(x.try_into()?)),
+                Some(Value::CheckTx(x)) => Ok(Request::CheckTx(x.try_into()?)),
+                Some(Value::Commit(pb::RequestCommit {})) => Ok(Request::Commit),
+                Some(Value::ListSnapshots(pb::RequestListSnapshots {})) => {
+                    Ok(Request::ListSnapshots)
+                },
+                Some(Value::OfferSnapshot(x)) => Ok(Request::OfferSnapshot(x.try_into()?)),
+                Some(Value::LoadSnapshotChunk(x)) => Ok(Request::LoadSnapshotChunk(x.try_into()?)),
+                Some(Value::ApplySnapshotChunk(x)) => {
+                    Ok(Request::ApplySnapshotChunk(x.try_into()?))
+                },
+                Some(Value::PrepareProposal(x)) => Ok(Request::PrepareProposal(x.try_into()?)),
+                Some(Value::ProcessProposal(x)) => Ok(Request::ProcessProposal(x.try_into()?)),
+                Some(Value::ExtendVote(x)) => Ok(Request::ExtendVote(x.try_into()?)),
+                Some(Value::VerifyVoteExtension(x)) => {
+                    Ok(Request::VerifyVoteExtension(x.try_into()?))
+                },
+                Some(Value::FinalizeBlock(x)) => Ok(Request::FinalizeBlock(x.try_into()?)),
+                None => Err(Error::missing_data()),
+            }
+        }
+    }
+
+    fn unsupported(method: &str) -> Error {
+        Error::unsupported_abci_method(method.to_string(), "0.38".to_string())
+    }
+}
@@ -42,28 +42,30 @@ pub struct ApplySnapshotChunk {
 
 use core::convert::TryFrom;
 
-use tendermint_proto::{abci as pb, Protobuf};
+use tendermint_proto::Protobuf;
 
-impl From<ApplySnapshotChunk> for pb::RequestApplySnapshotChunk {
-    fn from(apply_snapshot_chunk: ApplySnapshotChunk) -> Self {
-        Self {
-            index: apply_snapshot_chunk.index,
-            chunk: apply_snapshot_chunk.chunk,
-            sender: apply_snapshot_chunk.sender,
+abci_pb_modules! {
+    impl From<ApplySnapshotChunk> for pb::RequestApplySnapshotChunk {
+        fn from(apply_snapshot_chunk: ApplySnapshotChunk) -> Self {
+            Self {
+                index: apply_snapshot_chunk.index,
+                chunk: apply_snapshot_chunk.chunk,
+                sender: apply_snapshot_chunk.sender,
+            }
         }
     }
-}
 
-impl TryFrom<pb::RequestApplySnapshotChunk> for ApplySnapshotChunk {
-    type Error = crate::Error;
+    impl TryFrom<pb::RequestApplySnapshotChunk> for ApplySnapshotChunk {
+        type Error = crate::Error;
 
-    fn try_from(apply_snapshot_chunk: pb::RequestApplySnapshotChunk) -> Result<Self, Self::Error> {
-        Ok(Self {
-            index: apply_snapshot_chunk.index,
-            chunk: apply_snapshot_chunk.chunk,
-            sender: apply_snapshot_chunk.sender,
-        })
+        fn try_from(apply_snapshot_chunk: pb::RequestApplySnapshotChunk) -> Result<Self, Self::Error> {
+            Ok(Self {
+                index: apply_snapshot_chunk.index,
+                chunk: apply_snapshot_chunk.chunk,
+                sender: apply_snapshot_chunk.sender,
+            })
+        }
     }
-}
 
-impl Protobuf<pb::RequestApplySnapshotChunk> for ApplySnapshotChunk {}
+    impl Protobuf<pb::RequestApplySnapshotChunk> for ApplySnapshotChunk {}
+}
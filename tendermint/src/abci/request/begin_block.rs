@@ -30,44 +30,46 @@ pub struct BeginBlock {
 
 use core::convert::{TryFrom, TryInto};
 
-use tendermint_proto::{abci as pb, Protobuf};
+use tendermint_proto::Protobuf;
 
-impl From<BeginBlock> for pb::RequestBeginBlock {
-    fn from(begin_block: BeginBlock) -> Self {
-        Self {
-            hash: begin_block.hash,
-            header: Some(begin_block.header.into()),
-            last_commit_info: Some(begin_block.last_commit_info.into()),
-            byzantine_validators: begin_block
-                .byzantine_validators
-                .into_iter()
-                .map(Into::into)
-                .collect(),
+abci_pb_modules!(v0_34, v0_35, v0_37 => {
+    impl From<BeginBlock> for pb::RequestBeginBlock {
+        fn from(begin_block: BeginBlock) -> Self {
+            Self {
+                hash: begin_block.hash,
+                header: Some(begin_block.header.into()),
+                last_commit_info: Some(begin_block.last_commit_info.into()),
+                byzantine_validators: begin_block
+                    .byzantine_validators
+                    .into_iter()
+                    .map(Into::into)
+                    .collect(),
+            }
         }
     }
-}
 
-impl TryFrom<pb::RequestBeginBlock> for BeginBlock {
-    type Error = Error;
+    impl TryFrom<pb::RequestBeginBlock> for BeginBlock {
+        type Error = Error;
 
-    fn try_from(begin_block: pb::RequestBeginBlock) -> Result<Self, Self::Error> {
-        Ok(Self {
-            hash: begin_block.hash,
-            header: begin_block
-                .header
-                .ok_or_else(Error::missing_header)?
-                .try_into()?,
-            last_commit_info: begin_block
-                .last_commit_info
-                .ok_or_else(Error::missing_last_commit_info)?
-                .try_into()?,
-            byzantine_validators: begin_block
-                .byzantine_validators
-                .into_iter()
-                .map(TryInto::try_into)
-                .collect::<Result<_, _>>()?,
-        })
+        fn try_from(begin_block: pb::RequestBeginBlock) -> Result<Self, Self::Error> {
+            Ok(Self {
+                hash: begin_block.hash,
+                header: begin_block
+                    .header
+                    .ok_or_else(Error::missing_header)?
+                    .try_into()?,
+                last_commit_info: begin_block
+                    .last_commit_info
+                    .ok_or_else(Error::missing_last_commit_info)?
+                    .try_into()?,
+                byzantine_validators: begin_block
+                    .byzantine_validators
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+            })
+        }
     }
-}
 
-impl Protobuf<pb::RequestBeginBlock> for BeginBlock {}
+    impl Protobuf<pb::RequestBeginBlock> for BeginBlock {}
+});
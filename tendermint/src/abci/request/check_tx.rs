@@ -41,31 +41,33 @@ impl Default for CheckTxKind {
 
 use core::convert::TryFrom;
 
-use tendermint_proto::{abci as pb, Protobuf};
+use tendermint_proto::Protobuf;
 
-impl From<CheckTx> for pb::RequestCheckTx {
-    fn from(check_tx: CheckTx) -> Self {
-        Self {
-            tx: check_tx.tx,
-            r#type: check_tx.kind as i32,
+abci_pb_modules! {
+    impl From<CheckTx> for pb::RequestCheckTx {
+        fn from(check_tx: CheckTx) -> Self {
+            Self {
+                tx: check_tx.tx,
+                r#type: check_tx.kind as i32,
+            }
         }
     }
-}
 
-impl TryFrom<pb::RequestCheckTx> for CheckTx {
-    type Error = crate::Error;
+    impl TryFrom<pb::RequestCheckTx> for CheckTx {
+        type Error = crate::Error;
 
-    fn try_from(check_tx: pb::RequestCheckTx) -> Result<Self, Self::Error> {
-        let kind = match check_tx.r#type {
-            0 => CheckTxKind::New,
-            1 => CheckTxKind::Recheck,
-            _ => return Err(crate::Error::unsupported_check_tx_type()),
-        };
-        Ok(Self {
-            tx: check_tx.tx,
-            kind,
-        })
+        fn try_from(check_tx: pb::RequestCheckTx) -> Result<Self, Self::Error> {
+            let kind = match check_tx.r#type {
+                0 => CheckTxKind::New,
+                1 => CheckTxKind::Recheck,
+                _ => return Err(crate::Error::unsupported_check_tx_type()),
+            };
+            Ok(Self {
+                tx: check_tx.tx,
+                kind,
+            })
+        }
     }
-}
 
-impl Protobuf<pb::RequestCheckTx> for CheckTx {}
+    impl Protobuf<pb::RequestCheckTx> for CheckTx {}
+}
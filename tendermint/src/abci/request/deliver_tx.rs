@@ -15,20 +15,22 @@ pub struct DeliverTx {
 
 use core::convert::TryFrom;
 
-use tendermint_proto::{abci as pb, Protobuf};
+use tendermint_proto::Protobuf;
 
-impl From<DeliverTx> for pb::RequestDeliverTx {
-    fn from(deliver_tx: DeliverTx) -> Self {
-        Self { tx: deliver_tx.tx }
+abci_pb_modules!(v0_34, v0_35, v0_37 => {
+    impl From<DeliverTx> for pb::RequestDeliverTx {
+        fn from(deliver_tx: DeliverTx) -> Self {
+            Self { tx: deliver_tx.tx }
+        }
     }
-}
 
-impl TryFrom<pb::RequestDeliverTx> for DeliverTx {
-    type Error = crate::Error;
+    impl TryFrom<pb::RequestDeliverTx> for DeliverTx {
+        type Error = crate::Error;
 
-    fn try_from(deliver_tx: pb::RequestDeliverTx) -> Result<Self, Self::Error> {
-        Ok(Self { tx: deliver_tx.tx })
+        fn try_from(deliver_tx: pb::RequestDeliverTx) -> Result<Self, Self::Error> {
+            Ok(Self { tx: deliver_tx.tx })
+        }
     }
-}
 
-impl Protobuf<pb::RequestDeliverTx> for DeliverTx {}
+    impl Protobuf<pb::RequestDeliverTx> for DeliverTx {}
+});
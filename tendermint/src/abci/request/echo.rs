@@ -13,24 +13,26 @@ pub struct Echo {
 
 use core::convert::TryFrom;
 
-use tendermint_proto::{abci as pb, Protobuf};
-
-impl From<Echo> for pb::RequestEcho {
-    fn from(echo: Echo) -> Self {
-        Self {
-            message: echo.message,
+use tendermint_proto::Protobuf;
+
+abci_pb_modules! {
+    impl From<Echo> for pb::RequestEcho {
+        fn from(echo: Echo) -> Self {
+            Self {
+                message: echo.message,
+            }
         }
     }
-}
 
-impl TryFrom<pb::RequestEcho> for Echo {
-    type Error = crate::Error;
+    impl TryFrom<pb::RequestEcho> for Echo {
+        type Error = crate::Error;
 
-    fn try_from(echo: pb::RequestEcho) -> Result<Self, Self::Error> {
-        Ok(Self {
-            message: echo.message,
-        })
+        fn try_from(echo: pb::RequestEcho) -> Result<Self, Self::Error> {
+            Ok(Self {
+                message: echo.message,
+            })
+        }
     }
-}
 
-impl Protobuf<pb::RequestEcho> for Echo {}
+    impl Protobuf<pb::RequestEcho> for Echo {}
+}
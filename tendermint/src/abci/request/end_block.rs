@@ -13,24 +13,26 @@ pub struct EndBlock {
 
 use core::convert::TryFrom;
 
-use tendermint_proto::{abci as pb, Protobuf};
-
-impl From<EndBlock> for pb::RequestEndBlock {
-    fn from(end_block: EndBlock) -> Self {
-        Self {
-            height: end_block.height,
+use tendermint_proto::Protobuf;
+
+abci_pb_modules!(v0_34, v0_35, v0_37 => {
+    impl From<EndBlock> for pb::RequestEndBlock {
+        fn from(end_block: EndBlock) -> Self {
+            Self {
+                height: end_block.height,
+            }
         }
     }
-}
 
-impl TryFrom<pb::RequestEndBlock> for EndBlock {
-    type Error = crate::Error;
+    impl TryFrom<pb::RequestEndBlock> for EndBlock {
+        type Error = crate::Error;
 
-    fn try_from(end_block: pb::RequestEndBlock) -> Result<Self, Self::Error> {
-        Ok(Self {
-            height: end_block.height,
-        })
+        fn try_from(end_block: pb::RequestEndBlock) -> Result<Self, Self::Error> {
+            Ok(Self {
+                height: end_block.height,
+            })
+        }
     }
-}
 
-impl Protobuf<pb::RequestEndBlock> for EndBlock {}
+    impl Protobuf<pb::RequestEndBlock> for EndBlock {}
+});
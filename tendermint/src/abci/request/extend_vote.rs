@@ -0,0 +1,86 @@
+use bytes::Bytes;
+
+use super::super::types::{Evidence, LastCommitInfo};
+use crate::{account, block, prelude::*, Error, Hash, Time};
+
+#[doc = include_str!("../doc/request-extendvote.md")]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ExtendVote {
+    /// The hash of the block that the vote refers to.
+    pub hash: Hash,
+    /// The height of the block that the vote refers to.
+    pub height: block::Height,
+    /// The timestamp of the block that the vote refers to.
+    pub time: Time,
+    /// The transactions in the block that the vote refers to.
+    pub txs: Vec<Bytes>,
+    /// Information about the last commit, as decided by the proposer.
+    pub proposed_last_commit: Option<LastCommitInfo>,
+    /// Evidence of validator misbehavior.
+    pub misbehavior: Vec<Evidence>,
+    /// The merkle root of the next validator set.
+    pub next_validators_hash: Hash,
+    /// The address of the validator that proposed the block.
+    pub proposer_address: account::Id,
+}
+
+// =============================================================================
+// Protobuf conversions
+// =============================================================================
+
+use core::convert::{TryFrom, TryInto};
+
+use tendermint_proto::Protobuf;
+
+mod v0_38 {
+    use super::*;
+    use tendermint_proto::v0_38::abci as pb;
+
+    impl From<ExtendVote> for pb::RequestExtendVote {
+        fn from(extend_vote: ExtendVote) -> Self {
+            Self {
+                hash: Vec::from(extend_vote.hash).into(),
+                height: extend_vote.height.into(),
+                time: Some(extend_vote.time.into()),
+                txs: extend_vote.txs,
+                proposed_last_commit: extend_vote.proposed_last_commit.map(Into::into),
+                misbehavior: extend_vote
+                    .misbehavior
+                    .into_iter()
+                    .map(Into::into)
+                    .collect(),
+                next_validators_hash: Vec::from(extend_vote.next_validators_hash).into(),
+                proposer_address: Vec::from(extend_vote.proposer_address).into(),
+            }
+        }
+    }
+
+    impl TryFrom<pb::RequestExtendVote> for ExtendVote {
+        type Error = Error;
+
+        fn try_from(extend_vote: pb::RequestExtendVote) -> Result<Self, Self::Error> {
+            Ok(Self {
+                hash: extend_vote.hash.to_vec().try_into()?,
+                height: extend_vote.height.try_into()?,
+                time: extend_vote
+                    .time
+                    .ok_or_else(Error::missing_timestamp)?
+                    .try_into()?,
+                txs: extend_vote.txs,
+                proposed_last_commit: extend_vote
+                    .proposed_last_commit
+                    .map(TryInto::try_into)
+                    .transpose()?,
+                misbehavior: extend_vote
+                    .misbehavior
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+                next_validators_hash: extend_vote.next_validators_hash.to_vec().try_into()?,
+                proposer_address: extend_vote.proposer_address.to_vec().try_into()?,
+            })
+        }
+    }
+
+    impl Protobuf<pb::RequestExtendVote> for ExtendVote {}
+}
@@ -0,0 +1,89 @@
+use bytes::Bytes;
+
+use super::super::types::{Evidence, LastCommitInfo};
+use crate::{account, block, prelude::*, Error, Hash, Time};
+
+#[doc = include_str!("../doc/request-finalizeblock.md")]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct FinalizeBlock {
+    /// The transactions in the decided block.
+    pub txs: Vec<Bytes>,
+    /// Information about the last commit.
+    ///
+    /// This includes the round, the list of validators, and which validators
+    /// signed the last block.
+    pub decided_last_commit: LastCommitInfo,
+    /// Evidence of validator misbehavior.
+    pub misbehavior: Vec<Evidence>,
+    /// The hash of the decided block.
+    pub hash: Hash,
+    /// The height of the decided block.
+    pub height: block::Height,
+    /// The timestamp of the decided block.
+    pub time: Time,
+    /// The merkle root of the next validator set.
+    pub next_validators_hash: Hash,
+    /// The address of the validator that proposed the block.
+    pub proposer_address: account::Id,
+}
+
+// =============================================================================
+// Protobuf conversions
+// =============================================================================
+
+use core::convert::{TryFrom, TryInto};
+
+use tendermint_proto::Protobuf;
+
+mod v0_38 {
+    use super::*;
+    use tendermint_proto::v0_38::abci as pb;
+
+    impl From<FinalizeBlock> for pb::RequestFinalizeBlock {
+        fn from(finalize_block: FinalizeBlock) -> Self {
+            Self {
+                txs: finalize_block.txs,
+                decided_last_commit: Some(finalize_block.decided_last_commit.into()),
+                misbehavior: finalize_block
+                    .misbehavior
+                    .into_iter()
+                    .map(Into::into)
+                    .collect(),
+                hash: Vec::from(finalize_block.hash).into(),
+                height: finalize_block.height.into(),
+                time: Some(finalize_block.time.into()),
+                next_validators_hash: Vec::from(finalize_block.next_validators_hash).into(),
+                proposer_address: Vec::from(finalize_block.proposer_address).into(),
+            }
+        }
+    }
+
+    impl TryFrom<pb::RequestFinalizeBlock> for FinalizeBlock {
+        type Error = Error;
+
+        fn try_from(finalize_block: pb::RequestFinalizeBlock) -> Result<Self, Self::Error> {
+            Ok(Self {
+                txs: finalize_block.txs,
+                decided_last_commit: finalize_block
+                    .decided_last_commit
+                    .ok_or_else(Error::missing_last_commit_info)?
+                    .try_into()?,
+                misbehavior: finalize_block
+                    .misbehavior
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+                hash: finalize_block.hash.to_vec().try_into()?,
+                height: finalize_block.height.try_into()?,
+                time: finalize_block
+                    .time
+                    .ok_or_else(Error::missing_timestamp)?
+                    .try_into()?,
+                next_validators_hash: finalize_block.next_validators_hash.to_vec().try_into()?,
+                proposer_address: finalize_block.proposer_address.to_vec().try_into()?,
+            })
+        }
+    }
+
+    impl Protobuf<pb::RequestFinalizeBlock> for FinalizeBlock {}
+}
@@ -19,30 +19,63 @@ pub struct Info {
 
 use core::convert::TryFrom;
 
-use tendermint_proto::{abci as pb, Protobuf};
-
-impl From<Info> for pb::RequestInfo {
-    fn from(info: Info) -> Self {
-        Self {
-            version: info.version,
-            block_version: info.block_version,
-            p2p_version: info.p2p_version,
-            abci_version: info.abci_version,
+use tendermint_proto::Protobuf;
+
+mod v0_34 {
+    use super::*;
+    use tendermint_proto::v0_34::abci as pb;
+
+    // The v0.34 protocol does not report the ABCI version.
+    impl From<Info> for pb::RequestInfo {
+        fn from(info: Info) -> Self {
+            Self {
+                version: info.version,
+                block_version: info.block_version,
+                p2p_version: info.p2p_version,
+            }
         }
     }
-}
 
-impl TryFrom<pb::RequestInfo> for Info {
-    type Error = crate::Error;
+    impl TryFrom<pb::RequestInfo> for Info {
+        type Error = crate::Error;
 
-    fn try_from(info: pb::RequestInfo) -> Result<Self, Self::Error> {
-        Ok(Self {
-            version: info.version,
-            block_version: info.block_version,
-            p2p_version: info.p2p_version,
-            abci_version: info.abci_version,
-        })
+        fn try_from(info: pb::RequestInfo) -> Result<Self, Self::Error> {
+            Ok(Self {
+                version: info.version,
+                block_version: info.block_version,
+                p2p_version: info.p2p_version,
+                abci_version: String::new(),
+            })
+        }
     }
+
+    impl Protobuf<pb::RequestInfo> for Info {}
 }
 
-impl Protobuf<pb::RequestInfo> for Info {}
+abci_pb_modules!(v0_35, v0_37, v0_38 => {
+    impl From<Info> for pb::RequestInfo {
+        fn from(info: Info) -> Self {
+            Self {
+                version: info.version,
+                block_version: info.block_version,
+                p2p_version: info.p2p_version,
+                abci_version: info.abci_version,
+            }
+        }
+    }
+
+    impl TryFrom<pb::RequestInfo> for Info {
+        type Error = crate::Error;
+
+        fn try_from(info: pb::RequestInfo) -> Result<Self, Self::Error> {
+            Ok(Self {
+                version: info.version,
+                block_version: info.block_version,
+                p2p_version: info.p2p_version,
+                abci_version: info.abci_version,
+            })
+        }
+    }
+
+    impl Protobuf<pb::RequestInfo> for Info {}
+});
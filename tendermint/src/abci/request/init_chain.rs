@@ -28,46 +28,48 @@ pub struct InitChain {
 
 use core::convert::{TryFrom, TryInto};
 
-use tendermint_proto::{abci as pb, Protobuf};
+use tendermint_proto::Protobuf;
 
 use crate::Error;
 
-impl From<InitChain> for pb::RequestInitChain {
-    fn from(init_chain: InitChain) -> Self {
-        Self {
-            time: Some(init_chain.time.into()),
-            chain_id: init_chain.chain_id,
-            consensus_params: Some(init_chain.consensus_params.into()),
-            validators: init_chain.validators.into_iter().map(Into::into).collect(),
-            app_state_bytes: init_chain.app_state_bytes,
-            initial_height: init_chain.initial_height.into(),
+abci_pb_modules! {
+    impl From<InitChain> for pb::RequestInitChain {
+        fn from(init_chain: InitChain) -> Self {
+            Self {
+                time: Some(init_chain.time.into()),
+                chain_id: init_chain.chain_id,
+                consensus_params: Some(init_chain.consensus_params.into()),
+                validators: init_chain.validators.into_iter().map(Into::into).collect(),
+                app_state_bytes: init_chain.app_state_bytes,
+                initial_height: init_chain.initial_height.into(),
+            }
         }
     }
-}
 
-impl TryFrom<pb::RequestInitChain> for InitChain {
-    type Error = Error;
+    impl TryFrom<pb::RequestInitChain> for InitChain {
+        type Error = Error;
 
-    fn try_from(init_chain: pb::RequestInitChain) -> Result<Self, Self::Error> {
-        Ok(Self {
-            time: init_chain
-                .time
-                .ok_or_else(Error::missing_genesis_time)?
-                .try_into()?,
-            chain_id: init_chain.chain_id,
-            consensus_params: init_chain
-                .consensus_params
-                .ok_or_else(Error::missing_consensus_params)?
-                .try_into()?,
-            validators: init_chain
-                .validators
-                .into_iter()
-                .map(TryInto::try_into)
-                .collect::<Result<_, _>>()?,
-            app_state_bytes: init_chain.app_state_bytes,
-            initial_height: init_chain.initial_height.try_into()?,
-        })
+        fn try_from(init_chain: pb::RequestInitChain) -> Result<Self, Self::Error> {
+            Ok(Self {
+                time: init_chain
+                    .time
+                    .ok_or_else(Error::missing_genesis_time)?
+                    .try_into()?,
+                chain_id: init_chain.chain_id,
+                consensus_params: init_chain
+                    .consensus_params
+                    .ok_or_else(Error::missing_consensus_params)?
+                    .try_into()?,
+                validators: init_chain
+                    .validators
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+                app_state_bytes: init_chain.app_state_bytes,
+                initial_height: init_chain.initial_height.try_into()?,
+            })
+        }
     }
-}
 
-impl Protobuf<pb::RequestInitChain> for InitChain {}
+    impl Protobuf<pb::RequestInitChain> for InitChain {}
+}
@@ -17,28 +17,30 @@ pub struct LoadSnapshotChunk {
 
 use core::convert::TryFrom;
 
-use tendermint_proto::{abci as pb, Protobuf};
-
-impl From<LoadSnapshotChunk> for pb::RequestLoadSnapshotChunk {
-    fn from(load_snapshot_chunk: LoadSnapshotChunk) -> Self {
-        Self {
-            height: load_snapshot_chunk.height.into(),
-            format: load_snapshot_chunk.format,
-            chunk: load_snapshot_chunk.chunk,
+use tendermint_proto::Protobuf;
+
+abci_pb_modules! {
+    impl From<LoadSnapshotChunk> for pb::RequestLoadSnapshotChunk {
+        fn from(load_snapshot_chunk: LoadSnapshotChunk) -> Self {
+            Self {
+                height: load_snapshot_chunk.height.into(),
+                format: load_snapshot_chunk.format,
+                chunk: load_snapshot_chunk.chunk,
+            }
         }
     }
-}
 
-impl TryFrom<pb::RequestLoadSnapshotChunk> for LoadSnapshotChunk {
-    type Error = crate::Error;
+    impl TryFrom<pb::RequestLoadSnapshotChunk> for LoadSnapshotChunk {
+        type Error = crate::Error;
 
-    fn try_from(load_snapshot_chunk: pb::RequestLoadSnapshotChunk) -> Result<Self, Self::Error> {
-        Ok(Self {
-            height: load_snapshot_chunk.height.try_into()?,
-            format: load_snapshot_chunk.format,
-            chunk: load_snapshot_chunk.chunk,
-        })
+        fn try_from(load_snapshot_chunk: pb::RequestLoadSnapshotChunk) -> Result<Self, Self::Error> {
+            Ok(Self {
+                height: load_snapshot_chunk.height.try_into()?,
+                format: load_snapshot_chunk.format,
+                chunk: load_snapshot_chunk.chunk,
+            })
+        }
     }
-}
 
-impl Protobuf<pb::RequestLoadSnapshotChunk> for LoadSnapshotChunk {}
+    impl Protobuf<pb::RequestLoadSnapshotChunk> for LoadSnapshotChunk {}
+}
@@ -22,29 +22,31 @@ pub struct OfferSnapshot {
 
 use core::convert::{TryFrom, TryInto};
 
-use tendermint_proto::{abci as pb, Protobuf};
-
-impl From<OfferSnapshot> for pb::RequestOfferSnapshot {
-    fn from(offer_snapshot: OfferSnapshot) -> Self {
-        Self {
-            snapshot: Some(offer_snapshot.snapshot.into()),
-            app_hash: offer_snapshot.app_hash,
+use tendermint_proto::Protobuf;
+
+abci_pb_modules! {
+    impl From<OfferSnapshot> for pb::RequestOfferSnapshot {
+        fn from(offer_snapshot: OfferSnapshot) -> Self {
+            Self {
+                snapshot: Some(offer_snapshot.snapshot.into()),
+                app_hash: offer_snapshot.app_hash,
+            }
         }
     }
-}
 
-impl TryFrom<pb::RequestOfferSnapshot> for OfferSnapshot {
-    type Error = crate::Error;
-
-    fn try_from(offer_snapshot: pb::RequestOfferSnapshot) -> Result<Self, Self::Error> {
-        Ok(Self {
-            snapshot: offer_snapshot
-                .snapshot
-                .ok_or_else(crate::Error::missing_data)?
-                .try_into()?,
-            app_hash: offer_snapshot.app_hash,
-        })
+    impl TryFrom<pb::RequestOfferSnapshot> for OfferSnapshot {
+        type Error = crate::Error;
+
+        fn try_from(offer_snapshot: pb::RequestOfferSnapshot) -> Result<Self, Self::Error> {
+            Ok(Self {
+                snapshot: offer_snapshot
+                    .snapshot
+                    .ok_or_else(crate::Error::missing_data)?
+                    .try_into()?,
+                app_hash: offer_snapshot.app_hash,
+            })
+        }
     }
-}
 
-impl Protobuf<pb::RequestOfferSnapshot> for OfferSnapshot {}
+    impl Protobuf<pb::RequestOfferSnapshot> for OfferSnapshot {}
+}
@@ -0,0 +1,85 @@
+use bytes::Bytes;
+
+use super::super::types::{Evidence, ExtendedCommitInfo};
+use crate::{account, block, prelude::*, Error, Hash, Time};
+
+#[doc = include_str!("../doc/request-prepareproposal.md")]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PrepareProposal {
+    /// The maximum total size, in bytes, of the transactions that the
+    /// application may return.
+    pub max_tx_bytes: i64,
+    /// The transactions taken from the mempool for inclusion in the block.
+    pub txs: Vec<Bytes>,
+    /// Information about the last commit, as seen by this node, including vote
+    /// extensions.
+    pub local_last_commit: Option<ExtendedCommitInfo>,
+    /// Evidence of validator misbehavior.
+    pub misbehavior: Vec<Evidence>,
+    /// The height of the block being proposed.
+    pub height: block::Height,
+    /// The timestamp of the block being proposed.
+    pub time: Time,
+    /// The merkle root of the next validator set.
+    pub next_validators_hash: Hash,
+    /// The address of the validator proposing the block.
+    pub proposer_address: account::Id,
+}
+
+// =============================================================================
+// Protobuf conversions
+// =============================================================================
+
+use core::convert::{TryFrom, TryInto};
+
+use tendermint_proto::Protobuf;
+
+abci_pb_modules!(v0_37, v0_38 => {
+    impl From<PrepareProposal> for pb::RequestPrepareProposal {
+        fn from(prepare_proposal: PrepareProposal) -> Self {
+            Self {
+                max_tx_bytes: prepare_proposal.max_tx_bytes,
+                txs: prepare_proposal.txs,
+                local_last_commit: prepare_proposal.local_last_commit.map(Into::into),
+                misbehavior: prepare_proposal
+                    .misbehavior
+                    .into_iter()
+                    .map(Into::into)
+                    .collect(),
+                height: prepare_proposal.height.into(),
+                time: Some(prepare_proposal.time.into()),
+                next_validators_hash: Vec::from(prepare_proposal.next_validators_hash).into(),
+                proposer_address: Vec::from(prepare_proposal.proposer_address).into(),
+            }
+        }
+    }
+
+    impl TryFrom<pb::RequestPrepareProposal> for PrepareProposal {
+        type Error = Error;
+
+        fn try_from(prepare_proposal: pb::RequestPrepareProposal) -> Result<Self, Self::Error> {
+            Ok(Self {
+                max_tx_bytes: prepare_proposal.max_tx_bytes,
+                txs: prepare_proposal.txs,
+                local_last_commit: prepare_proposal
+                    .local_last_commit
+                    .map(TryInto::try_into)
+                    .transpose()?,
+                misbehavior: prepare_proposal
+                    .misbehavior
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+                height: prepare_proposal.height.try_into()?,
+                time: prepare_proposal
+                    .time
+                    .ok_or_else(Error::missing_timestamp)?
+                    .try_into()?,
+                next_validators_hash: prepare_proposal.next_validators_hash.to_vec().try_into()?,
+                proposer_address: prepare_proposal.proposer_address.to_vec().try_into()?,
+            })
+        }
+    }
+
+    impl Protobuf<pb::RequestPrepareProposal> for PrepareProposal {}
+});
@@ -0,0 +1,83 @@
+use bytes::Bytes;
+
+use super::super::types::{Evidence, LastCommitInfo};
+use crate::{account, block, prelude::*, Error, Hash, Time};
+
+#[doc = include_str!("../doc/request-processproposal.md")]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ProcessProposal {
+    /// The transactions in the proposed block.
+    pub txs: Vec<Bytes>,
+    /// Information about the last commit, as decided by the proposer.
+    pub proposed_last_commit: Option<LastCommitInfo>,
+    /// Evidence of validator misbehavior.
+    pub misbehavior: Vec<Evidence>,
+    /// The hash of the proposed block.
+    pub hash: Hash,
+    /// The height of the proposed block.
+    pub height: block::Height,
+    /// The timestamp of the proposed block.
+    pub time: Time,
+    /// The merkle root of the next validator set.
+    pub next_validators_hash: Hash,
+    /// The address of the validator that proposed the block.
+    pub proposer_address: account::Id,
+}
+
+// =============================================================================
+// Protobuf conversions
+// =============================================================================
+
+use core::convert::{TryFrom, TryInto};
+
+use tendermint_proto::Protobuf;
+
+abci_pb_modules!(v0_37, v0_38 => {
+    impl From<ProcessProposal> for pb::RequestProcessProposal {
+        fn from(process_proposal: ProcessProposal) -> Self {
+            Self {
+                txs: process_proposal.txs,
+                proposed_last_commit: process_proposal.proposed_last_commit.map(Into::into),
+                misbehavior: process_proposal
+                    .misbehavior
+                    .into_iter()
+                    .map(Into::into)
+                    .collect(),
+                hash: Vec::from(process_proposal.hash).into(),
+                height: process_proposal.height.into(),
+                time: Some(process_proposal.time.into()),
+                next_validators_hash: Vec::from(process_proposal.next_validators_hash).into(),
+                proposer_address: Vec::from(process_proposal.proposer_address).into(),
+            }
+        }
+    }
+
+    impl TryFrom<pb::RequestProcessProposal> for ProcessProposal {
+        type Error = Error;
+
+        fn try_from(process_proposal: pb::RequestProcessProposal) -> Result<Self, Self::Error> {
+            Ok(Self {
+                txs: process_proposal.txs,
+                proposed_last_commit: process_proposal
+                    .proposed_last_commit
+                    .map(TryInto::try_into)
+                    .transpose()?,
+                misbehavior: process_proposal
+                    .misbehavior
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+                hash: process_proposal.hash.to_vec().try_into()?,
+                height: process_proposal.height.try_into()?,
+                time: process_proposal
+                    .time
+                    .ok_or_else(Error::missing_timestamp)?
+                    .try_into()?,
+                next_validators_hash: process_proposal.next_validators_hash.to_vec().try_into()?,
+                proposer_address: process_proposal.proposer_address.to_vec().try_into()?,
+            })
+        }
+    }
+
+    impl Protobuf<pb::RequestProcessProposal> for ProcessProposal {}
+});
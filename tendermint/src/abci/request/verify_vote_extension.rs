@@ -0,0 +1,60 @@
+use bytes::Bytes;
+
+use crate::{account, block, prelude::*, Error, Hash};
+
+#[doc = include_str!("../doc/request-verifyvoteextension.md")]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct VerifyVoteExtension {
+    /// The hash of the block that the vote refers to.
+    pub hash: Hash,
+    /// The address of the validator that signed the vote extension.
+    pub validator_address: account::Id,
+    /// The height of the block that the vote refers to.
+    pub height: block::Height,
+    /// The vote extension to verify.
+    pub vote_extension: Bytes,
+}
+
+// =============================================================================
+// Protobuf conversions
+// =============================================================================
+
+use core::convert::{TryFrom, TryInto};
+
+use tendermint_proto::Protobuf;
+
+mod v0_38 {
+    use super::*;
+    use tendermint_proto::v0_38::abci as pb;
+
+    impl From<VerifyVoteExtension> for pb::RequestVerifyVoteExtension {
+        fn from(verify_vote_extension: VerifyVoteExtension) -> Self {
+            Self {
+                hash: Vec::from(verify_vote_extension.hash).into(),
+                validator_address: Vec::from(verify_vote_extension.validator_address).into(),
+                height: verify_vote_extension.height.into(),
+                vote_extension: verify_vote_extension.vote_extension,
+            }
+        }
+    }
+
+    impl TryFrom<pb::RequestVerifyVoteExtension> for VerifyVoteExtension {
+        type Error = Error;
+
+        fn try_from(
+            verify_vote_extension: pb::RequestVerifyVoteExtension,
+        ) -> Result<Self, Self::Error> {
+            Ok(Self {
+                hash: verify_vote_extension.hash.to_vec().try_into()?,
+                validator_address: verify_vote_extension
+                    .validator_address
+                    .to_vec()
+                    .try_into()?,
+                height: verify_vote_extension.height.try_into()?,
+                vote_extension: verify_vote_extension.vote_extension,
+            })
+        }
+    }
+
+    impl Protobuf<pb::RequestVerifyVoteExtension> for VerifyVoteExtension {}
+}
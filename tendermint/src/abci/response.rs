@@ -33,12 +33,17 @@ mod deliver_tx;
 mod echo;
 mod end_block;
 mod exception;
+mod extend_vote;
+mod finalize_block;
 mod info;
 mod init_chain;
 mod list_snapshots;
 mod load_snapshot_chunk;
 mod offer_snapshot;
+mod prepare_proposal;
+mod process_proposal;
 mod query;
+mod verify_vote_extension;
 
 pub use apply_snapshot_chunk::{ApplySnapshotChunk, ApplySnapshotChunkResult};
 pub use begin_block::BeginBlock;
@@ -48,12 +53,17 @@ pub use deliver_tx::DeliverTx;
 pub use echo::Echo;
 pub use end_block::EndBlock;
 pub use exception::Exception;
+pub use extend_vote::ExtendVote;
+pub use finalize_block::FinalizeBlock;
 pub use info::Info;
 pub use init_chain::InitChain;
 pub use list_snapshots::ListSnapshots;
 pub use load_snapshot_chunk::LoadSnapshotChunk;
 pub use offer_snapshot::OfferSnapshot;
+pub use prepare_proposal::PrepareProposal;
+pub use process_proposal::ProcessProposal;
 pub use query::Query;
+pub use verify_vote_extension::VerifyVoteExtension;
 
 /// All possible ABCI responses.
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -88,6 +98,16 @@ pub enum Response {
     LoadSnapshotChunk(LoadSnapshotChunk),
     #[doc = include_str!("doc/response-applysnapshotchunk.md")]
     ApplySnapshotChunk(ApplySnapshotChunk),
+    #[doc = include_str!("doc/response-prepareproposal.md")]
+    PrepareProposal(PrepareProposal),
+    #[doc = include_str!("doc/response-processproposal.md")]
+    ProcessProposal(ProcessProposal),
+    #[doc = include_str!("doc/response-extendvote.md")]
+    ExtendVote(ExtendVote),
+    #[doc = include_str!("doc/response-verifyvoteextension.md")]
+    VerifyVoteExtension(VerifyVoteExtension),
+    #[doc = include_str!("doc/response-finalizeblock.md")]
+    FinalizeBlock(FinalizeBlock),
 }
 
 /// The consensus category of ABCI responses.
@@ -103,6 +123,16 @@ pub enum ConsensusResponse {
     EndBlock(EndBlock),
     #[doc = include_str!("doc/response-commit.md")]
     Commit(Commit),
+    #[doc = include_str!("doc/response-prepareproposal.md")]
+    PrepareProposal(PrepareProposal),
+    #[doc = include_str!("doc/response-processproposal.md")]
+    ProcessProposal(ProcessProposal),
+    #[doc = include_str!("doc/response-extendvote.md")]
+    ExtendVote(ExtendVote),
+    #[doc = include_str!("doc/response-verifyvoteextension.md")]
+    VerifyVoteExtension(VerifyVoteExtension),
+    #[doc = include_str!("doc/response-finalizeblock.md")]
+    FinalizeBlock(FinalizeBlock),
 }
 
 impl From<ConsensusResponse> for Response {
@@ -113,6 +143,11 @@ impl From<ConsensusResponse> for Response {
             ConsensusResponse::DeliverTx(x) => Self::DeliverTx(x),
             ConsensusResponse::EndBlock(x) => Self::EndBlock(x),
             ConsensusResponse::Commit(x) => Self::Commit(x),
+            ConsensusResponse::PrepareProposal(x) => Self::PrepareProposal(x),
+            ConsensusResponse::ProcessProposal(x) => Self::ProcessProposal(x),
+            ConsensusResponse::ExtendVote(x) => Self::ExtendVote(x),
+            ConsensusResponse::VerifyVoteExtension(x) => Self::VerifyVoteExtension(x),
+            ConsensusResponse::FinalizeBlock(x) => Self::FinalizeBlock(x),
         }
     }
 }
@@ -126,6 +161,11 @@ impl TryFrom<Response> for ConsensusResponse {
             Response::DeliverTx(x) => Ok(Self::DeliverTx(x)),
             Response::EndBlock(x) => Ok(Self::EndBlock(x)),
             Response::Commit(x) => Ok(Self::Commit(x)),
+            Response::PrepareProposal(x) => Ok(Self::PrepareProposal(x)),
+            Response::ProcessProposal(x) => Ok(Self::ProcessProposal(x)),
+            Response::ExtendVote(x) => Ok(Self::ExtendVote(x)),
+            Response::VerifyVoteExtension(x) => Ok(Self::VerifyVoteExtension(x)),
+            Response::FinalizeBlock(x) => Ok(Self::FinalizeBlock(x)),
             _ => Err(Error::invalid_abci_response_type()),
         }
     }
@@ -230,56 +270,288 @@ impl TryFrom<Response> for SnapshotResponse {
 // Protobuf conversions
 // =============================================================================
 
-use tendermint_proto::{abci as pb, Protobuf};
-
-impl From<Response> for pb::Response {
-    fn from(response: Response) -> pb::Response {
-        use pb::response::Value;
-        let value = match response {
-            Response::Exception(x) => Some(Value::Exception(x.into())),
-            Response::Echo(x) => Some(Value::Echo(x.into())),
-            Response::Flush => Some(Value::Flush(Default::default())),
-            Response::Info(x) => Some(Value::Info(x.into())),
-            Response::InitChain(x) => Some(Value::InitChain(x.into())),
-            Response::Query(x) => Some(Value::Query(x.into())),
-            Response::BeginBlock(x) => Some(Value::BeginBlock(x.into())),
-            Response::CheckTx(x) => Some(Value::CheckTx(x.into())),
-            Response::DeliverTx(x) => Some(Value::DeliverTx(x.into())),
-            Response::EndBlock(x) => Some(Value::EndBlock(x.into())),
-            Response::Commit(x) => Some(Value::Commit(x.into())),
-            Response::ListSnapshots(x) => Some(Value::ListSnapshots(x.into())),
-            Response::OfferSnapshot(x) => Some(Value::OfferSnapshot(x.into())),
-            Response::LoadSnapshotChunk(x) => Some(Value::LoadSnapshotChunk(x.into())),
-            Response::ApplySnapshotChunk(x) => Some(Value::ApplySnapshotChunk(x.into())),
-        };
-        pb::Response { value }
+// ABCI++ methods are not part of the v0.34 protocol, and `SetOption` is not
+// supported by the domain types.
+mod v0_34 {
+    use super::*;
+    use tendermint_proto::v0_34::abci as pb;
+
+    impl TryFrom<Response> for pb::Response {
+        type Error = Error;
+
+        fn try_from(response: Response) -> Result<Self, Self::Error> {
+            use pb::response::Value;
+            let value = match response {
+                Response::Exception(x) => Some(Value::Exception(x.into())),
+                Response::Echo(x) => Some(Value::Echo(x.into())),
+                Response::Flush => Some(Value::Flush(Default::default())),
+                Response::Info(x) => Some(Value::Info(x.into())),
+                Response::InitChain(x) => Some(Value::InitChain(x.into())),
+                Response::Query(x) => Some(Value::Query(x.into())),
+                Response::BeginBlock(x) => Some(Value::BeginBlock(x.into())),
+                Response::CheckTx(x) => Some(Value::CheckTx(x.into())),
+                Response::DeliverTx(x) => Some(Value::DeliverTx(x.into())),
+                Response::EndBlock(x) => Some(Value::EndBlock(x.into())),
+                Response::Commit(x) => Some(Value::Commit(x.into())),
+                Response::ListSnapshots(x) => Some(Value::ListSnapshots(x.into())),
+                Response::OfferSnapshot(x) => Some(Value::OfferSnapshot(x.into())),
+                Response::LoadSnapshotChunk(x) => Some(Value::LoadSnapshotChunk(x.into())),
+                Response::ApplySnapshotChunk(x) => Some(Value::ApplySnapshotChunk(x.into())),
+                Response::PrepareProposal(_) => return Err(unsupported("PrepareProposal")),
+                Response::ProcessProposal(_) => return Err(unsupported("ProcessProposal")),
+                Response::ExtendVote(_) => return Err(unsupported("ExtendVote")),
+                Response::VerifyVoteExtension(_) => return Err(unsupported("VerifyVoteExtension")),
+                Response::FinalizeBlock(_) => return Err(unsupported("FinalizeBlock")),
+            };
+            Ok(pb::Response { value })
+        }
+    }
+
+    impl TryFrom<pb::Response> for Response {
+        type Error = Error;
+
+        fn try_from(response: pb::Response) -> Result<Self, Self::Error> {
+            use pb::response::Value;
+            match response.value {
+                Some(Value::Exception(x)) => Ok(Response::Exception(x.try_into()?)),
+                Some(Value::Echo(x)) => Ok(Response::Echo(x.try_into()?)),
+                Some(Value::Flush(_)) => Ok(Response::Flush),
+                Some(Value::Info(x)) => Ok(Response::Info(x.try_into()?)),
+                Some(Value::InitChain(x)) => Ok(Response::InitChain(x.try_into()?)),
+                Some(Value::Query(x)) => Ok(Response::Query(x.try_into()?)),
+                Some(Value::BeginBlock(x)) => Ok(Response::BeginBlock(x.try_into()?)),
+                Some(Value::CheckTx(x)) => Ok(Response::CheckTx(x.try_into()?)),
+                Some(Value::DeliverTx(x)) => Ok(Response::DeliverTx(x.try_into()?)),
+                Some(Value::EndBlock(x)) => Ok(Response::EndBlock(x.try_into()?)),
+                Some(Value::Commit(x)) => Ok(Response::Commit(x.try_into()?)),
+                Some(Value::ListSnapshots(x)) => Ok(Response::ListSnapshots(x.try_into()?)),
+                Some(Value::OfferSnapshot(x)) => Ok(Response::OfferSnapshot(x.try_into()?)),
+                Some(Value::LoadSnapshotChunk(x)) => Ok(Response::LoadSnapshotChunk(x.try_into()?)),
+                Some(Value::ApplySnapshotChunk(x)) => {
+                    Ok(Response::ApplySnapshotChunk(x.try_into()?))
+                },
+                Some(Value::SetOption(_)) => Err(unsupported("SetOption")),
+                None => Err(Error::missing_data()),
+            }
+        }
+    }
+
+    fn unsupported(method: &str) -> Error {
+        Error::unsupported_abci_method(method.to_string(), "0.34".to_string())
     }
 }
 
-impl TryFrom<pb::Response> for Response {
-    type Error = Error;
+// ABCI++ methods are not part of the v0.35 protocol.
+mod v0_35 {
+    use super::*;
+    use tendermint_proto::v0_35::abci as pb;
+
+    impl TryFrom<Response> for pb::Response {
+        type Error = Error;
+
+        fn try_from(response: Response) -> Result<Self, Self::Error> {
+            use pb::response::Value;
+            let value = match response {
+                Response::Exception(x) => Some(Value::Exception(x.into())),
+                Response::Echo(x) => Some(Value::Echo(x.into())),
+                Response::Flush => Some(Value::Flush(Default::default())),
+                Response::Info(x) => Some(Value::Info(x.into())),
+                Response::InitChain(x) => Some(Value::InitChain(x.into())),
+                Response::Query(x) => Some(Value::Query(x.into())),
+                Response::BeginBlock(x) => Some(Value::BeginBlock(x.into())),
+                Response::CheckTx(x) => Some(Value::CheckTx(x.into())),
+                Response::DeliverTx(x) => Some(Value::DeliverTx(x.into())),
+                Response::EndBlock(x) => Some(Value::EndBlock(x.into())),
+                Response::Commit(x) => Some(Value::Commit(x.into())),
+                Response::ListSnapshots(x) => Some(Value::ListSnapshots(x.into())),
+                Response::OfferSnapshot(x) => Some(Value::OfferSnapshot(x.into())),
+                Response::LoadSnapshotChunk(x) => Some(Value::LoadSnapshotChunk(x.into())),
+                Response::ApplySnapshotChunk(x) => Some(Value::ApplySnapshotChunk(x.into())),
+                Response::PrepareProposal(_) => return Err(unsupported("PrepareProposal")),
+                Response::ProcessProposal(_) => return Err(unsupported("ProcessProposal")),
+                Response::ExtendVote(_) => return Err(unsupported("ExtendVote")),
+                Response::VerifyVoteExtension(_) => return Err(unsupported("VerifyVoteExtension")),
+                Response::FinalizeBlock(_) => return Err(unsupported("FinalizeBlock")),
+            };
+            Ok(pb::Response { value })
+        }
+    }
+
+    impl TryFrom<pb::Response> for Response {
+        type Error = Error;
+
+        fn try_from(response: pb::Response) -> Result<Self, Self::Error> {
+            use pb::response::Value;
+            match response.value {
+                Some(Value::Exception(x)) => Ok(Response::Exception(x.try_into()?)),
+                Some(Value::Echo(x)) => Ok(Response::Echo(x.try_into()?)),
+                Some(Value::Flush(_)) => Ok(Response::Flush),
+                Some(Value::Info(x)) => Ok(Response::Info(x.try_into()?)),
+                Some(Value::InitChain(x)) => Ok(Response::InitChain(x.try_into()?)),
+                Some(Value::Query(x)) => Ok(Response::Query(x.try_into()?)),
+                Some(Value::BeginBlock(x)) => Ok(Response::BeginBlock(x.try_into()?)),
+                Some(Value::CheckTx(x)) => Ok(Response::CheckTx(x.try_into()?)),
+                Some(Value::DeliverTx(x)) => Ok(Response::DeliverTx(x.try_into()?)),
+                Some(Value::EndBlock(x)) => Ok(Response::EndBlock(x.try_into()?)),
+                Some(Value::Commit(x)) => Ok(Response::Commit(x.try_into()?)),
+                Some(Value::ListSnapshots(x)) => Ok(Response::ListSnapshots(x.try_into()?)),
+                Some(Value::OfferSnapshot(x)) => Ok(Response::OfferSnapshot(x.try_into()?)),
+                Some(Value::LoadSnapshotChunk(x)) => Ok(Response::LoadSnapshotChunk(x.try_into()?)),
+                Some(Value::ApplySnapshotChunk(x)) => {
+                    Ok(Response::ApplySnapshotChunk(x.try_into()?))
+                },
+                None => Err(Error::missing_data()),
+            }
+        }
+    }
+
+    fn unsupported(method: &str) -> Error {
+        Error::unsupported_abci_method(method.to_string(), "0.35".to_string())
+    }
+}
+
+// Of the ABCI++ methods, the v0.37 protocol only has `PrepareProposal` and
+// `ProcessProposal`.
+mod v0_37 {
+    use super::*;
+    use tendermint_proto::v0_37::abci as pb;
+
+    impl TryFrom<Response> for pb::Response {
+        type Error = Error;
+
+        fn try_from(response: Response) -> Result<Self, Self::Error> {
+            use pb::response::Value;
+            let value = match response {
+                Response::Exception(x) => Some(Value::Exception(x.into())),
+                Response::Echo(x) => Some(Value::Echo(x.into())),
+                Response::Flush => Some(Value::Flush(Default::default())),
+                Response::Info(x) => Some(Value::Info(x.into())),
+                Response::InitChain(x) => Some(Value::InitChain(x.into())),
+                Response::Query(x) => Some(Value::Query(x.into())),
+                Response::BeginBlock(x) => Some(Value::BeginBlock(x.into())),
+                Response::CheckTx(x) => Some(Value::CheckTx(x.into())),
+                Response::DeliverTx(x) => Some(Value::DeliverTx(x.into())),
+                Response::EndBlock(x) => Some(Value::EndBlock(x.into())),
+                Response::Commit(x) => Some(Value::Commit(x.into())),
+                Response::ListSnapshots(x) => Some(Value::ListSnapshots(x.into())),
+                Response::OfferSnapshot(x) => Some(Value::OfferSnapshot(x.into())),
+                Response::LoadSnapshotChunk(x) => Some(Value::LoadSnapshotChunk(x.into())),
+                Response::ApplySnapshotChunk(x) => Some(Value::ApplySnapshotChunk(x.into())),
+                Response::PrepareProposal(x) => Some(Value::PrepareProposal(x.into())),
+                Response::ProcessProposal(x) => Some(Value::ProcessProposal(x.into())),
+                Response::ExtendVote(_) => return Err(unsupported("ExtendVote")),
+                Response::VerifyVoteExtension(_) => return Err(unsupported("VerifyVoteExtension")),
+                Response::FinalizeBlock(_) => return Err(unsupported("FinalizeBlock")),
+            };
+            Ok(pb::Response { value })
+        }
+    }
+
+    impl TryFrom<pb::Response> for Response {
+        type Error = Error;
 
-    fn try_from(response: pb::Response) -> Result<Self, Self::Error> {
-        use pb::response::Value;
-        match response.value {
-            Some(Value::Exception(x)) => Ok(Response::Exception(x.try_into()?)),
-            Some(Value::Echo(x)) => Ok(Response::Echo(x.try_into()?)),
-            Some(Value::Flush(_)) => Ok(Response::Flush),
-            Some(Value::Info(x)) => Ok(Response::Info(x.try_into()?)),
-            Some(Value::InitChain(x)) => Ok(Response::InitChain(x.try_into()?)),
-            Some(Value::Query(x)) => Ok(Response::Query(x.try_into()?)),
-            Some(Value::BeginBlock(x)) => Ok(Response::BeginBlock(x.try_into()?)),
-            Some(Value::CheckTx(x)) => Ok(Response::CheckTx(x.try_into()?)),
-            Some(Value::DeliverTx(x)) => Ok(Response::DeliverTx(x.try_into()?)),
-            Some(Value::EndBlock(x)) => Ok(Response::EndBlock(x.try_into()?)),
-            Some(Value::Commit(x)) => Ok(Response::Commit(x.try_into()?)),
-            Some(Value::ListSnapshots(x)) => Ok(Response::ListSnapshots(x.try_into()?)),
-            Some(Value::OfferSnapshot(x)) => Ok(Response::OfferSnapshot(x.try_into()?)),
-            Some(Value::LoadSnapshotChunk(x)) => Ok(Response::LoadSnapshotChunk(x.try_into()?)),
-            Some(Value::ApplySnapshotChunk(x)) => Ok(Response::ApplySnapshotChunk(x.try_into()?)),
-            None => Err(crate::Error::missing_data()),
+        fn try_from(response: pb::Response) -> Result<Self, Self::Error> {
+            use pb::response::Value;
+            match response.value {
+                Some(Value::Exception(x)) => Ok(Response::Exception(x.try_into()?)),
+                Some(Value::Echo(x)) => Ok(Response::Echo(x.try_into()?)),
+                Some(Value::Flush(_)) => Ok(Response::Flush),
+                Some(Value::Info(x)) => Ok(Response::Info(x.try_into()?)),
+                Some(Value::InitChain(x)) => Ok(Response::InitChain(x.try_into()?)),
+                Some(Value::Query(x)) => Ok(Response::Query(x.try_into()?)),
+                Some(Value::BeginBlock(x)) => Ok(Response::BeginBlock(x.try_into()?)),
+                Some(Value::CheckTx(x)) => Ok(Response::CheckTx(x.try_into()?)),
+                Some(Value::DeliverTx(x)) => Ok(Response::DeliverTx(x.try_into()?)),
+                Some(Value::EndBlock(x)) => Ok(Response::EndBlock(x.try_into()?)),
+                Some(Value::Commit(x)) => Ok(Response::Commit(x.try_into()?)),
+                Some(Value::ListSnapshots(x)) => Ok(Response::ListSnapshots(x.try_into()?)),
+                Some(Value::OfferSnapshot(x)) => Ok(Response::OfferSnapshot(x.try_into()?)),
+                Some(Value::LoadSnapshotChunk(x)) => Ok(Response::LoadSnapshotChunk(x.try_into()?)),
+                Some(Value::ApplySnapshotChunk(x)) => {
+                    Ok(Response::ApplySnapshotChunk(x.try_into()?))
+                },
+                Some(Value::PrepareProposal(x)) => Ok(Response::PrepareProposal(x.try_into()?)),
+                Some(Value::ProcessProposal(x)) => Ok(Response::ProcessProposal(x.try_into()?)),
+                None => Err(Error::missing_data()),
+            }
         }
     }
+
+    fn unsupported(method: &str) -> Error {
+        Error::unsupported_abci_method(method.to_string(), "0.37".to_string())
+    }
 }
 
-impl Protobuf<pb::Response> for Response {}
+// The v0.38 protocol replaces `BeginBlock`, `DeliverTx` and `EndBlock` with
+// `FinalizeBlock`.
+mod v0_38 {
+    use super::*;
+    use tendermint_proto::v0_38::abci as pb;
+
+    impl TryFrom<Response> for pb::Response {
+        type Error = Error;
+
+        fn try_from(response: Response) -> Result<Self, Self::Error> {
+            use pb::response::Value;
+            let value = match response {
+                Response::Exception(x) => Some(Value::Exception(x.into())),
+                Response::Echo(x) => Some(Value::Echo(x.into())),
+                Response::Flush => Some(Value::Flush(Default::default())),
+                Response::Info(x) => Some(Value::Info(x.into())),
+                Response::InitChain(x) => Some(Value::InitChain(x.into())),
+                Response::Query(x) => Some(Value::Query(x.into())),
+                Response::BeginBlock(_) => return Err(unsupported("BeginBlock")),
+                Response::CheckTx(x) => Some(Value::CheckTx(x.into())),
+                Response::DeliverTx(_) => return Err(unsupported("DeliverTx")),
+                Response::EndBlock(_) => return Err(unsupported("EndBlock")),
+                Response::Commit(x) => Some(Value::Commit(x.into())),
+                Response::ListSnapshots(x) => Some(Value::ListSnapshots(x.into())),
+                Response::OfferSnapshot(x) => Some(Value::OfferSnapshot(x.into())),
+                Response::LoadSnapshotChunk(x) => Some(Value::LoadSnapshotChunk(x.into())),
+                Response::ApplySnapshotChunk(x) => Some(Value::ApplySnapshotChunk(x.into())),
+                Response::PrepareProposal(x) => Some(Value::PrepareProposal(x.into())),
+                Response::ProcessProposal(x) => Some(Value::ProcessProposal(x.into())),
+                Response::ExtendVote(x) => Some(Value::ExtendVote(x.into())),
+                Response::VerifyVoteExtension(x) => Some(Value::VerifyVoteExtension(x.into())),
+                Response::FinalizeBlock(x) => Some(Value::FinalizeBlock(x.into())),
+            };
+            Ok(pb::Response { value })
+        }
+    }
+
+    impl TryFrom<pb::Response> for Response {
+        type Error = Error;
+
+        fn try_from(response: pb::Response) -> Result<Self, Self::Error> {
+            use pb::response::Value;
+            match response.value {
+                Some(Value::Exception(x)) => Ok(Response::Exception(x.try_into()?)),
+                Some(Value::Echo(x)) => Ok(Response::Echo(x.try_into()?)),
+                Some(Value::Flush(_)) => Ok(Response::Flush),
+                Some(Value::Info(x)) => Ok(Response::Info(x.try_into()?)),
+                Some(Value::InitChain(x)) => Ok(Response::InitChain(x.try_into()?)),
+                Some(Value::Query(x)) => Ok(Response::Query(x.try_into()?)),
+                Some(Value::CheckTx(x)) => Ok(Response::CheckTx(x.try_into()?)),
+                Some(Value::Commit(x)) => Ok(Response::Commit(x.try_into()?)),
+                Some(Value::ListSnapshots(x)) => Ok(Response::ListSnapshots(x.try_into()?)),
+                Some(Value::OfferSnapshot(x)) => Ok(Response::OfferSnapshot(x.try_into()?)),
+                Some(Value::LoadSnapshotChunk(x)) => Ok(Response::LoadSnapshotChunk(x.try_into()?)),
+                Some(Value::ApplySnapshotChunk(x)) => {
+                    Ok(Response::ApplySnapshotChunk(x.try_into()?))
+                },
+                Some(Value::PrepareProposal(x)) => Ok(Response::PrepareProposal(x.try_into()?)),
+                Some(Value::ProcessProposal(x)) => Ok(Response::ProcessProposal(x.try_into()?)),
+                Some(Value::ExtendVote(x)) => Ok(Response::ExtendVote(x.try_into()?)),
+                Some(Value::VerifyVoteExtension(x)) => {
+                    Ok(Response::VerifyVoteExtension(x.try_into()?))
+                },
+                Some(Value::FinalizeBlock(x)) => Ok(Response::FinalizeBlock(x.try_into()?)),
+                None => Err(Error::missing_data()),
+            }
+        }
+    }
+
+    fn unsupported(method: &str) -> Error {
+        Error::unsupported_abci_method(method.to_string(), "0.38".to_string())
+    }
+}
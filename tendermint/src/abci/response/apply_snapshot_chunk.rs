@@ -52,37 +52,39 @@ impl Default for ApplySnapshotChunkResult {
 
 use core::convert::TryFrom;
 
-use tendermint_proto::{abci as pb, Protobuf};
+use tendermint_proto::Protobuf;
 
-impl From<ApplySnapshotChunk> for pb::ResponseApplySnapshotChunk {
-    fn from(apply_snapshot_chunk: ApplySnapshotChunk) -> Self {
-        Self {
-            result: apply_snapshot_chunk.result as i32,
-            refetch_chunks: apply_snapshot_chunk.refetch_chunks,
-            reject_senders: apply_snapshot_chunk.reject_senders,
+abci_pb_modules! {
+    impl From<ApplySnapshotChunk> for pb::ResponseApplySnapshotChunk {
+        fn from(apply_snapshot_chunk: ApplySnapshotChunk) -> Self {
+            Self {
+                result: apply_snapshot_chunk.result as i32,
+                refetch_chunks: apply_snapshot_chunk.refetch_chunks,
+                reject_senders: apply_snapshot_chunk.reject_senders,
+            }
         }
     }
-}
 
-impl TryFrom<pb::ResponseApplySnapshotChunk> for ApplySnapshotChunk {
-    type Error = crate::Error;
+    impl TryFrom<pb::ResponseApplySnapshotChunk> for ApplySnapshotChunk {
+        type Error = crate::Error;
 
-    fn try_from(apply_snapshot_chunk: pb::ResponseApplySnapshotChunk) -> Result<Self, Self::Error> {
-        let result = match apply_snapshot_chunk.result {
-            0 => ApplySnapshotChunkResult::Unknown,
-            1 => ApplySnapshotChunkResult::Accept,
-            2 => ApplySnapshotChunkResult::Abort,
-            3 => ApplySnapshotChunkResult::Retry,
-            4 => ApplySnapshotChunkResult::RetrySnapshot,
-            5 => ApplySnapshotChunkResult::RejectSnapshot,
-            _ => return Err(crate::Error::unsupported_apply_snapshot_chunk_result()),
-        };
-        Ok(Self {
-            result,
-            refetch_chunks: apply_snapshot_chunk.refetch_chunks,
-            reject_senders: apply_snapshot_chunk.reject_senders,
-        })
+        fn try_from(apply_snapshot_chunk: pb::ResponseApplySnapshotChunk) -> Result<Self, Self::Error> {
+            let result = match apply_snapshot_chunk.result {
+                0 => ApplySnapshotChunkResult::Unknown,
+                1 => ApplySnapshotChunkResult::Accept,
+                2 => ApplySnapshotChunkResult::Abort,
+                3 => ApplySnapshotChunkResult::Retry,
+                4 => ApplySnapshotChunkResult::RetrySnapshot,
+                5 => ApplySnapshotChunkResult::RejectSnapshot,
+                _ => return Err(crate::Error::unsupported_apply_snapshot_chunk_result()),
+            };
+            Ok(Self {
+                result,
+                refetch_chunks: apply_snapshot_chunk.refetch_chunks,
+                reject_senders: apply_snapshot_chunk.reject_senders,
+            })
+        }
     }
-}
 
-impl Protobuf<pb::ResponseApplySnapshotChunk> for ApplySnapshotChunk {}
+    impl Protobuf<pb::ResponseApplySnapshotChunk> for ApplySnapshotChunk {}
+}
@@ -14,28 +14,30 @@ pub struct BeginBlock {
 
 use core::convert::{TryFrom, TryInto};
 
-use tendermint_proto::{abci as pb, Protobuf};
-
-impl From<BeginBlock> for pb::ResponseBeginBlock {
-    fn from(begin_block: BeginBlock) -> Self {
-        Self {
-            events: begin_block.events.into_iter().map(Into::into).collect(),
+use tendermint_proto::Protobuf;
+
+abci_pb_modules!(v0_34, v0_35, v0_37 => {
+    impl From<BeginBlock> for pb::ResponseBeginBlock {
+        fn from(begin_block: BeginBlock) -> Self {
+            Self {
+                events: begin_block.events.into_iter().map(Into::into).collect(),
+            }
         }
     }
-}
 
-impl TryFrom<pb::ResponseBeginBlock> for BeginBlock {
-    type Error = crate::Error;
-
-    fn try_from(begin_block: pb::ResponseBeginBlock) -> Result<Self, Self::Error> {
-        Ok(Self {
-            events: begin_block
-                .events
-                .into_iter()
-                .map(TryInto::try_into)
-                .collect::<Result<_, _>>()?,
-        })
+    impl TryFrom<pb::ResponseBeginBlock> for BeginBlock {
+        type Error = crate::Error;
+
+        fn try_from(begin_block: pb::ResponseBeginBlock) -> Result<Self, Self::Error> {
+            Ok(Self {
+                events: begin_block
+                    .events
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+            })
+        }
     }
-}
 
-impl Protobuf<pb::ResponseBeginBlock> for BeginBlock {}
+    impl Protobuf<pb::ResponseBeginBlock> for BeginBlock {}
+});
@@ -45,47 +45,94 @@ pub struct CheckTx {
 
 use core::convert::{TryFrom, TryInto};
 
-use tendermint_proto::{abci as pb, Protobuf};
+use tendermint_proto::Protobuf;
 
-impl From<CheckTx> for pb::ResponseCheckTx {
-    fn from(check_tx: CheckTx) -> Self {
-        Self {
-            code: check_tx.code,
-            data: check_tx.data,
-            log: check_tx.log,
-            info: check_tx.info,
-            gas_wanted: check_tx.gas_wanted,
-            gas_used: check_tx.gas_used,
-            events: check_tx.events.into_iter().map(Into::into).collect(),
-            codespace: check_tx.codespace,
-            sender: check_tx.sender,
-            priority: check_tx.priority,
-            mempool_error: String::default(),
+abci_pb_modules!(v0_34, v0_35, v0_37 => {
+    impl From<CheckTx> for pb::ResponseCheckTx {
+        fn from(check_tx: CheckTx) -> Self {
+            Self {
+                code: check_tx.code,
+                data: check_tx.data,
+                log: check_tx.log,
+                info: check_tx.info,
+                gas_wanted: check_tx.gas_wanted,
+                gas_used: check_tx.gas_used,
+                events: check_tx.events.into_iter().map(Into::into).collect(),
+                codespace: check_tx.codespace,
+                sender: check_tx.sender,
+                priority: check_tx.priority,
+                mempool_error: String::default(),
+            }
         }
     }
-}
 
-impl TryFrom<pb::ResponseCheckTx> for CheckTx {
-    type Error = crate::Error;
+    impl TryFrom<pb::ResponseCheckTx> for CheckTx {
+        type Error = crate::Error;
 
-    fn try_from(check_tx: pb::ResponseCheckTx) -> Result<Self, Self::Error> {
-        Ok(Self {
-            code: check_tx.code,
-            data: check_tx.data,
-            log: check_tx.log,
-            info: check_tx.info,
-            gas_wanted: check_tx.gas_wanted,
-            gas_used: check_tx.gas_used,
-            events: check_tx
-                .events
-                .into_iter()
-                .map(TryInto::try_into)
-                .collect::<Result<_, _>>()?,
-            codespace: check_tx.codespace,
-            sender: check_tx.sender,
-            priority: check_tx.priority,
-        })
+        fn try_from(check_tx: pb::ResponseCheckTx) -> Result<Self, Self::Error> {
+            Ok(Self {
+                code: check_tx.code,
+                data: check_tx.data,
+                log: check_tx.log,
+                info: check_tx.info,
+                gas_wanted: check_tx.gas_wanted,
+                gas_used: check_tx.gas_used,
+                events: check_tx
+                    .events
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+                codespace: check_tx.codespace,
+                sender: check_tx.sender,
+                priority: check_tx.priority,
+            })
+        }
     }
-}
 
-impl Protobuf<pb::ResponseCheckTx> for CheckTx {}
+    impl Protobuf<pb::ResponseCheckTx> for CheckTx {}
+});
+
+// CometBFT v0.38 removed the priority mempool, along with the fields it used.
+mod v0_38 {
+    use super::*;
+    use tendermint_proto::v0_38::abci as pb;
+
+    impl From<CheckTx> for pb::ResponseCheckTx {
+        fn from(check_tx: CheckTx) -> Self {
+            Self {
+                code: check_tx.code,
+                data: check_tx.data,
+                log: check_tx.log,
+                info: check_tx.info,
+                gas_wanted: check_tx.gas_wanted,
+                gas_used: check_tx.gas_used,
+                events: check_tx.events.into_iter().map(Into::into).collect(),
+                codespace: check_tx.codespace,
+            }
+        }
+    }
+
+    impl TryFrom<pb::ResponseCheckTx> for CheckTx {
+        type Error = crate::Error;
+
+        fn try_from(check_tx: pb::ResponseCheckTx) -> Result<Self, Self::Error> {
+            Ok(Self {
+                code: check_tx.code,
+                data: check_tx.data,
+                log: check_tx.log,
+                info: check_tx.info,
+                gas_wanted: check_tx.gas_wanted,
+                gas_used: check_tx.gas_used,
+                events: check_tx
+                    .events
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+                codespace: check_tx.codespace,
+                ..Default::default()
+            })
+        }
+    }
+
+    impl Protobuf<pb::ResponseCheckTx> for CheckTx {}
+}
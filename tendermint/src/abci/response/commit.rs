@@ -20,26 +20,55 @@ pub struct Commit {
 
 use core::convert::TryFrom;
 
-use tendermint_proto::{abci as pb, Protobuf};
+use tendermint_proto::Protobuf;
 
-impl From<Commit> for pb::ResponseCommit {
-    fn from(commit: Commit) -> Self {
-        Self {
-            data: commit.data,
-            retain_height: commit.retain_height.into(),
+abci_pb_modules!(v0_34, v0_35, v0_37 => {
+    impl From<Commit> for pb::ResponseCommit {
+        fn from(commit: Commit) -> Self {
+            Self {
+                data: commit.data,
+                retain_height: commit.retain_height.into(),
+            }
         }
     }
-}
 
-impl TryFrom<pb::ResponseCommit> for Commit {
-    type Error = crate::Error;
+    impl TryFrom<pb::ResponseCommit> for Commit {
+        type Error = crate::Error;
 
-    fn try_from(commit: pb::ResponseCommit) -> Result<Self, Self::Error> {
-        Ok(Self {
-            data: commit.data,
-            retain_height: commit.retain_height.try_into()?,
-        })
+        fn try_from(commit: pb::ResponseCommit) -> Result<Self, Self::Error> {
+            Ok(Self {
+                data: commit.data,
+                retain_height: commit.retain_height.try_into()?,
+            })
+        }
     }
-}
 
-impl Protobuf<pb::ResponseCommit> for Commit {}
+    impl Protobuf<pb::ResponseCommit> for Commit {}
+});
+
+// CometBFT v0.38 returns the app hash from `FinalizeBlock` instead.
+mod v0_38 {
+    use super::*;
+    use tendermint_proto::v0_38::abci as pb;
+
+    impl From<Commit> for pb::ResponseCommit {
+        fn from(commit: Commit) -> Self {
+            Self {
+                retain_height: commit.retain_height.into(),
+            }
+        }
+    }
+
+    impl TryFrom<pb::ResponseCommit> for Commit {
+        type Error = crate::Error;
+
+        fn try_from(commit: pb::ResponseCommit) -> Result<Self, Self::Error> {
+            Ok(Self {
+                data: Bytes::new(),
+                retain_height: commit.retain_height.try_into()?,
+            })
+        }
+    }
+
+    impl Protobuf<pb::ResponseCommit> for Commit {}
+}
@@ -20,43 +20,45 @@ pub struct EndBlock {
 
 use core::convert::{TryFrom, TryInto};
 
-use tendermint_proto::{abci as pb, Protobuf};
-
-impl From<EndBlock> for pb::ResponseEndBlock {
-    fn from(end_block: EndBlock) -> Self {
-        Self {
-            validator_updates: end_block
-                .validator_updates
-                .into_iter()
-                .map(Into::into)
-                .collect(),
-            consensus_param_updates: end_block.consensus_param_updates.map(Into::into),
-            events: end_block.events.into_iter().map(Into::into).collect(),
+use tendermint_proto::Protobuf;
+
+abci_pb_modules!(v0_34, v0_35, v0_37 => {
+    impl From<EndBlock> for pb::ResponseEndBlock {
+        fn from(end_block: EndBlock) -> Self {
+            Self {
+                validator_updates: end_block
+                    .validator_updates
+                    .into_iter()
+                    .map(Into::into)
+                    .collect(),
+                consensus_param_updates: end_block.consensus_param_updates.map(Into::into),
+                events: end_block.events.into_iter().map(Into::into).collect(),
+            }
         }
     }
-}
 
-impl TryFrom<pb::ResponseEndBlock> for EndBlock {
-    type Error = crate::Error;
-
-    fn try_from(end_block: pb::ResponseEndBlock) -> Result<Self, Self::Error> {
-        Ok(Self {
-            validator_updates: end_block
-                .validator_updates
-                .into_iter()
-                .map(TryInto::try_into)
-                .collect::<Result<_, _>>()?,
-            consensus_param_updates: end_block
-                .consensus_param_updates
-                .map(TryInto::try_into)
-                .transpose()?,
-            events: end_block
-                .events
-                .into_iter()
-                .map(TryInto::try_into)
-                .collect::<Result<_, _>>()?,
-        })
+    impl TryFrom<pb::ResponseEndBlock> for EndBlock {
+        type Error = crate::Error;
+
+        fn try_from(end_block: pb::ResponseEndBlock) -> Result<Self, Self::Error> {
+            Ok(Self {
+                validator_updates: end_block
+                    .validator_updates
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+                consensus_param_updates: end_block
+                    .consensus_param_updates
+                    .map(TryInto::try_into)
+                    .transpose()?,
+                events: end_block
+                    .events
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+            })
+        }
     }
-}
 
-impl Protobuf<pb::ResponseEndBlock> for EndBlock {}
+    impl Protobuf<pb::ResponseEndBlock> for EndBlock {}
+});
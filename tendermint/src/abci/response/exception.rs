@@ -13,24 +13,26 @@ pub struct Exception {
 
 use core::convert::TryFrom;
 
-use tendermint_proto::{abci as pb, Protobuf};
-
-impl From<Exception> for pb::ResponseException {
-    fn from(exception: Exception) -> Self {
-        Self {
-            error: exception.error,
+use tendermint_proto::Protobuf;
+
+abci_pb_modules! {
+    impl From<Exception> for pb::ResponseException {
+        fn from(exception: Exception) -> Self {
+            Self {
+                error: exception.error,
+            }
         }
     }
-}
 
-impl TryFrom<pb::ResponseException> for Exception {
-    type Error = crate::Error;
+    impl TryFrom<pb::ResponseException> for Exception {
+        type Error = crate::Error;
 
-    fn try_from(exception: pb::ResponseException) -> Result<Self, Self::Error> {
-        Ok(Self {
-            error: exception.error,
-        })
+        fn try_from(exception: pb::ResponseException) -> Result<Self, Self::Error> {
+            Ok(Self {
+                error: exception.error,
+            })
+        }
     }
-}
 
-impl Protobuf<pb::ResponseException> for Exception {}
+    impl Protobuf<pb::ResponseException> for Exception {}
+}
@@ -0,0 +1,45 @@
+use bytes::Bytes;
+
+use crate::prelude::*;
+
+#[doc = include_str!("../doc/response-extendvote.md")]
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct ExtendVote {
+    /// The data to attach to the vote.
+    ///
+    /// **May be non-deterministic**.
+    pub vote_extension: Bytes,
+}
+
+// =============================================================================
+// Protobuf conversions
+// =============================================================================
+
+use core::convert::TryFrom;
+
+use tendermint_proto::Protobuf;
+
+mod v0_38 {
+    use super::*;
+    use tendermint_proto::v0_38::abci as pb;
+
+    impl From<ExtendVote> for pb::ResponseExtendVote {
+        fn from(extend_vote: ExtendVote) -> Self {
+            Self {
+                vote_extension: extend_vote.vote_extension,
+            }
+        }
+    }
+
+    impl TryFrom<pb::ResponseExtendVote> for ExtendVote {
+        type Error = crate::Error;
+
+        fn try_from(extend_vote: pb::ResponseExtendVote) -> Result<Self, Self::Error> {
+            Ok(Self {
+                vote_extension: extend_vote.vote_extension,
+            })
+        }
+    }
+
+    impl Protobuf<pb::ResponseExtendVote> for ExtendVote {}
+}
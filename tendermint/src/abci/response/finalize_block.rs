@@ -0,0 +1,90 @@
+use bytes::Bytes;
+
+use super::super::{
+    types::{ExecTxResult, ValidatorUpdate},
+    Event,
+};
+use crate::{consensus, prelude::*};
+
+#[doc = include_str!("../doc/response-finalizeblock.md")]
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct FinalizeBlock {
+    /// Events that occurred while executing the block, outside of any
+    /// transaction.
+    pub events: Vec<Event>,
+    /// The results of executing each of the block's transactions, in the same
+    /// order as the transactions.
+    pub tx_results: Vec<ExecTxResult>,
+    /// Changes to the validator set, if any.
+    ///
+    /// Setting the voting power to 0 removes a validator.
+    pub validator_updates: Vec<ValidatorUpdate>,
+    /// Changes to consensus parameters (optional).
+    pub consensus_param_updates: Option<consensus::Params>,
+    /// The application's state hash after executing the block.
+    pub app_hash: Bytes,
+}
+
+// =============================================================================
+// Protobuf conversions
+// =============================================================================
+
+use core::convert::{TryFrom, TryInto};
+
+use tendermint_proto::Protobuf;
+
+mod v0_38 {
+    use super::*;
+    use tendermint_proto::v0_38::abci as pb;
+
+    impl From<FinalizeBlock> for pb::ResponseFinalizeBlock {
+        fn from(finalize_block: FinalizeBlock) -> Self {
+            Self {
+                events: finalize_block.events.into_iter().map(Into::into).collect(),
+                tx_results: finalize_block
+                    .tx_results
+                    .into_iter()
+                    .map(Into::into)
+                    .collect(),
+                validator_updates: finalize_block
+                    .validator_updates
+                    .into_iter()
+                    .map(Into::into)
+                    .collect(),
+                consensus_param_updates: finalize_block.consensus_param_updates.map(Into::into),
+                app_hash: finalize_block.app_hash,
+            }
+        }
+    }
+
+    impl TryFrom<pb::ResponseFinalizeBlock> for FinalizeBlock {
+        type Error = crate::Error;
+
+        fn try_from(finalize_block: pb::ResponseFinalizeBlock) -> Result<Self, Self::Error> {
+            Ok(Self {
+                events: finalize_block
+                    .events
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+                tx_results: finalize_block
+                    .tx_results
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+                validator_updates: finalize_block
+                    .validator_updates
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+                consensus_param_updates: finalize_block
+                    .consensus_param_updates
+                    .map(TryInto::try_into)
+                    .transpose()?,
+                app_hash: finalize_block.app_hash,
+            })
+        }
+    }
+
+    impl Protobuf<pb::ResponseFinalizeBlock> for FinalizeBlock {}
+}
@@ -24,32 +24,34 @@ pub struct Info {
 
 use core::convert::TryFrom;
 
-use tendermint_proto::{abci as pb, Protobuf};
-
-impl From<Info> for pb::ResponseInfo {
-    fn from(info: Info) -> Self {
-        Self {
-            data: info.data,
-            version: info.version,
-            app_version: info.app_version,
-            last_block_height: info.last_block_height.into(),
-            last_block_app_hash: info.last_block_app_hash,
+use tendermint_proto::Protobuf;
+
+abci_pb_modules! {
+    impl From<Info> for pb::ResponseInfo {
+        fn from(info: Info) -> Self {
+            Self {
+                data: info.data,
+                version: info.version,
+                app_version: info.app_version,
+                last_block_height: info.last_block_height.into(),
+                last_block_app_hash: info.last_block_app_hash,
+            }
         }
     }
-}
 
-impl TryFrom<pb::ResponseInfo> for Info {
-    type Error = Error;
-
-    fn try_from(info: pb::ResponseInfo) -> Result<Self, Self::Error> {
-        Ok(Self {
-            data: info.data,
-            version: info.version,
-            app_version: info.app_version,
-            last_block_height: info.last_block_height.try_into()?,
-            last_block_app_hash: info.last_block_app_hash,
-        })
+    impl TryFrom<pb::ResponseInfo> for Info {
+        type Error = Error;
+
+        fn try_from(info: pb::ResponseInfo) -> Result<Self, Self::Error> {
+            Ok(Self {
+                data: info.data,
+                version: info.version,
+                app_version: info.app_version,
+                last_block_height: info.last_block_height.try_into()?,
+                last_block_app_hash: info.last_block_app_hash,
+            })
+        }
     }
-}
 
-impl Protobuf<pb::ResponseInfo> for Info {}
+    impl Protobuf<pb::ResponseInfo> for Info {}
+}
@@ -14,32 +14,34 @@ pub struct ListSnapshots {
 
 use core::convert::{TryFrom, TryInto};
 
-use tendermint_proto::{abci as pb, Protobuf};
-
-impl From<ListSnapshots> for pb::ResponseListSnapshots {
-    fn from(list_snapshots: ListSnapshots) -> Self {
-        Self {
-            snapshots: list_snapshots
-                .snapshots
-                .into_iter()
-                .map(Into::into)
-                .collect(),
+use tendermint_proto::Protobuf;
+
+abci_pb_modules! {
+    impl From<ListSnapshots> for pb::ResponseListSnapshots {
+        fn from(list_snapshots: ListSnapshots) -> Self {
+            Self {
+                snapshots: list_snapshots
+                    .snapshots
+                    .into_iter()
+                    .map(Into::into)
+                    .collect(),
+            }
         }
     }
-}
 
-impl TryFrom<pb::ResponseListSnapshots> for ListSnapshots {
-    type Error = crate::Error;
-
-    fn try_from(list_snapshots: pb::ResponseListSnapshots) -> Result<Self, Self::Error> {
-        Ok(Self {
-            snapshots: list_snapshots
-                .snapshots
-                .into_iter()
-                .map(TryInto::try_into)
-                .collect::<Result<_, _>>()?,
-        })
+    impl TryFrom<pb::ResponseListSnapshots> for ListSnapshots {
+        type Error = crate::Error;
+
+        fn try_from(list_snapshots: pb::ResponseListSnapshots) -> Result<Self, Self::Error> {
+            Ok(Self {
+                snapshots: list_snapshots
+                    .snapshots
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+            })
+        }
     }
-}
 
-impl Protobuf<pb::ResponseListSnapshots> for ListSnapshots {}
+    impl Protobuf<pb::ResponseListSnapshots> for ListSnapshots {}
+}
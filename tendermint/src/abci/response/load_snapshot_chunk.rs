@@ -18,24 +18,26 @@ pub struct LoadSnapshotChunk {
 
 use core::convert::TryFrom;
 
-use tendermint_proto::{abci as pb, Protobuf};
-
-impl From<LoadSnapshotChunk> for pb::ResponseLoadSnapshotChunk {
-    fn from(load_snapshot_chunk: LoadSnapshotChunk) -> Self {
-        Self {
-            chunk: load_snapshot_chunk.chunk,
+use tendermint_proto::Protobuf;
+
+abci_pb_modules! {
+    impl From<LoadSnapshotChunk> for pb::ResponseLoadSnapshotChunk {
+        fn from(load_snapshot_chunk: LoadSnapshotChunk) -> Self {
+            Self {
+                chunk: load_snapshot_chunk.chunk,
+            }
         }
     }
-}
 
-impl TryFrom<pb::ResponseLoadSnapshotChunk> for LoadSnapshotChunk {
-    type Error = crate::Error;
+    impl TryFrom<pb::ResponseLoadSnapshotChunk> for LoadSnapshotChunk {
+        type Error = crate::Error;
 
-    fn try_from(load_snapshot_chunk: pb::ResponseLoadSnapshotChunk) -> Result<Self, Self::Error> {
-        Ok(Self {
-            chunk: load_snapshot_chunk.chunk,
-        })
+        fn try_from(load_snapshot_chunk: pb::ResponseLoadSnapshotChunk) -> Result<Self, Self::Error> {
+            Ok(Self {
+                chunk: load_snapshot_chunk.chunk,
+            })
+        }
     }
-}
 
-impl Protobuf<pb::ResponseLoadSnapshotChunk> for LoadSnapshotChunk {}
+    impl Protobuf<pb::ResponseLoadSnapshotChunk> for LoadSnapshotChunk {}
+}
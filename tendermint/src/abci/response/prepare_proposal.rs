@@ -0,0 +1,40 @@
+use bytes::Bytes;
+
+use crate::prelude::*;
+
+#[doc = include_str!("../doc/response-prepareproposal.md")]
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct PrepareProposal {
+    /// The transactions to include in the block, in order.
+    pub txs: Vec<Bytes>,
+}
+
+// =============================================================================
+// Protobuf conversions
+// =============================================================================
+
+use core::convert::TryFrom;
+
+use tendermint_proto::Protobuf;
+
+abci_pb_modules!(v0_37, v0_38 => {
+    impl From<PrepareProposal> for pb::ResponsePrepareProposal {
+        fn from(prepare_proposal: PrepareProposal) -> Self {
+            Self {
+                txs: prepare_proposal.txs,
+            }
+        }
+    }
+
+    impl TryFrom<pb::ResponsePrepareProposal> for PrepareProposal {
+        type Error = crate::Error;
+
+        fn try_from(prepare_proposal: pb::ResponsePrepareProposal) -> Result<Self, Self::Error> {
+            Ok(Self {
+                txs: prepare_proposal.txs,
+            })
+        }
+    }
+
+    impl Protobuf<pb::ResponsePrepareProposal> for PrepareProposal {}
+});
@@ -0,0 +1,52 @@
+use crate::prelude::*;
+
+#[doc = include_str!("../doc/response-processproposal.md")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum ProcessProposal {
+    /// Unknown status, which makes Tendermint panic
+    Unknown = 0,
+    /// The proposal is valid, and may be voted for
+    Accept = 1,
+    /// The proposal is invalid, and must be rejected
+    Reject = 2,
+}
+
+impl Default for ProcessProposal {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+// =============================================================================
+// Protobuf conversions
+// =============================================================================
+
+use core::convert::TryFrom;
+
+use tendermint_proto::Protobuf;
+
+abci_pb_modules!(v0_37, v0_38 => {
+    impl From<ProcessProposal> for pb::ResponseProcessProposal {
+        fn from(process_proposal: ProcessProposal) -> Self {
+            Self {
+                status: process_proposal as i32,
+            }
+        }
+    }
+
+    impl TryFrom<pb::ResponseProcessProposal> for ProcessProposal {
+        type Error = crate::Error;
+
+        fn try_from(process_proposal: pb::ResponseProcessProposal) -> Result<Self, Self::Error> {
+            Ok(match process_proposal.status {
+                0 => ProcessProposal::Unknown,
+                1 => ProcessProposal::Accept,
+                2 => ProcessProposal::Reject,
+                _ => return Err(crate::Error::unsupported_process_proposal_status()),
+            })
+        }
+    }
+
+    impl Protobuf<pb::ResponseProcessProposal> for ProcessProposal {}
+});
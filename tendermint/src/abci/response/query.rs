@@ -25,7 +25,10 @@ pub struct Query {
     pub value: Bytes,
     /// Serialized proof for the value data, if requested, to be verified against
     /// the app hash for the given `height`.
-    pub proof: Option<merkle::Proof>,
+    ///
+    /// This used to be a [`merkle::Proof`], the former name of
+    /// [`merkle::ProofOps`].
+    pub proof: Option<merkle::ProofOps>,
     /// The block height from which data was derived.
     ///
     /// Note that this is the height of the block containing the application's
@@ -42,40 +45,42 @@ pub struct Query {
 
 use core::convert::{TryFrom, TryInto};
 
-use tendermint_proto::{abci as pb, Protobuf};
+use tendermint_proto::Protobuf;
 
-impl From<Query> for pb::ResponseQuery {
-    fn from(query: Query) -> Self {
-        Self {
-            code: query.code,
-            log: query.log,
-            info: query.info,
-            index: query.index,
-            key: query.key,
-            value: query.value,
-            proof_ops: query.proof.map(Into::into),
-            height: query.height.into(),
-            codespace: query.codespace,
+abci_pb_modules! {
+    impl From<Query> for pb::ResponseQuery {
+        fn from(query: Query) -> Self {
+            Self {
+                code: query.code,
+                log: query.log,
+                info: query.info,
+                index: query.index,
+                key: query.key,
+                value: query.value,
+                proof_ops: query.proof.map(Into::into),
+                height: query.height.into(),
+                codespace: query.codespace,
+            }
         }
     }
-}
 
-impl TryFrom<pb::ResponseQuery> for Query {
-    type Error = crate::Error;
+    impl TryFrom<pb::ResponseQuery> for Query {
+        type Error = crate::Error;
 
-    fn try_from(query: pb::ResponseQuery) -> Result<Self, Self::Error> {
-        Ok(Self {
-            code: query.code,
-            log: query.log,
-            info: query.info,
-            index: query.index,
-            key: query.key,
-            value: query.value,
-            proof: query.proof_ops.map(TryInto::try_into).transpose()?,
-            height: query.height.try_into()?,
-            codespace: query.codespace,
-        })
+        fn try_from(query: pb::ResponseQuery) -> Result<Self, Self::Error> {
+            Ok(Self {
+                code: query.code,
+                log: query.log,
+                info: query.info,
+                index: query.index,
+                key: query.key,
+                value: query.value,
+                proof: query.proof_ops.map(TryInto::try_into).transpose()?,
+                height: query.height.try_into()?,
+                codespace: query.codespace,
+            })
+        }
     }
-}
 
-impl Protobuf<pb::ResponseQuery> for Query {}
+    impl Protobuf<pb::ResponseQuery> for Query {}
+}
@@ -0,0 +1,57 @@
+use crate::prelude::*;
+
+#[doc = include_str!("../doc/response-verifyvoteextension.md")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum VerifyVoteExtension {
+    /// Unknown status, which makes Tendermint panic
+    Unknown = 0,
+    /// The vote extension is valid
+    Accept = 1,
+    /// The vote extension is invalid, which rejects the entire precommit
+    Reject = 2,
+}
+
+impl Default for VerifyVoteExtension {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
+
+// =============================================================================
+// Protobuf conversions
+// =============================================================================
+
+use core::convert::TryFrom;
+
+use tendermint_proto::Protobuf;
+
+mod v0_38 {
+    use super::*;
+    use tendermint_proto::v0_38::abci as pb;
+
+    impl From<VerifyVoteExtension> for pb::ResponseVerifyVoteExtension {
+        fn from(verify_vote_extension: VerifyVoteExtension) -> Self {
+            Self {
+                status: verify_vote_extension as i32,
+            }
+        }
+    }
+
+    impl TryFrom<pb::ResponseVerifyVoteExtension> for VerifyVoteExtension {
+        type Error = crate::Error;
+
+        fn try_from(
+            verify_vote_extension: pb::ResponseVerifyVoteExtension,
+        ) -> Result<Self, Self::Error> {
+            Ok(match verify_vote_extension.status {
+                0 => VerifyVoteExtension::Unknown,
+                1 => VerifyVoteExtension::Accept,
+                2 => VerifyVoteExtension::Reject,
+                _ => return Err(crate::Error::unsupported_verify_vote_extension_status()),
+            })
+        }
+    }
+
+    impl Protobuf<pb::ResponseVerifyVoteExtension> for VerifyVoteExtension {}
+}
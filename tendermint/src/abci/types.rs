@@ -9,6 +9,7 @@ use core::convert::{TryFrom, TryInto};
 
 use bytes::Bytes;
 
+use super::{response, Event};
 use crate::{block, prelude::*, vote, Error, PublicKey, Time};
 
 /// A validator address with voting power.
@@ -46,6 +47,24 @@ pub struct VoteInfo {
     pub signed_last_block: bool,
 }
 
+/// Information about a validator's vote on the last block, including the
+/// extension the validator attached to its vote.
+///
+/// [ABCI++ documentation](https://github.com/cometbft/cometbft/blob/v0.38.x/spec/abci/abci++_methods.md#extendedvoteinfo)
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ExtendedVoteInfo {
+    /// Identifies the validator.
+    pub validator: Validator,
+    /// Whether or not the validator signed the last block.
+    pub signed_last_block: bool,
+    /// The non-deterministic data provided by the validator's application.
+    pub vote_extension: Bytes,
+    /// The validator's signature of the vote extension.
+    ///
+    /// This is only sent by CometBFT v0.38, and is empty otherwise.
+    pub extension_signature: Bytes,
+}
+
 /// The possible kinds of [`Evidence`].
 ///
 /// Note: the
@@ -103,6 +122,66 @@ pub struct LastCommitInfo {
     pub votes: Vec<VoteInfo>,
 }
 
+/// Information on the last block commit, including vote extensions.
+///
+/// [ABCI++ documentation](https://github.com/cometbft/cometbft/blob/v0.38.x/spec/abci/abci++_methods.md#extendedcommitinfo)
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ExtendedCommitInfo {
+    /// The commit round.
+    pub round: block::Round,
+    /// The list of validator addresses in the last validator set, with their
+    /// voting power, whether or not they signed a vote, and their vote
+    /// extensions.
+    pub votes: Vec<ExtendedVoteInfo>,
+}
+
+/// The result of executing a single transaction as part of a
+/// `FinalizeBlock` request.
+///
+/// This has the same structure as a [`response::DeliverTx`].
+///
+/// [ABCI++ documentation](https://github.com/cometbft/cometbft/blob/v0.38.x/spec/abci/abci++_methods.md#exectxresult)
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct ExecTxResult {
+    /// The response code.
+    ///
+    /// This code should be `0` only if the transaction is fully valid.
+    pub code: u32,
+    /// Result bytes, if any.
+    pub data: Bytes,
+    /// The output of the application's logger.
+    ///
+    /// **May be non-deterministic**.
+    pub log: String,
+    /// Additional information.
+    ///
+    /// **May be non-deterministic**.
+    pub info: String,
+    /// Amount of gas requested for the transaction.
+    pub gas_wanted: i64,
+    /// Amount of gas consumed by the transaction.
+    pub gas_used: i64,
+    /// Events that occurred while executing the transaction.
+    pub events: Vec<Event>,
+    /// The namespace for the `code`.
+    pub codespace: String,
+}
+
+impl From<response::DeliverTx> for ExecTxResult {
+    fn from(deliver_tx: response::DeliverTx) -> Self {
+        Self {
+            code: deliver_tx.code,
+            data: deliver_tx.data,
+            log: deliver_tx.log,
+            info: deliver_tx.info,
+            gas_wanted: deliver_tx.gas_wanted,
+            gas_used: deliver_tx.gas_used,
+            events: deliver_tx.events,
+            codespace: deliver_tx.codespace,
+        }
+    }
+}
+
 /// Used for state sync snapshots.
 ///
 /// When sent across the network, a `Snapshot` can be at most 4 MB.
@@ -133,179 +212,431 @@ pub struct Snapshot {
 // Protobuf conversions
 // =============================================================================
 
-use tendermint_proto::{abci as pb, Protobuf};
+use tendermint_proto::Protobuf;
 
-impl From<Validator> for pb::Validator {
-    fn from(v: Validator) -> Self {
-        Self {
-            address: Bytes::copy_from_slice(&v.address[..]),
-            power: v.power.into(),
+abci_pb_modules! {
+    impl From<Validator> for pb::Validator {
+        fn from(v: Validator) -> Self {
+            Self {
+                address: Bytes::copy_from_slice(&v.address[..]),
+                power: v.power.into(),
+            }
         }
     }
-}
 
-impl TryFrom<pb::Validator> for Validator {
-    type Error = Error;
+    impl TryFrom<pb::Validator> for Validator {
+        type Error = Error;
+
+        fn try_from(vu: pb::Validator) -> Result<Self, Self::Error> {
+            let address = if vu.address.len() == 20 {
+                let mut bytes = [0u8; 20];
+                bytes.copy_from_slice(&vu.address);
+                bytes
+            } else {
+                return Err(Error::invalid_account_id_length());
+            };
+
+            Ok(Self {
+                address,
+                power: vu.power.try_into()?,
+            })
+        }
+    }
 
-    fn try_from(vu: pb::Validator) -> Result<Self, Self::Error> {
-        let address = if vu.address.len() == 20 {
-            let mut bytes = [0u8; 20];
-            bytes.copy_from_slice(&vu.address);
-            bytes
-        } else {
-            return Err(Error::invalid_account_id_length());
-        };
+    impl Protobuf<pb::Validator> for Validator {}
 
-        Ok(Self {
-            address,
-            power: vu.power.try_into()?,
-        })
+    impl From<ValidatorUpdate> for pb::ValidatorUpdate {
+        fn from(vu: ValidatorUpdate) -> Self {
+            Self {
+                pub_key: Some(vu.pub_key.into()),
+                power: vu.power.into(),
+            }
+        }
+    }
+
+    impl TryFrom<pb::ValidatorUpdate> for ValidatorUpdate {
+        type Error = Error;
+
+        fn try_from(vu: pb::ValidatorUpdate) -> Result<Self, Self::Error> {
+            Ok(Self {
+                pub_key: vu
+                    .pub_key
+                    .ok_or_else(Error::missing_public_key)?
+                    .try_into()?,
+                power: vu.power.try_into()?,
+            })
+        }
     }
-}
 
-impl Protobuf<pb::Validator> for Validator {}
+    impl Protobuf<pb::ValidatorUpdate> for ValidatorUpdate {}
 
-impl From<ValidatorUpdate> for pb::ValidatorUpdate {
-    fn from(vu: ValidatorUpdate) -> Self {
-        Self {
-            pub_key: Some(vu.pub_key.into()),
-            power: vu.power.into(),
+
+    impl From<Snapshot> for pb::Snapshot {
+        fn from(snapshot: Snapshot) -> Self {
+            Self {
+                height: snapshot.height.into(),
+                format: snapshot.format,
+                chunks: snapshot.chunks,
+                hash: snapshot.hash,
+                metadata: snapshot.metadata,
+            }
         }
     }
-}
 
-impl TryFrom<pb::ValidatorUpdate> for ValidatorUpdate {
-    type Error = Error;
-
-    fn try_from(vu: pb::ValidatorUpdate) -> Result<Self, Self::Error> {
-        Ok(Self {
-            pub_key: vu
-                .pub_key
-                .ok_or_else(Error::missing_public_key)?
-                .try_into()?,
-            power: vu.power.try_into()?,
-        })
+    impl TryFrom<pb::Snapshot> for Snapshot {
+        type Error = Error;
+
+        fn try_from(snapshot: pb::Snapshot) -> Result<Self, Self::Error> {
+            Ok(Self {
+                height: snapshot.height.try_into()?,
+                format: snapshot.format,
+                chunks: snapshot.chunks,
+                hash: snapshot.hash,
+                metadata: snapshot.metadata,
+            })
+        }
     }
+
+    impl Protobuf<pb::Snapshot> for Snapshot {}
 }
 
-impl Protobuf<pb::ValidatorUpdate> for ValidatorUpdate {}
+abci_pb_modules!(v0_34, v0_35, v0_37 => {
+    impl From<VoteInfo> for pb::VoteInfo {
+        fn from(vi: VoteInfo) -> Self {
+            Self {
+                validator: Some(vi.validator.into()),
+                signed_last_block: vi.signed_last_block,
+            }
+        }
+    }
 
-impl From<VoteInfo> for pb::VoteInfo {
-    fn from(vi: VoteInfo) -> Self {
-        Self {
-            validator: Some(vi.validator.into()),
-            signed_last_block: vi.signed_last_block,
+    impl TryFrom<pb::VoteInfo> for VoteInfo {
+        type Error = Error;
+
+        fn try_from(vi: pb::VoteInfo) -> Result<Self, Self::Error> {
+            Ok(Self {
+                validator: vi
+                    .validator
+                    .ok_or_else(Error::missing_validator)?
+                    .try_into()?,
+                signed_last_block: vi.signed_last_block,
+            })
         }
     }
-}
 
-impl TryFrom<pb::VoteInfo> for VoteInfo {
-    type Error = Error;
-
-    fn try_from(vi: pb::VoteInfo) -> Result<Self, Self::Error> {
-        Ok(Self {
-            validator: vi
-                .validator
-                .ok_or_else(Error::missing_validator)?
-                .try_into()?,
-            signed_last_block: vi.signed_last_block,
-        })
+    impl Protobuf<pb::VoteInfo> for VoteInfo {}
+});
+
+abci_pb_modules!(v0_34, v0_35 => {
+    impl From<Evidence> for pb::Evidence {
+        fn from(evidence: Evidence) -> Self {
+            Self {
+                r#type: evidence.kind as i32,
+                validator: Some(evidence.validator.into()),
+                height: evidence.height.into(),
+                time: Some(evidence.time.into()),
+                total_voting_power: evidence.total_voting_power.into(),
+            }
+        }
+    }
+
+    impl TryFrom<pb::Evidence> for Evidence {
+        type Error = Error;
+
+        fn try_from(evidence: pb::Evidence) -> Result<Self, Self::Error> {
+            let kind = match evidence.r#type {
+                0 => EvidenceKind::Unknown,
+                1 => EvidenceKind::DuplicateVote,
+                2 => EvidenceKind::LightClientAttack,
+                _ => return Err(Error::invalid_evidence()),
+            };
+
+            Ok(Self {
+                kind,
+                validator: evidence
+                    .validator
+                    .ok_or_else(Error::missing_validator)?
+                    .try_into()?,
+                height: evidence.height.try_into()?,
+                time: evidence
+                    .time
+                    .ok_or_else(Error::missing_timestamp)?
+                    .try_into()?,
+                total_voting_power: evidence.total_voting_power.try_into()?,
+            })
+        }
     }
-}
 
-impl Protobuf<pb::VoteInfo> for VoteInfo {}
+    impl Protobuf<pb::Evidence> for Evidence {}
 
-impl From<Evidence> for pb::Evidence {
-    fn from(evidence: Evidence) -> Self {
-        Self {
-            r#type: evidence.kind as i32,
-            validator: Some(evidence.validator.into()),
-            height: evidence.height.into(),
-            time: Some(evidence.time.into()),
-            total_voting_power: evidence.total_voting_power.into(),
+    impl From<LastCommitInfo> for pb::LastCommitInfo {
+        fn from(lci: LastCommitInfo) -> Self {
+            Self {
+                round: lci.round.into(),
+                votes: lci.votes.into_iter().map(Into::into).collect(),
+            }
         }
     }
-}
 
-impl TryFrom<pb::Evidence> for Evidence {
-    type Error = Error;
-
-    fn try_from(evidence: pb::Evidence) -> Result<Self, Self::Error> {
-        let kind = match evidence.r#type {
-            0 => EvidenceKind::Unknown,
-            1 => EvidenceKind::DuplicateVote,
-            2 => EvidenceKind::LightClientAttack,
-            _ => return Err(Error::invalid_evidence()),
-        };
-
-        Ok(Self {
-            kind,
-            validator: evidence
-                .validator
-                .ok_or_else(Error::missing_validator)?
-                .try_into()?,
-            height: evidence.height.try_into()?,
-            time: evidence
-                .time
-                .ok_or_else(Error::missing_timestamp)?
-                .try_into()?,
-            total_voting_power: evidence.total_voting_power.try_into()?,
-        })
+    impl TryFrom<pb::LastCommitInfo> for LastCommitInfo {
+        type Error = Error;
+
+        fn try_from(lci: pb::LastCommitInfo) -> Result<Self, Self::Error> {
+            Ok(Self {
+                round: lci.round.try_into()?,
+                votes: lci
+                    .votes
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+            })
+        }
     }
-}
 
-impl Protobuf<pb::Evidence> for Evidence {}
+    impl Protobuf<pb::LastCommitInfo> for LastCommitInfo {}
+});
+
+// CometBFT renamed `Evidence` to `Misbehavior`, and `LastCommitInfo` to
+// `CommitInfo`.
+abci_pb_modules!(v0_37, v0_38 => {
+    impl From<Evidence> for pb::Misbehavior {
+        fn from(evidence: Evidence) -> Self {
+            Self {
+                r#type: evidence.kind as i32,
+                validator: Some(evidence.validator.into()),
+                height: evidence.height.into(),
+                time: Some(evidence.time.into()),
+                total_voting_power: evidence.total_voting_power.into(),
+            }
+        }
+    }
 
-impl From<LastCommitInfo> for pb::LastCommitInfo {
-    fn from(lci: LastCommitInfo) -> Self {
-        Self {
-            round: lci.round.into(),
-            votes: lci.votes.into_iter().map(Into::into).collect(),
+    impl TryFrom<pb::Misbehavior> for Evidence {
+        type Error = Error;
+
+        fn try_from(evidence: pb::Misbehavior) -> Result<Self, Self::Error> {
+            let kind = match evidence.r#type {
+                0 => EvidenceKind::Unknown,
+                1 => EvidenceKind::DuplicateVote,
+                2 => EvidenceKind::LightClientAttack,
+                _ => return Err(Error::invalid_evidence()),
+            };
+
+            Ok(Self {
+                kind,
+                validator: evidence
+                    .validator
+                    .ok_or_else(Error::missing_validator)?
+                    .try_into()?,
+                height: evidence.height.try_into()?,
+                time: evidence
+                    .time
+                    .ok_or_else(Error::missing_timestamp)?
+                    .try_into()?,
+                total_voting_power: evidence.total_voting_power.try_into()?,
+            })
         }
     }
-}
 
-impl TryFrom<pb::LastCommitInfo> for LastCommitInfo {
-    type Error = Error;
-
-    fn try_from(lci: pb::LastCommitInfo) -> Result<Self, Self::Error> {
-        Ok(Self {
-            round: lci.round.try_into()?,
-            votes: lci
-                .votes
-                .into_iter()
-                .map(TryInto::try_into)
-                .collect::<Result<_, _>>()?,
-        })
+    impl Protobuf<pb::Misbehavior> for Evidence {}
+
+    impl From<LastCommitInfo> for pb::CommitInfo {
+        fn from(lci: LastCommitInfo) -> Self {
+            Self {
+                round: lci.round.into(),
+                votes: lci.votes.into_iter().map(Into::into).collect(),
+            }
+        }
     }
-}
 
-impl Protobuf<pb::LastCommitInfo> for LastCommitInfo {}
+    impl TryFrom<pb::CommitInfo> for LastCommitInfo {
+        type Error = Error;
+
+        fn try_from(lci: pb::CommitInfo) -> Result<Self, Self::Error> {
+            Ok(Self {
+                round: lci.round.try_into()?,
+                votes: lci
+                    .votes
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+            })
+        }
+    }
 
-impl From<Snapshot> for pb::Snapshot {
-    fn from(snapshot: Snapshot) -> Self {
-        Self {
-            height: snapshot.height.into(),
-            format: snapshot.format,
-            chunks: snapshot.chunks,
-            hash: snapshot.hash,
-            metadata: snapshot.metadata,
+    impl Protobuf<pb::CommitInfo> for LastCommitInfo {}
+
+    impl From<ExtendedCommitInfo> for pb::ExtendedCommitInfo {
+        fn from(eci: ExtendedCommitInfo) -> Self {
+            Self {
+                round: eci.round.into(),
+                votes: eci.votes.into_iter().map(Into::into).collect(),
+            }
+        }
+    }
+
+    impl TryFrom<pb::ExtendedCommitInfo> for ExtendedCommitInfo {
+        type Error = Error;
+
+        fn try_from(eci: pb::ExtendedCommitInfo) -> Result<Self, Self::Error> {
+            Ok(Self {
+                round: eci.round.try_into()?,
+                votes: eci
+                    .votes
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+            })
         }
     }
-}
 
-impl TryFrom<pb::Snapshot> for Snapshot {
-    type Error = Error;
-
-    fn try_from(snapshot: pb::Snapshot) -> Result<Self, Self::Error> {
-        Ok(Self {
-            height: snapshot.height.try_into()?,
-            format: snapshot.format,
-            chunks: snapshot.chunks,
-            hash: snapshot.hash,
-            metadata: snapshot.metadata,
-        })
+    impl Protobuf<pb::ExtendedCommitInfo> for ExtendedCommitInfo {}
+});
+
+mod v0_37 {
+    use super::*;
+    use tendermint_proto::v0_37::abci as pb;
+
+    impl From<ExtendedVoteInfo> for pb::ExtendedVoteInfo {
+        fn from(vi: ExtendedVoteInfo) -> Self {
+            Self {
+                validator: Some(vi.validator.into()),
+                signed_last_block: vi.signed_last_block,
+                vote_extension: vi.vote_extension,
+            }
+        }
     }
+
+    impl TryFrom<pb::ExtendedVoteInfo> for ExtendedVoteInfo {
+        type Error = Error;
+
+        fn try_from(vi: pb::ExtendedVoteInfo) -> Result<Self, Self::Error> {
+            Ok(Self {
+                validator: vi
+                    .validator
+                    .ok_or_else(Error::missing_validator)?
+                    .try_into()?,
+                signed_last_block: vi.signed_last_block,
+                vote_extension: vi.vote_extension,
+                extension_signature: Bytes::new(),
+            })
+        }
+    }
+
+    impl Protobuf<pb::ExtendedVoteInfo> for ExtendedVoteInfo {}
 }
 
-impl Protobuf<pb::Snapshot> for Snapshot {}
+mod v0_38 {
+    use super::*;
+    use tendermint_proto::v0_38::{abci as pb, types::BlockIdFlag};
+
+    // CometBFT v0.38 reports how each validator voted rather than whether it
+    // signed the last block. A vote for nil still counts as a signature.
+    fn block_id_flag(signed_last_block: bool) -> i32 {
+        if signed_last_block {
+            BlockIdFlag::Commit as i32
+        } else {
+            BlockIdFlag::Absent as i32
+        }
+    }
+
+    fn signed_last_block(block_id_flag: i32) -> Result<bool, Error> {
+        match BlockIdFlag::from_i32(block_id_flag) {
+            Some(BlockIdFlag::Absent) => Ok(false),
+            Some(BlockIdFlag::Commit) | Some(BlockIdFlag::Nil) => Ok(true),
+            _ => Err(Error::block_id_flag()),
+        }
+    }
+
+    impl From<VoteInfo> for pb::VoteInfo {
+        fn from(vi: VoteInfo) -> Self {
+            Self {
+                validator: Some(vi.validator.into()),
+                block_id_flag: block_id_flag(vi.signed_last_block),
+            }
+        }
+    }
+
+    impl TryFrom<pb::VoteInfo> for VoteInfo {
+        type Error = Error;
+
+        fn try_from(vi: pb::VoteInfo) -> Result<Self, Self::Error> {
+            Ok(Self {
+                validator: vi
+                    .validator
+                    .ok_or_else(Error::missing_validator)?
+                    .try_into()?,
+                signed_last_block: signed_last_block(vi.block_id_flag)?,
+            })
+        }
+    }
+
+    impl Protobuf<pb::VoteInfo> for VoteInfo {}
+
+    impl From<ExtendedVoteInfo> for pb::ExtendedVoteInfo {
+        fn from(vi: ExtendedVoteInfo) -> Self {
+            Self {
+                validator: Some(vi.validator.into()),
+                vote_extension: vi.vote_extension,
+                extension_signature: vi.extension_signature,
+                block_id_flag: block_id_flag(vi.signed_last_block),
+            }
+        }
+    }
+
+    impl TryFrom<pb::ExtendedVoteInfo> for ExtendedVoteInfo {
+        type Error = Error;
+
+        fn try_from(vi: pb::ExtendedVoteInfo) -> Result<Self, Self::Error> {
+            Ok(Self {
+                validator: vi
+                    .validator
+                    .ok_or_else(Error::missing_validator)?
+                    .try_into()?,
+                signed_last_block: signed_last_block(vi.block_id_flag)?,
+                vote_extension: vi.vote_extension,
+                extension_signature: vi.extension_signature,
+            })
+        }
+    }
+
+    impl Protobuf<pb::ExtendedVoteInfo> for ExtendedVoteInfo {}
+
+    impl From<ExecTxResult> for pb::ExecTxResult {
+        fn from(result: ExecTxResult) -> Self {
+            Self {
+                code: result.code,
+                data: result.data,
+                log: result.log,
+                info: result.info,
+                gas_wanted: result.gas_wanted,
+                gas_used: result.gas_used,
+                events: result.events.into_iter().map(Into::into).collect(),
+                codespace: result.codespace,
+            }
+        }
+    }
+
+    impl TryFrom<pb::ExecTxResult> for ExecTxResult {
+        type Error = Error;
+
+        fn try_from(result: pb::ExecTxResult) -> Result<Self, Self::Error> {
+            Ok(Self {
+                code: result.code,
+                data: result.data,
+                log: result.log,
+                info: result.info,
+                gas_wanted: result.gas_wanted,
+                gas_used: result.gas_used,
+                events: result
+                    .events
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+                codespace: result.codespace,
+            })
+        }
+    }
+
+    impl Protobuf<pb::ExecTxResult> for ExecTxResult {}
+}
@@ -26,7 +26,7 @@ pub use self::{
     round::*,
     size::Size,
 };
-use crate::{error::Error, evidence, prelude::*};
+use crate::{error::Error, evidence, prelude::*, tx, Hash};
 
 /// Blocks consist of a header, transactions, votes (the commit), and a list of
 /// evidence of malfeasance (i.e. signing conflicting votes).
@@ -92,7 +92,8 @@ impl From<Block> for RawBlock {
             header: Some(value.header.into()),
             data: Some(RawData { txs: value.data }),
             evidence: Some(value.evidence.into()),
-            last_commit: value.last_commit.map(Into::into),
+            // Go encodes the empty commit of the first block
+            last_commit: Some(value.last_commit.unwrap_or_default().into()),
         }
     }
 }
@@ -142,4 +143,186 @@ impl Block {
     pub fn last_commit(&self) -> &Option<Commit> {
         &self.last_commit
     }
+
+    /// Split the protobuf encoding of this block into parts of the given size,
+    /// usually [`parts::BLOCK_PART_SIZE_BYTES`], which is also the maximum.
+    pub fn make_part_set(&self, part_size: u32) -> Result<parts::PartSet, Error> {
+        parts::PartSet::from_data(&self.encode_vec(), part_size)
+    }
+
+    /// Decode a block from the parts of a complete part set.
+    pub fn from_part_set(part_set: &parts::PartSet) -> Result<Self, Error> {
+        let data = part_set
+            .data()
+            .ok_or_else(|| Error::incomplete_part_set(part_set.count(), part_set.total()))?;
+        Self::decode_vec(&data).map_err(|e| Error::invalid_block(e.to_string()))
+    }
+
+    /// Check that the hashes and the last block ID in the header agree with
+    /// the transactions, evidence and last commit of the block.
+    ///
+    /// This does not verify any signatures, but ensures that a block whose
+    /// header has been verified (e.g. by a light client) carries the contents
+    /// that header commits to.
+    ///
+    /// <https://github.com/tendermint/tendermint/blob/c8483531d8e756f7fbb812db1dd16d841cdf298a/types/block.go#L52>
+    pub fn validate_basic(&self) -> Result<(), Error> {
+        let height = self.header.height.value();
+        if height == 0 {
+            return Err(Error::invalid_block("block is at height 0".to_string()));
+        }
+        match &self.last_commit {
+            Some(_) if height == 1 => {
+                return Err(Error::invalid_block(
+                    "last_commit is filled on first block".to_string(),
+                ))
+            },
+            None if height != 1 => {
+                return Err(Error::invalid_block(
+                    "last_commit is empty on non-first block".to_string(),
+                ))
+            },
+            Some(last_commit) => {
+                if last_commit.height.value() + 1 != height {
+                    return Err(Error::invalid_block(format!(
+                        "last_commit is at height {} instead of {}",
+                        last_commit.height,
+                        height - 1
+                    )));
+                }
+                if self.header.last_block_id != Some(last_commit.block_id) {
+                    return Err(Error::last_block_id_mismatch(
+                        self.header.last_block_id,
+                        last_commit.block_id,
+                    ));
+                }
+            },
+            None => (),
+        }
+
+        // The first block's last commit is empty
+        let last_commit_hash = self
+            .last_commit
+            .as_ref()
+            .map_or_else(|| Commit::default().hash(), Commit::hash);
+        check_hash(
+            "last_commit_hash",
+            self.header.last_commit_hash,
+            last_commit_hash,
+        )?;
+        check_hash(
+            "data_hash",
+            self.header.data_hash,
+            tx::data_hash(&self.data),
+        )?;
+        check_hash(
+            "evidence_hash",
+            self.header.evidence_hash,
+            self.evidence.hash(),
+        )?;
+        Ok(())
+    }
+}
+
+fn check_hash(field: &str, header: Option<Hash>, computed: Hash) -> Result<(), Error> {
+    let header = header.unwrap_or_default();
+    if header != computed {
+        return Err(Error::block_hash_mismatch(
+            field.to_string(),
+            header,
+            computed,
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorDetail;
+
+    fn block() -> Block {
+        let mut header: Header = serde_json::from_str(include_str!(
+            "../tests/support/serialization/block/header.json"
+        ))
+        .unwrap();
+        let txs = vec![b"a=1".to_vec(), b"b=2".to_vec()];
+        let last_commit = Commit {
+            height: Height::try_from(14_u64).unwrap(),
+            round: Round::default(),
+            block_id: header.last_block_id.unwrap(),
+            signatures: vec![],
+        };
+        header.last_commit_hash = Some(last_commit.hash());
+        header.data_hash = Some(tx::data_hash(&txs));
+        header.evidence_hash = Some(evidence::Data::default().hash());
+        Block::new(header, txs, evidence::Data::default(), Some(last_commit)).unwrap()
+    }
+
+    fn mismatched_field(block: &Block) -> String {
+        match block.validate_basic().unwrap_err().detail() {
+            ErrorDetail::BlockHashMismatch(e) => e.field.clone(),
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn validate_basic_checks_header_hashes() {
+        let block = block();
+        block.validate_basic().unwrap();
+
+        let mut tampered = block.clone();
+        tampered.data.push(b"c=3".to_vec());
+        assert_eq!(mismatched_field(&tampered), "data_hash");
+
+        let mut tampered = block.clone();
+        tampered.header.evidence_hash = None;
+        assert_eq!(mismatched_field(&tampered), "evidence_hash");
+
+        let mut tampered = block.clone();
+        tampered
+            .last_commit
+            .as_mut()
+            .unwrap()
+            .signatures
+            .push(CommitSig::BlockIdFlagAbsent);
+        assert_eq!(mismatched_field(&tampered), "last_commit_hash");
+    }
+
+    #[test]
+    fn blocks_roundtrip_through_part_sets() {
+        let block = block();
+        let sent = block.make_part_set(64).unwrap();
+        assert!(sent.total() > 1);
+
+        let mut received = parts::PartSet::from_header(sent.header()).unwrap();
+        for index in 0..sent.total() {
+            assert!(Block::from_part_set(&received).is_err());
+            received
+                .add_part(sent.part(index).unwrap().clone())
+                .unwrap();
+        }
+        assert_eq!(Block::from_part_set(&received).unwrap(), block);
+    }
+
+    #[test]
+    fn validate_basic_checks_last_commit() {
+        let block = block();
+
+        let mut tampered = block.clone();
+        tampered.header.last_block_id = Some(Id::default());
+        assert!(matches!(
+            tampered.validate_basic().unwrap_err().detail(),
+            ErrorDetail::LastBlockIdMismatch(_)
+        ));
+
+        let mut tampered = block.clone();
+        tampered.last_commit.as_mut().unwrap().height = Height::try_from(13_u64).unwrap();
+        assert!(tampered.validate_basic().is_err());
+
+        // Rejected rather than computing the height of the last commit
+        let mut tampered = block;
+        tampered.header.height = Height::try_from(0_u64).unwrap();
+        assert!(tampered.validate_basic().is_err());
+    }
 }
@@ -2,13 +2,16 @@
 
 use core::convert::{TryFrom, TryInto};
 
+use prost::Message;
 use serde::{Deserialize, Serialize};
-use tendermint_proto::types::Commit as RawCommit;
+use tendermint_proto::types::{Commit as RawCommit, CommitSig as RawCommitSig};
 
 use crate::{
     block::{commit_sig::CommitSig, Height, Id, Round},
     error::Error,
+    merkle::simple_hash_from_byte_vectors,
     prelude::*,
+    Hash,
 };
 
 /// Commit contains the justification (ie. a set of signatures) that a block was committed by a set
@@ -64,6 +67,20 @@ impl From<Commit> for RawCommit {
     }
 }
 
+impl Commit {
+    /// Compute the Merkle root of the protobuf-encoded signatures of this
+    /// commit, as found in the `last_commit_hash` of the header of the next
+    /// block.
+    pub fn hash(&self) -> Hash {
+        Hash::Sha256(simple_hash_from_byte_vectors(
+            self.signatures
+                .iter()
+                .map(|sig| RawCommitSig::from(sig.clone()).encode_to_vec())
+                .collect(),
+        ))
+    }
+}
+
 impl Default for Commit {
     fn default() -> Self {
         Commit {
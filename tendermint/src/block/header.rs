@@ -149,7 +149,8 @@ impl From<Header> for RawHeader {
             chain_id: value.chain_id.into(),
             height: value.height.into(),
             time: Some(value.time.into()),
-            last_block_id: value.last_block_id.map(Into::into),
+            // Not nullable in Go, where an unfilled block ID is encoded as such
+            last_block_id: Some(value.last_block_id.unwrap_or_default().into()),
             last_commit_hash: value.last_commit_hash.unwrap_or_default().into(),
             data_hash: value.data_hash.unwrap_or_default().into(),
             validators_hash: value.validators_hash.into(),
@@ -1,11 +1,12 @@
 //! Block parts
 
-use core::convert::TryFrom;
+use core::convert::{TryFrom, TryInto};
 
 use serde::{Deserialize, Serialize};
 use tendermint_proto::{
     types::{
-        CanonicalPartSetHeader as RawCanonicalPartSetHeader, PartSetHeader as RawPartSetHeader,
+        CanonicalPartSetHeader as RawCanonicalPartSetHeader, Part as RawPart,
+        PartSetHeader as RawPartSetHeader,
     },
     Protobuf,
 };
@@ -13,10 +14,21 @@ use tendermint_proto::{
 use crate::{
     error::Error,
     hash::{Algorithm, SHA256_HASH_SIZE},
+    merkle::{self, proofs_from_byte_vectors},
     prelude::*,
     Hash,
 };
 
+/// The size of the parts blocks are split into for gossiping, except for the
+/// last part of a block, which may be smaller.
+pub const BLOCK_PART_SIZE_BYTES: u32 = 65536;
+
+/// The maximum size of a block, as enforced by Tendermint (100MB).
+pub const MAX_BLOCK_SIZE_BYTES: u32 = 104_857_600;
+
+/// The maximum number of parts a block can be split into.
+pub const MAX_BLOCK_PARTS_COUNT: u32 = MAX_BLOCK_SIZE_BYTES / BLOCK_PART_SIZE_BYTES + 1;
+
 /// Block parts header
 #[derive(
     Clone, Copy, Debug, Default, Hash, Eq, PartialEq, PartialOrd, Ord, Deserialize, Serialize,
@@ -95,3 +107,273 @@ impl Header {
         Ok(Header { total, hash })
     }
 }
+
+/// A part of an encoded block, along with the proof of its inclusion in the
+/// Merkle tree over all parts whose root is the hash of the part set header.
+///
+/// <https://github.com/tendermint/tendermint/blob/c8483531d8e756f7fbb812db1dd16d841cdf298a/types/part_set.go#L23>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Part {
+    /// Index of this part within the block
+    pub index: u32,
+
+    /// Bytes of the encoded block within this part
+    pub bytes: Vec<u8>,
+
+    /// Proof of the inclusion of the bytes in the part set
+    pub proof: merkle::Proof,
+}
+
+impl Protobuf<RawPart> for Part {}
+
+impl TryFrom<RawPart> for Part {
+    type Error = Error;
+
+    fn try_from(value: RawPart) -> Result<Self, Self::Error> {
+        Ok(Self {
+            index: value.index,
+            bytes: value.bytes,
+            proof: value.proof.ok_or_else(Error::missing_proof)?.try_into()?,
+        })
+    }
+}
+
+impl From<Part> for RawPart {
+    fn from(value: Part) -> Self {
+        RawPart {
+            index: value.index,
+            bytes: value.bytes,
+            proof: Some(value.proof.into()),
+        }
+    }
+}
+
+/// The set of parts an encoded block is split into, which is either built
+/// from the block itself or filled part by part as they are received.
+///
+/// <https://github.com/tendermint/tendermint/blob/c8483531d8e756f7fbb812db1dd16d841cdf298a/types/part_set.go#L143>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PartSet {
+    header: Header,
+    parts: Vec<Option<Part>>,
+    count: u32,
+}
+
+impl PartSet {
+    /// Split the given data into parts of the given size, each with the proof
+    /// of its inclusion in the set.
+    ///
+    /// The part size must be between 1 and [`BLOCK_PART_SIZE_BYTES`], as
+    /// larger parts are rejected by [`PartSet::add_part`].
+    pub fn from_data(data: &[u8], part_size: u32) -> Result<Self, Error> {
+        if part_size == 0 || part_size > BLOCK_PART_SIZE_BYTES {
+            return Err(Error::invalid_part(format!(
+                "part size {} is not between 1 and {} bytes",
+                part_size, BLOCK_PART_SIZE_BYTES
+            )));
+        }
+        let chunks: Vec<Vec<u8>> = data
+            .chunks(part_size as usize)
+            .map(<[u8]>::to_vec)
+            .collect();
+        let (root, proofs) = proofs_from_byte_vectors(chunks.clone());
+        let parts: Vec<Option<Part>> = chunks
+            .into_iter()
+            .zip(proofs)
+            .enumerate()
+            .map(|(index, (bytes, proof))| {
+                Some(Part {
+                    index: index as u32,
+                    bytes,
+                    proof,
+                })
+            })
+            .collect();
+        let total = parts.len() as u32;
+        Ok(Self {
+            header: Header {
+                total,
+                hash: Hash::Sha256(root),
+            },
+            parts,
+            count: total,
+        })
+    }
+
+    /// Create an empty part set, to be filled with the parts matching the
+    /// given header via [`PartSet::add_part`].
+    ///
+    /// Fails if the header has more than [`MAX_BLOCK_PARTS_COUNT`] parts.
+    pub fn from_header(header: Header) -> Result<Self, Error> {
+        if header.total > MAX_BLOCK_PARTS_COUNT {
+            return Err(Error::invalid_part_set_header(format!(
+                "{} parts is more than the maximum of {}",
+                header.total, MAX_BLOCK_PARTS_COUNT
+            )));
+        }
+        Ok(Self {
+            header,
+            parts: vec![None; header.total as usize],
+            count: 0,
+        })
+    }
+
+    /// The header of this part set.
+    pub fn header(&self) -> Header {
+        self.header
+    }
+
+    /// The total number of parts in the set.
+    pub fn total(&self) -> u32 {
+        self.header.total
+    }
+
+    /// The number of parts added to the set so far.
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Whether all parts have been added to the set.
+    pub fn is_complete(&self) -> bool {
+        self.count == self.header.total
+    }
+
+    /// Get the part with the given index, if it has been added to the set.
+    pub fn part(&self, index: u32) -> Option<&Part> {
+        self.parts.get(index as usize)?.as_ref()
+    }
+
+    /// Verify the given part against the header of the set and add it.
+    ///
+    /// Returns `false` if the set already contains the part.
+    pub fn add_part(&mut self, part: Part) -> Result<bool, Error> {
+        if part.index >= self.header.total {
+            return Err(Error::invalid_part(format!(
+                "index {} is out of bounds for {} parts",
+                part.index, self.header.total
+            )));
+        }
+        if self.parts[part.index as usize].is_some() {
+            return Ok(false);
+        }
+        if part.bytes.len() > BLOCK_PART_SIZE_BYTES as usize {
+            return Err(Error::invalid_part(format!(
+                "part is larger than {} bytes",
+                BLOCK_PART_SIZE_BYTES
+            )));
+        }
+        if part.proof.index != u64::from(part.index)
+            || part.proof.total != u64::from(self.header.total)
+        {
+            return Err(Error::invalid_part(format!(
+                "proof is for part {} of {} instead of part {} of {}",
+                part.proof.index, part.proof.total, part.index, self.header.total
+            )));
+        }
+        let root = match self.header.hash {
+            Hash::Sha256(root) => root,
+            Hash::None => return Err(Error::invalid_part("part set has no hash".to_string())),
+        };
+        part.proof
+            .verify(&root, &part.bytes)
+            .map_err(|e| Error::invalid_part(e.to_string()))?;
+        let index = part.index as usize;
+        self.parts[index] = Some(part);
+        self.count += 1;
+        Ok(true)
+    }
+
+    /// Reassemble the data split into the parts of the set, if all of them
+    /// have been added.
+    pub fn data(&self) -> Option<Vec<u8>> {
+        if !self.is_complete() {
+            return None;
+        }
+        Some(
+            self.parts
+                .iter()
+                .flatten()
+                .flat_map(|part| part.bytes.iter().copied())
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[test]
+    fn parts_are_split_and_reassembled() {
+        let data = data(10 * 1024 + 1);
+        let sent = PartSet::from_data(&data, 1024).unwrap();
+        assert_eq!(sent.total(), 11);
+        assert!(sent.is_complete());
+        assert_eq!(sent.part(10).unwrap().bytes.len(), 1);
+        assert_eq!(sent.data().unwrap(), data);
+
+        // Parts may arrive in any order, and more than once
+        let mut received = PartSet::from_header(sent.header()).unwrap();
+        assert!(received.data().is_none());
+        for index in (0..sent.total()).rev() {
+            let part = sent.part(index).unwrap().clone();
+            assert!(received.add_part(part.clone()).unwrap());
+            assert!(!received.add_part(part).unwrap());
+        }
+        assert!(received.is_complete());
+        assert_eq!(received, sent);
+        assert_eq!(received.data().unwrap(), data);
+    }
+
+    #[test]
+    fn invalid_parts_are_rejected() {
+        let sent = PartSet::from_data(&data(4096), 1024).unwrap();
+        let mut received = PartSet::from_header(sent.header()).unwrap();
+
+        let mut part = sent.part(1).unwrap().clone();
+        part.bytes[0] ^= 1;
+        assert!(received.add_part(part).is_err());
+
+        let mut part = sent.part(1).unwrap().clone();
+        part.index = 2;
+        assert!(received.add_part(part).is_err());
+
+        let mut part = sent.part(1).unwrap().clone();
+        part.index = 4;
+        assert!(received.add_part(part).is_err());
+
+        // Parts of another block
+        let other = PartSet::from_data(&data(4097), 1024).unwrap();
+        assert!(received.add_part(other.part(1).unwrap().clone()).is_err());
+        assert_eq!(received.count(), 0);
+    }
+
+    #[test]
+    fn invalid_part_sizes_are_rejected() {
+        assert!(PartSet::from_data(&data(10), 0).is_err());
+        assert!(PartSet::from_data(&data(10), BLOCK_PART_SIZE_BYTES + 1).is_err());
+        let sent = PartSet::from_data(&data(10), BLOCK_PART_SIZE_BYTES).unwrap();
+        assert_eq!(sent.total(), 1);
+    }
+
+    #[test]
+    fn oversized_part_set_headers_are_rejected() {
+        assert_eq!(MAX_BLOCK_PARTS_COUNT, 1601);
+        let header =
+            Header::new(MAX_BLOCK_PARTS_COUNT, Hash::Sha256([0; SHA256_HASH_SIZE])).unwrap();
+        assert!(PartSet::from_header(header).is_ok());
+        let header = Header::new(u32::MAX, Hash::Sha256([0; SHA256_HASH_SIZE])).unwrap();
+        assert!(PartSet::from_header(header).is_err());
+    }
+
+    #[test]
+    fn parts_roundtrip() {
+        let sent = PartSet::from_data(&data(3000), 1024).unwrap();
+        let part = sent.part(2).unwrap().clone();
+        assert_eq!(Part::decode_vec(&part.encode_vec()).unwrap(), part);
+    }
+}
@@ -56,3 +56,71 @@ impl From<Size> for RawSize {
         }
     }
 }
+
+/// The v0.34 ABCI protocol carries block size parameters in a message of its
+/// own.
+mod v0_34 {
+    use tendermint_proto::v0_34::abci::BlockParams as RawSize;
+
+    use super::*;
+
+    impl Protobuf<RawSize> for Size {}
+
+    impl TryFrom<RawSize> for Size {
+        type Error = Error;
+
+        fn try_from(value: RawSize) -> Result<Self, Self::Error> {
+            Ok(Self {
+                max_bytes: value
+                    .max_bytes
+                    .try_into()
+                    .map_err(Error::integer_overflow)?,
+                max_gas: value.max_gas,
+                time_iota_ms: Self::default_time_iota_ms(),
+            })
+        }
+    }
+
+    impl From<Size> for RawSize {
+        fn from(value: Size) -> Self {
+            RawSize {
+                max_bytes: value.max_bytes as i64,
+                max_gas: value.max_gas,
+            }
+        }
+    }
+}
+
+/// CometBFT v0.38 defines the block size parameters alongside its extended
+/// consensus parameters.
+mod v0_38 {
+    use tendermint_proto::v0_38::types::BlockParams as RawSize;
+
+    use super::*;
+
+    impl Protobuf<RawSize> for Size {}
+
+    impl TryFrom<RawSize> for Size {
+        type Error = Error;
+
+        fn try_from(value: RawSize) -> Result<Self, Self::Error> {
+            Ok(Self {
+                max_bytes: value
+                    .max_bytes
+                    .try_into()
+                    .map_err(Error::integer_overflow)?,
+                max_gas: value.max_gas,
+                time_iota_ms: Self::default_time_iota_ms(),
+            })
+        }
+    }
+
+    impl From<Size> for RawSize {
+        fn from(value: Size) -> Self {
+            RawSize {
+                max_bytes: value.max_bytes as i64,
+                max_gas: value.max_gas,
+            }
+        }
+    }
+}
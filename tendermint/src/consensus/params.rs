@@ -28,6 +28,11 @@ pub struct Params {
     /// Version parameters
     #[serde(skip)] // Todo: FIXME kvstore /genesis returns '{}' instead of '{app_version: "0"}'
     pub version: Option<VersionParams>,
+    /// Parameters specific to the Application Blockchain Interface.
+    ///
+    /// These are only carried by CometBFT v0.38 and later.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub abci: Option<AbciParams>,
 }
 
 impl Protobuf<RawParams> for Params {}
@@ -50,6 +55,7 @@ impl TryFrom<RawParams> for Params {
                 .ok_or_else(Error::invalid_validator_params)?
                 .try_into()?,
             version: value.version.map(TryFrom::try_from).transpose()?,
+            abci: None,
         })
     }
 }
@@ -65,6 +71,163 @@ impl From<Params> for RawParams {
     }
 }
 
+/// The v0.34 ABCI protocol carries consensus parameters in a message of its
+/// own.
+mod v0_34 {
+    use tendermint_proto::v0_34::abci::ConsensusParams as RawParams;
+
+    use super::*;
+
+    impl Protobuf<RawParams> for Params {}
+
+    impl TryFrom<RawParams> for Params {
+        type Error = Error;
+
+        fn try_from(value: RawParams) -> Result<Self, Self::Error> {
+            Ok(Self {
+                block: value
+                    .block
+                    .ok_or_else(|| Error::invalid_block("missing block".to_string()))?
+                    .try_into()?,
+                evidence: value
+                    .evidence
+                    .ok_or_else(Error::invalid_evidence)?
+                    .try_into()?,
+                validator: value
+                    .validator
+                    .ok_or_else(Error::invalid_validator_params)?
+                    .try_into()?,
+                version: value.version.map(TryFrom::try_from).transpose()?,
+                abci: None,
+            })
+        }
+    }
+
+    impl From<Params> for RawParams {
+        fn from(value: Params) -> Self {
+            RawParams {
+                block: Some(value.block.into()),
+                evidence: Some(value.evidence.into()),
+                validator: Some(value.validator.into()),
+                version: value.version.map(From::from),
+            }
+        }
+    }
+}
+
+/// CometBFT v0.38 extends the consensus parameters with ABCI parameters.
+mod v0_38 {
+    use tendermint_proto::v0_38::types::{
+        AbciParams as RawAbciParams, ConsensusParams as RawParams,
+        ValidatorParams as RawValidatorParams, VersionParams as RawVersionParams,
+    };
+
+    use super::*;
+
+    impl Protobuf<RawParams> for Params {}
+
+    impl TryFrom<RawParams> for Params {
+        type Error = Error;
+
+        fn try_from(value: RawParams) -> Result<Self, Self::Error> {
+            Ok(Self {
+                block: value
+                    .block
+                    .ok_or_else(|| Error::invalid_block("missing block".to_string()))?
+                    .try_into()?,
+                evidence: value
+                    .evidence
+                    .ok_or_else(Error::invalid_evidence)?
+                    .try_into()?,
+                validator: value
+                    .validator
+                    .ok_or_else(Error::invalid_validator_params)?
+                    .try_into()?,
+                version: value.version.map(TryFrom::try_from).transpose()?,
+                abci: value.abci.map(TryFrom::try_from).transpose()?,
+            })
+        }
+    }
+
+    impl From<Params> for RawParams {
+        fn from(value: Params) -> Self {
+            RawParams {
+                block: Some(value.block.into()),
+                evidence: Some(value.evidence.into()),
+                validator: Some(value.validator.into()),
+                version: value.version.map(From::from),
+                abci: value.abci.map(From::from),
+            }
+        }
+    }
+
+    impl Protobuf<RawValidatorParams> for ValidatorParams {}
+
+    impl TryFrom<RawValidatorParams> for ValidatorParams {
+        type Error = Error;
+
+        fn try_from(value: RawValidatorParams) -> Result<Self, Self::Error> {
+            Ok(Self {
+                pub_key_types: value.pub_key_types.iter().map(|f| key_type(f)).collect(),
+            })
+        }
+    }
+
+    impl From<ValidatorParams> for RawValidatorParams {
+        fn from(value: ValidatorParams) -> Self {
+            RawValidatorParams {
+                pub_key_types: super::RawValidatorParams::from(value).pub_key_types,
+            }
+        }
+    }
+
+    impl Protobuf<RawVersionParams> for VersionParams {}
+
+    impl TryFrom<RawVersionParams> for VersionParams {
+        type Error = Error;
+
+        fn try_from(value: RawVersionParams) -> Result<Self, Self::Error> {
+            Ok(Self {
+                app_version: value.app,
+            })
+        }
+    }
+
+    impl From<VersionParams> for RawVersionParams {
+        fn from(value: VersionParams) -> Self {
+            RawVersionParams {
+                app: value.app_version,
+            }
+        }
+    }
+
+    impl Protobuf<RawAbciParams> for AbciParams {}
+
+    impl TryFrom<RawAbciParams> for AbciParams {
+        type Error = Error;
+
+        fn try_from(value: RawAbciParams) -> Result<Self, Self::Error> {
+            let vote_extensions_enable_height = match value.vote_extensions_enable_height {
+                0 => None,
+                height => Some(height.try_into()?),
+            };
+            Ok(Self {
+                vote_extensions_enable_height,
+            })
+        }
+    }
+
+    impl From<AbciParams> for RawAbciParams {
+        fn from(value: AbciParams) -> Self {
+            RawAbciParams {
+                vote_extensions_enable_height: value
+                    .vote_extensions_enable_height
+                    .map_or(0, From::from),
+            }
+        }
+    }
+}
+
 /// ValidatorParams restrict the public key types validators can use.
 ///
 /// [Tendermint documentation](https://docs.tendermint.com/master/spec/core/data_structures.html#validatorparams)
@@ -141,3 +304,99 @@ impl From<VersionParams> for RawVersionParams {
         }
     }
 }
+
+/// ABCI Parameters
+///
+/// [CometBFT documentation](https://github.com/cometbft/cometbft/blob/v0.38.x/spec/abci/abci++_app_requirements.md#abciparamsvoteextensionsenableheight)
+#[derive(Clone, Serialize, Deserialize, Debug, Eq, PartialEq, Default)]
+pub struct AbciParams {
+    /// The first height at which vote extensions are enabled, or `None` if
+    /// they are disabled.
+    ///
+    /// Vote extensions are disabled by setting this height to 0.
+    #[serde(with = "enable_height")]
+    pub vote_extensions_enable_height: Option<block::Height>,
+}
+
+/// Serialization of a height that is disabled when set to 0.
+mod enable_height {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::block::Height;
+
+    pub fn serialize<S>(value: &Option<Height>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        value.unwrap_or_else(|| 0_u32.into()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Height>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let height = Height::deserialize(deserializer)?;
+        Ok(Some(height).filter(|height| height.value() != 0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use tendermint_proto::{v0_38::types::ConsensusParams as RawParamsV0_38, Protobuf};
+
+    use super::*;
+
+    fn params(abci: Option<AbciParams>) -> Params {
+        Params {
+            block: block::Size {
+                max_bytes: 22020096,
+                max_gas: -1,
+                time_iota_ms: block::Size::default_time_iota_ms(),
+            },
+            evidence: evidence::Params {
+                max_age_num_blocks: 100000,
+                max_age_duration: evidence::Duration(Duration::from_secs(172800)),
+                max_bytes: 1048576,
+            },
+            validator: ValidatorParams {
+                pub_key_types: vec![public_key::Algorithm::Ed25519],
+            },
+            version: Some(VersionParams { app_version: 1 }),
+            abci,
+        }
+    }
+
+    #[test]
+    fn abci_params_round_trip_through_v0_38() {
+        for height in [None, Some(block::Height::from(10_u32))] {
+            let params = params(Some(AbciParams {
+                vote_extensions_enable_height: height,
+            }));
+            let encoded = Protobuf::<RawParamsV0_38>::encode_vec(&params);
+            let decoded = <Params as Protobuf<RawParamsV0_38>>::decode_vec(&encoded).unwrap();
+            assert_eq!(decoded, params);
+        }
+
+        // Earlier versions have no ABCI parameters
+        let encoded = Protobuf::<RawParams>::encode_vec(&params(Some(AbciParams::default())));
+        let decoded = <Params as Protobuf<RawParams>>::decode_vec(&encoded).unwrap();
+        assert_eq!(decoded, params(None));
+    }
+
+    #[test]
+    fn abci_params_serialize_disabled_height_as_zero() {
+        let disabled = AbciParams::default();
+        let json = serde_json::to_string(&disabled).unwrap();
+        assert_eq!(json, r#"{"vote_extensions_enable_height":"0"}"#);
+        assert_eq!(serde_json::from_str::<AbciParams>(&json).unwrap(), disabled);
+
+        let enabled: AbciParams =
+            serde_json::from_str(r#"{"vote_extensions_enable_height":"10"}"#).unwrap();
+        assert_eq!(
+            enabled.vote_extensions_enable_height,
+            Some(block::Height::from(10_u32))
+        );
+    }
+}
@@ -6,7 +6,7 @@ use core::num::TryFromIntError;
 use flex_error::{define_error, DisplayOnly};
 use serde::{Deserialize, Serialize};
 
-use crate::{account, vote};
+use crate::{account, block, vote, Hash};
 
 define_error! {
     #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -65,6 +65,14 @@ define_error! {
             [ DisplayOnly<TryFromIntError> ]
             |_| { format_args!("negative validator index") },
 
+        NegativeProofTotal
+            [ DisplayOnly<TryFromIntError> ]
+            |_| { format_args!("negative number of leaves in Merkle proof") },
+
+        NegativeProofIndex
+            [ DisplayOnly<TryFromIntError> ]
+            |_| { format_args!("negative leaf index in Merkle proof") },
+
         InvalidHashSize
             |_| { format_args!("invalid hash: expected hash size to be 32 bytes") },
 
@@ -100,6 +108,14 @@ define_error! {
             { detail : String }
             |_| { format_args!("invalid part set header") },
 
+        InvalidPart
+            { reason: String }
+            | e | { format_args!("invalid block part: {}", e.reason) },
+
+        IncompletePartSet
+            { count: u32, total: u32 }
+            | e | { format_args!("part set is incomplete: {} of {} parts", e.count, e.total) },
+
         MissingHeader
             |_| { format_args!("missing header field") },
 
@@ -133,6 +149,9 @@ define_error! {
         MissingConsensusParams
             |_| { format_args!("missing consensus params") },
 
+        MissingProof
+            |_| { format_args!("missing proof") },
+
         InvalidTimestamp
             { reason: String }
             | e | { format_args!("invalid timestamp: {}", e.reason) },
@@ -141,6 +160,32 @@ define_error! {
             { reason: String }
             | e | { format_args!("invalid block: {}", e.reason) },
 
+        BlockHashMismatch
+            { field: String, header: Hash, computed: Hash }
+            | e | {
+                format_args!("block header's {} ({}) does not match the one computed from the block ({})",
+                    e.field, e.header, e.computed)
+            },
+
+        LastBlockIdMismatch
+            { header: Option<block::Id>, commit: block::Id }
+            | e | {
+                format_args!("block header's last_block_id ({:?}) does not match the block ID of its last commit ({})",
+                    e.header, e.commit)
+            },
+
+        InvalidMerkleProof
+            { reason: String }
+            | e | { format_args!("invalid Merkle proof: {}", e.reason) },
+
+        UnknownProofOpType
+            { field_type: String }
+            | e | { format_args!("no decoder registered for proof operations of type {:?}", e.field_type) },
+
+        InvalidKeyPath
+            { reason: String }
+            | e | { format_args!("invalid key path: {}", e.reason) },
+
         InvalidFirstHeader
             |_| { format_args!("last_block_id is not null on first height") },
 
@@ -188,6 +233,16 @@ define_error! {
         UnsupportedOfferSnapshotChunkResult
             |_| { format_args!("unsupported OfferSnapshotChunkResult type" ) },
 
+        UnsupportedProcessProposalStatus
+            |_| { format_args!("unsupported ProcessProposal status" ) },
+
+        UnsupportedVerifyVoteExtensionStatus
+            |_| { format_args!("unsupported VerifyVoteExtension status" ) },
+
+        UnsupportedAbciMethod
+            { method: String, version: String }
+            |e| { format_args!("ABCI method {} is not part of the v{} ABCI protocol", e.method, e.version) },
+
         RawVotingPowerMismatch
             { raw: vote::Power, computed: vote::Power }
             |e| { format_args!("mismatch between raw voting ({0:?}) and computed one ({1:?})", e.raw, e.computed) },
@@ -200,6 +255,18 @@ define_error! {
             { account: account::Id }
             |e| { format_args!("proposer with address '{0}' no found in validator set", e.account) },
 
+        DuplicateValidatorUpdate
+            { account: account::Id }
+            |e| { format_args!("duplicate update of validator with address '{0}'", e.account) },
+
+        InvalidValidatorUpdate
+            { reason: String }
+            |e| { format_args!("invalid validator update: {}", e.reason) },
+
+        InvalidDuplicateVoteEvidence
+            { reason: String }
+            |e| { format_args!("invalid duplicate vote evidence: {}", e.reason) },
+
         TimeParse
             [ DisplayOnly<time::error::Parse> ]
             |_| { format_args!("time parsing error") },
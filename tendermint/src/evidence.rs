@@ -5,6 +5,7 @@ use core::{
     slice,
 };
 
+use prost::Message;
 use serde::{Deserialize, Serialize};
 use tendermint_proto::{
     google::protobuf::Duration as RawDuration,
@@ -12,18 +13,27 @@ use tendermint_proto::{
         evidence::{Sum as RawSum, Sum},
         DuplicateVoteEvidence as RawDuplicateVoteEvidence, Evidence as RawEvidence,
         EvidenceList as RawEvidenceList, EvidenceParams as RawEvidenceParams,
+        LightBlock as RawLightBlock, LightClientAttackEvidence as RawLightClientAttackEvidence,
+        ValidatorSet as RawValidatorSet,
     },
     Protobuf,
 };
 
 use crate::{
-    block::signed_header::SignedHeader, error::Error, prelude::*, serializers, vote::Power, Time,
-    Vote,
+    account,
+    block::{self, signed_header::SignedHeader, CommitSig},
+    chain,
+    error::Error,
+    merkle::simple_hash_from_byte_vectors,
+    prelude::*,
+    serializers, validator,
+    vote::Power,
+    Hash, Time, Vote,
 };
 
-/// Evidence of malfeasance by validators (i.e. signing conflicting votes).
-/// encoded using an Amino prefix. There is currently only a single type of
-/// evidence: `DuplicateVoteEvidence`.
+/// Evidence of malfeasance by validators, either signing conflicting votes
+/// (`DuplicateVoteEvidence`) or attempting to mislead a light client
+/// (`LightClientAttackEvidence`).
 ///
 /// <https://github.com/tendermint/spec/blob/d46cd7f573a2c6a2399fcab2cde981330aa63f37/spec/core/data_structures.md#evidence>
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -34,12 +44,9 @@ pub enum Evidence {
     //#[serde(rename = "tendermint/DuplicateVoteEvidence")]
     DuplicateVote(Box<DuplicateVoteEvidence>),
 
-    /// Conflicting headers evidence - Todo: this is not implemented in protobuf, it's ignored now
-    //#[serde(rename = "tendermint/ConflictingHeadersEvidence")]
-    ConflictingHeaders(Box<ConflictingHeadersEvidence>),
-
-    /// LightClient attack evidence - Todo: Implement details
-    LightClientAttackEvidence,
+    /// Light client attack evidence
+    //#[serde(rename = "tendermint/LightClientAttackEvidence")]
+    LightClientAttack(Box<LightClientAttackEvidence>),
 }
 
 impl TryFrom<RawEvidence> for Evidence {
@@ -48,7 +55,25 @@ impl TryFrom<RawEvidence> for Evidence {
     fn try_from(value: RawEvidence) -> Result<Self, Self::Error> {
         match value.sum.ok_or_else(Error::invalid_evidence)? {
             Sum::DuplicateVoteEvidence(ev) => Ok(Evidence::DuplicateVote(Box::new(ev.try_into()?))),
-            Sum::LightClientAttackEvidence(_ev) => Ok(Evidence::LightClientAttackEvidence),
+            Sum::LightClientAttackEvidence(ev) => {
+                Ok(Evidence::LightClientAttack(Box::new(ev.try_into()?)))
+            },
+        }
+    }
+}
+
+impl Evidence {
+    /// The encoding of this evidence whose Merkle root is the evidence hash of
+    /// a block, i.e. the protobuf encoding of the evidence itself (as opposed
+    /// to that of an [`Evidence`] wrapping it).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Evidence::DuplicateVote(ev) => {
+                RawDuplicateVoteEvidence::from((**ev).clone()).encode_to_vec()
+            },
+            Evidence::LightClientAttack(ev) => {
+                RawLightClientAttackEvidence::from((**ev).clone()).encode_to_vec()
+            },
         }
     }
 }
@@ -59,8 +84,9 @@ impl From<Evidence> for RawEvidence {
             Evidence::DuplicateVote(ev) => RawEvidence {
                 sum: Some(RawSum::DuplicateVoteEvidence((*ev).into())),
             },
-            Evidence::ConflictingHeaders(_ev) => RawEvidence { sum: None }, // Todo: implement
-            Evidence::LightClientAttackEvidence => RawEvidence { sum: None }, // Todo: implement
+            Evidence::LightClientAttack(ev) => RawEvidence {
+                sum: Some(RawSum::LightClientAttackEvidence((*ev).into())),
+            },
         }
     }
 }
@@ -104,47 +130,367 @@ impl From<DuplicateVoteEvidence> for RawDuplicateVoteEvidence {
             vote_a: Some(value.vote_a.into()),
             vote_b: Some(value.vote_b.into()),
             total_voting_power: value.total_voting_power.into(),
-            validator_power: value.total_voting_power.into(),
+            validator_power: value.validator_power.into(),
             timestamp: Some(value.timestamp.into()),
         }
     }
 }
 
 impl DuplicateVoteEvidence {
-    /// constructor
-    pub fn new(vote_a: Vote, vote_b: Vote) -> Result<Self, Error> {
-        if vote_a.height != vote_b.height {
+    /// Create the evidence of the given conflicting votes of a validator of
+    /// the given set, for the height of the block with the given time.
+    ///
+    /// The votes are ordered by block ID, as expected by
+    /// [`DuplicateVoteEvidence::validate_basic`].
+    pub fn new(
+        vote_1: Vote,
+        vote_2: Vote,
+        block_time: Time,
+        validators: &validator::Set,
+    ) -> Result<Self, Error> {
+        if vote_1.height != vote_2.height {
             return Err(Error::invalid_evidence());
         }
-        // Todo: make more assumptions about what is considered a valid evidence for duplicate vote
+        let validator = validators
+            .validator(vote_1.validator_address)
+            .ok_or_else(|| {
+                Error::invalid_duplicate_vote_evidence(format!(
+                    "validator {} is not in the validator set",
+                    vote_1.validator_address
+                ))
+            })?;
+        let (vote_a, vote_b) = if block_id_key(&vote_1) < block_id_key(&vote_2) {
+            (vote_1, vote_2)
+        } else {
+            (vote_2, vote_1)
+        };
         Ok(Self {
             vote_a,
             vote_b,
-            total_voting_power: Default::default(),
-            validator_power: Default::default(),
-            timestamp: Time::unix_epoch(),
+            total_voting_power: validators.total_voting_power(),
+            validator_power: validator.power,
+            timestamp: block_time,
         })
     }
+
     /// Get votes
     pub fn votes(&self) -> (&Vote, &Vote) {
         (&self.vote_a, &self.vote_b)
     }
+
+    /// Get the total voting power of the validator set
+    pub fn total_voting_power(&self) -> Power {
+        self.total_voting_power
+    }
+
+    /// Get the voting power of the validator who cast the votes
+    pub fn validator_power(&self) -> Power {
+        self.validator_power
+    }
+
+    /// Get the time of the block at the height of the votes
+    pub fn timestamp(&self) -> Time {
+        self.timestamp
+    }
+
+    /// Check that the votes are in canonical order, i.e. ordered by block ID.
+    pub fn validate_basic(&self) -> Result<(), Error> {
+        if block_id_key(&self.vote_a) >= block_id_key(&self.vote_b) {
+            return Err(Error::invalid_duplicate_vote_evidence(
+                "votes are not ordered by block ID".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Verify that this is the evidence of a validator of the given set
+    /// signing two conflicting votes on the chain with the given ID.
+    pub fn verify(&self, chain_id: &chain::Id, validators: &validator::Set) -> Result<(), Error> {
+        self.validate_basic()?;
+
+        let (a, b) = (&self.vote_a, &self.vote_b);
+        if a.height != b.height || a.round != b.round || a.vote_type != b.vote_type {
+            return Err(Error::invalid_duplicate_vote_evidence(format!(
+                "height/round/type do not match: {}/{}/{} vs {}/{}/{}",
+                a.height, a.round, a.vote_type, b.height, b.round, b.vote_type
+            )));
+        }
+        if a.validator_address != b.validator_address {
+            return Err(Error::invalid_duplicate_vote_evidence(
+                "validator addresses do not match".to_string(),
+            ));
+        }
+        if a.validator_index != b.validator_index {
+            return Err(Error::invalid_duplicate_vote_evidence(format!(
+                "validator indices do not match: {} and {}",
+                a.validator_index, b.validator_index
+            )));
+        }
+        if a.block_id == b.block_id {
+            return Err(Error::invalid_duplicate_vote_evidence(
+                "block IDs are the same".to_string(),
+            ));
+        }
+
+        let validator = validators.validator(a.validator_address).ok_or_else(|| {
+            Error::invalid_duplicate_vote_evidence(format!(
+                "validator {} is not in the validator set",
+                a.validator_address
+            ))
+        })?;
+        if account::Id::from(validator.pub_key) != a.validator_address {
+            return Err(Error::invalid_duplicate_vote_evidence(
+                "validator public key does not match its address".to_string(),
+            ));
+        }
+        if validator.power != self.validator_power {
+            return Err(Error::invalid_duplicate_vote_evidence(format!(
+                "validator power {} does not match the one in the validator set {}",
+                self.validator_power, validator.power
+            )));
+        }
+        if validators.total_voting_power() != self.total_voting_power {
+            return Err(Error::invalid_duplicate_vote_evidence(format!(
+                "total voting power {} does not match the one of the validator set {}",
+                self.total_voting_power,
+                validators.total_voting_power()
+            )));
+        }
+
+        for (name, vote) in [("A", a), ("B", b)] {
+            let signature = vote.signature.as_ref().ok_or_else(|| {
+                Error::invalid_duplicate_vote_evidence(format!("vote {} is not signed", name))
+            })?;
+            let sign_bytes = vote
+                .to_signable_vec(chain_id.clone())
+                .map_err(|e| Error::invalid_duplicate_vote_evidence(e.to_string()))?;
+            validator
+                .verify_signature(&sign_bytes, signature)
+                .map_err(|e| {
+                    Error::invalid_duplicate_vote_evidence(format!(
+                        "invalid signature of vote {}: {}",
+                        name, e
+                    ))
+                })?;
+        }
+
+        Ok(())
+    }
 }
 
-/// Conflicting headers evidence.
-// Todo: This struct doesn't seem to have a protobuf definition.
-#[derive(Clone, Debug, PartialEq)]
-pub struct ConflictingHeadersEvidence {
-    //#[serde(rename = "H1")]
-    h1: SignedHeader,
-    //#[serde(rename = "H2")]
-    h2: SignedHeader,
+/// The key by which the votes of duplicate vote evidence are ordered, i.e. the
+/// hash of the block ID followed by the encoding of its part set header.
+fn block_id_key(vote: &Vote) -> Vec<u8> {
+    let block_id = vote.block_id.unwrap_or_default();
+    let mut key = block_id.hash.as_bytes().to_vec();
+    key.extend(block_id.part_set_header.encode_vec());
+    key
+}
+
+/// Evidence of a light client attack, i.e. of a block conflicting with the
+/// one committed at the same height that was signed by enough validators to
+/// mislead a light client.
+///
+/// <https://github.com/tendermint/spec/blob/d46cd7f573a2c6a2399fcab2cde981330aa63f37/spec/core/data_structures.md#lightclientattackevidence>
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(
+    try_from = "RawLightClientAttackEvidence",
+    into = "RawLightClientAttackEvidence"
+)]
+pub struct LightClientAttackEvidence {
+    /// The conflicting block, along with the validator set that signed it
+    pub conflicting_block: ConflictingBlock,
+    /// The height of the last block both the conflicting block and the
+    /// committed one can be traced back to
+    pub common_height: block::Height,
+    /// The validators who signed the conflicting block
+    pub byzantine_validators: Vec<validator::Info>,
+    /// The total voting power of the validator set at the common height
+    pub total_voting_power: Power,
+    /// The time of the block at the common height
+    pub timestamp: Time,
 }
 
-impl ConflictingHeadersEvidence {
-    /// Create a new evidence of conflicting headers
-    pub fn new(h1: SignedHeader, h2: SignedHeader) -> Self {
-        Self { h1, h2 }
+impl Protobuf<RawLightClientAttackEvidence> for LightClientAttackEvidence {}
+
+impl TryFrom<RawLightClientAttackEvidence> for LightClientAttackEvidence {
+    type Error = Error;
+
+    fn try_from(value: RawLightClientAttackEvidence) -> Result<Self, Self::Error> {
+        Ok(Self {
+            conflicting_block: value
+                .conflicting_block
+                .ok_or_else(Error::missing_evidence)?
+                .try_into()?,
+            common_height: value.common_height.try_into()?,
+            byzantine_validators: value
+                .byzantine_validators
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<Vec<_>, _>>()?,
+            total_voting_power: value.total_voting_power.try_into()?,
+            timestamp: value
+                .timestamp
+                .ok_or_else(Error::missing_timestamp)?
+                .try_into()?,
+        })
+    }
+}
+
+impl From<LightClientAttackEvidence> for RawLightClientAttackEvidence {
+    fn from(value: LightClientAttackEvidence) -> Self {
+        RawLightClientAttackEvidence {
+            conflicting_block: Some(value.conflicting_block.into()),
+            common_height: value.common_height.into(),
+            byzantine_validators: value
+                .byzantine_validators
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            total_voting_power: value.total_voting_power.into(),
+            timestamp: Some(value.timestamp.into()),
+        }
+    }
+}
+
+impl LightClientAttackEvidence {
+    /// Create the evidence of an attack by the given conflicting block, as
+    /// detected by a light client which verified both that block and the
+    /// `trusted` one at the same height from the `common` block and its
+    /// validator set.
+    ///
+    /// If the conflicting block was signed by another validator set than the
+    /// trusted one (a lunatic attack), the evidence points at the common block,
+    /// otherwise (an equivocation or amnesia attack) at the trusted one.
+    pub fn new(
+        conflicting_block: ConflictingBlock,
+        trusted: &SignedHeader,
+        common: &SignedHeader,
+        common_validators: &validator::Set,
+    ) -> Self {
+        let mut evidence = Self {
+            conflicting_block,
+            common_height: trusted.header.height,
+            byzantine_validators: Vec::new(),
+            total_voting_power: Default::default(),
+            timestamp: trusted.header.time,
+        };
+        if evidence.conflicting_header_is_invalid(&trusted.header) {
+            evidence.common_height = common.header.height;
+            evidence.timestamp = common.header.time;
+            evidence.total_voting_power = common_validators.total_voting_power();
+        } else {
+            // The validator set hashes of both headers are equal
+            evidence.total_voting_power = evidence
+                .conflicting_block
+                .validator_set
+                .total_voting_power();
+        }
+        evidence.byzantine_validators =
+            evidence.get_byzantine_validators(common_validators, trusted);
+        evidence
+    }
+
+    /// Whether the conflicting header could not have been produced by the
+    /// trusted header's validators, i.e. whether it commits to other
+    /// validators, consensus parameters or application state.
+    pub fn conflicting_header_is_invalid(&self, trusted: &block::Header) -> bool {
+        let conflicting = &self.conflicting_block.signed_header.header;
+        trusted.validators_hash != conflicting.validators_hash
+            || trusted.next_validators_hash != conflicting.next_validators_hash
+            || trusted.consensus_hash != conflicting.consensus_hash
+            || trusted.app_hash != conflicting.app_hash
+            || trusted.last_results_hash != conflicting.last_results_hash
+    }
+
+    /// Determine the validators responsible for the attack, sorted by voting
+    /// power:
+    /// - for a lunatic attack, the validators of the common validator set who
+    ///   signed the conflicting block;
+    /// - for an equivocation attack, i.e. if both blocks were committed in the
+    ///   same round, the validators who voted in both commits;
+    /// - for an amnesia attack, none, as they cannot be told apart from the
+    ///   honest validators.
+    pub fn get_byzantine_validators(
+        &self,
+        common_validators: &validator::Set,
+        trusted: &SignedHeader,
+    ) -> Vec<validator::Info> {
+        let conflicting = &self.conflicting_block.signed_header.commit;
+        let mut validators: Vec<validator::Info> =
+            if self.conflicting_header_is_invalid(&trusted.header) {
+                conflicting
+                    .signatures
+                    .iter()
+                    .filter(|sig| sig.is_commit())
+                    .filter_map(|sig| common_validators.validator(sig.validator_address()?))
+                    .collect()
+            } else if trusted.commit.round == conflicting.round {
+                // Both validator sets are the same, hence so is the order of
+                // the signatures of both commits. Validators who voted in
+                // both, even if for nil, voted twice in the same round.
+                conflicting
+                    .signatures
+                    .iter()
+                    .zip(&trusted.commit.signatures)
+                    .filter(|(a, b)| {
+                        !matches!(a, CommitSig::BlockIdFlagAbsent)
+                            && !matches!(b, CommitSig::BlockIdFlagAbsent)
+                    })
+                    .filter_map(|(a, _)| {
+                        self.conflicting_block
+                            .validator_set
+                            .validator(a.validator_address()?)
+                    })
+                    .collect()
+            } else {
+                Vec::new()
+            };
+        validators.sort_by_key(|v| (core::cmp::Reverse(v.power), v.address));
+        validators
+    }
+}
+
+/// A block conflicting with the one committed at the same height, as provided
+/// to a light client: its signed header and the validator set which signed it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawLightBlock", into = "RawLightBlock")]
+pub struct ConflictingBlock {
+    /// Header and commit of the block
+    pub signed_header: SignedHeader,
+    /// Validator set at the block height
+    pub validator_set: validator::Set,
+}
+
+impl Protobuf<RawLightBlock> for ConflictingBlock {}
+
+impl TryFrom<RawLightBlock> for ConflictingBlock {
+    type Error = Error;
+
+    fn try_from(value: RawLightBlock) -> Result<Self, Self::Error> {
+        Ok(Self {
+            signed_header: value
+                .signed_header
+                .ok_or_else(Error::invalid_signed_header)?
+                .try_into()?,
+            validator_set: value
+                .validator_set
+                .ok_or_else(Error::missing_validator)?
+                .try_into()?,
+        })
+    }
+}
+
+impl From<ConflictingBlock> for RawLightBlock {
+    fn from(value: ConflictingBlock) -> Self {
+        RawLightBlock {
+            signed_header: Some(value.signed_header.into()),
+            validator_set: Some(RawValidatorSet {
+                // Left out by Go, which hashes the encoding of the evidence
+                total_voting_power: 0,
+                ..value.validator_set.into()
+            }),
+        }
     }
 }
 
@@ -204,6 +550,14 @@ impl Data {
     pub fn iter(&self) -> slice::Iter<'_, Evidence> {
         self.as_ref().iter()
     }
+
+    /// Compute the Merkle root of the evidence, as found in the `evidence_hash`
+    /// of the header of the block containing it.
+    pub fn hash(&self) -> Hash {
+        Hash::Sha256(simple_hash_from_byte_vectors(
+            self.iter().map(Evidence::to_bytes).collect(),
+        ))
+    }
 }
 
 impl AsRef<[Evidence]> for Data {
@@ -269,6 +623,43 @@ impl From<Params> for RawEvidenceParams {
     }
 }
 
+/// Evidence parameters as part of the CometBFT v0.38 consensus parameters.
+mod v0_38 {
+    use tendermint_proto::v0_38::types::EvidenceParams as RawEvidenceParams;
+
+    use super::*;
+
+    impl Protobuf<RawEvidenceParams> for Params {}
+
+    impl TryFrom<RawEvidenceParams> for Params {
+        type Error = Error;
+
+        fn try_from(value: RawEvidenceParams) -> Result<Self, Self::Error> {
+            Ok(Self {
+                max_age_num_blocks: value
+                    .max_age_num_blocks
+                    .try_into()
+                    .map_err(Error::negative_max_age_num)?,
+                max_age_duration: value
+                    .max_age_duration
+                    .ok_or_else(Error::missing_max_age_duration)?
+                    .try_into()?,
+                max_bytes: value.max_bytes,
+            })
+        }
+    }
+
+    impl From<Params> for RawEvidenceParams {
+        fn from(value: Params) -> Self {
+            Self {
+                max_age_num_blocks: value.max_age_num_blocks.try_into().unwrap(),
+                max_age_duration: Some(value.max_age_duration.into()),
+                max_bytes: value.max_bytes,
+            }
+        }
+    }
+}
+
 /// Duration is a wrapper around core::time::Duration
 /// essentially, to keep the usages look cleaner
 /// i.e. you can avoid using serde annotations everywhere
@@ -305,3 +696,199 @@ impl From<Duration> for RawDuration {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use core::convert::TryFrom;
+
+    use ed25519_consensus::SigningKey;
+    use tendermint_proto::types::Evidence as RawEvidence;
+
+    use super::{ConflictingBlock, DuplicateVoteEvidence, Evidence, LightClientAttackEvidence};
+    use crate::{
+        account,
+        block::{self, signed_header::SignedHeader, CommitSig},
+        chain,
+        hash::Algorithm,
+        prelude::*,
+        validator, vote, Hash, PublicKey, Signature, Time, Vote,
+    };
+
+    fn signing_key(seed: u8) -> SigningKey {
+        SigningKey::from([seed; 32])
+    }
+
+    fn validators() -> validator::Set {
+        let validators = [(1, 30_u32), (2, 20)]
+            .iter()
+            .map(|&(seed, power)| {
+                validator::Info::new(
+                    PublicKey::Ed25519(signing_key(seed).verification_key()),
+                    power.into(),
+                )
+            })
+            .collect();
+        validator::Set::without_proposer(validators)
+    }
+
+    fn signed_vote(chain_id: &chain::Id, block_hash: u8) -> Vote {
+        let key = signing_key(1);
+        let mut vote = Vote {
+            vote_type: vote::Type::Precommit,
+            height: 10_u32.into(),
+            round: 1_u16.into(),
+            block_id: Some(block::Id {
+                hash: Hash::from_bytes(Algorithm::Sha256, &[block_hash; 32]).unwrap(),
+                part_set_header: block::parts::Header::new(
+                    1,
+                    Hash::from_bytes(Algorithm::Sha256, &[block_hash; 32]).unwrap(),
+                )
+                .unwrap(),
+            }),
+            timestamp: Some(Time::from_unix_timestamp(1_600_000_000, 0).unwrap()),
+            validator_address: account::Id::from(key.verification_key()),
+            validator_index: 0_u32.try_into().unwrap(),
+            signature: None,
+        };
+        let sign_bytes = vote.to_signable_vec(chain_id.clone()).unwrap();
+        vote.signature = Signature::new(key.sign(&sign_bytes).to_bytes()).unwrap();
+        vote
+    }
+
+    #[test]
+    fn duplicate_votes_are_verified() {
+        let chain_id = chain::Id::try_from("test-chain").unwrap();
+        let validators = validators();
+        let block_time = Time::from_unix_timestamp(1_600_000_001, 0).unwrap();
+        let (vote_1, vote_2) = (signed_vote(&chain_id, 2), signed_vote(&chain_id, 1));
+
+        let evidence =
+            DuplicateVoteEvidence::new(vote_1.clone(), vote_2.clone(), block_time, &validators)
+                .unwrap();
+        assert_eq!(evidence.votes(), (&vote_2, &vote_1));
+        assert_eq!(evidence.total_voting_power().value(), 50);
+        assert_eq!(evidence.validator_power().value(), 30);
+        assert_eq!(evidence.timestamp(), block_time);
+        evidence.verify(&chain_id, &validators).unwrap();
+
+        // Another chain, or another validator set
+        let other_chain = chain::Id::try_from("other-chain").unwrap();
+        assert!(evidence.verify(&other_chain, &validators).is_err());
+        let mut others = validators.validators().clone();
+        others.pop();
+        let others = validator::Set::without_proposer(others);
+        assert!(evidence.verify(&chain_id, &others).is_err());
+
+        // Votes out of order, for the same block, or tampered with
+        let mut tampered = evidence.clone();
+        core::mem::swap(&mut tampered.vote_a, &mut tampered.vote_b);
+        assert!(tampered.validate_basic().is_err());
+        let mut tampered = evidence.clone();
+        tampered.vote_b = tampered.vote_a.clone();
+        assert!(tampered.verify(&chain_id, &validators).is_err());
+        let mut tampered = evidence.clone();
+        tampered.vote_b.round = 2_u16.into();
+        assert!(tampered.verify(&chain_id, &validators).is_err());
+        // The validator index is not signed, so only the comparison of the
+        // votes' indices catches this
+        let mut tampered = evidence.clone();
+        tampered.vote_b.validator_index = 1_u32.try_into().unwrap();
+        let err = tampered.verify(&chain_id, &validators).unwrap_err();
+        assert!(
+            err.to_string().contains("validator indices do not match"),
+            "{}",
+            err
+        );
+        let mut tampered = evidence;
+        tampered.vote_b.signature = None;
+        assert!(tampered.verify(&chain_id, &validators).is_err());
+
+        // A vote of a validator outside of the set
+        let mut stranger = signed_vote(&chain_id, 1);
+        stranger.validator_address = account::Id::new([0; account::LENGTH]);
+        assert!(DuplicateVoteEvidence::new(
+            stranger,
+            signed_vote(&chain_id, 2),
+            block_time,
+            &validators
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn duplicate_vote_evidence_roundtrips() {
+        let chain_id = chain::Id::try_from("test-chain").unwrap();
+        let evidence = Evidence::DuplicateVote(Box::new(
+            DuplicateVoteEvidence::new(
+                signed_vote(&chain_id, 1),
+                signed_vote(&chain_id, 2),
+                Time::from_unix_timestamp(1_600_000_001, 0).unwrap(),
+                &validators(),
+            )
+            .unwrap(),
+        ));
+        let raw = RawEvidence::from(evidence.clone());
+        assert_eq!(Evidence::try_from(raw).unwrap(), evidence);
+
+        // Go's JSON encoding, in which only the votes have field tags
+        let json = serde_json::to_value(&evidence).unwrap();
+        assert_eq!(json["type"], "tendermint/DuplicateVoteEvidence");
+        assert_eq!(json["value"]["TotalVotingPower"], "50");
+        assert_eq!(json["value"]["ValidatorPower"], "30");
+        assert_eq!(json["value"]["Timestamp"], "2020-09-13T12:26:41Z");
+        assert_eq!(serde_json::from_value::<Evidence>(json).unwrap(), evidence);
+    }
+
+    fn signed_header(data_hash: u8, signatures: Vec<CommitSig>) -> SignedHeader {
+        let mut header: block::Header = serde_json::from_str(include_str!(
+            "../tests/support/serialization/block/header.json"
+        ))
+        .unwrap();
+        header.data_hash = Some(Hash::from_bytes(Algorithm::Sha256, &[data_hash; 32]).unwrap());
+        let commit = block::Commit {
+            height: header.height,
+            round: 1_u16.into(),
+            block_id: block::Id {
+                hash: header.hash(),
+                part_set_header: block::parts::Header::default(),
+            },
+            signatures,
+        };
+        SignedHeader::new(header, commit).unwrap()
+    }
+
+    #[test]
+    fn equivocating_validators_are_byzantine_unless_absent() {
+        let validators = validators();
+        let (a, b) = (
+            validators.validators()[0].address,
+            validators.validators()[1].address,
+        );
+        let time = Time::from_unix_timestamp(1_600_000_000, 0).unwrap();
+        let commit = |validator_address| CommitSig::BlockIdFlagCommit {
+            validator_address,
+            timestamp: time,
+            signature: None,
+        };
+        let nil = |validator_address| CommitSig::BlockIdFlagNil {
+            validator_address,
+            timestamp: time,
+            signature: None,
+        };
+
+        // The first validator voted for nil in the trusted commit, while the
+        // second one is absent from the conflicting commit
+        let trusted = signed_header(1, vec![nil(a), commit(b)]);
+        let conflicting = ConflictingBlock {
+            signed_header: signed_header(2, vec![commit(a), CommitSig::BlockIdFlagAbsent]),
+            validator_set: validators.clone(),
+        };
+        let evidence = LightClientAttackEvidence::new(conflicting, &trusted, &trusted, &validators);
+        assert!(!evidence.conflicting_header_is_invalid(&trusted.header));
+        assert_eq!(evidence.common_height, trusted.header.height);
+        assert_eq!(
+            evidence.byzantine_validators,
+            vec![validators.validators()[0].clone()]
+        );
+    }
+}
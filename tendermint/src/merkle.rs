@@ -1,9 +1,19 @@
 //! Merkle tree used in Tendermint networks
 
+pub mod ics23;
 pub mod proof;
+pub mod runtime;
+pub mod value_op;
 
 use sha2::{Digest, Sha256};
 
+pub use self::{
+    ics23::{CommitmentOp, PROOF_OP_IAVL_COMMITMENT, PROOF_OP_SIMPLE_MERKLE_COMMITMENT},
+    proof::Proof,
+    runtime::{ProofOperator, ProofRuntime},
+    value_op::{ValueOp, PROOF_OP_VALUE},
+};
+
 use crate::prelude::*;
 
 /// Size of Merkle root hash
@@ -19,6 +29,77 @@ pub fn simple_hash_from_byte_vectors(byte_vecs: Vec<Vec<u8>>) -> Hash {
     simple_hash_from_byte_slices_inner(byte_vecs.as_slice())
 }
 
+/// Compute a simple Merkle root from vectors of arbitrary byte vectors, along
+/// with a proof of inclusion for each of them in the same order.
+pub fn proofs_from_byte_vectors(byte_vecs: Vec<Vec<u8>>) -> (Hash, Vec<Proof>) {
+    let leaf_hashes: Vec<Hash> = byte_vecs.iter().map(|bytes| leaf_hash(bytes)).collect();
+    let (root, trails) = aunts_from_leaf_hashes(&leaf_hashes);
+    let total = leaf_hashes.len() as u64;
+    let proofs = leaf_hashes
+        .into_iter()
+        .zip(trails)
+        .enumerate()
+        .map(|(index, (leaf_hash, aunts))| Proof {
+            total,
+            index: index as u64,
+            leaf_hash,
+            aunts,
+        })
+        .collect();
+    (root, proofs)
+}
+
+// recurse into subtrees, collecting the aunts of each leaf from the bottom of
+// the tree up
+fn aunts_from_leaf_hashes(leaf_hashes: &[Hash]) -> (Hash, Vec<Vec<Hash>>) {
+    match leaf_hashes.len() {
+        0 => (empty_hash(), vec![]),
+        1 => (leaf_hashes[0], vec![vec![]]),
+        length => {
+            let k = get_split_point(length);
+            let (left, mut left_trails) = aunts_from_leaf_hashes(&leaf_hashes[..k]);
+            let (right, right_trails) = aunts_from_leaf_hashes(&leaf_hashes[k..]);
+            for trail in left_trails.iter_mut() {
+                trail.push(right);
+            }
+            left_trails.extend(right_trails.into_iter().map(|mut trail| {
+                trail.push(left);
+                trail
+            }));
+            (inner_hash(&left, &right), left_trails)
+        },
+    }
+}
+
+// recompute the root hash from a leaf hash and its aunts, returning `None` if
+// the aunts do not match the position of the leaf in the tree
+fn compute_hash_from_aunts(
+    index: usize,
+    total: usize,
+    leaf_hash: Hash,
+    aunts: &[Hash],
+) -> Option<Hash> {
+    if index >= total {
+        return None;
+    }
+    if total == 1 {
+        return if aunts.is_empty() {
+            Some(leaf_hash)
+        } else {
+            None
+        };
+    }
+    let (last, rest) = aunts.split_last()?;
+    let k = get_split_point(total);
+    if index < k {
+        let left = compute_hash_from_aunts(index, k, leaf_hash, rest)?;
+        Some(inner_hash(&left, last))
+    } else {
+        let right = compute_hash_from_aunts(index - k, total - k, leaf_hash, rest)?;
+        Some(inner_hash(last, &right))
+    }
+}
+
 // recurse into subtrees
 fn simple_hash_from_byte_slices_inner(byte_slices: &[Vec<u8>]) -> Hash {
     let length = byte_slices.len();
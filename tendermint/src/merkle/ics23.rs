@@ -0,0 +1,958 @@
+//! Verification of [ICS23] commitment proofs, as returned by Cosmos SDK-based
+//! applications for their IAVL stores and for the simple Merkle tree of their
+//! multistore.
+//!
+//! The [`CommitmentOp`] decoders registered with the default
+//! [`ProofRuntime`](super::ProofRuntime) verify such proofs as part of a chain
+//! of [`ProofOp`]s.
+//!
+//! [ICS23]: https://github.com/cosmos/ibc/tree/main/spec/core/ics-023-vector-commitments
+
+use prost::Message;
+use sha2::{Digest, Sha256, Sha512};
+use tendermint_proto::ics23::{
+    batch_entry, commitment_proof, compressed_batch_entry, BatchEntry, BatchProof, CommitmentProof,
+    CompressedBatchProof, CompressedExistenceProof, ExistenceProof, HashOp, InnerOp, InnerSpec,
+    LeafOp, LengthOp, NonExistenceProof, ProofSpec,
+};
+
+use super::{
+    get_split_point,
+    proof::{Proof, ProofOp},
+    runtime::ProofOperator,
+};
+use crate::{prelude::*, Error};
+
+/// The type of the [`ProofOp`]s encoding an ICS23 proof against an IAVL tree.
+pub const PROOF_OP_IAVL_COMMITMENT: &str = "ics23:iavl";
+
+/// The type of the [`ProofOp`]s encoding an ICS23 proof against a simple
+/// Merkle tree, such as a Cosmos SDK multistore.
+pub const PROOF_OP_SIMPLE_MERKLE_COMMITMENT: &str = "ics23:simple";
+
+macro_rules! ensure {
+    ($cond:expr, $($arg:tt)+) => {
+        if !$cond {
+            return Err(Error::invalid_merkle_proof(format!($($arg)+)));
+        }
+    };
+}
+
+/// The [`ProofSpec`] of the IAVL trees of Cosmos SDK stores.
+pub fn iavl_spec() -> ProofSpec {
+    ProofSpec {
+        leaf_spec: Some(LeafOp {
+            hash: HashOp::Sha256.into(),
+            prehash_key: HashOp::NoHash.into(),
+            prehash_value: HashOp::Sha256.into(),
+            length: LengthOp::VarProto.into(),
+            prefix: vec![0],
+        }),
+        inner_spec: Some(InnerSpec {
+            child_order: vec![0, 1],
+            child_size: 33,
+            min_prefix_length: 4,
+            max_prefix_length: 12,
+            empty_child: vec![],
+            hash: HashOp::Sha256.into(),
+        }),
+        max_depth: 0,
+        min_depth: 0,
+    }
+}
+
+/// The [`ProofSpec`] of Tendermint's simple Merkle trees over key/value pairs
+/// encoded as by [`ValueOp::leaf`](super::ValueOp::leaf).
+pub fn tendermint_spec() -> ProofSpec {
+    ProofSpec {
+        leaf_spec: Some(LeafOp {
+            hash: HashOp::Sha256.into(),
+            prehash_key: HashOp::NoHash.into(),
+            prehash_value: HashOp::Sha256.into(),
+            length: LengthOp::VarProto.into(),
+            prefix: vec![0],
+        }),
+        inner_spec: Some(InnerSpec {
+            child_order: vec![0, 1],
+            child_size: 32,
+            min_prefix_length: 1,
+            max_prefix_length: 1,
+            empty_child: vec![],
+            hash: HashOp::Sha256.into(),
+        }),
+        max_depth: 0,
+        min_depth: 0,
+    }
+}
+
+/// Verify that the given proof proves the given key to map to the given value
+/// in the tree with the given root.
+pub fn verify_membership(
+    spec: &ProofSpec,
+    root: &[u8],
+    proof: &CommitmentProof,
+    key: &[u8],
+    value: &[u8],
+) -> Result<(), Error> {
+    let proof = decompress(proof)?;
+    let existence = existence_proof_for_key(&proof, key)
+        .ok_or_else(|| Error::invalid_merkle_proof("no existence proof for the key".to_string()))?;
+    verify_existence(existence, spec, root, key, value)
+}
+
+/// Verify that the given proof proves the absence of the given key from the
+/// tree with the given root.
+pub fn verify_non_membership(
+    spec: &ProofSpec,
+    root: &[u8],
+    proof: &CommitmentProof,
+    key: &[u8],
+) -> Result<(), Error> {
+    let proof = decompress(proof)?;
+    let non_existence = non_existence_proof_for_key(&proof, key).ok_or_else(|| {
+        Error::invalid_merkle_proof("no non-existence proof for the key".to_string())
+    })?;
+    verify_non_existence(non_existence, spec, root, key)
+}
+
+/// Compute the root of the tree the given proof was made against, without
+/// verifying it.
+pub fn calculate_root(proof: &CommitmentProof) -> Result<Vec<u8>, Error> {
+    match &proof.proof {
+        Some(commitment_proof::Proof::Exist(p)) => calculate_existence_root(p),
+        Some(commitment_proof::Proof::Nonexist(p)) => calculate_non_existence_root(p),
+        Some(commitment_proof::Proof::Batch(p)) => match p.entries.first() {
+            Some(BatchEntry {
+                proof: Some(batch_entry::Proof::Exist(p)),
+            }) => calculate_existence_root(p),
+            Some(BatchEntry {
+                proof: Some(batch_entry::Proof::Nonexist(p)),
+            }) => calculate_non_existence_root(p),
+            _ => Err(Error::invalid_merkle_proof(
+                "batch proof has no entries".to_string(),
+            )),
+        },
+        Some(commitment_proof::Proof::Compressed(_)) => calculate_root(&decompress(proof)?),
+        None => Err(Error::invalid_merkle_proof(
+            "empty commitment proof".to_string(),
+        )),
+    }
+}
+
+/// Convert a proof of the inclusion of the leaf encoding the given key/value
+/// pair in a simple Merkle tree into an equivalent ICS23 existence proof,
+/// which can be verified against the [`tendermint_spec`].
+pub fn convert_existence_proof(
+    proof: &Proof,
+    key: &[u8],
+    value: &[u8],
+) -> Result<ExistenceProof, Error> {
+    let mut aunt_is_right = Vec::with_capacity(proof.aunts.len());
+    let (mut index, mut total) = (proof.index, proof.total);
+    // From the root down, whether the leaf is in the left subtree
+    while total > 1 {
+        let split = get_split_point(total as usize) as u64;
+        aunt_is_right.push(index < split);
+        if index < split {
+            total = split;
+        } else {
+            index -= split;
+            total -= split;
+        }
+    }
+    ensure!(
+        aunt_is_right.len() == proof.aunts.len(),
+        "expected {} aunts, got {}",
+        aunt_is_right.len(),
+        proof.aunts.len()
+    );
+    let path = proof
+        .aunts
+        .iter()
+        .zip(aunt_is_right.iter().rev())
+        .map(|(aunt, &is_right)| {
+            let mut inner = InnerOp {
+                hash: HashOp::Sha256.into(),
+                prefix: vec![1],
+                suffix: vec![],
+            };
+            if is_right {
+                inner.suffix = aunt.to_vec();
+            } else {
+                inner.prefix.extend_from_slice(aunt);
+            }
+            inner
+        })
+        .collect();
+    Ok(ExistenceProof {
+        key: key.to_vec(),
+        value: value.to_vec(),
+        leaf: tendermint_spec().leaf_spec,
+        path,
+    })
+}
+
+/// Verifies an ICS23 [`CommitmentProof`] of the value (or the absence of any
+/// value) under its key against the root it computes, as part of a chain of
+/// [`ProofOp`]s.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CommitmentOp {
+    field_type: String,
+    spec: ProofSpec,
+    key: Vec<u8>,
+    proof: CommitmentProof,
+}
+
+impl CommitmentOp {
+    /// Constructor for an operation verifying proofs against an IAVL tree.
+    pub fn new_iavl(key: Vec<u8>, proof: CommitmentProof) -> Self {
+        Self {
+            field_type: PROOF_OP_IAVL_COMMITMENT.to_string(),
+            spec: iavl_spec(),
+            key,
+            proof,
+        }
+    }
+
+    /// Constructor for an operation verifying proofs against a simple Merkle
+    /// tree.
+    pub fn new_simple(key: Vec<u8>, proof: CommitmentProof) -> Self {
+        Self {
+            field_type: PROOF_OP_SIMPLE_MERKLE_COMMITMENT.to_string(),
+            spec: tendermint_spec(),
+            key,
+            proof,
+        }
+    }
+
+    /// Decode a [`PROOF_OP_IAVL_COMMITMENT`] or
+    /// [`PROOF_OP_SIMPLE_MERKLE_COMMITMENT`] operation. Usable as an
+    /// [`OpDecoder`](super::runtime::OpDecoder).
+    pub fn decode(op: &ProofOp) -> Result<Box<dyn ProofOperator>, Error> {
+        let new = match op.field_type.as_str() {
+            PROOF_OP_IAVL_COMMITMENT => Self::new_iavl,
+            PROOF_OP_SIMPLE_MERKLE_COMMITMENT => Self::new_simple,
+            _ => return Err(Error::unknown_proof_op_type(op.field_type.clone())),
+        };
+        let proof = CommitmentProof::decode(op.data.as_slice())
+            .map_err(|e| Error::invalid_merkle_proof(format!("invalid commitment proof: {}", e)))?;
+        Ok(Box::new(new(op.key.clone(), proof)))
+    }
+}
+
+impl ProofOperator for CommitmentOp {
+    fn run(&self, args: Vec<Vec<u8>>) -> Result<Vec<Vec<u8>>, Error> {
+        let root = calculate_root(&self.proof)?;
+        match args.as_slice() {
+            [] => verify_non_membership(&self.spec, &root, &self.proof, &self.key)?,
+            [value] => verify_membership(&self.spec, &root, &self.proof, &self.key, value)?,
+            _ => {
+                return Err(Error::invalid_merkle_proof(format!(
+                    "expected 0 or 1 arguments, got {}",
+                    args.len()
+                )))
+            },
+        }
+        Ok(vec![root])
+    }
+
+    fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    fn proof_op(&self) -> ProofOp {
+        ProofOp {
+            field_type: self.field_type.clone(),
+            key: self.key.clone(),
+            data: self.proof.encode_to_vec(),
+        }
+    }
+}
+
+fn existence_proof_for_key<'a>(
+    proof: &'a CommitmentProof,
+    key: &[u8],
+) -> Option<&'a ExistenceProof> {
+    match &proof.proof {
+        Some(commitment_proof::Proof::Exist(p)) if p.key == key => Some(p),
+        Some(commitment_proof::Proof::Batch(batch)) => {
+            batch.entries.iter().find_map(|entry| match &entry.proof {
+                Some(batch_entry::Proof::Exist(p)) if p.key == key => Some(p),
+                _ => None,
+            })
+        },
+        _ => None,
+    }
+}
+
+fn non_existence_proof_for_key<'a>(
+    proof: &'a CommitmentProof,
+    key: &[u8],
+) -> Option<&'a NonExistenceProof> {
+    let surrounds_key = |p: &NonExistenceProof| {
+        !matches!(&p.left, Some(left) if left.key.as_slice() >= key)
+            && !matches!(&p.right, Some(right) if right.key.as_slice() <= key)
+    };
+    match &proof.proof {
+        Some(commitment_proof::Proof::Nonexist(p)) if surrounds_key(p) => Some(p),
+        Some(commitment_proof::Proof::Batch(batch)) => {
+            batch.entries.iter().find_map(|entry| match &entry.proof {
+                Some(batch_entry::Proof::Nonexist(p)) if surrounds_key(p) => Some(p),
+                _ => None,
+            })
+        },
+        _ => None,
+    }
+}
+
+// Expand a compressed batch proof, leaving other proofs untouched
+fn decompress(proof: &CommitmentProof) -> Result<CommitmentProof, Error> {
+    let compressed = match &proof.proof {
+        Some(commitment_proof::Proof::Compressed(compressed)) => compressed,
+        _ => return Ok(proof.clone()),
+    };
+    let entries = compressed
+        .entries
+        .iter()
+        .map(|entry| {
+            let proof = match &entry.proof {
+                Some(compressed_batch_entry::Proof::Exist(p)) => Some(batch_entry::Proof::Exist(
+                    decompress_existence(compressed, p)?,
+                )),
+                Some(compressed_batch_entry::Proof::Nonexist(p)) => {
+                    Some(batch_entry::Proof::Nonexist(NonExistenceProof {
+                        key: p.key.clone(),
+                        left: p
+                            .left
+                            .as_ref()
+                            .map(|left| decompress_existence(compressed, left))
+                            .transpose()?,
+                        right: p
+                            .right
+                            .as_ref()
+                            .map(|right| decompress_existence(compressed, right))
+                            .transpose()?,
+                    }))
+                },
+                None => None,
+            };
+            Ok(BatchEntry { proof })
+        })
+        .collect::<Result<_, Error>>()?;
+    Ok(CommitmentProof {
+        proof: Some(commitment_proof::Proof::Batch(BatchProof { entries })),
+    })
+}
+
+fn decompress_existence(
+    compressed: &CompressedBatchProof,
+    proof: &CompressedExistenceProof,
+) -> Result<ExistenceProof, Error> {
+    let path = proof
+        .path
+        .iter()
+        .map(|&i| {
+            usize::try_from(i)
+                .ok()
+                .and_then(|i| compressed.lookup_inners.get(i))
+                .cloned()
+                .ok_or_else(|| {
+                    Error::invalid_merkle_proof(format!("invalid inner op lookup index {}", i))
+                })
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(ExistenceProof {
+        key: proof.key.clone(),
+        value: proof.value.clone(),
+        leaf: proof.leaf.clone(),
+        path,
+    })
+}
+
+fn verify_existence(
+    proof: &ExistenceProof,
+    spec: &ProofSpec,
+    root: &[u8],
+    key: &[u8],
+    value: &[u8],
+) -> Result<(), Error> {
+    check_existence_spec(proof, spec)?;
+    ensure!(proof.key == key, "provided key does not match the proof");
+    ensure!(
+        proof.value == value,
+        "provided value does not match the proof"
+    );
+    let calculated = calculate_existence_root(proof)?;
+    ensure!(
+        calculated == root,
+        "calculated root does not match the root"
+    );
+    Ok(())
+}
+
+fn verify_non_existence(
+    proof: &NonExistenceProof,
+    spec: &ProofSpec,
+    root: &[u8],
+    key: &[u8],
+) -> Result<(), Error> {
+    let inner_spec = spec
+        .inner_spec
+        .as_ref()
+        .ok_or_else(|| Error::invalid_merkle_proof("spec has no inner spec".to_string()))?;
+    if let Some(left) = &proof.left {
+        verify_existence(left, spec, root, &left.key, &left.value)?;
+        ensure!(
+            key > left.key.as_slice(),
+            "key is not right of the left proof"
+        );
+    }
+    if let Some(right) = &proof.right {
+        verify_existence(right, spec, root, &right.key, &right.value)?;
+        ensure!(
+            key < right.key.as_slice(),
+            "key is not left of the right proof"
+        );
+    }
+    match (&proof.left, &proof.right) {
+        (None, None) => Err(Error::invalid_merkle_proof(
+            "both left and right proofs are missing".to_string(),
+        )),
+        (None, Some(right)) => {
+            ensure!(
+                is_left_most(inner_spec, &right.path),
+                "left proof is missing, so the right proof must be left-most"
+            );
+            Ok(())
+        },
+        (Some(left), None) => {
+            ensure!(
+                is_right_most(inner_spec, &left.path),
+                "right proof is missing, so the left proof must be right-most"
+            );
+            Ok(())
+        },
+        (Some(left), Some(right)) => {
+            ensure!(
+                is_left_neighbor(inner_spec, &left.path, &right.path),
+                "left and right proofs are not neighbors"
+            );
+            Ok(())
+        },
+    }
+}
+
+fn check_existence_spec(proof: &ExistenceProof, spec: &ProofSpec) -> Result<(), Error> {
+    let (leaf_spec, inner_spec) = match (&spec.leaf_spec, &spec.inner_spec) {
+        (Some(leaf_spec), Some(inner_spec)) => (leaf_spec, inner_spec),
+        _ => {
+            return Err(Error::invalid_merkle_proof(
+                "spec has no leaf or inner spec".to_string(),
+            ))
+        },
+    };
+    let leaf = proof
+        .leaf
+        .as_ref()
+        .ok_or_else(|| Error::invalid_merkle_proof("existence proof has no leaf op".to_string()))?;
+    ensure!(
+        leaf.hash == leaf_spec.hash
+            && leaf.prehash_key == leaf_spec.prehash_key
+            && leaf.prehash_value == leaf_spec.prehash_value
+            && leaf.length == leaf_spec.length,
+        "leaf op does not match the spec"
+    );
+    ensure!(
+        leaf.prefix.starts_with(&leaf_spec.prefix),
+        "leaf op prefix does not start with the spec's prefix"
+    );
+    let is_iavl = is_iavl_spec(spec);
+    if is_iavl {
+        let remaining = check_iavl_prefix(&leaf.prefix, 0)?;
+        ensure!(
+            remaining == 0,
+            "IAVL leaf op prefix has {} unexpected trailing bytes",
+            remaining
+        );
+    }
+    if spec.min_depth > 0 {
+        ensure!(
+            proof.path.len() >= spec.min_depth as usize,
+            "proof path is shorter than the spec's minimum depth"
+        );
+    }
+    if spec.max_depth > 0 {
+        ensure!(
+            proof.path.len() <= spec.max_depth as usize,
+            "proof path is longer than the spec's maximum depth"
+        );
+    }
+    let child_size = positive(inner_spec.child_size)?;
+    let max_left_child_bytes = inner_spec.child_order.len().saturating_sub(1) * child_size;
+    for (index, inner) in proof.path.iter().enumerate() {
+        ensure!(
+            inner.hash == inner_spec.hash,
+            "inner op hash does not match the spec"
+        );
+        if is_iavl {
+            // Inner ops are followed by the length prefix of the child's hash,
+            // preceded by the left sibling's hash and its length prefix, if any
+            let remaining = check_iavl_prefix(&inner.prefix, index + 1)?;
+            ensure!(
+                remaining == 1 || remaining == 1 + child_size,
+                "IAVL inner op prefix has {} unexpected trailing bytes",
+                remaining
+            );
+        }
+        ensure!(
+            !inner.prefix.starts_with(&leaf_spec.prefix),
+            "inner op prefix starts with the leaf prefix"
+        );
+        ensure!(
+            inner.prefix.len() >= positive(inner_spec.min_prefix_length)?,
+            "inner op prefix is too short"
+        );
+        ensure!(
+            inner.prefix.len() <= positive(inner_spec.max_prefix_length)? + max_left_child_bytes,
+            "inner op prefix is too long"
+        );
+        ensure!(
+            inner.suffix.len() % child_size == 0,
+            "inner op suffix is malformed"
+        );
+    }
+    Ok(())
+}
+
+fn is_iavl_spec(spec: &ProofSpec) -> bool {
+    let iavl = iavl_spec();
+    spec.leaf_spec == iavl.leaf_spec && spec.inner_spec == iavl.inner_spec
+}
+
+// Check the height, size and version of the subtree that start the prefix of
+// an IAVL leaf op (at layer 0) or inner op (at layers 1 and above), as ICS23's
+// `validateIavlOps` does, returning the length of the rest of the prefix.
+fn check_iavl_prefix(prefix: &[u8], layer: usize) -> Result<usize, Error> {
+    let mut buf = prefix;
+    let mut read_varint = |field: &str| {
+        prost::encoding::decode_varint(&mut buf)
+            // Zigzag-decode the signed varint
+            .map(|n| (n >> 1) as i64 ^ -((n & 1) as i64))
+            .map_err(|e| Error::invalid_merkle_proof(format!("invalid IAVL {}: {}", field, e)))
+    };
+    let height = read_varint("height")?;
+    let size = read_varint("size")?;
+    let version = read_varint("version")?;
+    ensure!(
+        height >= 0 && height as u64 >= layer as u64,
+        "IAVL height {} is lower than the layer number {}",
+        height,
+        layer
+    );
+    ensure!(size >= 0, "IAVL size {} is negative", size);
+    ensure!(version >= 0, "IAVL version {} is negative", version);
+    if layer == 0 {
+        ensure!(height == 0, "IAVL leaf height {} is not 0", height);
+        ensure!(size == 1, "IAVL leaf size {} is not 1", size);
+    }
+    Ok(buf.len())
+}
+
+fn calculate_existence_root(proof: &ExistenceProof) -> Result<Vec<u8>, Error> {
+    ensure!(!proof.key.is_empty(), "existence proof has an empty key");
+    ensure!(
+        !proof.value.is_empty(),
+        "existence proof has an empty value"
+    );
+    let leaf = proof
+        .leaf
+        .as_ref()
+        .ok_or_else(|| Error::invalid_merkle_proof("existence proof has no leaf op".to_string()))?;
+    let mut hash = apply_leaf(leaf, &proof.key, &proof.value)?;
+    for inner in &proof.path {
+        hash = apply_inner(inner, &hash)?;
+    }
+    Ok(hash)
+}
+
+fn calculate_non_existence_root(proof: &NonExistenceProof) -> Result<Vec<u8>, Error> {
+    match (&proof.left, &proof.right) {
+        (Some(existence), _) | (None, Some(existence)) => calculate_existence_root(existence),
+        (None, None) => Err(Error::invalid_merkle_proof(
+            "both left and right proofs are missing".to_string(),
+        )),
+    }
+}
+
+fn apply_leaf(leaf: &LeafOp, key: &[u8], value: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut data = leaf.prefix.clone();
+    data.extend(do_length(leaf.length, do_hash(leaf.prehash_key, key)?)?);
+    data.extend(do_length(leaf.length, do_hash(leaf.prehash_value, value)?)?);
+    do_hash(leaf.hash, &data)
+}
+
+fn apply_inner(inner: &InnerOp, child: &[u8]) -> Result<Vec<u8>, Error> {
+    ensure!(!child.is_empty(), "inner op needs a child value");
+    let mut data = inner.prefix.clone();
+    data.extend_from_slice(child);
+    data.extend_from_slice(&inner.suffix);
+    do_hash(inner.hash, &data)
+}
+
+fn do_hash(hash_op: i32, data: &[u8]) -> Result<Vec<u8>, Error> {
+    match HashOp::from_i32(hash_op) {
+        Some(HashOp::NoHash) => Ok(data.to_vec()),
+        Some(HashOp::Sha256) => Ok(Sha256::digest(data).to_vec()),
+        Some(HashOp::Sha512) => Ok(Sha512::digest(data).to_vec()),
+        _ => Err(Error::invalid_merkle_proof(format!(
+            "unsupported hash operation {}",
+            hash_op
+        ))),
+    }
+}
+
+fn do_length(length_op: i32, data: Vec<u8>) -> Result<Vec<u8>, Error> {
+    match LengthOp::from_i32(length_op) {
+        Some(LengthOp::NoPrefix) => Ok(data),
+        Some(LengthOp::VarProto) => {
+            let mut prefixed = Vec::with_capacity(data.len() + 10);
+            prost::encoding::encode_varint(data.len() as u64, &mut prefixed);
+            prefixed.extend(data);
+            Ok(prefixed)
+        },
+        Some(LengthOp::Fixed32Little) => {
+            let mut prefixed = (data.len() as u32).to_le_bytes().to_vec();
+            prefixed.extend(data);
+            Ok(prefixed)
+        },
+        Some(LengthOp::Require32Bytes) => {
+            ensure!(data.len() == 32, "expected 32 bytes, got {}", data.len());
+            Ok(data)
+        },
+        Some(LengthOp::Require64Bytes) => {
+            ensure!(data.len() == 64, "expected 64 bytes, got {}", data.len());
+            Ok(data)
+        },
+        _ => Err(Error::invalid_merkle_proof(format!(
+            "unsupported length operation {}",
+            length_op
+        ))),
+    }
+}
+
+fn positive(value: i32) -> Result<usize, Error> {
+    usize::try_from(value)
+        .map_err(|_| Error::invalid_merkle_proof(format!("invalid spec value {}", value)))
+}
+
+// The range of prefix lengths and the suffix length of the inner ops of the
+// given branch of a node
+struct Padding {
+    min_prefix: usize,
+    max_prefix: usize,
+    suffix: usize,
+}
+
+fn padding(spec: &InnerSpec, branch: usize) -> Option<Padding> {
+    let index = spec
+        .child_order
+        .iter()
+        .position(|&child| usize::try_from(child).ok() == Some(branch))?;
+    let child_size = usize::try_from(spec.child_size).ok()?;
+    let prefix = index * child_size;
+    Some(Padding {
+        min_prefix: prefix + usize::try_from(spec.min_prefix_length).ok()?,
+        max_prefix: prefix + usize::try_from(spec.max_prefix_length).ok()?,
+        suffix: (spec.child_order.len() - 1 - index) * child_size,
+    })
+}
+
+fn has_padding(inner: &InnerOp, padding: &Padding) -> bool {
+    inner.prefix.len() >= padding.min_prefix
+        && inner.prefix.len() <= padding.max_prefix
+        && inner.suffix.len() == padding.suffix
+}
+
+fn is_branch(spec: &InnerSpec, path: &[InnerOp], branch: usize) -> bool {
+    match padding(spec, branch) {
+        Some(padding) => path.iter().all(|inner| has_padding(inner, &padding)),
+        None => false,
+    }
+}
+
+fn is_left_most(spec: &InnerSpec, path: &[InnerOp]) -> bool {
+    is_branch(spec, path, 0)
+}
+
+fn is_right_most(spec: &InnerSpec, path: &[InnerOp]) -> bool {
+    match spec.child_order.len().checked_sub(1) {
+        Some(last) => is_branch(spec, path, last),
+        None => false,
+    }
+}
+
+// Whether the two paths (ordered from the leaf to the root) lead to adjacent
+// leaves
+fn is_left_neighbor(spec: &InnerSpec, left: &[InnerOp], right: &[InnerOp]) -> bool {
+    let (mut left, mut right) = (left, right);
+    // Skip the common path from the root
+    loop {
+        match (left.split_last(), right.split_last()) {
+            (Some((top_left, rest_left)), Some((top_right, rest_right))) => {
+                if top_left.prefix == top_right.prefix && top_left.suffix == top_right.suffix {
+                    left = rest_left;
+                    right = rest_right;
+                    continue;
+                }
+                // The paths diverge here, into adjacent branches
+                return is_left_step(spec, top_left, top_right)
+                    && is_right_most(spec, rest_left)
+                    && is_left_most(spec, rest_right);
+            },
+            _ => return false,
+        }
+    }
+}
+
+fn is_left_step(spec: &InnerSpec, left: &InnerOp, right: &InnerOp) -> bool {
+    match (
+        order_from_padding(spec, left),
+        order_from_padding(spec, right),
+    ) {
+        (Some(left), Some(right)) => right == left + 1,
+        _ => false,
+    }
+}
+
+fn order_from_padding(spec: &InnerSpec, inner: &InnerOp) -> Option<usize> {
+    (0..spec.child_order.len()).find(
+        |&branch| matches!(padding(spec, branch), Some(padding) if has_padding(inner, &padding)),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::merkle::{proofs_from_byte_vectors, runtime::ProofRuntime, ValueOp};
+
+    const KEYS: [&str; 5] = ["b", "d", "f", "h", "j"];
+
+    // A simple Merkle tree over the given keys, each mapping to its uppercase
+    // version, along with ICS23 existence proofs for each of them
+    fn tree(keys: &[&str]) -> (Vec<u8>, Vec<ExistenceProof>) {
+        let leaves = keys
+            .iter()
+            .map(|key| ValueOp::leaf(key.as_bytes(), key.to_uppercase().as_bytes()))
+            .collect();
+        let (root, proofs) = proofs_from_byte_vectors(leaves);
+        let proofs = proofs
+            .iter()
+            .zip(keys)
+            .map(|(proof, key)| {
+                convert_existence_proof(proof, key.as_bytes(), key.to_uppercase().as_bytes())
+                    .unwrap()
+            })
+            .collect();
+        (root.to_vec(), proofs)
+    }
+
+    fn exist(proof: ExistenceProof) -> CommitmentProof {
+        CommitmentProof {
+            proof: Some(commitment_proof::Proof::Exist(proof)),
+        }
+    }
+
+    fn non_exist(
+        key: &str,
+        left: Option<ExistenceProof>,
+        right: Option<ExistenceProof>,
+    ) -> CommitmentProof {
+        CommitmentProof {
+            proof: Some(commitment_proof::Proof::Nonexist(NonExistenceProof {
+                key: key.as_bytes().to_vec(),
+                left,
+                right,
+            })),
+        }
+    }
+
+    #[test]
+    fn existence_proofs_are_verified() {
+        let spec = tendermint_spec();
+        let (root, proofs) = tree(&KEYS);
+        for (key, proof) in KEYS.iter().zip(&proofs) {
+            let proof = exist(proof.clone());
+            assert_eq!(calculate_root(&proof).unwrap(), root);
+            let value = key.to_uppercase();
+            verify_membership(&spec, &root, &proof, key.as_bytes(), value.as_bytes()).unwrap();
+            assert!(verify_membership(&spec, &root, &proof, key.as_bytes(), b"X").is_err());
+            assert!(verify_membership(&spec, &root, &proof, b"x", value.as_bytes()).is_err());
+            assert!(
+                verify_membership(&spec, &[0; 32], &proof, key.as_bytes(), value.as_bytes())
+                    .is_err()
+            );
+        }
+
+        // IAVL inner ops have longer prefixes, and children of another size
+        let proof = exist(proofs[0].clone());
+        assert!(verify_membership(&iavl_spec(), &root, &proof, b"b", b"B").is_err());
+    }
+
+    #[test]
+    fn iavl_prefixes_are_checked() {
+        // A leaf of height 0 and size 1 at version 1, as the left child of an
+        // inner node of height 1 and size 2, itself the right child of the
+        // root of height 2 and size 3, both at version 2
+        let leaf_prefix = vec![0, 2, 2];
+        let inner_prefix = vec![2, 4, 4, 32];
+        let mut root_prefix = vec![4, 6, 4, 32];
+        root_prefix.extend_from_slice(&[2; 32]);
+        root_prefix.push(32);
+        let proof = |leaf_prefix: &[u8], inner_prefix: &[u8], root_prefix: &[u8]| {
+            let mut suffix = vec![32];
+            suffix.extend_from_slice(&[1; 32]);
+            let proof = exist(ExistenceProof {
+                key: b"a".to_vec(),
+                value: b"A".to_vec(),
+                leaf: Some(LeafOp {
+                    prefix: leaf_prefix.to_vec(),
+                    ..iavl_spec().leaf_spec.unwrap()
+                }),
+                path: vec![
+                    InnerOp {
+                        hash: HashOp::Sha256.into(),
+                        prefix: inner_prefix.to_vec(),
+                        suffix,
+                    },
+                    InnerOp {
+                        hash: HashOp::Sha256.into(),
+                        prefix: root_prefix.to_vec(),
+                        suffix: vec![],
+                    },
+                ],
+            });
+            let root = calculate_root(&proof).unwrap();
+            verify_membership(&iavl_spec(), &root, &proof, b"a", b"A")
+        };
+        proof(&leaf_prefix, &inner_prefix, &root_prefix).unwrap();
+
+        // Leaves of another size, or followed by more data
+        assert!(proof(&[0, 4, 2], &inner_prefix, &root_prefix).is_err());
+        assert!(proof(&[0, 2, 2, 0], &inner_prefix, &root_prefix).is_err());
+        // Negative versions
+        assert!(proof(&[0, 2, 1], &inner_prefix, &root_prefix).is_err());
+        // Inner nodes no higher than their children
+        assert!(proof(&leaf_prefix, &inner_prefix, &[2, 6, 4, 32]).is_err());
+        // Inner nodes with malformed children, or truncated varints
+        assert!(proof(&leaf_prefix, &[2, 4, 4, 32, 0], &root_prefix).is_err());
+        assert!(proof(&leaf_prefix, &[2, 4, 0x80, 0x80], &root_prefix).is_err());
+    }
+
+    #[test]
+    fn non_existence_proofs_are_verified() {
+        let spec = tendermint_spec();
+        let (root, proofs) = tree(&KEYS);
+        let p = |i: usize| Some(proofs[i].clone());
+
+        // Between neighbors, before the first and after the last key
+        let proof = non_exist("e", p(1), p(2));
+        verify_non_membership(&spec, &root, &proof, b"e").unwrap();
+        let proof = non_exist("a", None, p(0));
+        verify_non_membership(&spec, &root, &proof, b"a").unwrap();
+        let proof = non_exist("k", p(4), None);
+        verify_non_membership(&spec, &root, &proof, b"k").unwrap();
+
+        // Keys that are not between the neighbors
+        let proof = non_exist("e", p(1), p(2));
+        assert!(verify_non_membership(&spec, &root, &proof, b"d").is_err());
+        assert!(verify_non_membership(&spec, &root, &proof, b"g").is_err());
+        // Leaves that are not neighbors
+        let proof = non_exist("e", p(1), p(3));
+        assert!(verify_non_membership(&spec, &root, &proof, b"e").is_err());
+        // Leaves that are not at the edges of the tree
+        let proof = non_exist("c", None, p(1));
+        assert!(verify_non_membership(&spec, &root, &proof, b"c").is_err());
+        let proof = non_exist("i", p(3), None);
+        assert!(verify_non_membership(&spec, &root, &proof, b"i").is_err());
+    }
+
+    #[test]
+    fn commitment_ops_are_chained() {
+        // A multistore whose "bank" store contains the keys
+        let (store_root, store_proofs) = tree(&KEYS);
+        let stores = ["acc", "bank", "staking"];
+        let leaves = stores
+            .iter()
+            .map(|store| {
+                let root = if *store == "bank" {
+                    store_root.clone()
+                } else {
+                    Sha256::digest(store.as_bytes()).to_vec()
+                };
+                ValueOp::leaf(store.as_bytes(), &root)
+            })
+            .collect();
+        let (app_hash, multistore_proofs) = proofs_from_byte_vectors(leaves);
+        let multistore_proof =
+            convert_existence_proof(&multistore_proofs[1], b"bank", &store_root).unwrap();
+
+        let proof_ops = |store_proof: CommitmentProof, key: &str| crate::merkle::proof::ProofOps {
+            ops: vec![
+                CommitmentOp::new_simple(key.as_bytes().to_vec(), store_proof).proof_op(),
+                CommitmentOp::new_simple(b"bank".to_vec(), exist(multistore_proof.clone()))
+                    .proof_op(),
+            ],
+        };
+        let runtime = ProofRuntime::default();
+
+        let proof = proof_ops(exist(store_proofs[2].clone()), "f");
+        runtime
+            .verify_value(&proof, &app_hash, "/bank/f", b"F")
+            .unwrap();
+        assert!(runtime
+            .verify_value(&proof, &app_hash, "/bank/f", b"G")
+            .is_err());
+        assert!(runtime
+            .verify_value(&proof, &app_hash, "/acc/f", b"F")
+            .is_err());
+
+        let proof = proof_ops(
+            non_exist(
+                "g",
+                Some(store_proofs[2].clone()),
+                Some(store_proofs[3].clone()),
+            ),
+            "g",
+        );
+        runtime
+            .verify_absence(&proof, &app_hash, "/bank/g")
+            .unwrap();
+        assert!(runtime
+            .verify_value(&proof, &app_hash, "/bank/g", b"G")
+            .is_err());
+    }
+
+    #[test]
+    fn compressed_proofs_are_decompressed() {
+        let spec = tendermint_spec();
+        let (root, proofs) = tree(&KEYS);
+        let lookup_inners = proofs[0].path.clone();
+        let proof = CommitmentProof {
+            proof: Some(commitment_proof::Proof::Compressed(CompressedBatchProof {
+                entries: vec![tendermint_proto::ics23::CompressedBatchEntry {
+                    proof: Some(compressed_batch_entry::Proof::Exist(
+                        CompressedExistenceProof {
+                            key: proofs[0].key.clone(),
+                            value: proofs[0].value.clone(),
+                            leaf: proofs[0].leaf.clone(),
+                            path: (0..lookup_inners.len() as i32).collect(),
+                        },
+                    )),
+                }],
+                lookup_inners,
+            })),
+        };
+        assert_eq!(calculate_root(&proof).unwrap(), root);
+        verify_membership(&spec, &root, &proof, b"b", b"B").unwrap();
+    }
+}
@@ -3,16 +3,129 @@ use core::convert::TryFrom;
 
 use serde::{Deserialize, Serialize};
 use tendermint_proto::{
-    crypto::{ProofOp as RawProofOp, ProofOps as RawProofOps},
+    crypto::{Proof as RawProof, ProofOp as RawProofOp, ProofOps as RawProofOps},
     Protobuf,
 };
 
+use super::{compute_hash_from_aunts, leaf_hash, Hash, HASH_SIZE};
 use crate::{prelude::*, serializers, Error};
 
-/// Proof is Merkle proof defined by the list of ProofOps
-/// <https://github.com/tendermint/tendermint/blob/c8483531d8e756f7fbb812db1dd16d841cdf298a/crypto/merkle/merkle.proto#L26>
+/// The maximum number of aunts in a [`Proof`], which bounds the number of
+/// leaves of the tree it proves inclusion in to 2^100.
+pub const MAX_AUNTS: usize = 100;
+
+/// Proof of the inclusion of a leaf in a simple Merkle tree, as produced by
+/// [`proofs_from_byte_vectors`].
+///
+/// <https://github.com/tendermint/tendermint/blob/c8483531d8e756f7fbb812db1dd16d841cdf298a/proto/tendermint/crypto/proof.proto#L9>
+///
+/// [`proofs_from_byte_vectors`]: super::proofs_from_byte_vectors
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawProof", into = "RawProof")]
 pub struct Proof {
+    /// The number of leaves in the tree
+    pub total: u64,
+    /// The index of the proven leaf
+    pub index: u64,
+    /// The hash of the proven leaf
+    pub leaf_hash: Hash,
+    /// The hashes of the sibling subtrees on the path from the leaf to the
+    /// root, starting from the bottom of the tree
+    pub aunts: Vec<Hash>,
+}
+
+impl Proof {
+    /// Verify that this proves the inclusion of the given leaf in the tree
+    /// with the given root hash.
+    pub fn verify(&self, root_hash: &Hash, leaf: &[u8]) -> Result<(), Error> {
+        if self.leaf_hash != leaf_hash(leaf) {
+            return Err(Error::invalid_merkle_proof(
+                "leaf hash does not match the leaf".to_string(),
+            ));
+        }
+        match self.compute_root_hash() {
+            Some(computed) if computed == *root_hash => Ok(()),
+            Some(_) => Err(Error::invalid_merkle_proof(
+                "computed root hash does not match the root hash".to_string(),
+            )),
+            None => Err(Error::invalid_merkle_proof(
+                "number of aunts does not match the size of the tree".to_string(),
+            )),
+        }
+    }
+
+    /// Compute the root hash of the tree from the leaf hash and the aunts.
+    ///
+    /// Returns `None` if the index or the number of aunts are inconsistent
+    /// with the size of the tree.
+    pub fn compute_root_hash(&self) -> Option<Hash> {
+        let index = usize::try_from(self.index).ok()?;
+        let total = usize::try_from(self.total).ok()?;
+        compute_hash_from_aunts(index, total, self.leaf_hash, &self.aunts)
+    }
+}
+
+impl Protobuf<RawProof> for Proof {}
+
+impl TryFrom<RawProof> for Proof {
+    type Error = Error;
+
+    fn try_from(value: RawProof) -> Result<Self, Self::Error> {
+        if value.aunts.len() > MAX_AUNTS {
+            return Err(Error::invalid_merkle_proof(format!(
+                "expected no more than {} aunts, got {}",
+                MAX_AUNTS,
+                value.aunts.len()
+            )));
+        }
+        Ok(Self {
+            total: value
+                .total
+                .try_into()
+                .map_err(Error::negative_proof_total)?,
+            index: value
+                .index
+                .try_into()
+                .map_err(Error::negative_proof_index)?,
+            leaf_hash: to_hash(&value.leaf_hash)?,
+            aunts: value
+                .aunts
+                .iter()
+                .map(|aunt| to_hash(aunt))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<Proof> for RawProof {
+    fn from(value: Proof) -> Self {
+        RawProof {
+            // Both are bounded by the number of leaves, which always fits
+            total: value.total as i64,
+            index: value.index as i64,
+            leaf_hash: value.leaf_hash.to_vec(),
+            aunts: value.aunts.iter().map(|aunt| aunt.to_vec()).collect(),
+        }
+    }
+}
+
+fn to_hash(bytes: &[u8]) -> Result<Hash, Error> {
+    if bytes.len() != HASH_SIZE {
+        return Err(Error::invalid_hash_size());
+    }
+    let mut hash = [0_u8; HASH_SIZE];
+    hash.copy_from_slice(bytes);
+    Ok(hash)
+}
+
+/// ProofOps is Merkle proof defined by the list of ProofOps
+///
+/// This type used to be named `Proof`, which now names a [`Proof`] of
+/// inclusion in a simple Merkle tree.
+///
+/// <https://github.com/tendermint/tendermint/blob/c8483531d8e756f7fbb812db1dd16d841cdf298a/crypto/merkle/merkle.proto#L26>
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct ProofOps {
     /// The list of ProofOps
     pub ops: Vec<ProofOp>,
 }
@@ -58,9 +171,9 @@ impl From<ProofOp> for RawProofOp {
     }
 }
 
-impl Protobuf<RawProofOps> for Proof {}
+impl Protobuf<RawProofOps> for ProofOps {}
 
-impl TryFrom<RawProofOps> for Proof {
+impl TryFrom<RawProofOps> for ProofOps {
     type Error = Error;
 
     fn try_from(value: RawProofOps) -> Result<Self, Self::Error> {
@@ -70,8 +183,8 @@ impl TryFrom<RawProofOps> for Proof {
     }
 }
 
-impl From<Proof> for RawProofOps {
-    fn from(value: Proof) -> Self {
+impl From<ProofOps> for RawProofOps {
+    fn from(value: ProofOps) -> Self {
         let ops: Vec<RawProofOp> = value.ops.into_iter().map(RawProofOp::from).collect();
 
         RawProofOps { ops }
@@ -80,8 +193,12 @@ impl From<Proof> for RawProofOps {
 
 #[cfg(test)]
 mod test {
-    use super::Proof;
-    use crate::test::test_serialization_roundtrip;
+    use core::convert::TryFrom;
+
+    use tendermint_proto::crypto::Proof as RawProof;
+
+    use super::{Proof, ProofOps, MAX_AUNTS};
+    use crate::{merkle::proofs_from_byte_vectors, prelude::*, test::test_serialization_roundtrip};
 
     #[test]
     fn serialization_roundtrip() {
@@ -100,6 +217,77 @@ mod test {
                 }
             ]
         }"#;
-        test_serialization_roundtrip::<Proof>(payload);
+        test_serialization_roundtrip::<ProofOps>(payload);
+    }
+
+    fn items(count: usize) -> Vec<Vec<u8>> {
+        (0..count)
+            .map(|i| format!("item-{}", i).into_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn proofs_verify_against_root() {
+        for total in 1..=20 {
+            let items = items(total);
+            let (root, proofs) = proofs_from_byte_vectors(items.clone());
+            assert_eq!(
+                root,
+                crate::merkle::simple_hash_from_byte_vectors(items.clone())
+            );
+            assert_eq!(proofs.len(), total);
+            for (index, (proof, item)) in proofs.iter().zip(&items).enumerate() {
+                assert_eq!(proof.index, index as u64);
+                assert_eq!(proof.total, total as u64);
+                assert_eq!(proof.compute_root_hash(), Some(root));
+                proof.verify(&root, item).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn tampered_proofs_fail_verification() {
+        let items = items(7);
+        let (root, proofs) = proofs_from_byte_vectors(items.clone());
+        let proof = &proofs[3];
+
+        // Wrong leaf
+        assert!(proof.verify(&root, &items[4]).is_err());
+        // Wrong root
+        assert!(proof.verify(&[0; 32], &items[3]).is_err());
+
+        // Wrong position in the tree
+        let mut moved = proof.clone();
+        moved.index = 4;
+        assert!(moved.verify(&root, &items[3]).is_err());
+        moved.index = 7;
+        assert!(moved.compute_root_hash().is_none());
+
+        // Tampered or missing aunts
+        let mut tampered = proof.clone();
+        tampered.aunts[0][0] ^= 1;
+        assert!(tampered.verify(&root, &items[3]).is_err());
+        tampered.aunts = proof.aunts[1..].to_vec();
+        assert!(tampered.compute_root_hash().is_none());
+    }
+
+    #[test]
+    fn proof_protobuf_roundtrip() {
+        let (_, proofs) = proofs_from_byte_vectors(items(5));
+        let proof = proofs[2].clone();
+        let raw = RawProof::from(proof.clone());
+        assert_eq!(raw.total, 5);
+        assert_eq!(raw.index, 2);
+        assert_eq!(Proof::try_from(raw.clone()).unwrap(), proof);
+
+        let mut negative = raw.clone();
+        negative.index = -1;
+        assert!(Proof::try_from(negative).is_err());
+        let mut short_hash = raw.clone();
+        short_hash.aunts[0].pop();
+        assert!(Proof::try_from(short_hash).is_err());
+        let mut too_many_aunts = raw;
+        too_many_aunts.aunts = vec![vec![0; 32]; MAX_AUNTS + 1];
+        assert!(Proof::try_from(too_many_aunts).is_err());
     }
 }
@@ -0,0 +1,337 @@
+//! Runtime for the verification of Merkle proofs made up of a chain of
+//! [`ProofOp`]s, such as the ones returned by ABCI queries.
+//!
+//! Each [`ProofOp`] is decoded into a [`ProofOperator`] by the decoder
+//! registered for its type. Starting from the proven value (or nothing, when
+//! proving absence), each operator computes the root of its tree, which the
+//! next operator proves to be included in its own tree under the next key of
+//! the key path, until the last operator produces the application hash.
+//!
+//! <https://github.com/tendermint/tendermint/blob/c8483531d8e756f7fbb812db1dd16d841cdf298a/crypto/merkle/proof_op.go>
+
+use alloc::collections::BTreeMap;
+use core::fmt::{self, Display};
+
+use super::{
+    ics23::{CommitmentOp, PROOF_OP_IAVL_COMMITMENT, PROOF_OP_SIMPLE_MERKLE_COMMITMENT},
+    proof::{ProofOp, ProofOps},
+    value_op::{ValueOp, PROOF_OP_VALUE},
+};
+use crate::{prelude::*, Error};
+
+/// A single, decoded step in the verification of a chain of Merkle proofs.
+pub trait ProofOperator {
+    /// Compute the root of this operator's tree from the given arguments,
+    /// which are either the proven value or the root computed by the previous
+    /// operator.
+    fn run(&self, args: Vec<Vec<u8>>) -> Result<Vec<Vec<u8>>, Error>;
+
+    /// The key under which this operator proves its arguments to be included
+    /// in its tree, if any.
+    fn key(&self) -> &[u8];
+
+    /// Encode this operator back into a [`ProofOp`].
+    fn proof_op(&self) -> ProofOp;
+}
+
+/// Decodes a [`ProofOp`] of a specific type into its [`ProofOperator`].
+pub type OpDecoder = fn(&ProofOp) -> Result<Box<dyn ProofOperator>, Error>;
+
+/// A chain of decoded [`ProofOperator`]s, ordered from the proven value to the
+/// root.
+pub struct ProofOperators(pub Vec<Box<dyn ProofOperator>>);
+
+impl ProofOperators {
+    /// Run the operators against the given arguments, checking that they
+    /// consume the given key path (from its last key to its first) and
+    /// produce the given root.
+    pub fn verify(&self, root: &[u8], key_path: &str, args: Vec<Vec<u8>>) -> Result<(), Error> {
+        let mut keys = key_path_to_keys(key_path)?;
+        let mut args = args;
+        for (i, op) in self.0.iter().enumerate() {
+            let key = op.key();
+            if !key.is_empty() {
+                match keys.pop() {
+                    None => {
+                        return Err(Error::invalid_merkle_proof(format!(
+                            "key path has insufficient number of parts: expected no more keys but got {:?}",
+                            String::from_utf8_lossy(key)
+                        )))
+                    },
+                    Some(last_key) if last_key != key => {
+                        return Err(Error::invalid_merkle_proof(format!(
+                            "key mismatch on operation #{}: expected {:?} but got {:?}",
+                            i,
+                            String::from_utf8_lossy(&last_key),
+                            String::from_utf8_lossy(key)
+                        )))
+                    },
+                    Some(_) => (),
+                }
+            }
+            args = op.run(args)?;
+        }
+        if args.first().map(Vec::as_slice) != Some(root) {
+            return Err(Error::invalid_merkle_proof(
+                "calculated root hash does not match the root".to_string(),
+            ));
+        }
+        if !keys.is_empty() {
+            return Err(Error::invalid_merkle_proof(
+                "key path was not fully consumed".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Decodes and verifies chains of [`ProofOp`]s, using the decoders registered
+/// for each type of operation.
+///
+/// The default runtime can decode [`ValueOp`]s and ICS23 [`CommitmentOp`]s
+/// against IAVL and simple Merkle trees. Decoders for other types of
+/// operations (e.g. those of an application's own stores) are added via
+/// [`ProofRuntime::register_op_decoder`].
+pub struct ProofRuntime {
+    decoders: BTreeMap<String, OpDecoder>,
+}
+
+impl ProofRuntime {
+    /// Constructor for a runtime without any registered decoders.
+    pub fn new() -> Self {
+        Self {
+            decoders: BTreeMap::new(),
+        }
+    }
+
+    /// Register the decoder for operations of the given type, replacing any
+    /// decoder previously registered for it.
+    pub fn register_op_decoder(&mut self, field_type: impl Into<String>, decoder: OpDecoder) {
+        self.decoders.insert(field_type.into(), decoder);
+    }
+
+    /// Decode a single operation.
+    pub fn decode(&self, op: &ProofOp) -> Result<Box<dyn ProofOperator>, Error> {
+        let decoder = self
+            .decoders
+            .get(&op.field_type)
+            .ok_or_else(|| Error::unknown_proof_op_type(op.field_type.clone()))?;
+        decoder(op)
+    }
+
+    /// Decode all operations of a proof.
+    pub fn decode_proof(&self, proof: &ProofOps) -> Result<ProofOperators, Error> {
+        proof
+            .ops
+            .iter()
+            .map(|op| self.decode(op))
+            .collect::<Result<_, _>>()
+            .map(ProofOperators)
+    }
+
+    /// Verify that the given proof proves the value under the given key path
+    /// to be included in the tree with the given root.
+    pub fn verify_value(
+        &self,
+        proof: &ProofOps,
+        root: &[u8],
+        key_path: &str,
+        value: &[u8],
+    ) -> Result<(), Error> {
+        self.verify(proof, root, key_path, vec![value.to_vec()])
+    }
+
+    /// Verify that the given proof proves the absence of any value under the
+    /// given key path in the tree with the given root.
+    pub fn verify_absence(
+        &self,
+        proof: &ProofOps,
+        root: &[u8],
+        key_path: &str,
+    ) -> Result<(), Error> {
+        self.verify(proof, root, key_path, vec![])
+    }
+
+    /// Verify the given proof against the given root, key path and initial
+    /// arguments.
+    pub fn verify(
+        &self,
+        proof: &ProofOps,
+        root: &[u8],
+        key_path: &str,
+        args: Vec<Vec<u8>>,
+    ) -> Result<(), Error> {
+        self.decode_proof(proof)?.verify(root, key_path, args)
+    }
+}
+
+impl Default for ProofRuntime {
+    fn default() -> Self {
+        let mut runtime = Self::new();
+        runtime.register_op_decoder(PROOF_OP_VALUE, ValueOp::decode);
+        runtime.register_op_decoder(PROOF_OP_IAVL_COMMITMENT, CommitmentOp::decode);
+        runtime.register_op_decoder(PROOF_OP_SIMPLE_MERKLE_COMMITMENT, CommitmentOp::decode);
+        runtime
+    }
+}
+
+/// The encoding of a single key within a [`KeyPath`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyEncoding {
+    /// Percent-encoded, for mostly human-readable keys.
+    Url,
+    /// Hex-encoded, with an `x:` prefix, for binary keys.
+    Hex,
+}
+
+/// Builder for the key paths accepted by [`ProofRuntime::verify`], of the form
+/// `/<key>/<key>/...`, ordered from the root to the proven value.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KeyPath {
+    keys: Vec<(Vec<u8>, KeyEncoding)>,
+}
+
+impl KeyPath {
+    /// Constructor for an empty key path.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a key, encoded as given, to the key path.
+    pub fn append_key(mut self, key: impl Into<Vec<u8>>, encoding: KeyEncoding) -> Self {
+        self.keys.push((key.into(), encoding));
+        self
+    }
+}
+
+impl Display for KeyPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (key, encoding) in &self.keys {
+            f.write_str("/")?;
+            match encoding {
+                KeyEncoding::Url => {
+                    for byte in key {
+                        match byte {
+                            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                                write!(f, "{}", *byte as char)?
+                            },
+                            _ => write!(f, "%{:02X}", byte)?,
+                        }
+                    }
+                },
+                KeyEncoding::Hex => {
+                    f.write_str("x:")?;
+                    for byte in key {
+                        write!(f, "{:02X}", byte)?;
+                    }
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Decode the keys of a key path of the form `/<key>/<key>/...`, where each
+/// key is either percent-encoded or hex-encoded with an `x:` prefix.
+pub fn key_path_to_keys(path: &str) -> Result<Vec<Vec<u8>>, Error> {
+    let path = path.strip_prefix('/').ok_or_else(|| {
+        Error::invalid_key_path("key path must start with a forward slash '/'".to_string())
+    })?;
+    path.split('/')
+        .map(|part| match part.strip_prefix("x:") {
+            Some(hex) => subtle_encoding::hex::decode(hex.to_ascii_lowercase()).map_err(|e| {
+                Error::invalid_key_path(format!("failed to decode hex key {:?}: {}", hex, e))
+            }),
+            None => percent_decode(part),
+        })
+        .collect()
+}
+
+fn percent_decode(part: &str) -> Result<Vec<u8>, Error> {
+    let bytes = part.as_bytes();
+    let mut key = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let byte = bytes
+                .get(i + 1..i + 3)
+                .and_then(|hex| core::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| {
+                    Error::invalid_key_path(format!("invalid percent-encoding in key {:?}", part))
+                })?;
+            key.push(byte);
+            i += 3;
+        } else {
+            key.push(bytes[i]);
+            i += 1;
+        }
+    }
+    Ok(key)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::merkle::proofs_from_byte_vectors;
+
+    #[test]
+    fn key_paths_roundtrip() {
+        let path = KeyPath::new()
+            .append_key("store/key", KeyEncoding::Url)
+            .append_key(vec![0x00, 0xff], KeyEncoding::Hex);
+        assert_eq!(path.to_string(), "/store%2Fkey/x:00FF");
+        assert_eq!(
+            key_path_to_keys(&path.to_string()).unwrap(),
+            vec![b"store/key".to_vec(), vec![0x00, 0xff]]
+        );
+        assert!(key_path_to_keys("no-slash").is_err());
+        assert!(key_path_to_keys("/bad%2").is_err());
+        assert!(key_path_to_keys("/x:zz").is_err());
+    }
+
+    fn value_proof(kvs: &[(&str, &str)], index: usize) -> (Vec<u8>, ProofOps) {
+        let leaves = kvs
+            .iter()
+            .map(|(key, value)| ValueOp::leaf(key.as_bytes(), value.as_bytes()))
+            .collect();
+        let (root, mut proofs) = proofs_from_byte_vectors(leaves);
+        let op = ValueOp::new(kvs[index].0.as_bytes().to_vec(), proofs.swap_remove(index));
+        (
+            root.to_vec(),
+            ProofOps {
+                ops: vec![op.proof_op()],
+            },
+        )
+    }
+
+    #[test]
+    fn values_are_verified() {
+        let kvs = [("a", "1"), ("b", "2"), ("c", "3")];
+        let (root, proof) = value_proof(&kvs, 1);
+        let runtime = ProofRuntime::default();
+        runtime.verify_value(&proof, &root, "/b", b"2").unwrap();
+
+        // Wrong value, key or root
+        assert!(runtime.verify_value(&proof, &root, "/b", b"3").is_err());
+        assert!(runtime.verify_value(&proof, &root, "/c", b"2").is_err());
+        assert!(runtime.verify_value(&proof, &[0; 32], "/b", b"2").is_err());
+        // Key path not fully consumed, or too short
+        assert!(runtime
+            .verify_value(&proof, &root, "/store/b", b"2")
+            .is_err());
+        assert!(runtime.verify_value(&proof, &root, "/", b"2").is_err());
+    }
+
+    #[test]
+    fn unknown_op_types_are_rejected() {
+        let (root, mut proof) = value_proof(&[("a", "1")], 0);
+        assert!(ProofRuntime::new()
+            .verify_value(&proof, &root, "/a", b"1")
+            .is_err());
+        proof.ops[0].field_type = "iavl:v".to_string();
+        assert!(ProofRuntime::default()
+            .verify_value(&proof, &root, "/a", b"1")
+            .is_err());
+    }
+}
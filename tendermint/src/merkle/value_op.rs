@@ -0,0 +1,105 @@
+//! Merkle proofs of values in a simple Merkle tree of key/value pairs.
+
+use prost::Message;
+use sha2::{Digest, Sha256};
+use tendermint_proto::crypto::ValueOp as RawValueOp;
+
+use super::{
+    leaf_hash,
+    proof::{Proof, ProofOp},
+    runtime::ProofOperator,
+};
+use crate::{prelude::*, Error};
+
+/// The type of the [`ProofOp`]s encoding a [`ValueOp`].
+pub const PROOF_OP_VALUE: &str = "simple:v";
+
+/// Proves the inclusion of a value under a key in a simple Merkle tree, whose
+/// leaves are the key/value pairs of a store as encoded by [`ValueOp::leaf`].
+///
+/// <https://github.com/tendermint/tendermint/blob/c8483531d8e756f7fbb812db1dd16d841cdf298a/crypto/merkle/proof_value.go>
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValueOp {
+    key: Vec<u8>,
+    proof: Proof,
+}
+
+impl ValueOp {
+    /// Constructor.
+    pub fn new(key: Vec<u8>, proof: Proof) -> Self {
+        Self { key, proof }
+    }
+
+    /// The proof of the inclusion of the key/value pair in the tree.
+    pub fn proof(&self) -> &Proof {
+        &self.proof
+    }
+
+    /// Decode a [`PROOF_OP_VALUE`] operation. Usable as an
+    /// [`OpDecoder`](super::runtime::OpDecoder).
+    pub fn decode(op: &ProofOp) -> Result<Box<dyn ProofOperator>, Error> {
+        if op.field_type != PROOF_OP_VALUE {
+            return Err(Error::unknown_proof_op_type(op.field_type.clone()));
+        }
+        let raw = RawValueOp::decode(op.data.as_slice())
+            .map_err(|e| Error::invalid_merkle_proof(format!("invalid value op: {}", e)))?;
+        let proof = raw
+            .proof
+            .ok_or_else(|| Error::invalid_merkle_proof("missing value op proof".to_string()))?
+            .try_into()?;
+        Ok(Box::new(Self::new(op.key.clone(), proof)))
+    }
+
+    /// The leaf encoding the given key/value pair, i.e. the length-prefixed
+    /// key followed by the length-prefixed hash of the value.
+    pub fn leaf(key: &[u8], value: &[u8]) -> Vec<u8> {
+        let value_hash = Sha256::digest(value);
+        let mut leaf = Vec::with_capacity(key.len() + value_hash.len() + 4);
+        prost::encoding::encode_varint(key.len() as u64, &mut leaf);
+        leaf.extend_from_slice(key);
+        prost::encoding::encode_varint(value_hash.len() as u64, &mut leaf);
+        leaf.extend_from_slice(&value_hash);
+        leaf
+    }
+}
+
+impl ProofOperator for ValueOp {
+    fn run(&self, args: Vec<Vec<u8>>) -> Result<Vec<Vec<u8>>, Error> {
+        let value = match args.as_slice() {
+            [value] => value,
+            _ => {
+                return Err(Error::invalid_merkle_proof(format!(
+                    "expected 1 argument, got {}",
+                    args.len()
+                )))
+            },
+        };
+        if leaf_hash(&Self::leaf(&self.key, value)) != self.proof.leaf_hash {
+            return Err(Error::invalid_merkle_proof(
+                "leaf hash does not match the key/value pair".to_string(),
+            ));
+        }
+        let root = self.proof.compute_root_hash().ok_or_else(|| {
+            Error::invalid_merkle_proof(
+                "number of aunts does not match the size of the tree".to_string(),
+            )
+        })?;
+        Ok(vec![root.to_vec()])
+    }
+
+    fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    fn proof_op(&self) -> ProofOp {
+        let raw = RawValueOp {
+            key: self.key.clone(),
+            proof: Some(self.proof.clone().into()),
+        };
+        ProofOp {
+            field_type: PROOF_OP_VALUE.to_string(),
+            key: self.key.clone(),
+            data: raw.encode_to_vec(),
+        }
+    }
+}
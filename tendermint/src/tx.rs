@@ -0,0 +1,175 @@
+//! Transactions and the proofs of their inclusion in blocks
+
+use core::convert::{TryFrom, TryInto};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tendermint_proto::{types::TxProof as RawTxProof, Protobuf};
+
+use crate::{
+    block::Header,
+    hash::{Algorithm, Hash},
+    merkle::{self, proofs_from_byte_vectors, simple_hash_from_byte_vectors},
+    prelude::*,
+    Error,
+};
+
+/// Proof of the inclusion of a transaction in a block, i.e. in the Merkle tree
+/// over the hashes of the block's transactions whose root is the block
+/// header's `data_hash`.
+///
+/// <https://github.com/tendermint/tendermint/blob/c8483531d8e756f7fbb812db1dd16d841cdf298a/types/tx.go#L83>
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawTxProof", into = "RawTxProof")]
+pub struct Proof {
+    /// The Merkle root of the hashes of the block's transactions
+    pub root_hash: Hash,
+    /// The transaction
+    pub data: Vec<u8>,
+    /// The proof of the inclusion of the transaction's hash
+    pub proof: merkle::Proof,
+}
+
+impl Proof {
+    /// Create the proof of the inclusion of the transaction at the given index
+    /// among the given transactions of a block.
+    ///
+    /// Returns `None` if the index is out of bounds.
+    pub fn new<T: AsRef<[u8]>>(txs: &[T], index: usize) -> Option<Self> {
+        let data = txs.get(index)?.as_ref().to_vec();
+        let (root, mut proofs) = proofs_from_byte_vectors(txs.iter().map(tx_hash).collect());
+        Some(Self {
+            root_hash: Hash::Sha256(root),
+            data,
+            proof: proofs.swap_remove(index),
+        })
+    }
+
+    /// The leaf of the Merkle tree proven by this proof, i.e. the hash of the
+    /// transaction.
+    pub fn leaf(&self) -> Vec<u8> {
+        tx_hash(&self.data)
+    }
+
+    /// Validate this proof against the given data hash of a block.
+    pub fn validate(&self, data_hash: &Hash) -> Result<(), Error> {
+        if self.root_hash != *data_hash {
+            return Err(Error::invalid_merkle_proof(
+                "proof root hash does not match the data hash".to_string(),
+            ));
+        }
+        let root_hash = match self.root_hash {
+            Hash::Sha256(root_hash) => root_hash,
+            Hash::None => {
+                return Err(Error::invalid_merkle_proof(
+                    "proof has an empty root hash".to_string(),
+                ))
+            },
+        };
+        if self.proof.index >= self.proof.total {
+            return Err(Error::invalid_merkle_proof(format!(
+                "proof index {} is out of bounds for {} transactions",
+                self.proof.index, self.proof.total
+            )));
+        }
+        self.proof.verify(&root_hash, &self.leaf())
+    }
+
+    /// Verify that this proves the inclusion of the transaction in the block
+    /// with the given header.
+    ///
+    /// The header itself should have been verified beforehand, e.g. as part of
+    /// a light block verified by a light client.
+    pub fn verify(&self, header: &Header) -> Result<(), Error> {
+        let data_hash = header
+            .data_hash
+            .ok_or_else(|| Error::invalid_merkle_proof("header has no data hash".to_string()))?;
+        self.validate(&data_hash)
+    }
+}
+
+impl Protobuf<RawTxProof> for Proof {}
+
+impl TryFrom<RawTxProof> for Proof {
+    type Error = Error;
+
+    fn try_from(value: RawTxProof) -> Result<Self, Self::Error> {
+        Ok(Self {
+            root_hash: Hash::from_bytes(Algorithm::Sha256, &value.root_hash)?,
+            data: value.data,
+            proof: value.proof.ok_or_else(Error::missing_proof)?.try_into()?,
+        })
+    }
+}
+
+impl From<Proof> for RawTxProof {
+    fn from(value: Proof) -> Self {
+        RawTxProof {
+            root_hash: value.root_hash.into(),
+            data: value.data,
+            proof: Some(value.proof.into()),
+        }
+    }
+}
+
+/// Compute the Merkle root of the hashes of the given transactions of a block,
+/// as found in the `data_hash` of its header.
+pub fn data_hash<T: AsRef<[u8]>>(txs: &[T]) -> Hash {
+    Hash::Sha256(simple_hash_from_byte_vectors(
+        txs.iter().map(tx_hash).collect(),
+    ))
+}
+
+fn tx_hash(tx: impl AsRef<[u8]>) -> Vec<u8> {
+    Sha256::digest(tx.as_ref()).to_vec()
+}
+
+#[cfg(test)]
+mod test {
+    use core::convert::TryFrom;
+
+    use tendermint_proto::types::TxProof as RawTxProof;
+
+    use super::{data_hash, Proof};
+    use crate::{hash::Hash, prelude::*, test::test_serialization_roundtrip};
+
+    const TXS: [&str; 5] = ["name=satoshi", "name=hal", "a=1", "b=2", "c=3"];
+
+    #[test]
+    fn proofs_are_validated() {
+        let proofs: Vec<Proof> = (0..TXS.len())
+            .map(|i| Proof::new(&TXS, i).unwrap())
+            .collect();
+        let data_hash = data_hash(&TXS);
+        assert!(Proof::new(&TXS, TXS.len()).is_none());
+
+        for (tx, proof) in TXS.iter().zip(&proofs) {
+            assert_eq!(proof.data, tx.as_bytes());
+            assert_eq!(proof.root_hash, data_hash);
+            proof.validate(&data_hash).unwrap();
+
+            // Another data hash, or another transaction
+            assert!(proof.validate(&Hash::Sha256([0; 32])).is_err());
+            let mut tampered = proof.clone();
+            tampered.data = b"name=mallory".to_vec();
+            assert!(tampered.validate(&data_hash).is_err());
+        }
+
+        // The proof of another transaction
+        let mut tampered = proofs[0].clone();
+        tampered.data = proofs[1].data.clone();
+        assert!(tampered.validate(&data_hash).is_err());
+    }
+
+    #[test]
+    fn proofs_roundtrip() {
+        let proof = Proof::new(&TXS, 2).unwrap();
+        test_serialization_roundtrip::<Proof>(&serde_json::to_string(&proof).unwrap());
+        let raw = RawTxProof::from(proof.clone());
+        assert_eq!(Proof::try_from(raw.clone()).unwrap(), proof);
+
+        let mut missing = raw;
+        missing.proof = None;
+        assert!(Proof::try_from(missing).is_err());
+    }
+}
@@ -12,10 +12,18 @@ use tendermint_proto::{
 };
 
 use crate::{
-    account, hash::Hash, merkle, prelude::*, public_key::deserialize_public_key, vote, Error,
-    PublicKey, Signature,
+    abci::types::ValidatorUpdate, account, hash::Hash, merkle, prelude::*,
+    public_key::deserialize_public_key, vote, Error, PublicKey, Signature,
 };
 
+/// The maximum total voting power of a validator set, which bounds proposer
+/// priorities well within the range of `i64`.
+pub const MAX_TOTAL_VOTING_POWER: i64 = i64::MAX / 8;
+
+/// Proposer priorities are kept within this many times the total voting power
+/// of each other.
+const PRIORITY_WINDOW_SIZE_FACTOR: i64 = 2;
+
 /// Validator set contains a vector of validators
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Set {
@@ -39,9 +47,11 @@ impl TryFrom<RawValidatorSet> for Set {
         let proposer = value.proposer.map(TryInto::try_into).transpose()?;
         let validator_set = Self::new(validators, proposer);
 
-        // Ensure that the raw voting power matches the computed one
+        // Ensure that the raw voting power matches the computed one, unless
+        // it was left out (as Go does, e.g. within light client attack
+        // evidence)
         let raw_voting_power = value.total_voting_power.try_into()?;
-        if raw_voting_power != validator_set.total_voting_power() {
+        if value.total_voting_power != 0 && raw_voting_power != validator_set.total_voting_power() {
             return Err(Error::raw_voting_power_mismatch(
                 raw_voting_power,
                 validator_set.total_voting_power(),
@@ -143,6 +153,229 @@ impl Set {
 
         Hash::Sha256(merkle::simple_hash_from_byte_vectors(validator_bytes))
     }
+
+    /// Advance the proposer priorities by the given number of rounds, making
+    /// the validator selected in the last round the proposer.
+    ///
+    /// Does nothing if the set is empty or `times` is zero.
+    ///
+    /// <https://github.com/tendermint/tendermint/blob/c8483531d8e756f7fbb812db1dd16d841cdf298a/types/validator_set.go#L110>
+    pub fn increment_proposer_priority(&mut self, times: u32) {
+        if self.validators.is_empty() || times == 0 {
+            return;
+        }
+        // Keep the spread of the priorities proportional to the total power
+        let diff_max = PRIORITY_WINDOW_SIZE_FACTOR.saturating_mul(self.total_power());
+        self.rescale_priorities(diff_max);
+        self.shift_by_avg_proposer_priority();
+
+        let mut proposer = 0;
+        for _ in 0..times {
+            let total_power = self.total_power();
+            for validator in &mut self.validators {
+                validator.proposer_priority = validator
+                    .proposer_priority
+                    .value()
+                    .saturating_add(validator.power.value() as i64)
+                    .into();
+            }
+            proposer = self.most_priority_index();
+            let validator = &mut self.validators[proposer];
+            validator.proposer_priority = validator
+                .proposer_priority
+                .value()
+                .saturating_sub(total_power)
+                .into();
+        }
+        self.proposer = Some(self.validators[proposer].clone());
+    }
+
+    /// Apply the given validator updates, where an update with zero power
+    /// removes the validator, and recompute the proposer priorities.
+    ///
+    /// The updates are rejected as a whole, leaving the set untouched, if they
+    /// contain duplicate validators, remove unknown validators or all of them,
+    /// or if the total voting power would exceed [`MAX_TOTAL_VOTING_POWER`].
+    /// The proposer of the updated set is determined by the next call to
+    /// [`Set::increment_proposer_priority`].
+    ///
+    /// <https://github.com/tendermint/tendermint/blob/c8483531d8e756f7fbb812db1dd16d841cdf298a/types/validator_set.go#L593>
+    pub fn update_with_change_set(&mut self, changes: Vec<ValidatorUpdate>) -> Result<(), Error> {
+        if changes.is_empty() {
+            return Ok(());
+        }
+        let mut changes: Vec<Info> = changes
+            .into_iter()
+            .map(|update| Info::new(update.pub_key, update.power))
+            .collect();
+        changes.sort_by_key(|change| change.address);
+        if let Some(pair) = changes
+            .windows(2)
+            .find(|pair| pair[0].address == pair[1].address)
+        {
+            return Err(Error::duplicate_validator_update(pair[0].address));
+        }
+        if let Some(change) = changes
+            .iter()
+            .find(|change| change.power.value() > MAX_TOTAL_VOTING_POWER as u64)
+        {
+            return Err(Error::invalid_validator_update(format!(
+                "voting power of {} can't be higher than {}, got {}",
+                change.address, MAX_TOTAL_VOTING_POWER, change.power
+            )));
+        }
+        let (removals, mut updates): (Vec<Info>, Vec<Info>) = changes
+            .into_iter()
+            .partition(|change| change.power.is_zero());
+
+        let added = updates
+            .iter()
+            .filter(|update| self.validator(update.address).is_none())
+            .count();
+        if added == 0 && removals.len() == self.validators.len() {
+            return Err(Error::invalid_validator_update(
+                "applying the changes would result in an empty set".to_string(),
+            ));
+        }
+
+        let mut removed_power = 0;
+        for removal in &removals {
+            match self.validator(removal.address) {
+                Some(validator) => removed_power += validator.power.value() as i64,
+                None => {
+                    return Err(Error::invalid_validator_update(format!(
+                        "failed to find validator {} to remove",
+                        removal.address
+                    )))
+                },
+            }
+        }
+
+        // Apply the largest decreases in power first, so that the total power
+        // is bounded at every step
+        let mut deltas: Vec<(i64, account::Id)> = updates
+            .iter()
+            .map(|update| {
+                let current = self
+                    .validator(update.address)
+                    .map_or(0, |validator| validator.power.value() as i64);
+                (update.power.value() as i64 - current, update.address)
+            })
+            .collect();
+        deltas.sort_by_key(|(delta, _)| *delta);
+        let mut total_after_removals = self.total_power() - removed_power;
+        for (delta, address) in deltas {
+            total_after_removals += delta;
+            if total_after_removals > MAX_TOTAL_VOTING_POWER {
+                return Err(Error::invalid_validator_update(format!(
+                    "failed to add/update validator {}, total voting power would exceed the max allowed {}",
+                    address, MAX_TOTAL_VOTING_POWER
+                )));
+            }
+        }
+        let total_before_removals = total_after_removals + removed_power;
+
+        // New validators start with a priority low enough that they can't
+        // reset a negative priority by unbonding and bonding again
+        for update in &mut updates {
+            match self.validator(update.address) {
+                Some(validator) => {
+                    update.name = validator.name;
+                    update.proposer_priority = validator.proposer_priority;
+                },
+                None => {
+                    update.proposer_priority =
+                        (-(total_before_removals + (total_before_removals >> 3))).into();
+                },
+            }
+        }
+
+        self.validators.retain(|validator| {
+            !removals
+                .iter()
+                .chain(&updates)
+                .any(|change| change.address == validator.address)
+        });
+        self.validators.extend(updates);
+        self.total_voting_power = vote::Power::try_from(
+            self.validators
+                .iter()
+                .map(|validator| validator.power.value())
+                .sum::<u64>(),
+        )?;
+        self.proposer = None;
+
+        let diff_max = PRIORITY_WINDOW_SIZE_FACTOR * self.total_power();
+        self.rescale_priorities(diff_max);
+        self.shift_by_avg_proposer_priority();
+        Self::sort_validators(&mut self.validators);
+        Ok(())
+    }
+
+    fn total_power(&self) -> i64 {
+        self.total_voting_power.value() as i64
+    }
+
+    // Scale down the priorities so that they are at most `diff_max` apart
+    fn rescale_priorities(&mut self, diff_max: i64) {
+        if diff_max <= 0 {
+            return;
+        }
+        let priorities = self.validators.iter().map(|v| v.proposer_priority.value());
+        let (min, max) = match (priorities.clone().min(), priorities.max()) {
+            (Some(min), Some(max)) => (min, max),
+            _ => return,
+        };
+        // As in Go, where the difference may overflow
+        let diff = max.wrapping_sub(min).wrapping_abs();
+        if diff > diff_max {
+            let ratio = (diff + diff_max - 1) / diff_max;
+            for validator in &mut self.validators {
+                validator.proposer_priority = (validator.proposer_priority.value() / ratio).into();
+            }
+        }
+    }
+
+    // Center the priorities around zero
+    fn shift_by_avg_proposer_priority(&mut self) {
+        if self.validators.is_empty() {
+            return;
+        }
+        let sum: i128 = self
+            .validators
+            .iter()
+            .map(|validator| i128::from(validator.proposer_priority.value()))
+            .sum();
+        // Rounded down, as Go's big.Int division, and within bounds since it
+        // is the average of i64 values
+        let avg = sum.div_euclid(self.validators.len() as i128) as i64;
+        for validator in &mut self.validators {
+            validator.proposer_priority = validator
+                .proposer_priority
+                .value()
+                .saturating_sub(avg)
+                .into();
+        }
+    }
+
+    // The validator with the highest priority, or the lowest address among
+    // those with the highest priority
+    fn most_priority_index(&self) -> usize {
+        let mut most = 0;
+        for (index, validator) in self.validators.iter().enumerate().skip(1) {
+            let current = &self.validators[most];
+            if (
+                validator.proposer_priority,
+                core::cmp::Reverse(validator.address),
+            ) > (
+                current.proposer_priority,
+                core::cmp::Reverse(current.address),
+            ) {
+                most = index;
+            }
+        }
+        most
+    }
 }
 
 /// Validator information
@@ -443,4 +676,119 @@ mod tests {
         assert_eq!(u64::from(update1.power), 573929);
         assert_eq!(update1, update2);
     }
+
+    const KEYS: [[u8; 32]; 4] = [
+        [
+            48, 163, 55, 132, 231, 147, 230, 163, 56, 158, 127, 218, 179, 139, 212, 103, 218, 89,
+            122, 126, 229, 88, 84, 48, 32, 0, 185, 174, 63, 72, 203, 52,
+        ],
+        [
+            54, 253, 174, 153, 121, 74, 145, 180, 111, 16, 214, 48, 193, 109, 104, 134, 55, 162,
+            151, 16, 182, 114, 125, 135, 32, 195, 236, 248, 64, 112, 74, 101,
+        ],
+        [
+            182, 205, 13, 86, 147, 27, 65, 49, 160, 118, 11, 180, 117, 35, 206, 35, 68, 19, 27,
+            173, 69, 92, 204, 224, 200, 51, 249, 81, 105, 128, 112, 244,
+        ],
+        [
+            110, 147, 87, 120, 27, 218, 66, 209, 81, 4, 169, 153, 64, 163, 137, 89, 168, 97, 219,
+            233, 42, 119, 24, 61, 47, 59, 76, 31, 182, 60, 13, 4,
+        ],
+    ];
+
+    fn update(key: usize, power: u64) -> ValidatorUpdate {
+        ValidatorUpdate {
+            pub_key: PublicKey::from_raw_ed25519(&KEYS[key]).unwrap(),
+            power: vote::Power::try_from(power).unwrap(),
+        }
+    }
+
+    fn priorities(set: &Set) -> Vec<(u64, i64)> {
+        set.validators()
+            .iter()
+            .map(|v| (v.power(), v.proposer_priority.value()))
+            .collect()
+    }
+
+    #[test]
+    fn proposers_rotate_by_power() {
+        let mut set = Set::without_proposer(vec![
+            make_validator(KEYS[0].to_vec(), 1),
+            make_validator(KEYS[1].to_vec(), 2),
+        ]);
+        let (a, b) = (set.validators[1].address, set.validators[0].address);
+        let proposers: Vec<account::Id> = (0..6)
+            .map(|_| {
+                set.increment_proposer_priority(1);
+                set.proposer().as_ref().unwrap().address
+            })
+            .collect();
+        assert_eq!(proposers, [b, a, b, b, a, b]);
+
+        let mut set = Set::without_proposer(vec![
+            make_validator(KEYS[0].to_vec(), 1000),
+            make_validator(KEYS[1].to_vec(), 300),
+            make_validator(KEYS[2].to_vec(), 330),
+        ]);
+        let mut counts = [0; 3];
+        for _ in 0..1630 {
+            set.increment_proposer_priority(1);
+            let proposer = set.proposer().as_ref().unwrap().address;
+            let index = set
+                .validators()
+                .iter()
+                .position(|v| v.address == proposer)
+                .unwrap();
+            counts[index] += 1;
+        }
+        assert_eq!(counts, [1000, 330, 300]);
+    }
+
+    #[test]
+    fn change_sets_are_applied() {
+        let mut set = Set::without_proposer(vec![
+            make_validator(KEYS[0].to_vec(), 1),
+            make_validator(KEYS[1].to_vec(), 2),
+        ]);
+        set.increment_proposer_priority(1);
+        assert_eq!(priorities(&set), [(2, -1), (1, 1)]);
+
+        // New validators start at -1.125 times the total power, before centering
+        set.update_with_change_set(vec![update(2, 3)]).unwrap();
+        assert_eq!(priorities(&set), [(3, -4), (2, 1), (1, 3)]);
+        assert_eq!(set.total_voting_power().value(), 6);
+        assert!(set.proposer().is_none());
+
+        // The average priority is rounded down
+        set.update_with_change_set(vec![update(0, 0)]).unwrap();
+        assert_eq!(priorities(&set), [(3, -2), (2, 3)]);
+        assert_eq!(set.total_voting_power().value(), 5);
+    }
+
+    #[test]
+    fn invalid_change_sets_are_rejected() {
+        let mut set = Set::without_proposer(vec![
+            make_validator(KEYS[0].to_vec(), 1),
+            make_validator(KEYS[1].to_vec(), 2),
+        ]);
+        set.increment_proposer_priority(1);
+        let original = set.clone();
+
+        let max = MAX_TOTAL_VOTING_POWER as u64;
+        for changes in [
+            vec![update(2, 3), update(2, 4)],
+            vec![update(3, 0)],
+            vec![update(0, 0), update(1, 0)],
+            vec![update(2, max + 1)],
+            vec![update(2, max - 2), update(3, 1)],
+        ] {
+            assert!(set.update_with_change_set(changes).is_err());
+            assert_eq!(set, original);
+        }
+
+        // Decreasing some powers makes room for others
+        set.update_with_change_set(vec![update(1, 1), update(2, max - 2)])
+            .unwrap();
+        assert_eq!(set.total_voting_power().value(), max);
+    }
 }
@@ -8,6 +8,24 @@ pub const TENDERMINT_REPO: &str = "https://github.com/tendermint/tendermint";
 // Commit ID (full length): d7d0ffea13c60c98b812d243ba5a2c375f341c15
 pub const TENDERMINT_COMMITISH: &str = "v0.35.4";
 
+/// Tendermint commitish whose ABCI protobuf definitions are additionally
+/// compiled into the `v0_34` module, for applications serving v0.34 chains.
+pub const TENDERMINT_V0_34_COMMITISH: &str = "v0.34.20";
+
+/// CometBFT repository URL, whose ABCI protobuf definitions are compiled into
+/// the `v0_37` and `v0_38` modules.
+pub const COMETBFT_REPO: &str = "https://github.com/cometbft/cometbft";
+/// CometBFT commitish of the `v0_37` ABCI module.
+pub const COMETBFT_V0_37_COMMITISH: &str = "v0.37.2";
+/// CometBFT commitish of the `v0_38` ABCI module.
+pub const COMETBFT_V0_38_COMMITISH: &str = "v0.38.0";
+
+/// ICS23 repository URL, whose commitment proof definitions are compiled into
+/// the `ics23` module.
+pub const ICS23_REPO: &str = "https://github.com/confio/ics23";
+/// ICS23 commitish.
+pub const ICS23_COMMITISH: &str = "v0.9.0";
+
 /// Predefined custom attributes for message annotations
 const PRIMITIVE_ENUM: &str = r#"#[derive(::num_derive::FromPrimitive, ::num_derive::ToPrimitive)]"#;
 const SERIALIZED: &str = r#"#[derive(::serde::Deserialize, ::serde::Serialize)]"#;
@@ -34,6 +52,13 @@ const RENAME_SRPUBKEY: &str = r#"#[serde(rename = "tendermint/PubKeySr25519", wi
 const RENAME_DUPLICATEVOTE: &str = r#"#[serde(rename = "tendermint/DuplicateVoteEvidence")]"#;
 const RENAME_LIGHTCLIENTATTACK: &str =
     r#"#[serde(rename = "tendermint/LightClientAttackEvidence")]"#;
+// Fields of evidence which have no JSON tags in Go
+const RENAME_CONFLICTING_BLOCK: &str = r#"#[serde(rename = "ConflictingBlock")]"#;
+const RENAME_COMMON_HEIGHT: &str = r#"#[serde(rename = "CommonHeight")]"#;
+const RENAME_BYZANTINE_VALIDATORS: &str = r#"#[serde(rename = "ByzantineValidators")]"#;
+const RENAME_TOTAL_VOTING_POWER: &str = r#"#[serde(rename = "TotalVotingPower")]"#;
+const RENAME_VALIDATOR_POWER: &str = r#"#[serde(rename = "ValidatorPower")]"#;
+const RENAME_TIMESTAMP: &str = r#"#[serde(rename = "Timestamp")]"#;
 const EVIDENCE_VARIANT: &str = r#"#[serde(from = "crate::serializers::evidence::EvidenceVariant", into = "crate::serializers::evidence::EvidenceVariant")]"#;
 const ALIAS_PARTS: &str = r#"#[serde(alias = "parts")]"#;
 
@@ -147,6 +172,70 @@ pub static CUSTOM_FIELD_ATTRIBUTES: &[(&str, &str)] = &[
         ".tendermint.types.Validator.proposer_priority",
         QUOTED_WITH_DEFAULT,
     ), // Default is for /genesis deserialization
+    (
+        ".tendermint.types.ValidatorSet.total_voting_power",
+        QUOTED_WITH_DEFAULT,
+    ), // Left out by Go
+    (
+        ".tendermint.types.DuplicateVoteEvidence.total_voting_power",
+        RENAME_TOTAL_VOTING_POWER,
+    ),
+    (
+        ".tendermint.types.DuplicateVoteEvidence.total_voting_power",
+        QUOTED,
+    ),
+    (
+        ".tendermint.types.DuplicateVoteEvidence.validator_power",
+        RENAME_VALIDATOR_POWER,
+    ),
+    (
+        ".tendermint.types.DuplicateVoteEvidence.validator_power",
+        QUOTED,
+    ),
+    (
+        ".tendermint.types.DuplicateVoteEvidence.timestamp",
+        RENAME_TIMESTAMP,
+    ),
+    (
+        ".tendermint.types.DuplicateVoteEvidence.timestamp",
+        OPTIONAL,
+    ),
+    (
+        ".tendermint.types.LightClientAttackEvidence.conflicting_block",
+        RENAME_CONFLICTING_BLOCK,
+    ),
+    (
+        ".tendermint.types.LightClientAttackEvidence.common_height",
+        RENAME_COMMON_HEIGHT,
+    ),
+    (
+        ".tendermint.types.LightClientAttackEvidence.common_height",
+        QUOTED,
+    ),
+    (
+        ".tendermint.types.LightClientAttackEvidence.byzantine_validators",
+        RENAME_BYZANTINE_VALIDATORS,
+    ),
+    (
+        ".tendermint.types.LightClientAttackEvidence.byzantine_validators",
+        NULLABLE,
+    ),
+    (
+        ".tendermint.types.LightClientAttackEvidence.total_voting_power",
+        RENAME_TOTAL_VOTING_POWER,
+    ),
+    (
+        ".tendermint.types.LightClientAttackEvidence.total_voting_power",
+        QUOTED,
+    ),
+    (
+        ".tendermint.types.LightClientAttackEvidence.timestamp",
+        RENAME_TIMESTAMP,
+    ),
+    (
+        ".tendermint.types.LightClientAttackEvidence.timestamp",
+        OPTIONAL,
+    ),
     (".tendermint.types.BlockMeta.block_size", QUOTED),
     (".tendermint.types.BlockMeta.num_txs", QUOTED),
     (".tendermint.crypto.PublicKey.sum.ed25519", RENAME_EDPUBKEY),
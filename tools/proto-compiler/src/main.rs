@@ -1,5 +1,6 @@
 use std::env::var;
-use std::path::PathBuf;
+use std::fs::{copy, create_dir_all};
+use std::path::{Path, PathBuf};
 use tempfile::tempdir;
 
 mod functions;
@@ -7,7 +8,9 @@ use functions::{copy_files, find_proto_files, generate_tendermint_lib, get_commi
 
 mod constants;
 use constants::{
-    CUSTOM_FIELD_ATTRIBUTES, CUSTOM_TYPE_ATTRIBUTES, TENDERMINT_COMMITISH, TENDERMINT_REPO,
+    COMETBFT_REPO, COMETBFT_V0_37_COMMITISH, COMETBFT_V0_38_COMMITISH, CUSTOM_FIELD_ATTRIBUTES,
+    CUSTOM_TYPE_ATTRIBUTES, ICS23_COMMITISH, ICS23_REPO, TENDERMINT_COMMITISH, TENDERMINT_REPO,
+    TENDERMINT_V0_34_COMMITISH,
 };
 
 fn main() {
@@ -55,13 +58,113 @@ fn main() {
     // List available proto files
     let protos = find_proto_files(proto_paths);
 
+    println!("[info] => Creating structs.");
+    compile(&protos, &proto_includes_paths, &out_dir);
+
+    println!("[info] => Removing old structs and copying new structs.");
+    copy_files(&out_dir, &target_dir); // This panics if it fails.
+    generate_tendermint_lib(&out_dir, &tendermint_lib_target);
+
+    // Only the ABCI definitions of Tendermint v0.34 and CometBFT v0.37 and
+    // v0.38 are compiled, into separate modules, since all other packages are
+    // wire-compatible. The exception are the types of CometBFT v0.38, whose
+    // consensus parameters gained ABCI parameters.
+    let abci_versions = [
+        (
+            "TENDERMINT_V0_34_DIR",
+            "tendermint-v0.34",
+            TENDERMINT_REPO,
+            TENDERMINT_V0_34_COMMITISH,
+            "v0_34",
+            &["tendermint.abci"][..],
+        ),
+        (
+            "COMETBFT_V0_37_DIR",
+            "cometbft-v0.37",
+            COMETBFT_REPO,
+            COMETBFT_V0_37_COMMITISH,
+            "v0_37",
+            &["tendermint.abci"][..],
+        ),
+        (
+            "COMETBFT_V0_38_DIR",
+            "cometbft-v0.38",
+            COMETBFT_REPO,
+            COMETBFT_V0_38_COMMITISH,
+            "v0_38",
+            &["tendermint.abci", "tendermint.types"][..],
+        ),
+    ];
+    for (dir_var, default_dir, repo, commitish, module, packages) in abci_versions {
+        let dir = PathBuf::from(var(dir_var).unwrap_or_else(|_| {
+            root.join("..")
+                .join("target")
+                .join(default_dir)
+                .to_str()
+                .unwrap()
+                .to_string()
+        }));
+        println!(
+            "[info] => Fetching {} at {} into {:?}",
+            repo, commitish, dir
+        );
+        get_commitish(&dir, repo, commitish);
+        println!("[info] => Creating {} ABCI structs.", module);
+        compile_abci(&dir, &target_dir.join(module), packages);
+    }
+
+    // The ICS23 commitment proofs returned by Cosmos SDK-based applications.
+    let ics23_dir = PathBuf::from(var("ICS23_DIR").unwrap_or_else(|_| {
+        root.join("..")
+            .join("target")
+            .join("ics23")
+            .to_str()
+            .unwrap()
+            .to_string()
+    }));
+    println!(
+        "[info] => Fetching {} at {} into {:?}",
+        ICS23_REPO, ICS23_COMMITISH, ics23_dir
+    );
+    get_commitish(&ics23_dir, ICS23_REPO, ICS23_COMMITISH);
+    let ics23_out_dir = tempdir().map(|d| d.into_path()).unwrap();
+    println!("[info] => Creating ICS23 structs.");
+    compile(
+        &[ics23_dir.join("proofs.proto")],
+        &[ics23_dir.clone()],
+        &ics23_out_dir,
+    );
+    copy(ics23_out_dir.join("ics23.rs"), target_dir.join("ics23.rs")).unwrap();
+
+    println!("[info] => Done!");
+}
+
+/// Compile the ABCI protos of the Tendermint or CometBFT checkout in `dir`,
+/// keeping the structs of the given packages in `target_dir`.
+fn compile_abci(dir: &Path, target_dir: &Path, packages: &[&str]) {
+    let out_dir = tempdir().map(|d| d.into_path()).unwrap();
+    let protos = find_proto_files(vec![dir.join("proto").join("tendermint").join("abci")]);
+    let includes_paths = vec![dir.join("proto"), dir.join("third_party").join("proto")];
+    compile(&protos, &includes_paths, &out_dir);
+    // Dependencies of the ABCI protos are generated alongside them, but only
+    // those of the given packages are kept.
+    create_dir_all(target_dir).unwrap();
+    for package in packages {
+        let file = format!("{}.rs", package);
+        copy(out_dir.join(&file), target_dir.join(&file)).unwrap();
+    }
+}
+
+/// Compile the given proto files into `out_dir`, with the crate's custom
+/// attributes applied.
+fn compile(protos: &[PathBuf], includes: &[PathBuf], out_dir: &Path) {
     let mut pb = prost_build::Config::new();
 
     // Use shared Bytes buffers for ABCI messages:
     pb.bytes(&[".tendermint.abci"]);
 
     // Compile proto files with added annotations, exchange prost_types to our own
-    pb.out_dir(&out_dir);
+    pb.out_dir(out_dir);
     for type_attribute in CUSTOM_TYPE_ATTRIBUTES {
         pb.type_attribute(type_attribute.0, type_attribute.1);
     }
@@ -80,20 +183,16 @@ fn main() {
         ".google.protobuf.Timestamp",
         "super::super::google::protobuf::Timestamp",
     );
-    println!("[info] => Creating structs.");
     tonic_build::configure()
-        .out_dir(&out_dir)
-        .build_client(false)
+        .out_dir(out_dir)
+        .build_client(true)
         .build_server(true)
+        .client_mod_attribute("tendermint.abci", "#[cfg(feature = \"grpc\")]")
+        .client_mod_attribute("tendermint.privval", "#[cfg(feature = \"grpc\")]")
+        .client_mod_attribute("tendermint.rpc.grpc", "#[cfg(feature = \"grpc\")]")
         .server_mod_attribute("tendermint.abci", "#[cfg(feature = \"grpc\")]")
         .server_mod_attribute("tendermint.privval", "#[cfg(feature = \"grpc\")]")
         .server_mod_attribute("tendermint.rpc.grpc", "#[cfg(feature = \"grpc\")]")
-        .compile_with_config(pb, &protos, &proto_includes_paths)
+        .compile_with_config(pb, protos, includes)
         .unwrap();
-
-    println!("[info] => Removing old structs and copying new structs.");
-    copy_files(&out_dir, &target_dir); // This panics if it fails.
-    generate_tendermint_lib(&out_dir, &tendermint_lib_target);
-
-    println!("[info] => Done!");
 }